@@ -0,0 +1,155 @@
+// Compare the precision and the cost of the abstract domains
+//
+// Each program in `tests/annotated/` is analyzed by `mir-checker` with each domain, which overrides
+// the domain given by the `// OPTIONS:` of the program. A warning whose cause is expected by the
+// `// EXPECTED:` annotation of its line is a true positive, and any other warning is a false
+// positive, see `tests/common/mod.rs` for the annotations. The results are printed as a markdown
+// table, together with the analysis time and the peak memory of `mir-checker`:
+//
+//     cargo bench --bench domain_precision
+//     cargo bench --bench domain_precision -- interval octagon
+//
+// The domains can be given as arguments, all the domains are compared by default.
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use common::{collect_programs, corpus_dir, difference, LineCauses};
+use rust_mir_checker::analysis::option::{AbstractDomainType, DOMAIN_TYPES};
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+/// The result of analyzing a program with a domain
+struct Measurement {
+    // Causes of all the warnings, `None` if the analysis failed
+    warned: Option<LineCauses>,
+    time: Duration,
+    // In kilobytes
+    peak_memory: i64,
+}
+
+/// The results of a domain summed over the corpus
+#[derive(Default)]
+struct Total {
+    warnings: usize,
+    false_positives: usize,
+    missed: usize,
+    failures: usize,
+    time: Duration,
+    peak_memory: i64,
+}
+
+/// Run the checker with `domain`, the peak memory is the maximum resident set size reported by `wait4`
+fn run_checker(program: &Path, domain: &str, out_dir: &Path) -> Measurement {
+    let start = Instant::now();
+    let mut child = common::checker_command(program, out_dir)
+        .args(["--domain", domain])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("Cannot run mir-checker");
+    // Read until the checker exits, so that it never blocks on a full pipe
+    let mut report = String::new();
+    child
+        .stdout
+        .take()
+        .expect("stdout is piped")
+        .read_to_string(&mut report)
+        .expect("Cannot read the output of mir-checker");
+
+    let mut status = 0;
+    let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+    let pid = unsafe { libc::wait4(child.id() as libc::pid_t, &mut status, 0, &mut usage) };
+    let time = start.elapsed();
+    let succeeded = pid > 0 && libc::WIFEXITED(status) && libc::WEXITSTATUS(status) == 0;
+    Measurement {
+        warned: if succeeded {
+            common::reported_causes(&report).ok()
+        } else {
+            None
+        },
+        time,
+        peak_memory: usage.ru_maxrss,
+    }
+}
+
+fn main() {
+    // `cargo bench` passes `--bench`, the other arguments select the domains
+    let selected: Vec<String> = std::env::args()
+        .skip(1)
+        .filter(|arg| !arg.starts_with("--"))
+        .collect();
+    let domains: Vec<&AbstractDomainType> = DOMAIN_TYPES
+        .iter()
+        .filter(|domain| selected.is_empty() || selected.iter().any(|name| name == domain.name()))
+        .collect();
+    if domains.is_empty() {
+        eprintln!("No domain is selected from {:?}", selected);
+        std::process::exit(1);
+    }
+
+    let out_dir = std::env::temp_dir().join("mir-checker-domain-precision");
+    fs::create_dir_all(&out_dir).expect("Cannot create the output directory");
+    let programs = collect_programs(&corpus_dir());
+    let mut totals: Vec<Total> = domains.iter().map(|_| Total::default()).collect();
+
+    println!("| Program | Domain | Warnings | False positives | Missed bugs | Time (ms) | Peak memory (MB) |");
+    println!("|---|---|---|---|---|---|---|");
+    for program in &programs {
+        let name = program.file_stem().unwrap().to_string_lossy().into_owned();
+        let expected = common::expected_causes(program);
+        for (domain, total) in domains.iter().zip(totals.iter_mut()) {
+            let measurement = run_checker(program, domain.name(), &out_dir);
+            total.time += measurement.time;
+            total.peak_memory = total.peak_memory.max(measurement.peak_memory);
+            let (warnings, false_positives, missed) = match &measurement.warned {
+                Some(warned) => {
+                    let warnings = warned.values().map(|causes| causes.len()).sum::<usize>();
+                    let false_positives = difference(warned, &expected).len();
+                    let missed = difference(&expected, warned).len();
+                    total.warnings += warnings;
+                    total.false_positives += false_positives;
+                    total.missed += missed;
+                    (
+                        warnings.to_string(),
+                        false_positives.to_string(),
+                        missed.to_string(),
+                    )
+                }
+                None => {
+                    total.failures += 1;
+                    ("failed".to_owned(), "-".to_owned(), "-".to_owned())
+                }
+            };
+            println!(
+                "| {} | {} | {} | {} | {} | {} | {:.1} |",
+                name,
+                domain.name(),
+                warnings,
+                false_positives,
+                missed,
+                measurement.time.as_millis(),
+                measurement.peak_memory as f64 / 1024.0
+            );
+        }
+    }
+
+    println!();
+    println!("| Domain | Warnings | False positives | Missed bugs | Failures | Total time (ms) | Peak memory (MB) |");
+    println!("|---|---|---|---|---|---|---|");
+    for (domain, total) in domains.iter().zip(totals.iter()) {
+        println!(
+            "| {} | {} | {} | {} | {} | {} | {:.1} |",
+            domain.name(),
+            total.warnings,
+            total.false_positives,
+            total.missed,
+            total.failures,
+            total.time.as_millis(),
+            total.peak_memory as f64 / 1024.0
+        );
+    }
+}
@@ -0,0 +1,295 @@
+// Fuzz `TypeVisitor::get_path_rustc_type` with arbitrary paths
+//
+//     cargo fuzz run path_type_resolution
+//
+// The paths are resolved against the MIR bodies of the functions in `MOCK_SOURCE`, which is compiled for each input,
+// so the harness only runs a few inputs per second. A panic, e.g., an out-of-bound index or an `unreachable!()`,
+// is reported as a crash, whereas the unit type is the expected fallback for the paths that cannot be resolved.
+
+#![no_main]
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_span;
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use rust_mir_checker::analysis::memory::path::{Path, PathSelector};
+use rust_mir_checker::analysis::memory::symbolic_value::{self, SymbolicValue};
+use rust_mir_checker::analysis::mir_visitor::type_visitor::TypeVisitor;
+use rust_mir_checker::utils;
+use rustc_driver::Compilation;
+use rustc_hir::def::DefKind;
+use rustc_interface::{interface, Queries};
+use rustc_middle::mir;
+use rustc_middle::ty::TyCtxt;
+use rustc_span::DUMMY_SP;
+use std::rc::Rc;
+use std::sync::OnceLock;
+
+/// The functions whose MIR bodies are used as mock bodies, their locals cover structs, enums, unions, tuples, arrays,
+/// slices, strings, raw pointers, smart pointers, closures and generic parameters
+const MOCK_SOURCE: &str = r#"
+pub struct Point {
+    pub x: i32,
+    pub y: i64,
+}
+
+pub enum Shape {
+    Circle(f64),
+    Rect { w: u32, h: u32 },
+    Empty,
+}
+
+pub union Bits {
+    pub int: u32,
+    pub float: f32,
+}
+
+pub static COUNTER: usize = 0;
+
+pub fn structs(p: &Point, s: Shape, b: Bits) -> (i64, Shape) {
+    let bits = unsafe { b.int } as i64;
+    (p.y + bits, s)
+}
+
+pub fn collections(a: [u8; 4], s: &[u16], t: &str, v: Vec<i32>) -> usize {
+    a[1] as usize + s.len() + t.len() + v.capacity() + COUNTER
+}
+
+pub fn pointers(b: Box<Point>, r: std::rc::Rc<[u8]>, p: *const i8) -> u8 {
+    let f = |x: u8| x.wrapping_add(b.x as u8);
+    f(r[0]) ^ unsafe { *p as u8 }
+}
+
+pub fn generic<T: Clone>(x: &T, n: Option<T>) -> T {
+    n.unwrap_or_else(|| x.clone())
+}
+"#;
+
+/// The names of the functions of `MOCK_SOURCE`, one of which is selected by the input
+const MOCK_FUNCTIONS: [&str; 4] = ["structs", "collections", "pointers", "generic"];
+
+/// A mirror of `PathEnum`, which cannot derive `Arbitrary` because it contains `DefId`s and symbolic values
+#[derive(Arbitrary, Debug)]
+enum FuzzPath {
+    Alias {
+        bottom: bool,
+        value: u8,
+    },
+    HeapAllocation {
+        id: u8,
+        registered: bool,
+        block: u8,
+    },
+    LocalVariable {
+        ordinal: u8,
+    },
+    Parameter {
+        ordinal: u8,
+    },
+    Result,
+    StaticVariable {
+        named: bool,
+    },
+    QualifiedPath {
+        qualifier: Box<FuzzPath>,
+        selector: FuzzSelector,
+    },
+}
+
+/// A mirror of `PathSelector`, whose symbolic values are constants
+#[derive(Arbitrary, Debug)]
+enum FuzzSelector {
+    Deref,
+    Discriminant,
+    Field(u8),
+    FatPointerData,
+    FatPointerMeta,
+    IteratorItem,
+    IteratorCount,
+    Capacity,
+    ResourceState,
+    Freed,
+    Written,
+    Index(u8),
+    Slice(u8),
+    ConstantIndex {
+        offset: u8,
+        min_length: u8,
+        from_end: bool,
+    },
+}
+
+#[derive(Arbitrary, Debug)]
+struct FuzzInput {
+    /// The index of the mock function in `MOCK_FUNCTIONS`
+    function: u8,
+    /// How many of the parameter types are taken as the actual argument types, as if the function is called
+    argument_count: u8,
+    path: FuzzPath,
+}
+
+/// Converts the mirror types to a path, registering the heap allocation sites in `type_visitor`
+fn build_path(path: &FuzzPath, tcx: TyCtxt<'_>, type_visitor: &mut TypeVisitor<'_>) -> Rc<Path> {
+    match path {
+        FuzzPath::Alias { bottom, value } => {
+            let value = if *bottom {
+                symbolic_value::BOTTOM
+            } else {
+                SymbolicValue::from(*value as u128)
+            };
+            Path::new_alias(Rc::new(value))
+        }
+        FuzzPath::HeapAllocation {
+            id,
+            registered,
+            block,
+        } => {
+            if *registered {
+                let block_count = type_visitor.mir.basic_blocks.len();
+                let location = mir::Location {
+                    block: mir::BasicBlock::from_usize(*block as usize % block_count),
+                    statement_index: 0,
+                };
+                type_visitor
+                    .heap_allocation_sites
+                    .insert(*id as u32, location);
+            }
+            Path::new_heap_allocation(*id as u32)
+        }
+        FuzzPath::LocalVariable { ordinal } => Path::new_local(*ordinal as usize, 0),
+        FuzzPath::Parameter { ordinal } => Path::new_parameter(*ordinal as usize, 0),
+        FuzzPath::Result => Path::new_result(),
+        FuzzPath::StaticVariable { named } => {
+            if *named {
+                Path::new_named("COUNTER")
+            } else {
+                let counter = tcx
+                    .hir()
+                    .body_owners()
+                    .find(|def_id| matches!(tcx.def_kind(*def_id), DefKind::Static { .. }))
+                    .expect("`COUNTER` is defined in the mock source");
+                Path::new_static(tcx, counter.to_def_id())
+            }
+        }
+        FuzzPath::QualifiedPath {
+            qualifier,
+            selector,
+        } => {
+            let qualifier = build_path(qualifier, tcx, type_visitor);
+            let selector = match selector {
+                FuzzSelector::Deref => PathSelector::Deref,
+                FuzzSelector::Discriminant => PathSelector::Discriminant,
+                FuzzSelector::Field(ordinal) => PathSelector::Field(*ordinal as usize),
+                FuzzSelector::FatPointerData => PathSelector::FatPointerData,
+                FuzzSelector::FatPointerMeta => PathSelector::FatPointerMeta,
+                FuzzSelector::IteratorItem => PathSelector::IteratorItem,
+                FuzzSelector::IteratorCount => PathSelector::IteratorCount,
+                FuzzSelector::Capacity => PathSelector::Capacity,
+                FuzzSelector::ResourceState => PathSelector::ResourceState,
+                FuzzSelector::Freed => PathSelector::Freed,
+                FuzzSelector::Written => PathSelector::Written,
+                FuzzSelector::Index(index) => {
+                    PathSelector::Index(Rc::new(SymbolicValue::from(*index as u128)))
+                }
+                FuzzSelector::Slice(count) => {
+                    PathSelector::Slice(Rc::new(SymbolicValue::from(*count as u128)))
+                }
+                FuzzSelector::ConstantIndex {
+                    offset,
+                    min_length,
+                    from_end,
+                } => PathSelector::ConstantIndex {
+                    offset: *offset as u64,
+                    min_length: *min_length as u64,
+                    from_end: *from_end,
+                },
+            };
+            Path::new_qualified(qualifier, Rc::new(selector))
+        }
+    }
+}
+
+struct FuzzCallbacks {
+    input: FuzzInput,
+}
+
+impl rustc_driver::Callbacks for FuzzCallbacks {
+    fn after_analysis<'compiler, 'tcx>(
+        &mut self,
+        _compiler: &'compiler interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries
+            .global_ctxt()
+            .unwrap()
+            .enter(|tcx| self.resolve_path(tcx));
+        // Nothing needs to be generated
+        Compilation::Stop
+    }
+}
+
+impl FuzzCallbacks {
+    fn resolve_path(&self, tcx: TyCtxt<'_>) {
+        let name = MOCK_FUNCTIONS[self.input.function as usize % MOCK_FUNCTIONS.len()];
+        let def_id = tcx
+            .hir()
+            .body_owners()
+            .find(|def_id| {
+                tcx.def_kind(*def_id) == DefKind::Fn
+                    && tcx
+                        .opt_item_name(def_id.to_def_id())
+                        .map(|n| n.as_str() == name)
+                        == Some(true)
+            })
+            .expect("the mock function is defined in the mock source")
+            .to_def_id();
+        let mir = tcx.optimized_mir(def_id).clone();
+        let mut type_visitor = TypeVisitor::new(def_id, mir, tcx);
+        let argument_count = (self.input.argument_count as usize).min(type_visitor.mir.arg_count);
+        type_visitor.actual_argument_types = type_visitor
+            .mir
+            .args_iter()
+            .take(argument_count)
+            .map(|local| type_visitor.mir.local_decls[local].ty)
+            .collect();
+
+        let path = build_path(&self.input.path, tcx, &mut type_visitor);
+        type_visitor.get_path_rustc_type(&path, DUMMY_SP);
+    }
+}
+
+/// The mock source is written once, since the compiler reads its input from a file
+fn mock_source_file() -> &'static str {
+    static MOCK_SOURCE_FILE: OnceLock<String> = OnceLock::new();
+    MOCK_SOURCE_FILE.get_or_init(|| {
+        let file = std::env::temp_dir().join(format!("mir-checker-fuzz-{}.rs", std::process::id()));
+        std::fs::write(&file, MOCK_SOURCE).expect("failed to write the mock source");
+        file.to_string_lossy().to_string()
+    })
+}
+
+fuzz_target!(|input: FuzzInput| {
+    let mut args: Vec<String> = vec![
+        "rustc".to_owned(),
+        mock_source_file().to_owned(),
+        "--crate-type=lib".to_owned(),
+        "--crate-name=mock".to_owned(),
+        "-Zalways_encode_mir".to_owned(),
+        "-Cpanic=abort".to_owned(),
+        "--out-dir".to_owned(),
+        std::env::temp_dir().to_string_lossy().to_string(),
+    ];
+    if let Some(sysroot) = utils::compile_time_sysroot() {
+        args.push("--sysroot".to_owned());
+        args.push(sysroot);
+    }
+    let mut callbacks = FuzzCallbacks { input };
+    rustc_driver::RunCompiler::new(&args, &mut callbacks)
+        .run()
+        .expect("the mock source compiles");
+});
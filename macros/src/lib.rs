@@ -9,3 +9,6 @@ macro_rules! verify {
 
 // Dummy function
 pub fn mir_checker_verify(_condition: bool) {}
+
+// Dummy function, the precondition injected by `#[requires(...)]` of `mir-checker-macros`
+pub fn mir_checker_requires(_condition: bool) {}
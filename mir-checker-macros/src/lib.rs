@@ -0,0 +1,29 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, parse_quote, Expr, ItemFn};
+
+/// Specifies a precondition of a function, e.g., `#[requires(x > 0 && y < 100)]`
+///
+/// The condition is checked by an `assert!` at the beginning of the function body, so a violation also panics at
+/// run time. When the crate is analyzed, the condition is passed to `macros::mir_checker_requires` before the
+/// assertion, and MirChecker assumes it at the entry of the function, or checks it against the state of the caller
+/// if the function is analyzed for a call. The crate must depend on `macros` as well
+#[proc_macro_attribute]
+pub fn requires(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let condition = parse_macro_input!(attr as Expr);
+    let mut function = parse_macro_input!(item as ItemFn);
+    let message = format!("precondition violated: {}", quote!(#condition));
+    let body = &function.block;
+    function.block = parse_quote!({
+        if cfg!(mir_checker) {
+            macros::mir_checker_requires(#condition);
+        }
+        assert!(#condition, "{}", #message);
+        #body
+    });
+    // MirChecker recognizes the functions with preconditions by this attribute
+    function
+        .attrs
+        .push(parse_quote!(#[cfg_attr(mir_checker, mir_checker::requires)]));
+    quote!(#function).into()
+}
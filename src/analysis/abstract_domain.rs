@@ -6,10 +6,12 @@ use crate::analysis::memory::symbolic_value::{SymbolicValue, SymbolicValueTrait}
 use crate::analysis::numerical::apron_domain::{
     ApronAbstractDomain, ApronDomainType, GetManagerTrait,
 };
+use crate::analysis::numerical::float_domain::FloatValue;
 use crate::analysis::numerical::lattice::LatticeTrait;
+use crate::analysis::numerical::sparse_domain::SparseApronDomain;
 use rug::Integer;
 use rustc_middle::mir;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
 use std::fmt;
 use std::rc::Rc;
@@ -21,11 +23,16 @@ where
     ApronAbstractDomain<DomainType>: GetManagerTrait,
 {
     // Only stores the values of paths that are integers
-    pub numerical_domain: ApronAbstractDomain<DomainType>,
+    pub numerical_domain: SparseApronDomain<DomainType>,
     // Stores all the symbolic values
     pub symbolic_domain: SymbolicDomain,
     // Stores branch conditions
     pub exit_conditions: HashMap<mir::BasicBlock, Rc<SymbolicValue>>,
+    // Stores the paths that have been moved out by `Operand::Move` and not re-initialized yet
+    pub moved_paths: HashSet<Rc<Path>>,
+    // Stores the values of the float-typed local variables, used by the float NaN checker
+    // A variable that is not present is unknown, see `FloatValue::unknown`
+    pub float_values: HashMap<mir::Local, FloatValue>,
 }
 
 impl<DomainType> fmt::Debug for AbstractDomain<DomainType>
@@ -59,6 +66,7 @@ where
     pub fn leq(&self, other: &Self) -> bool {
         // TODO: consider symbolic domain
         self.numerical_domain.leq(&other.numerical_domain)
+            && (self.is_bottom() || float_values_leq(&self.float_values, &other.float_values))
     }
 
     pub fn is_empty(&self) -> bool {
@@ -67,9 +75,11 @@ where
 
     pub fn default() -> Self {
         Self {
-            numerical_domain: ApronAbstractDomain::default(),
+            numerical_domain: SparseApronDomain::default(),
             symbolic_domain: SymbolicDomain::default(),
             exit_conditions: HashMap::new(),
+            moved_paths: HashSet::new(),
+            float_values: HashMap::new(),
         }
     }
 
@@ -85,6 +95,14 @@ where
         self.numerical_domain.forget(path);
     }
 
+    /// Remove several paths at once, the numerical domain is updated by a single call to Apron
+    pub fn remove_all(&mut self, paths: &[Rc<Path>]) {
+        for path in paths {
+            self.symbolic_domain.forget(path);
+        }
+        self.numerical_domain.forget_all(paths);
+    }
+
     pub fn rename(&mut self, old_path: &Rc<Path>, new_path: &Rc<Path>) {
         debug!("Renaming {:?} to {:?}", old_path, new_path);
         self.numerical_domain.rename(old_path, new_path);
@@ -117,9 +135,38 @@ where
         }
     }
 
+    /// Returns true if the path, or any of its qualifiers, has been moved out
+    pub fn is_moved(&self, path: &Rc<Path>) -> bool {
+        !self.moved_paths.is_empty()
+            && self
+                .moved_paths
+                .iter()
+                .any(|moved| moved == path || path.is_rooted_by(moved))
+    }
+
+    /// Marks `source_path` as moved out after `target_path = move source_path`
+    pub fn mark_moved(&mut self, source_path: Rc<Path>, target_path: &Rc<Path>) {
+        self.reinitialize(target_path);
+        self.moved_paths.insert(source_path);
+    }
+
+    /// Writing into a path re-initializes it and the moved paths that qualify it
+    fn reinitialize(&mut self, path: &Rc<Path>) {
+        if !self.moved_paths.is_empty() {
+            self.moved_paths
+                .retain(|moved| moved != path && !path.is_rooted_by(moved));
+        }
+    }
+
     /// Updates the path to value map so that the given path now points to the given value.
     pub fn update_value_at(&mut self, path: Rc<Path>, value: Rc<SymbolicValue>) {
         debug!("Updating value at {:?}, value: {:?}", path, value);
+        self.reinitialize(&path);
+        self.assign_value_at(path, value);
+    }
+
+    /// Stores `value` at `path` in the numerical domain or in the symbolic domain, depending on the kind of `value`
+    fn assign_value_at(&mut self, path: Rc<Path>, value: Rc<SymbolicValue>) {
         if value.is_bottom() || value.is_top() {
             debug!("Value is bottom or top, ignore");
             self.symbolic_domain.value_map.remove(&path);
@@ -165,6 +212,65 @@ where
         }
     }
 
+    /// Joins the value at `path` with `value`, used when `path` may or may not be updated
+    /// Only the value at `path` is changed, so the relations between `path` and the other paths are lost
+    /// The path is forgotten if its old or new value is unknown, or if they are not in the same domain
+    pub fn weak_update_value_at(&mut self, path: Rc<Path>, value: Rc<SymbolicValue>) {
+        match &value.expression {
+            // Joining the value at `path` with itself does not change it
+            Expression::Numerical(rpath) | Expression::Variable { path: rpath, .. }
+                if *rpath == path =>
+            {
+                return;
+            }
+            _ => {}
+        }
+        let old_interval = if self.numerical_domain.contains(&path) {
+            Some(self.numerical_domain.get_interval(&path))
+        } else {
+            None
+        };
+        let old_value = self.symbolic_domain.value_map.get(&path).cloned();
+
+        // The new value is stored from scratch to find which domains it belongs to
+        // The path is not re-initialized, since it may not be updated
+        self.symbolic_domain.value_map.remove(&path);
+        self.numerical_domain.forget(&path);
+        let is_unknown = value.is_bottom()
+            || value.is_top()
+            || old_value
+                .as_ref()
+                .map_or(false, |old_value| old_value.is_top())
+            || (old_interval.is_none() && old_value.is_none());
+        if is_unknown {
+            return;
+        }
+        self.assign_value_at(path.clone(), value);
+
+        let new_interval = if self.numerical_domain.contains(&path) {
+            Some(self.numerical_domain.get_interval(&path))
+        } else {
+            None
+        };
+        match (old_interval, new_interval) {
+            (Some(old_interval), Some(new_interval)) => self
+                .numerical_domain
+                .set_interval(&path, old_interval.join(&new_interval)),
+            _ => self.numerical_domain.forget(&path),
+        }
+        let new_value = self.symbolic_domain.value_map.get(&path).cloned();
+        match (old_value, new_value) {
+            (Some(old_value), Some(new_value)) => {
+                self.symbolic_domain
+                    .value_map
+                    .insert(path, old_value.join(new_value));
+            }
+            _ => {
+                self.symbolic_domain.value_map.remove(&path);
+            }
+        }
+    }
+
     pub fn join(&self, other: &Self) -> Self {
         let numerical = self.numerical_domain.join(&other.numerical_domain);
         let symbolic = self.symbolic_domain.lub(&other.symbolic_domain);
@@ -172,6 +278,8 @@ where
             numerical_domain: numerical,
             symbolic_domain: symbolic,
             exit_conditions: HashMap::new(),
+            moved_paths: self.moved_paths.union(&other.moved_paths).cloned().collect(),
+            float_values: self.combine_float_values(other, FloatValue::join),
         }
     }
 
@@ -182,6 +290,8 @@ where
             numerical_domain: numerical,
             symbolic_domain: other.symbolic_domain.clone(),
             exit_conditions: HashMap::new(),
+            moved_paths: self.moved_paths.union(&other.moved_paths).cloned().collect(),
+            float_values: other.float_values.clone(),
         }
     }
 
@@ -193,6 +303,8 @@ where
             numerical_domain: numerical,
             symbolic_domain: symbolic,
             exit_conditions: HashMap::new(),
+            moved_paths: self.moved_paths.union(&other.moved_paths).cloned().collect(),
+            float_values: self.combine_float_values(other, FloatValue::widen),
         }
     }
 
@@ -207,9 +319,34 @@ where
             // Seems like no need to do narrowing for symbolic domain
             symbolic_domain: other.symbolic_domain.clone(),
             exit_conditions: HashMap::new(),
+            moved_paths: self.moved_paths.union(&other.moved_paths).cloned().collect(),
+            float_values: other.float_values.clone(),
         }
     }
 
+    /// Combines the float values of both states with `operation`, e.g., `FloatValue::join`, where a variable that is
+    /// only present in one state is unknown in the other one
+    /// The values of an unreachable state are ignored
+    fn combine_float_values(
+        &self,
+        other: &Self,
+        operation: fn(&FloatValue, &FloatValue) -> FloatValue,
+    ) -> HashMap<mir::Local, FloatValue> {
+        if self.is_bottom() {
+            return other.float_values.clone();
+        }
+        if other.is_bottom() {
+            return self.float_values.clone();
+        }
+        let mut float_values = HashMap::new();
+        for local in self.float_values.keys().chain(other.float_values.keys()) {
+            let left = float_value(&self.float_values, local);
+            let right = float_value(&other.float_values, local);
+            float_values.insert(*local, operation(&left, &right));
+        }
+        float_values
+    }
+
     pub fn subset(&self, other: &Self) -> bool {
         let value_map1 = &self.symbolic_domain.value_map;
         let value_map2 = &other.symbolic_domain.value_map;
@@ -231,3 +368,21 @@ where
         true
     }
 }
+
+/// The value of `local` in `values`, which is unknown if it is not present
+fn float_value(values: &HashMap<mir::Local, FloatValue>, local: &mir::Local) -> FloatValue {
+    values
+        .get(local)
+        .copied()
+        .unwrap_or_else(FloatValue::unknown)
+}
+
+/// True if the float values of `left` are included in the ones of `right`
+fn float_values_leq(
+    left: &HashMap<mir::Local, FloatValue>,
+    right: &HashMap<mir::Local, FloatValue>,
+) -> bool {
+    left.keys()
+        .chain(right.keys())
+        .all(|local| float_value(left, local).leq(&float_value(right, local)))
+}
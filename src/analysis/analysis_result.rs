@@ -27,4 +27,12 @@ pub enum AnalysisError {
 
 pub struct AnalysisInfo {
     pub analysis_time: Duration,
+    // The number of functions in the crate that are reachable from the entry points
+    pub reachable_functions: usize,
+    // The names of the functions in the crate that are not reachable from the entry points
+    pub unreachable_functions: Vec<String>,
+    // The number of times that unsupported MIR patterns are skipped, the results may be imprecise if it is not zero
+    pub unsupported_patterns: usize,
+    // The number of branches that are proved to be never taken
+    pub pruned_branches: usize,
 }
@@ -1,17 +1,29 @@
 use crate::analysis::abstract_domain::AbstractDomain;
 use crate::analysis::analysis_result::{AnalysisInfo, Result};
 use crate::analysis::analyzer::analysis_trait::StaticAnalysis;
+use crate::analysis::checkpoint::Checkpoint;
+use crate::analysis::dataflow::DataFlowGraph;
 use crate::analysis::diagnostics::Diagnostic;
 use crate::analysis::global_context::GlobalContext;
+use crate::analysis::memory::utils;
 use crate::analysis::mir_visitor::body_visitor::WtoFixPointIterator;
 use crate::analysis::numerical::apron_domain::{
     ApronAbstractDomain, ApronDomainType, ApronInterval, ApronLinearEqualities, ApronOctagon,
     ApronPkgridPolyhedraLinCongruences, ApronPolyhedra, ApronPplLinearCongruences,
-    ApronPplPolyhedra, GetManagerTrait,
+    ApronPplPolyhedra, ApronWrappingInteger, GetManagerTrait,
 };
-use crate::analysis::option::AbstractDomainType;
-use log::info;
-use rustc_hir::def_id::DefId;
+use crate::analysis::numerical::interval::Interval;
+use crate::analysis::option::{AbstractDomainType, OutputFormat};
+use crate::analysis::reachability;
+use crate::analysis::sqlite_export::{self, AnalysisRun, DiagnosticRecord};
+use crate::analysis::summary::{self, FunctionSummary};
+use log::{error, info};
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::{DefId, LOCAL_CRATE};
+use rustc_span::source_map::SourceMap;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
 use std::time::Instant;
 
 /// Traverse over a crate, analyze all functions and emit diagnoses
@@ -33,9 +45,9 @@ impl<'tcx, 'compiler> StaticAnalysis<'tcx, 'compiler> for NumericalAnalysis<'tcx
             .into_values()
             .flatten()
             .map(|d| {
-                // If `deny_warnings` flag is set, change all diagnoses' level to `error`
+                // If `deny_warnings` flag is set, change all diagnoses' level to `error`, except for notes
                 // This is used for debugging
-                if self.context.analysis_options.deny_warnings {
+                if self.context.analysis_options.deny_warnings && !d.is_note() {
                     d.upgrade_to_error()
                 } else {
                     d
@@ -49,7 +61,10 @@ impl<'tcx, 'compiler> StaticAnalysis<'tcx, 'compiler> for NumericalAnalysis<'tcx
         let mut res: Vec<Diagnostic<'_>> = Vec::new();
         if let Some(suppressed_warnings) = &self.context.analysis_options.suppressed_warnings {
             for diag in diagnostics.into_iter() {
-                if suppressed_warnings.contains(&diag.cause) {
+                if suppressed_warnings
+                    .iter()
+                    .any(|cause| cause.matches(&diag.cause))
+                {
                     diag.cancel();
                 } else {
                     res.push(diag);
@@ -61,19 +76,86 @@ impl<'tcx, 'compiler> StaticAnalysis<'tcx, 'compiler> for NumericalAnalysis<'tcx
 
         // According to `memory_safety_only` flag, filter only memory-safety diagnosis
         // Cancel other diagnoses that will not be emitted
+        let mut to_emit: Vec<Diagnostic<'_>> = Vec::new();
         if self.context.analysis_options.memory_safety_only {
             for diag in res.into_iter() {
                 if diag.is_memory_safety {
-                    diag.emit()
+                    to_emit.push(diag);
                 } else {
                     diag.cancel();
                 }
             }
         } else {
-            for diag in res.into_iter() {
-                diag.emit()
-            }
+            to_emit = res;
         };
+
+        // According to `output_format` flag, either let rustc emit the diagnoses, or print them in the format
+        // of a CI system or an editor and cancel them
+        let error_count = to_emit.iter().filter(|diag| diag.is_error()).count();
+        let panic_count = to_emit.iter().filter(|diag| !diag.is_note()).count();
+        let output_format = self.context.analysis_options.output_format;
+        match output_format {
+            OutputFormat::Human => {
+                for diag in to_emit.into_iter() {
+                    diag.emit()
+                }
+            }
+            OutputFormat::GithubActions => {
+                let source_map = self.context.session.source_map();
+                for diag in to_emit.into_iter() {
+                    println!("{}", Self::format_github_actions(&diag, source_map));
+                    diag.cancel();
+                }
+            }
+            OutputFormat::Vscode => {
+                let source_map = self.context.session.source_map();
+                for diag in to_emit.into_iter() {
+                    println!("{}", Self::format_vscode(&diag, source_map));
+                    diag.cancel();
+                }
+            }
+            OutputFormat::Gitlab => {
+                let source_map = self.context.session.source_map();
+                let report: Vec<serde_json::Value> = to_emit
+                    .iter()
+                    .map(|diag| Self::format_gitlab(diag, source_map))
+                    .collect();
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&report).expect("failed to serialize diagnoses")
+                );
+                for diag in to_emit.into_iter() {
+                    diag.cancel();
+                }
+            }
+            OutputFormat::Sqlite => {
+                let source_map = self.context.session.source_map();
+                let records: Vec<DiagnosticRecord> = to_emit
+                    .iter()
+                    .map(|diag| Self::format_sqlite(diag, source_map))
+                    .collect();
+                self.export_diagnostics(&records);
+                for diag in to_emit.into_iter() {
+                    diag.cancel();
+                }
+            }
+        }
+
+        // The printed errors are cancelled, so still fail the compilation when `deny_warnings` is set
+        if output_format != OutputFormat::Human && error_count > 0 {
+            self.context.session.dcx().err(format!(
+                "[MirChecker] {} error(s) reported in {:?} format",
+                error_count, output_format
+            ));
+        }
+
+        // In the panic-freedom mode, fail the compilation if any site may panic, even if it is only a warning
+        if self.context.analysis_options.panic_freedom && panic_count > 0 {
+            self.context.session.dcx().err(format!(
+                "[MirChecker] {} site(s) may panic, which is not allowed by `--panic-freedom`",
+                panic_count
+            ));
+        }
     }
 
     fn run(mut self) -> Result<AnalysisInfo> {
@@ -88,51 +170,117 @@ impl<'tcx, 'compiler> StaticAnalysis<'tcx, 'compiler> for NumericalAnalysis<'tcx
             "Widening Delay: {}",
             self.context.analysis_options.widening_delay
         );
-        info!(
-            "Start Analyzing Entry Point Function: {}",
-            self.context.tcx.item_name(self.context.entry_point)
-        );
 
-        // Start analysis with the entry point
-        let def_id = self.context.entry_point;
+        // Compute the functions that are reachable from the entry points, the others are never analyzed
+        let tcx = self.context.tcx;
+        let call_graph = self.context.build_call_graph();
+        self.context.reachable_functions = call_graph.keys().copied().collect();
+        if let Some(file_name) = &self.context.analysis_options.dump_callgraph {
+            self.dump_call_graph(&call_graph, file_name);
+        }
+        if let Some(file_name) = &self.context.analysis_options.dump_dataflow {
+            let data_flow_graphs = call_graph
+                .keys()
+                .map(|def_id| (*def_id, DataFlowGraph::new(tcx.optimized_mir(*def_id))))
+                .collect();
+            self.dump_data_flow_graphs(&data_flow_graphs, file_name);
+            self.context.data_flow_graphs = Some(data_flow_graphs);
+        }
+        let unreachable_def_ids: Vec<DefId> = reachability::local_functions(tcx)
+            .into_iter()
+            .filter(|def_id| !self.context.reachable_functions.contains(def_id))
+            .collect();
+        if self
+            .context
+            .analysis_options
+            .check_use_of_uninitialized_global
+        {
+            self.context.statics_written_elsewhere =
+                reachability::mentioned_mutable_statics(tcx, unreachable_def_ids.iter().copied());
+        }
+        let mut unreachable_functions: Vec<String> = unreachable_def_ids
+            .into_iter()
+            .map(|def_id| tcx.def_path_str(def_id))
+            .collect();
+        unreachable_functions.sort();
 
-        match self.context.analysis_options.domain_type {
-            AbstractDomainType::Interval => {
-                self.analyze_function(def_id, AbstractDomain::<ApronInterval>::default());
+        if let Some(file_name) = self.context.analysis_options.trace_file() {
+            // Start from an empty trace, the lines are appended while analyzing the traced function
+            #[cfg(feature = "trace")]
+            if let Err(e) = std::fs::write(&file_name, "") {
+                error!("Failed to create the trace file {}: {}", file_name, e);
             }
-            AbstractDomainType::Octagon => {
-                self.analyze_function(def_id, AbstractDomain::<ApronOctagon>::default());
-            }
-            AbstractDomainType::Polyhedra => {
-                self.analyze_function(def_id, AbstractDomain::<ApronPolyhedra>::default());
-            }
-            AbstractDomainType::LinearEqualities => {
-                self.analyze_function(def_id, AbstractDomain::<ApronLinearEqualities>::default());
-            }
-            AbstractDomainType::PplPolyhedra => {
-                self.analyze_function(def_id, AbstractDomain::<ApronPplPolyhedra>::default());
+            #[cfg(not(feature = "trace"))]
+            log::warn!(
+                "No trace is written to {}, rebuild with the `trace` feature to enable `--trace-function`",
+                file_name
+            );
+        }
+
+        // The calls between mutually recursive functions use their summaries, see `summarize_recursive_component`
+        let components = self
+            .context
+            .analysis_order
+            .get_or_init(|| reachability::bottom_up_order(tcx, &call_graph))
+            .clone();
+        for (index, component) in components.iter().enumerate() {
+            if component.len() > 1 {
+                for def_id in self.summarized_functions(component) {
+                    self.context.recursive_components.insert(def_id, index);
+                }
             }
-            AbstractDomainType::PplLinearCongruences => {
-                self.analyze_function(
-                    def_id,
-                    AbstractDomain::<ApronPplLinearCongruences>::default(),
-                );
+        }
+
+        if self.context.analysis_options.bottom_up {
+            // Analyze the callees before their callers, so that the callers can use their summaries
+            self.analyze_bottom_up(&call_graph, &components);
+        } else {
+            for component in components.iter().filter(|component| component.len() > 1) {
+                let functions = self.summarized_functions(component);
+                self.summarize_recursive_component(&functions);
             }
-            AbstractDomainType::PkgridPolyhedraLinCongruences => {
-                self.analyze_function(
-                    def_id,
-                    AbstractDomain::<ApronPkgridPolyhedraLinCongruences>::default(),
+            // Start analysis with each entry point
+            for def_id in self.context.entry_points.clone() {
+                info!(
+                    "Start Analyzing Entry Point Function: {}",
+                    self.context.tcx.item_name(def_id)
                 );
+                self.analyze_entry_point(def_id);
             }
         }
 
         info!("================== Numerical Analysis Ends ==================");
 
+        if let Some(file_name) = &self.context.analysis_options.export_invariants {
+            self.export_loop_invariants(file_name);
+        }
+        if let Some(file_name) = &self.context.analysis_options.export_contracts {
+            self.export_contracts(file_name);
+        }
+        if let Some(cache_dir) = &self.context.analysis_options.cache_dir {
+            let tcx = self.context.tcx;
+            // Distinguish the library and binaries of a package, which may have the same crate name
+            let file_name = format!(
+                "{}-{:016x}",
+                tcx.crate_name(LOCAL_CRATE),
+                tcx.stable_crate_id(LOCAL_CRATE).as_u64()
+            );
+            summary::store_summaries(cache_dir, &file_name, &self.context.summaries);
+        }
+
+        let reachable_functions = self.context.reachable_functions.len();
+        let unsupported_patterns = self.context.unsupported_patterns;
+        let pruned_branches = self.context.pruned_branches;
+
         info!("================== Start To Output Diagnostics ==================");
         self.emit_diagnostics();
 
         Ok(AnalysisInfo {
             analysis_time: timer.elapsed(),
+            reachable_functions,
+            unreachable_functions,
+            unsupported_patterns,
+            pruned_branches,
         })
     }
 
@@ -145,17 +293,48 @@ impl<'tcx, 'compiler> StaticAnalysis<'tcx, 'compiler> for NumericalAnalysis<'tcx
         ApronAbstractDomain<DomainType>: GetManagerTrait,
     {
         let func_name = self.context.tcx.item_name(def_id);
+        if !self.context.reachable_functions.contains(&def_id) {
+            info!(
+                "Skip {}, which is not reachable from the entry points",
+                func_name
+            );
+            return;
+        }
         info!(
             "================== Fixed-Point Algorithm Starts To Analyze: {} ==================",
             func_name
         );
 
         // Compute the fixed-point of the function specified by `def_id`
+        let export_contracts = self.context.analysis_options.export_contracts.is_some();
         let mut wto_visitor =
             WtoFixPointIterator::new(&mut self.context, def_id, abstract_domain, 0, vec![]);
         wto_visitor.init_promote_constants();
+        if export_contracts {
+            wto_visitor.record_entry_values();
+        }
         wto_visitor.run();
 
+        // The arguments of an analyzed function start at top, so the exit state can be used as its contract
+        if export_contracts {
+            wto_visitor.record_contract();
+        }
+        // For the same reason, the range of the return value can be used as its summary
+        if wto_visitor.context.analysis_options.cache_dir.is_some()
+            || wto_visitor.context.analysis_options.bottom_up
+        {
+            wto_visitor.record_summary();
+        }
+        if let Some(checkpoint_dir) = &wto_visitor.context.analysis_options.checkpoint_dir {
+            Checkpoint::new(&wto_visitor).store(checkpoint_dir);
+        }
+        if let Some(dump_dir) = &wto_visitor.context.analysis_options.dump_states {
+            wto_visitor.dump_states(std::path::Path::new(dump_dir));
+        }
+        if let Some(dump_dir) = &wto_visitor.context.analysis_options.dump_wto {
+            wto_visitor.dump_wto(std::path::Path::new(dump_dir));
+        }
+
         // Execute bug detector
         wto_visitor.run_checker();
 
@@ -165,6 +344,413 @@ impl<'tcx, 'compiler> StaticAnalysis<'tcx, 'compiler> for NumericalAnalysis<'tcx
             func_name
         );
 
+        // The paths of this function are no longer used, unless they are referenced by the global context
+        self.context.path_interner.collect_garbage();
+        debug!(
+            "{} paths are interned after analyzing {:?}",
+            self.context.path_interner.len(),
+            func_name
+        );
+
         info!("================== Fixed-Point Algorithm Ends ==================");
     }
 }
+
+impl<'tcx, 'compiler> NumericalAnalysis<'tcx, 'compiler> {
+    /// Analyze `def_id` with unknown arguments, in the abstract domain specified by the options
+    fn analyze_entry_point(&mut self, def_id: DefId) {
+        self.analyze_with_unknown_arguments(def_id, false);
+    }
+
+    /// Analyze `def_id` with unknown arguments only to record its summary, without checking it
+    fn summarize_function(&mut self, def_id: DefId) {
+        self.analyze_with_unknown_arguments(def_id, true);
+    }
+
+    fn analyze_with_unknown_arguments(&mut self, def_id: DefId, summary_only: bool) {
+        match self.context.analysis_options.domain_type {
+            AbstractDomainType::Interval => {
+                self.analyze_in::<ApronInterval>(def_id, summary_only);
+            }
+            AbstractDomainType::Octagon => {
+                self.analyze_in::<ApronOctagon>(def_id, summary_only);
+            }
+            AbstractDomainType::Polyhedra => {
+                self.analyze_in::<ApronPolyhedra>(def_id, summary_only);
+            }
+            AbstractDomainType::LinearEqualities => {
+                self.analyze_in::<ApronLinearEqualities>(def_id, summary_only);
+            }
+            AbstractDomainType::PplPolyhedra => {
+                self.analyze_in::<ApronPplPolyhedra>(def_id, summary_only);
+            }
+            AbstractDomainType::PplLinearCongruences => {
+                self.analyze_in::<ApronPplLinearCongruences>(def_id, summary_only);
+            }
+            AbstractDomainType::PkgridPolyhedraLinCongruences => {
+                self.analyze_in::<ApronPkgridPolyhedraLinCongruences>(def_id, summary_only);
+            }
+            AbstractDomainType::WrappingInteger => {
+                self.analyze_in::<ApronWrappingInteger>(def_id, summary_only);
+            }
+        }
+    }
+
+    fn analyze_in<DomainType>(&mut self, def_id: DefId, summary_only: bool)
+    where
+        DomainType: ApronDomainType,
+        ApronAbstractDomain<DomainType>: GetManagerTrait,
+    {
+        let abstract_domain = AbstractDomain::<DomainType>::default();
+        if !summary_only {
+            self.analyze_function(def_id, abstract_domain);
+            return;
+        }
+        if !self.context.reachable_functions.contains(&def_id) {
+            return;
+        }
+        let mut wto_visitor =
+            WtoFixPointIterator::new(&mut self.context, def_id, abstract_domain, 0, vec![]);
+        wto_visitor.init_promote_constants();
+        wto_visitor.run();
+        wto_visitor.record_summary();
+        // The bugs are reported when the function is checked with the final summaries
+        wto_visitor.cancel_diagnostics();
+        self.context.path_interner.collect_garbage();
+    }
+
+    /// Analyze the reachable functions with unknown arguments in the bottom-up order of the call graph, and
+    /// record their summaries, which are used by the callers analyzed later instead of their bodies
+    /// The summaries of a recursive component are computed by `summarize_recursive_component` before its
+    /// functions are checked, so that the bugs are found with the final summaries
+    fn analyze_bottom_up(
+        &mut self,
+        call_graph: &HashMap<DefId, HashSet<DefId>>,
+        components: &[Vec<DefId>],
+    ) {
+        let tcx = self.context.tcx;
+        for component in components {
+            let functions = self.summarized_functions(component);
+            let is_recursive =
+                component.len() > 1 || call_graph[&component[0]].contains(&component[0]);
+            if is_recursive {
+                self.summarize_recursive_component(&functions);
+            }
+            for def_id in functions {
+                info!("Start Analyzing Function: {}", tcx.def_path_str(def_id));
+                self.analyze_entry_point(def_id);
+            }
+        }
+    }
+
+    /// The functions of `component` that are analyzed with unknown arguments to compute their summaries
+    /// Closures and generic functions are analyzed in the context of their callers, where the captured variables
+    /// and the generic arguments are known
+    fn summarized_functions(&self, component: &[DefId]) -> Vec<DefId> {
+        let tcx = self.context.tcx;
+        component
+            .iter()
+            .copied()
+            .filter(|def_id| {
+                self.context.entry_points.contains(def_id)
+                    || (tcx.def_kind(*def_id) != DefKind::Closure
+                        && !tcx.generics_of(*def_id).requires_monomorphization(tcx))
+            })
+            .collect()
+    }
+
+    /// Compute the summaries of the functions of a recursive component, by analyzing them with unknown arguments
+    /// in turn until no summary changes, where the calls between them use the summaries computed so far
+    /// The summaries start at bottom, i.e., only the base cases return, instead of top, which could never be
+    /// refined. They are joined for the first `widening_delay` iterations and widened afterwards to ensure
+    /// termination
+    fn summarize_recursive_component(&mut self, functions: &[DefId]) {
+        let tcx = self.context.tcx;
+        let domain = self.context.analysis_options.domain_type;
+        let widening_delay = self.context.analysis_options.widening_delay;
+        // Only the functions that return integers have summaries
+        let functions: Vec<DefId> = functions
+            .iter()
+            .copied()
+            .filter(|def_id| {
+                self.context
+                    .get_wto(*def_id)
+                    .get_mir()
+                    .return_ty()
+                    .is_integral()
+            })
+            .collect();
+        for def_id in functions.iter() {
+            self.context.summaries.insert(
+                utils::summary_key_str(tcx, *def_id),
+                FunctionSummary::new(domain, Interval::bottom()),
+            );
+        }
+
+        let mut iteration = 0;
+        loop {
+            iteration += 1;
+            let mut is_stable = true;
+            for def_id in functions.iter() {
+                info!(
+                    "Start Analyzing Recursive Function: {}, iteration: {}",
+                    tcx.def_path_str(*def_id),
+                    iteration
+                );
+                let summary_key = utils::summary_key_str(tcx, *def_id);
+                let before = self.context.summaries[&summary_key].clone();
+                self.summarize_function(*def_id);
+                let after = self.context.summaries[&summary_key].clone();
+                // Make sure the summaries only grow, and converge after `widening_delay` iterations
+                let next = if iteration <= widening_delay {
+                    before.join(&after)
+                } else {
+                    before.widening_with(&after)
+                };
+                if next != before {
+                    is_stable = false;
+                }
+                self.context.summaries.insert(summary_key, next);
+            }
+            if is_stable {
+                break;
+            }
+        }
+    }
+
+    /// Write the inferred loop invariants into `file_name`, sorted by function name and line number
+    /// Each loop is annotated in the form `// inv: x in [0, 100], y >= 0`
+    fn export_loop_invariants(&self, file_name: &str) {
+        let source_map = self.context.session.source_map();
+        let mut invariants: Vec<(String, usize, &String)> = self
+            .context
+            .loop_invariants
+            .iter()
+            .map(|((def_id, _bb), (span, invariant))| {
+                let func_name = self.context.tcx.def_path_str(*def_id);
+                let line = source_map.lookup_char_pos(span.lo()).line;
+                (func_name, line, invariant)
+            })
+            .collect();
+        invariants.sort();
+
+        let mut output = String::new();
+        for (func_name, line, invariant) in invariants {
+            output.push_str(&format!("// {}, line {}\n", func_name, line));
+            output.push_str(&format!("// inv: {}\n", invariant));
+        }
+
+        if let Err(e) = std::fs::write(file_name, output) {
+            error!("Failed to export loop invariants to {}: {}", file_name, e);
+        } else {
+            info!("Loop invariants have been exported to {}", file_name);
+        }
+    }
+
+    /// Format a diagnosis as a workflow command of GitHub Actions, e.g.,
+    /// `::warning file=src/main.rs,line=3,col=5::[MirChecker] Possible error: ...`
+    fn format_github_actions(diag: &Diagnostic<'_>, source_map: &SourceMap) -> String {
+        // Special characters must be percent-encoded, see
+        // https://github.com/actions/toolkit/blob/main/packages/core/src/command.ts
+        let escape_data = |s: &str| {
+            s.replace('%', "%25")
+                .replace('\r', "%0D")
+                .replace('\n', "%0A")
+        };
+        let escape_property = |s: &str| escape_data(s).replace(':', "%3A").replace(',', "%2C");
+        let command = if diag.is_error() {
+            "error"
+        } else if diag.is_note() {
+            "notice"
+        } else {
+            "warning"
+        };
+        let properties = match diag.location(source_map) {
+            Some((file, line, col)) => {
+                format!(" file={},line={},col={}", escape_property(&file), line, col)
+            }
+            None => String::new(),
+        };
+        format!(
+            "::{}{}::{}",
+            command,
+            properties,
+            escape_data(&diag.message())
+        )
+    }
+
+    /// Format a diagnosis as a GCC-style line that can be parsed by the `$gcc` problem matcher of VS Code,
+    /// e.g., `src/main.rs:3:5: warning: [Index] [MirChecker] Possible error: ...`
+    fn format_vscode(diag: &Diagnostic<'_>, source_map: &SourceMap) -> String {
+        let repr = diag.pretty_print(source_map);
+        let severity = if diag.is_error() { "error" } else { "warning" };
+        format!(
+            "{}:{}:{}: {}: [{}] {}",
+            repr.file,
+            repr.line,
+            repr.col,
+            severity,
+            repr.cause,
+            repr.message.replace('\n', " ")
+        )
+    }
+
+    /// Format a diagnosis as an issue of the code quality report of GitLab CI, see
+    /// https://docs.gitlab.com/ee/ci/testing/code_quality.html#implement-a-custom-tool
+    fn format_gitlab(diag: &Diagnostic<'_>, source_map: &SourceMap) -> serde_json::Value {
+        let repr = diag.pretty_print(source_map);
+        // GitLab uses the fingerprint to track an issue across pipelines
+        let mut hasher = DefaultHasher::new();
+        (&repr.message, &repr.file, repr.line, repr.col).hash(&mut hasher);
+        serde_json::json!({
+            "description": repr.message,
+            "check_name": repr.cause.to_string(),
+            "fingerprint": format!("{:016x}", hasher.finish()),
+            "severity": if diag.is_error() { "major" } else if diag.is_note() { "info" } else { "minor" },
+            "location": { "path": repr.file, "lines": { "begin": repr.line } },
+        })
+    }
+
+    /// Convert a diagnosis into a row of the `diagnostics` table, see `sqlite_export`
+    fn format_sqlite(diag: &Diagnostic<'_>, source_map: &SourceMap) -> DiagnosticRecord {
+        let repr = diag.pretty_print(source_map);
+        DiagnosticRecord {
+            file: repr.file,
+            line: repr.line,
+            col: repr.col,
+            cause: repr.cause.to_string(),
+            is_memory_safety: diag.is_memory_safety,
+            message: repr.message,
+        }
+    }
+
+    /// Append the diagnoses to the database given by `--output-file`, the compilation fails if the database cannot
+    /// be written, so that a CI job does not silently lose its diagnoses
+    fn export_diagnostics(&self, records: &[DiagnosticRecord]) {
+        let options = &self.context.analysis_options;
+        let file_name = options
+            .output_file
+            .as_deref()
+            .expect("`--output-format sqlite` requires `--output-file`");
+        let crate_name = options
+            .output_crate_name
+            .clone()
+            .unwrap_or_else(|| self.context.tcx.crate_name(LOCAL_CRATE).to_string());
+        let run = AnalysisRun::current(crate_name);
+        match sqlite_export::export_diagnostics(file_name, &run, records) {
+            Ok(count) => info!(
+                "{} diagnoses of run {} have been exported to {}",
+                count, run.run_id, file_name
+            ),
+            Err(e) => {
+                self.context.session.dcx().err(format!(
+                    "[MirChecker] failed to export diagnoses to {}: {}",
+                    file_name, e
+                ));
+            }
+        }
+    }
+
+    /// Write the inferred preconditions into `file_name` as a JSON array
+    /// Each element is of the form `{ "function": "foo", "requires": "x >= 0 && x < 100" }`
+    fn export_contracts(&self, file_name: &str) {
+        let mut contracts: Vec<(String, &String)> = self
+            .context
+            .contracts
+            .iter()
+            .map(|(def_id, requires)| (self.context.tcx.def_path_str(*def_id), requires))
+            .collect();
+        contracts.sort();
+
+        let contracts: Vec<serde_json::Value> = contracts
+            .into_iter()
+            .map(|(func_name, requires)| {
+                serde_json::json!({ "function": func_name, "requires": requires })
+            })
+            .collect();
+        let output =
+            serde_json::to_string_pretty(&contracts).expect("failed to serialize contracts");
+
+        if let Err(e) = std::fs::write(file_name, output) {
+            error!("Failed to export contracts to {}: {}", file_name, e);
+        } else {
+            info!("Contracts have been exported to {}", file_name);
+        }
+    }
+
+    /// Write the call graph into `file_name` in the Graphviz dot format, where the entry points are boxes
+    fn dump_call_graph(&self, call_graph: &HashMap<DefId, HashSet<DefId>>, file_name: &str) {
+        let tcx = self.context.tcx;
+        let mut functions: Vec<(String, DefId)> = call_graph
+            .keys()
+            .map(|def_id| (tcx.def_path_str(*def_id), *def_id))
+            .collect();
+        functions.sort_by(|a, b| a.0.cmp(&b.0));
+        let node_ids: HashMap<DefId, usize> = functions
+            .iter()
+            .enumerate()
+            .map(|(i, (_, def_id))| (*def_id, i))
+            .collect();
+
+        let mut output = String::from("digraph callgraph {\n");
+        for (i, (func_name, def_id)) in functions.iter().enumerate() {
+            let shape = if self.context.entry_points.contains(def_id) {
+                "box"
+            } else {
+                "ellipse"
+            };
+            output.push_str(&format!(
+                "    n{} [label=\"{}\", shape={}];\n",
+                i,
+                func_name.replace('\\', "\\\\").replace('"', "\\\""),
+                shape
+            ));
+        }
+        for (i, (_, def_id)) in functions.iter().enumerate() {
+            let mut callees: Vec<usize> = call_graph[def_id]
+                .iter()
+                .filter_map(|callee| node_ids.get(callee).copied())
+                .collect();
+            callees.sort_unstable();
+            for callee in callees {
+                output.push_str(&format!("    n{} -> n{};\n", i, callee));
+            }
+        }
+        output.push_str("}\n");
+
+        if let Err(e) = std::fs::write(file_name, output) {
+            error!("Failed to dump the call graph to {}: {}", file_name, e);
+        } else {
+            info!("The call graph has been dumped to {}", file_name);
+        }
+    }
+
+    /// Write the data flow graphs into `file_name` in the Graphviz dot format, one subgraph per function
+    fn dump_data_flow_graphs(
+        &self,
+        data_flow_graphs: &HashMap<DefId, DataFlowGraph>,
+        file_name: &str,
+    ) {
+        let tcx = self.context.tcx;
+        let mut functions: Vec<(String, &DataFlowGraph)> = data_flow_graphs
+            .iter()
+            .map(|(def_id, graph)| (tcx.def_path_str(*def_id), graph))
+            .collect();
+        functions.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut output = String::from("digraph dataflow {\n");
+        for (i, (func_name, graph)) in functions.iter().enumerate() {
+            output.push_str(&graph.to_dot(func_name, &format!("f{}", i)));
+        }
+        output.push_str("}\n");
+
+        if let Err(e) = std::fs::write(file_name, output) {
+            error!(
+                "Failed to dump the data flow graphs to {}: {}",
+                file_name, e
+            );
+        } else {
+            info!("The data flow graphs have been dumped to {}", file_name);
+        }
+    }
+}
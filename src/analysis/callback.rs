@@ -1,7 +1,9 @@
 use crate::analysis::analyzer::analysis_trait::StaticAnalysis;
 use crate::analysis::analyzer::numerical_analysis::NumericalAnalysis;
 use crate::analysis::global_context::GlobalContext;
-use crate::analysis::option::AnalysisOption;
+use crate::analysis::option::{AnalysisOption, AnalysisType};
+use crate::analysis::summary;
+use crate::checker::{ffi_checker, taint_sink_checker};
 use log::{error, info};
 use rustc_driver::Compilation;
 use rustc_interface::interface;
@@ -34,6 +36,35 @@ impl rustc_driver::Callbacks for MirCheckerCallbacks {
         info!("Source file: {}", self.source_name);
     }
 
+    /// Called after parsing the crate root, before the macros are expanded and the tools are registered
+    fn after_crate_root_parsing<'tcx>(
+        &mut self,
+        compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        // Allow tool attributes like `#[mir_checker::taint_source]`, unless the crate root already does
+        // `tcx.hir().krate_attrs()` is only available after the tools are registered, so the inner attributes of
+        // the parsed crate root are inspected instead, which are the same attributes
+        let mut krate = queries.parse().unwrap();
+        let crate_attrs: Vec<(String, Vec<String>)> = krate
+            .attrs
+            .iter()
+            .filter_map(|attr| {
+                let names = attr
+                    .meta_item_list()
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(|item| item.ident())
+                    .map(|ident| ident.to_string())
+                    .collect();
+                Some((attr.ident()?.to_string(), names))
+            })
+            .collect();
+        let attrs = tool_crate_attrs(&crate_attrs);
+        rustc_builtin_macros::cmdline_attrs::inject(&mut krate, &compiler.sess.psess, &attrs);
+        Compilation::Continue
+    }
+
     /// Called after analysis. Return value instructs the compiler whether to
     /// continue the compilation afterwards (defaults to `Compilation::Continue`)
     fn after_analysis<'compiler, 'tcx>(
@@ -55,11 +86,14 @@ impl MirCheckerCallbacks {
         compiler: &'compiler interface::Compiler,
         tcx: TyCtxt<'tcx>,
     ) {
-        if self.source_name.contains("/libcore")
-            || self.source_name.contains("/compiler_builtins")
-            || self.source_name.contains("/liballoc")
-            || self.source_name.contains("/macro")
-            || self.source_name.contains("/libc")
+        // The crates of the sysroot are skipped, unless `no_std` is set, where they may be built from source
+        // together with the analyzed crate, e.g., by `-Zbuild-std` for an embedded target
+        if !self.analysis_options.no_std
+            && (self.source_name.contains("/libcore")
+                || self.source_name.contains("/compiler_builtins")
+                || self.source_name.contains("/liballoc")
+                || self.source_name.contains("/macro")
+                || self.source_name.contains("/libc"))
         {
             info!(
                 "Find filename that should skip the analysis: {}",
@@ -69,9 +103,46 @@ impl MirCheckerCallbacks {
         }
 
         // Initialize global analysis context
-        if let Some(global_context) =
-            GlobalContext::new(&compiler.sess, tcx, self.analysis_options.clone())
-        {
+        let entry_points = GlobalContext::find_entry_points(tcx, &self.analysis_options);
+        if let Some(mut global_context) = GlobalContext::new(
+            &compiler.sess,
+            tcx,
+            self.analysis_options.clone(),
+            entry_points,
+        ) {
+            if self.analysis_options.dump_analysis_order {
+                for def_id in global_context.functions_in_analysis_order() {
+                    println!("{}", tcx.def_path_str(def_id));
+                }
+                return;
+            }
+            // Load the summaries of the crates that have been analyzed before, e.g., the dependencies of
+            // this crate in the same workspace
+            if let Some(cache_dir) = &self.analysis_options.cache_dir {
+                global_context.dependency_summaries = summary::load_summaries(cache_dir);
+            }
+            // The stub file has been validated when the options are parsed
+            if let Some(ffi_stubs) = &self.analysis_options.ffi_stubs {
+                match ffi_checker::load_ffi_stubs(ffi_stubs) {
+                    Ok(contracts) => global_context.ffi_contracts = contracts,
+                    Err(e) => error!("Failed to load the FFI contracts from {}: {}", ffi_stubs, e),
+                }
+            }
+            // The configuration has been validated when the options are parsed
+            if self.analysis_options.analysis_type == AnalysisType::Taint {
+                match taint_sink_checker::load_taint_config(&self.analysis_options.config) {
+                    Ok(config) => {
+                        global_context.taint_source_functions =
+                            taint_sink_checker::resolve_functions(tcx, &config.sources);
+                        global_context.taint_sink_functions =
+                            taint_sink_checker::resolve_functions(tcx, &config.sinks);
+                    }
+                    Err(e) => error!(
+                        "Failed to load the taint sources and sinks from {}: {}",
+                        self.analysis_options.config, e
+                    ),
+                }
+            }
             // Initialize numerical analyzer
             let numerical_analysis = NumericalAnalysis::new(global_context);
             // Run analyzer
@@ -80,6 +151,21 @@ impl MirCheckerCallbacks {
                     "Numerical Analysis Completed: {} ms",
                     analysis_result.analysis_time.as_millis()
                 );
+                info!(
+                    "Reachable functions: {}, unreachable functions: {}",
+                    analysis_result.reachable_functions,
+                    analysis_result.unreachable_functions.len()
+                );
+                for func_name in analysis_result.unreachable_functions.iter() {
+                    info!("Unreachable function: {}", func_name);
+                }
+                info!("Pruned branches: {}", analysis_result.pruned_branches);
+                if analysis_result.unsupported_patterns > 0 {
+                    warn!(
+                        "{} unsupported MIR patterns are skipped, the results may be imprecise",
+                        analysis_result.unsupported_patterns
+                    );
+                }
             } else {
                 error!("Numerical Analysis Failed");
             }
@@ -88,3 +174,23 @@ impl MirCheckerCallbacks {
         }
     }
 }
+
+/// The crate attributes that register `mir_checker` as a tool, so that the analyzed crate can use tool attributes
+/// like `#[mir_checker::taint_source]`. The attributes that the crate root already has are left out, because
+/// enabling a feature or registering a tool twice is an error. `crate_attrs` are the inner attributes of the crate
+/// root, each with the names in its list, e.g., `("feature", ["register_tool"])` for `#![feature(register_tool)]`
+pub fn tool_crate_attrs(crate_attrs: &[(String, Vec<String>)]) -> Vec<String> {
+    let has_attr = |attr: &str, name: &str| {
+        crate_attrs
+            .iter()
+            .any(|(attr_name, names)| attr_name == attr && names.iter().any(|n| n == name))
+    };
+    let mut attrs = Vec::new();
+    if !has_attr("feature", "register_tool") {
+        attrs.push("feature(register_tool)".to_string());
+    }
+    if !has_attr("register_tool", "mir_checker") {
+        attrs.push("register_tool(mir_checker)".to_string());
+    }
+    attrs
+}
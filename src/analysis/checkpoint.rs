@@ -0,0 +1,333 @@
+use crate::analysis::memory::constant_value::ConstantValue;
+use crate::analysis::memory::expression::{Expression, ExpressionType};
+use crate::analysis::memory::path::{Path, PathEnum, PathSelector};
+use crate::analysis::memory::symbolic_value::SymbolicValue;
+use crate::analysis::mir_visitor::body_visitor::WtoFixPointIterator;
+use crate::analysis::numerical::apron_domain::{
+    ApronAbstractDomain, ApronDomainType, ApronInterval, ApronLinearEqualities, ApronOctagon,
+    ApronPkgridPolyhedraLinCongruences, ApronPolyhedra, ApronPplLinearCongruences,
+    ApronPplPolyhedra, ApronWrappingInteger, GetManagerTrait,
+};
+use crate::analysis::numerical::linear_constraint::{
+    LinearConstraint, LinearConstraintSystem, LinearExpression,
+};
+use crate::analysis::option::{AbstractDomainType, AnalysisOption};
+use crate::analysis::z3_solver::{SmtResult, Z3Solver};
+use crate::checker::assertion_checker::CheckerResult;
+use rug::Integer;
+use rustc_middle::mir;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::rc::Rc;
+
+/// The version of the checkpoint format, checkpoints are only read by the same minor version of MirChecker
+pub const CHECKPOINT_VERSION: &str = concat!(
+    env!("CARGO_PKG_VERSION_MAJOR"),
+    ".",
+    env!("CARGO_PKG_VERSION_MINOR")
+);
+
+/// The name of the file that the data of `function` is written into, where the characters that cannot appear in a
+/// file name are replaced by `_`, e.g., `foo__bar` for `foo::bar`
+pub fn function_file_name(function: &str) -> String {
+    function
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// An assertion of a function, together with the condition that must hold for it to succeed
+#[derive(Clone, Debug)]
+pub struct CheckpointAssertion {
+    // The basic block that is terminated by the assertion
+    pub block: usize,
+
+    // The location of the assertion, i.e., `file:line:col`
+    pub location: String,
+
+    // The message of the assertion, with the variable names recovered
+    pub message: String,
+
+    // The condition over the numerical variables, `None` if it is not linear
+    pub condition: Option<LinearConstraintSystem>,
+}
+
+/// The numerical state of a function after its fixed-point is reached, which is enough to run the assertion checker
+/// again without compiling the crate, e.g., `{"version": "0.1", "function": "main", "domain": "interval",
+/// "states": {"3": <state>}, "assertions": [...]}`, where each state is written by `ApronAbstractDomain::to_json`
+#[derive(Clone, Debug)]
+pub struct Checkpoint {
+    pub function: String,
+    pub domain: AbstractDomainType,
+
+    // The post-condition of each reachable basic block
+    pub states: BTreeMap<usize, serde_json::Value>,
+
+    pub assertions: Vec<CheckpointAssertion>,
+}
+
+impl Checkpoint {
+    /// Take a checkpoint of the function analyzed by `body_visitor`, which must have reached its fixed-point
+    pub fn new<DomainType>(body_visitor: &WtoFixPointIterator<'_, '_, '_, DomainType>) -> Self
+    where
+        DomainType: ApronDomainType,
+        ApronAbstractDomain<DomainType>: GetManagerTrait,
+    {
+        let states = body_visitor
+            .post
+            .iter()
+            .map(|(bb, state)| (bb.index(), state.numerical_domain.to_dense().to_json()))
+            .collect();
+        let source_map = body_visitor.context.session.source_map();
+        let mut assertions = Vec::new();
+        for (bb, bb_data) in body_visitor.wto.basic_blocks.iter_enumerated() {
+            let term = bb_data.terminator();
+            if let mir::TerminatorKind::Assert {
+                cond,
+                expected,
+                msg,
+                ..
+            } = &term.kind
+            {
+                let cond_val = cond
+                    .place()
+                    .and_then(|place| body_visitor.place_to_abstract_value.get(&place));
+                if let Some(cond_val) = cond_val {
+                    let loc = source_map.lookup_char_pos(term.source_info.span.lo());
+                    assertions.push(CheckpointAssertion {
+                        block: bb.index(),
+                        location: format!(
+                            "{}:{}:{}",
+                            loc.file.name.prefer_local(),
+                            loc.line,
+                            loc.col.0 + 1
+                        ),
+                        message: body_visitor.recover_var_name(msg),
+                        condition: Self::get_condition(body_visitor, msg, cond_val, *expected),
+                    });
+                }
+            }
+        }
+        Self {
+            function: body_visitor.context.tcx.def_path_str(body_visitor.def_id),
+            domain: body_visitor.context.analysis_options.domain_type,
+            states,
+            assertions,
+        }
+    }
+
+    /// The linear constraints that hold if `cond == expected`, which mirrors `AssertionChecker`
+    fn get_condition<DomainType>(
+        body_visitor: &WtoFixPointIterator<'_, '_, '_, DomainType>,
+        msg: &mir::AssertKind<mir::Operand<'_>>,
+        cond: &Rc<SymbolicValue>,
+        expected: bool,
+    ) -> Option<LinearConstraintSystem>
+    where
+        DomainType: ApronDomainType,
+        ApronAbstractDomain<DomainType>: GetManagerTrait,
+    {
+        match &cond.expression {
+            Expression::CompileTimeConstant(ConstantValue::Int(constant)) => {
+                return if constant == &Integer::from(expected) {
+                    Some(LinearConstraint::new_true().into())
+                } else {
+                    Some(LinearConstraint::new_false().into())
+                };
+            }
+            // The compiler generated bit indicating overflow: `path.1`, the value is in `path.0`
+            Expression::Variable { path, .. } if matches!(msg, mir::AssertKind::Overflow(..)) => {
+                if let PathEnum::QualifiedPath {
+                    qualifier,
+                    selector,
+                    ..
+                } = &path.value
+                {
+                    if **selector == PathSelector::Field(1) {
+                        let value_path = Path::new_field(qualifier.clone(), 0);
+                        let rustc_type = body_visitor
+                            .type_visitor
+                            .get_cached_path_type(&value_path)?;
+                        let exp_type: ExpressionType = rustc_type.kind().into();
+                        let mut csts = LinearConstraintSystem::default();
+                        csts.add(LinearConstraint::LessEq(
+                            LinearExpression::default() - exp_type.max_value_int()
+                                + value_path.clone(),
+                        ));
+                        csts.add(LinearConstraint::LessEq(
+                            LinearExpression::default() + exp_type.min_value_int() - value_path,
+                        ));
+                        return Some(csts);
+                    }
+                }
+            }
+            _ => {}
+        }
+        let csts = LinearConstraintSystem::try_from(cond.clone()).ok()?;
+        if expected {
+            Some(csts)
+        } else if csts.size() == 1 {
+            csts.into_iter().next().map(|cst| cst.negate().into())
+        } else {
+            None
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        let states: serde_json::Map<String, serde_json::Value> = self
+            .states
+            .iter()
+            .map(|(bb, state)| (bb.to_string(), state.clone()))
+            .collect();
+        let assertions: Vec<serde_json::Value> = self
+            .assertions
+            .iter()
+            .map(|assertion| {
+                serde_json::json!({
+                    "block": assertion.block,
+                    "location": assertion.location,
+                    "message": assertion.message,
+                    "condition": assertion.condition.as_ref().map(LinearConstraintSystem::to_json),
+                })
+            })
+            .collect();
+        serde_json::json!({
+            "version": CHECKPOINT_VERSION,
+            "function": self.function,
+            "domain": self.domain.name(),
+            "states": states,
+            "assertions": assertions,
+        })
+    }
+
+    pub fn from_json(value: &serde_json::Value) -> Result<Self, String> {
+        let version = value.get("version").and_then(|v| v.as_str());
+        if version != Some(CHECKPOINT_VERSION) {
+            return Err(format!(
+                "the checkpoint is written by version {}, but only version {} is supported",
+                version.unwrap_or("unknown"),
+                CHECKPOINT_VERSION
+            ));
+        }
+        Self::from_json_fields(value).ok_or_else(|| String::from("ill-formed checkpoint"))
+    }
+
+    fn from_json_fields(value: &serde_json::Value) -> Option<Self> {
+        let function = value.get("function")?.as_str()?.to_string();
+        let domain = AnalysisOption::get_domain_type(value.get("domain")?.as_str()?)?;
+        let mut states = BTreeMap::new();
+        for (bb, state) in value.get("states")?.as_object()? {
+            states.insert(bb.parse::<usize>().ok()?, state.clone());
+        }
+        let mut assertions = Vec::new();
+        for assertion in value.get("assertions")?.as_array()? {
+            let condition = match assertion.get("condition")? {
+                serde_json::Value::Null => None,
+                condition => Some(LinearConstraintSystem::from_json(condition)?),
+            };
+            assertions.push(CheckpointAssertion {
+                block: assertion.get("block")?.as_u64()? as usize,
+                location: assertion.get("location")?.as_str()?.to_string(),
+                message: assertion.get("message")?.as_str()?.to_string(),
+                condition,
+            });
+        }
+        Some(Self {
+            function,
+            domain,
+            states,
+            assertions,
+        })
+    }
+
+    /// Write the checkpoint into `<checkpoint_dir>/<function>.json`, see `function_file_name`
+    pub fn store(&self, checkpoint_dir: &str) {
+        let name = function_file_name(&self.function);
+        let output = serde_json::to_string_pretty(&self.to_json())
+            .expect("failed to serialize the checkpoint");
+        let file_path = std::path::Path::new(checkpoint_dir).join(format!("{}.json", name));
+        if let Err(e) =
+            std::fs::create_dir_all(checkpoint_dir).and_then(|_| std::fs::write(&file_path, output))
+        {
+            error!("Failed to store the checkpoint to {:?}: {}", file_path, e);
+        } else {
+            info!(
+                "Stored the checkpoint of {} to {:?}",
+                self.function, file_path
+            );
+        }
+    }
+
+    pub fn load(file_path: &str) -> Result<Self, String> {
+        let content = std::fs::read_to_string(file_path).map_err(|e| e.to_string())?;
+        let value =
+            serde_json::from_str::<serde_json::Value>(&content).map_err(|e| e.to_string())?;
+        Self::from_json(&value)
+    }
+
+    /// Check each assertion against the state of its basic block, in the abstract domain the checkpoint is taken in
+    /// The assertions in unreachable basic blocks are skipped, as the assertion checker does
+    pub fn replay(&self) -> Result<Vec<(&CheckpointAssertion, CheckerResult)>, String> {
+        match self.domain {
+            AbstractDomainType::Interval => self.replay_in::<ApronInterval>(),
+            AbstractDomainType::Octagon => self.replay_in::<ApronOctagon>(),
+            AbstractDomainType::Polyhedra => self.replay_in::<ApronPolyhedra>(),
+            AbstractDomainType::LinearEqualities => self.replay_in::<ApronLinearEqualities>(),
+            AbstractDomainType::PplPolyhedra => self.replay_in::<ApronPplPolyhedra>(),
+            AbstractDomainType::PplLinearCongruences => {
+                self.replay_in::<ApronPplLinearCongruences>()
+            }
+            AbstractDomainType::PkgridPolyhedraLinCongruences => {
+                self.replay_in::<ApronPkgridPolyhedraLinCongruences>()
+            }
+            AbstractDomainType::WrappingInteger => self.replay_in::<ApronWrappingInteger>(),
+        }
+    }
+
+    fn replay_in<DomainType>(&self) -> Result<Vec<(&CheckpointAssertion, CheckerResult)>, String>
+    where
+        DomainType: ApronDomainType,
+        ApronAbstractDomain<DomainType>: GetManagerTrait,
+    {
+        let solver = Z3Solver::default();
+        let mut results = Vec::new();
+        for assertion in &self.assertions {
+            let state = match self.states.get(&assertion.block) {
+                Some(state) => {
+                    ApronAbstractDomain::<DomainType>::from_json(state).ok_or_else(|| {
+                        format!("ill-formed state of basic block {}", assertion.block)
+                    })?
+                }
+                None => continue,
+            };
+            let condition = match &assertion.condition {
+                Some(condition) => condition,
+                None => {
+                    results.push((assertion, CheckerResult::Warning));
+                    continue;
+                }
+            };
+            for cst in &LinearConstraintSystem::from(&state) {
+                solver.assert(&solver.get_as_z3_expression(cst));
+            }
+            let cond_expr = solver.get_csts_as_z3_expression(condition);
+            let result = match solver.solve_expression(&cond_expr) {
+                // The assertion always fails
+                SmtResult::Unsat => CheckerResult::Unsafe,
+                SmtResult::Sat => {
+                    if solver.solve_expression(&solver.make_not_z3_expression(cond_expr))
+                        == SmtResult::Unsat
+                    {
+                        CheckerResult::Safe
+                    } else {
+                        CheckerResult::Warning
+                    }
+                }
+                SmtResult::Unknown => CheckerResult::Warning,
+            };
+            solver.reset();
+            results.push((assertion, result));
+        }
+        Ok(results)
+    }
+}
@@ -0,0 +1,232 @@
+use std::collections::HashSet;
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// A value in a configuration file, only the TOML values that the configurations of the checkers use are supported
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConfigValue {
+    Boolean(bool),
+    Integer(i64),
+    String(String),
+    Array(Vec<ConfigValue>),
+}
+
+/// A key of a table with its value, and the line of the key, which the errors in the value are reported at
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConfigEntry {
+    pub key: String,
+    pub line: usize,
+    pub value: ConfigValue,
+}
+
+/// A table of a configuration file with the line of its header, the keys before the first header are in a table
+/// without a name
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConfigTable {
+    pub name: Option<String>,
+    pub line: usize,
+    pub entries: Vec<ConfigEntry>,
+}
+
+/// Parses a configuration file in a subset of TOML, which is shared by `--ffi-stubs` and the taint sources and
+/// sinks, e.g.,
+///
+/// ```toml
+/// [taint]
+/// sources = ["std::env::var"] # the untrusted inputs
+/// sinks = [
+///     "std::process::Command::arg",
+/// ]
+/// ```
+///
+/// The tables are `[name]` or `["name"]` headers, and the values are strings, integers, booleans and arrays of them,
+/// which may span several lines
+/// The error names the line of the first invalid token, e.g., `line 2: the string is not closed by `"``
+pub fn parse_config(content: &str) -> Result<Vec<ConfigTable>, String> {
+    let mut parser = Parser {
+        chars: content.chars().peekable(),
+        line: 1,
+    };
+    let mut tables = vec![ConfigTable {
+        name: None,
+        line: 1,
+        entries: Vec::new(),
+    }];
+    let mut names = HashSet::new();
+    let mut keys = HashSet::new();
+    loop {
+        parser.skip_whitespace(true);
+        match parser.chars.peek() {
+            None => break,
+            Some('[') => {
+                parser.chars.next();
+                parser.skip_whitespace(false);
+                let name = parser.parse_key()?;
+                parser.skip_whitespace(false);
+                if parser.chars.next_if_eq(&']').is_none() {
+                    return Err(parser.error("the table header is not closed by `]`"));
+                }
+                if name.is_empty() {
+                    return Err(parser.error("the table header does not name a table"));
+                }
+                if !names.insert(name.clone()) {
+                    return Err(parser.error(&format!("`{}` is defined twice", name)));
+                }
+                keys.clear();
+                tables.push(ConfigTable {
+                    name: Some(name),
+                    line: parser.line,
+                    entries: Vec::new(),
+                });
+            }
+            Some(_) => {
+                let key = parser.parse_key()?;
+                parser.skip_whitespace(false);
+                if key.is_empty() || parser.chars.next_if_eq(&'=').is_none() {
+                    return Err(parser.error("expected `key = value` or `[table]`"));
+                }
+                if !keys.insert(key.clone()) {
+                    return Err(parser.error(&format!("`{}` is defined twice", key)));
+                }
+                let line = parser.line;
+                parser.skip_whitespace(false);
+                let value = parser.parse_value()?;
+                tables
+                    .last_mut()
+                    .unwrap()
+                    .entries
+                    .push(ConfigEntry { key, line, value });
+            }
+        }
+        // A header or a key-value pair is followed by a comment or the end of the line
+        parser.skip_whitespace(false);
+        if !matches!(parser.chars.peek(), None | Some('\n')) {
+            return Err(parser.error("expected the end of the line"));
+        }
+    }
+    Ok(tables)
+}
+
+/// Reads the configuration file in a subset of TOML, keeping track of the current line for the errors
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, message: &str) -> String {
+        format!("line {}: {}", self.line, message)
+    }
+
+    /// Skips the spaces and the comments, and the line breaks if `newlines` is true
+    fn skip_whitespace(&mut self, newlines: bool) {
+        while let Some(c) = self.chars.peek() {
+            match c {
+                ' ' | '\t' | '\r' => {}
+                '\n' if newlines => self.line += 1,
+                '#' => {
+                    while self.chars.next_if(|c| *c != '\n').is_some() {}
+                    continue;
+                }
+                _ => break,
+            }
+            self.chars.next();
+        }
+    }
+
+    /// Parses a bare key, e.g., `non_null`, or a quoted key, e.g., `"strlen"`, which is empty if there is none
+    fn parse_key(&mut self) -> Result<String, String> {
+        if self.chars.peek() == Some(&'"') {
+            return self.parse_string();
+        }
+        let mut key = String::new();
+        while let Some(c) = self
+            .chars
+            .next_if(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '-')
+        {
+            key.push(c);
+        }
+        Ok(key)
+    }
+
+    fn parse_value(&mut self) -> Result<ConfigValue, String> {
+        match self.chars.peek() {
+            Some('"') => Ok(ConfigValue::String(self.parse_string()?)),
+            Some('[') => self.parse_array(),
+            _ => {
+                let mut token = String::new();
+                while let Some(c) = self
+                    .chars
+                    .next_if(|c| !c.is_whitespace() && !matches!(c, ',' | ']' | '#'))
+                {
+                    token.push(c);
+                }
+                match token.as_str() {
+                    "" => Err(self.error("expected a value")),
+                    "true" => Ok(ConfigValue::Boolean(true)),
+                    "false" => Ok(ConfigValue::Boolean(false)),
+                    _ => token
+                        .replace('_', "")
+                        .parse()
+                        .map(ConfigValue::Integer)
+                        .map_err(|_| {
+                            self.error(&format!(
+                                "`{}` is not a quoted string, an integer or a boolean",
+                                token
+                            ))
+                        }),
+                }
+            }
+        }
+    }
+
+    /// Parses a string in double quotes, with the escapes `\"`, `\\`, `\n` and `\t`
+    fn parse_string(&mut self) -> Result<String, String> {
+        self.chars.next();
+        let mut string = String::new();
+        loop {
+            match self.chars.next() {
+                None | Some('\n') => {
+                    return Err(self.error("the string is not closed by `\"`"));
+                }
+                Some('"') => return Ok(string),
+                Some('\\') => match self.chars.next() {
+                    Some('"') => string.push('"'),
+                    Some('\\') => string.push('\\'),
+                    Some('n') => string.push('\n'),
+                    Some('t') => string.push('\t'),
+                    _ => return Err(self.error("unsupported escape in the string")),
+                },
+                Some(c) => string.push(c),
+            }
+        }
+    }
+
+    /// Parses an array, whose items may be on several lines and followed by a comma
+    /// The error of an array that is not closed names the line it starts at
+    fn parse_array(&mut self) -> Result<ConfigValue, String> {
+        let start_line = self.line;
+        let not_closed = || format!("line {}: the array is not closed by `]`", start_line);
+        self.chars.next();
+        let mut items = Vec::new();
+        loop {
+            self.skip_whitespace(true);
+            match self.chars.peek() {
+                None => return Err(not_closed()),
+                Some(']') => break,
+                Some(_) => items.push(self.parse_value()?),
+            }
+            self.skip_whitespace(true);
+            match self.chars.peek() {
+                None => return Err(not_closed()),
+                Some(',') => {
+                    self.chars.next();
+                }
+                Some(']') => break,
+                Some(_) => return Err(self.error("expected `,` or `]` after an item of the array")),
+            }
+        }
+        self.chars.next();
+        Ok(ConfigValue::Array(items))
+    }
+}
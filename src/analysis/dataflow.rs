@@ -0,0 +1,191 @@
+use crate::analysis::memory::path::Path;
+use rustc_middle::mir;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+/// The data flow graph of a function, where the nodes are the paths of the places in its MIR body, and an edge
+/// `source -> target` means that the value of `source` flows into `target`
+/// The graph is built from the MIR body without the abstract state, so it is flow-insensitive, and the elements
+/// of an array or a slice are represented by the array or the slice itself
+#[derive(Clone, Debug, Default)]
+pub struct DataFlowGraph {
+    // The paths that the value of each path flows into
+    successors: HashMap<Rc<Path>, HashSet<Rc<Path>>>,
+    // The paths whose values flow into each path
+    predecessors: HashMap<Rc<Path>, HashSet<Rc<Path>>>,
+}
+
+impl DataFlowGraph {
+    /// Builds the data flow graph of `body`, by tracing the operands of the right-hand side of each assignment to
+    /// the assigned place, and the arguments of each call to its destination
+    pub fn new(body: &mir::Body<'_>) -> Self {
+        let mut graph = Self::default();
+        for block_data in body.basic_blocks.iter() {
+            for statement in &block_data.statements {
+                if let mir::StatementKind::Assign(box (place, rvalue)) = &statement.kind {
+                    let target = Self::get_path_for_place(place, body.arg_count);
+                    for source in Self::get_rvalue_sources(rvalue, body.arg_count) {
+                        graph.add_edge(source, target.clone());
+                    }
+                }
+            }
+            if let mir::TerminatorKind::Call {
+                args, destination, ..
+            } = &block_data.terminator().kind
+            {
+                // The return value of a callee may depend on any of its arguments
+                let target = Self::get_path_for_place(destination, body.arg_count);
+                for arg in args {
+                    if let Some(source) = Self::get_operand_source(&arg.node, body.arg_count) {
+                        graph.add_edge(source, target.clone());
+                    }
+                }
+            }
+        }
+        graph
+    }
+
+    /// Adds the edge `source -> target`, the nodes are created if they do not exist
+    pub fn add_edge(&mut self, source: Rc<Path>, target: Rc<Path>) {
+        self.predecessors
+            .entry(target.clone())
+            .or_default()
+            .insert(source.clone());
+        self.predecessors.entry(source.clone()).or_default();
+        self.successors.entry(target.clone()).or_default();
+        self.successors.entry(source).or_default().insert(target);
+    }
+
+    /// Returns all the nodes of the graph
+    pub fn nodes(&self) -> impl Iterator<Item = &Rc<Path>> {
+        self.successors.keys()
+    }
+
+    /// Returns the number of the edges of the graph
+    pub fn edge_count(&self) -> usize {
+        self.successors.values().map(|targets| targets.len()).sum()
+    }
+
+    /// Returns the paths that the value of `path` flows into directly
+    pub fn successors(&self, path: &Rc<Path>) -> impl Iterator<Item = &Rc<Path>> {
+        self.successors.get(path).into_iter().flatten()
+    }
+
+    /// Returns the paths whose values flow into `path` directly
+    pub fn predecessors(&self, path: &Rc<Path>) -> impl Iterator<Item = &Rc<Path>> {
+        self.predecessors.get(path).into_iter().flatten()
+    }
+
+    /// Returns true if the value of `source` flows into `target` through one or more edges
+    pub fn flows_to(&self, source: &Rc<Path>, target: &Rc<Path>) -> bool {
+        let mut visited = HashSet::new();
+        let mut worklist = vec![source];
+        while let Some(path) = worklist.pop() {
+            for successor in self.successors(path) {
+                if successor == target {
+                    return true;
+                }
+                if visited.insert(successor) {
+                    worklist.push(successor);
+                }
+            }
+        }
+        false
+    }
+
+    /// Writes the graph as a subgraph named `name` in the Graphviz dot format, the nodes and edges are sorted so that
+    /// the output is deterministic, and the node ids are prefixed with `prefix` to be unique among the subgraphs
+    pub fn to_dot(&self, name: &str, prefix: &str) -> String {
+        let mut nodes: Vec<(String, &Rc<Path>)> = self
+            .nodes()
+            .map(|path| (format!("{:?}", path), path))
+            .collect();
+        nodes.sort_by(|a, b| a.0.cmp(&b.0));
+        let node_ids: HashMap<&Rc<Path>, usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, (_, path))| (*path, i))
+            .collect();
+
+        let escape = |label: &str| label.replace('\\', "\\\\").replace('"', "\\\"");
+        let mut output = format!(
+            "    subgraph \"cluster_{}\" {{\n        label=\"{}\";\n",
+            prefix,
+            escape(name)
+        );
+        for (i, (label, _)) in nodes.iter().enumerate() {
+            output.push_str(&format!(
+                "        {}_{} [label=\"{}\"];\n",
+                prefix,
+                i,
+                escape(label)
+            ));
+        }
+        for (i, (_, path)) in nodes.iter().enumerate() {
+            let mut targets: Vec<usize> = self.successors(path).map(|t| node_ids[t]).collect();
+            targets.sort_unstable();
+            for target in targets {
+                output.push_str(&format!(
+                    "        {}_{} -> {}_{};\n",
+                    prefix, i, prefix, target
+                ));
+            }
+        }
+        output.push_str("    }\n");
+        output
+    }
+
+    /// The path of `place`, where the projections into the elements of arrays and slices are dropped, since the
+    /// index is not known without the abstract state
+    fn get_path_for_place(place: &mir::Place<'_>, arg_count: usize) -> Rc<Path> {
+        let base_path = Path::new_local_parameter_or_result(place.local.as_usize(), 0, arg_count);
+        place
+            .projection
+            .iter()
+            .fold(base_path, |path, elem| match elem {
+                mir::ProjectionElem::Deref => Path::new_deref(path),
+                mir::ProjectionElem::Field(field, _) => Path::new_field(path, field.index()),
+                _ => path,
+            })
+    }
+
+    fn get_operand_source(operand: &mir::Operand<'_>, arg_count: usize) -> Option<Rc<Path>> {
+        match operand {
+            mir::Operand::Copy(place) | mir::Operand::Move(place) => {
+                Some(Self::get_path_for_place(place, arg_count))
+            }
+            mir::Operand::Constant(..) => None,
+        }
+    }
+
+    /// The paths whose values flow into the result of `rvalue`
+    fn get_rvalue_sources(rvalue: &mir::Rvalue<'_>, arg_count: usize) -> Vec<Rc<Path>> {
+        let operand_source =
+            |operand: &mir::Operand<'_>| Self::get_operand_source(operand, arg_count);
+        match rvalue {
+            mir::Rvalue::Use(operand)
+            | mir::Rvalue::Repeat(operand, _)
+            | mir::Rvalue::Cast(_, operand, _)
+            | mir::Rvalue::UnaryOp(_, operand)
+            | mir::Rvalue::ShallowInitBox(operand, _) => {
+                operand_source(operand).into_iter().collect()
+            }
+            mir::Rvalue::BinaryOp(_, box (left, right))
+            | mir::Rvalue::CheckedBinaryOp(_, box (left, right)) => [left, right]
+                .into_iter()
+                .filter_map(operand_source)
+                .collect(),
+            mir::Rvalue::Aggregate(_, operands) => {
+                operands.iter().filter_map(operand_source).collect()
+            }
+            // A reference carries the value of the place it refers to
+            mir::Rvalue::Ref(_, _, place)
+            | mir::Rvalue::AddressOf(_, place)
+            | mir::Rvalue::Len(place)
+            | mir::Rvalue::Discriminant(place)
+            | mir::Rvalue::CopyForDeref(place) => vec![Self::get_path_for_place(place, arg_count)],
+            // E.g., `NullaryOp` and `ThreadLocalRef`, whose values do not depend on any place
+            _ => vec![],
+        }
+    }
+}
@@ -0,0 +1,107 @@
+// Compare the diagnostics of two runs, used by `mir-checker-diff`
+
+use serde_json::Value;
+
+/// How many lines a diagnostic may move between the runs and still match, e.g., after a line is inserted above it
+pub const LINE_DRIFT: usize = 2;
+
+/// A diagnostic read from a code quality report written by `--output-format gitlab`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiagnosticEntry {
+    pub file: String,
+    pub line: usize,
+    pub cause: String,
+    pub message: String,
+}
+
+/// The diagnostics of the new run compared with the old run
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct DiagnosticDiff {
+    /// The diagnostics of the new run that do not match any diagnostic of the old run, i.e., the regressions
+    pub new: Vec<DiagnosticEntry>,
+    /// The diagnostics of the old run that do not match any diagnostic of the new run
+    pub fixed: Vec<DiagnosticEntry>,
+    /// The pairs of matching diagnostics, the one of the old run first
+    pub unchanged: Vec<(DiagnosticEntry, DiagnosticEntry)>,
+}
+
+/// Parses a code quality report, i.e., a JSON array of issues like
+/// `{"description": ..., "check_name": "Index", "location": {"path": "src/main.rs", "lines": {"begin": 3}}}`
+pub fn parse_report(text: &str) -> Result<Vec<DiagnosticEntry>, String> {
+    let report: Value = serde_json::from_str(text).map_err(|e| e.to_string())?;
+    let issues = report
+        .as_array()
+        .ok_or_else(|| String::from("expected an array of diagnostics"))?;
+    issues
+        .iter()
+        .enumerate()
+        .map(|(index, issue)| {
+            let field = |value: Option<&Value>, name: &str| {
+                value
+                    .cloned()
+                    .ok_or_else(|| format!("diagnostic {} has no `{}`", index, name))
+            };
+            let location = field(issue.get("location"), "location")?;
+            let file = field(location.get("path"), "location.path")?;
+            let line = field(
+                location.get("lines").and_then(|lines| lines.get("begin")),
+                "location.lines.begin",
+            )?;
+            let cause = field(issue.get("check_name"), "check_name")?;
+            let message = field(issue.get("description"), "description")?;
+            match (file, line.as_u64(), cause, message) {
+                (Value::String(file), Some(line), Value::String(cause), Value::String(message)) => {
+                    Ok(DiagnosticEntry {
+                        file,
+                        line: line as usize,
+                        cause,
+                        message,
+                    })
+                }
+                _ => Err(format!("diagnostic {} has a field of a wrong type", index)),
+            }
+        })
+        .collect()
+}
+
+/// Matches the diagnostics of the two runs, two diagnostics match if they have the same file and cause, and their
+/// lines differ by at most `LINE_DRIFT`. The closest pairs are matched first, so that a diagnostic that did not move
+/// is not taken by a neighbouring one, and each diagnostic is matched at most once
+pub fn diff(old: &[DiagnosticEntry], new: &[DiagnosticEntry]) -> DiagnosticDiff {
+    let mut candidates: Vec<(usize, usize, usize)> = Vec::new();
+    for (new_index, new_entry) in new.iter().enumerate() {
+        for (old_index, old_entry) in old.iter().enumerate() {
+            let distance = new_entry.line.abs_diff(old_entry.line);
+            if new_entry.file == old_entry.file
+                && new_entry.cause == old_entry.cause
+                && distance <= LINE_DRIFT
+            {
+                candidates.push((distance, new_index, old_index));
+            }
+        }
+    }
+    candidates.sort();
+    let mut old_matched = vec![false; old.len()];
+    let mut new_matched: Vec<Option<usize>> = vec![None; new.len()];
+    for (_, new_index, old_index) in candidates {
+        if new_matched[new_index].is_none() && !old_matched[old_index] {
+            new_matched[new_index] = Some(old_index);
+            old_matched[old_index] = true;
+        }
+    }
+    let mut result = DiagnosticDiff::default();
+    for (new_entry, matched) in new.iter().zip(new_matched) {
+        match matched {
+            Some(old_index) => result
+                .unchanged
+                .push((old[old_index].clone(), new_entry.clone())),
+            None => result.new.push(new_entry.clone()),
+        }
+    }
+    for (old_entry, matched) in old.iter().zip(old_matched) {
+        if !matched {
+            result.fixed.push(old_entry.clone());
+        }
+    }
+    result
+}
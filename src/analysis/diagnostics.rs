@@ -1,25 +1,57 @@
 use rustc_errors::Diag as DiagnosticBuilder;
 use rustc_hir::def_id::DefId;
 use rustc_middle::mir;
+use rustc_span::source_map::SourceMap;
 use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::fmt;
 use std::marker::PhantomData;
 use std::ops::Deref;
 
 /// Define the cause of a diagnostic message
 /// Used to provide user options to suppress some specific kinds of warnings
 /// So that we can decrease the false-positive rate
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum DiagnosticCause {
-    Bitwise,    // Bit-wise overflow
-    Arithmetic, // Arithmetic overflow
-    Assembly,   // Inline assembly
-    Comparison, // Comparison operations
-    DivZero,    // Division by zero / remainder by zero
-    Memory,     // Memory-safety issues
-    Panic,      // Run into panic code
-    Index,      // Out-of-bounds access
-    Other,      // Other
+    Bitwise,        // Bit-wise overflow
+    Arithmetic,     // Arithmetic overflow
+    Assembly,       // Inline assembly
+    Comparison,     // Comparison operations
+    DivZero,        // Division by zero / remainder by zero
+    Memory,         // Memory-safety issues
+    Panic,          // Run into panic code
+    Index,          // Out-of-bounds access
+    FormatString,   // Format strings derived from taint sources
+    DeadBranch,     // Branches that are never taken, and code that is never reached
+    Termination,    // Loops that may not terminate
+    Deadlock,       // Locks that may be acquired in different orders
+    Channel,        // Sends that may block on a full channel
+    TaintSink,      // Arguments of taint sinks derived from taint sources
+    Other,          // Other
+    Custom(String), // User-defined categories of custom checkers, e.g., `Custom("unsafe-ffi")`
+}
+
+impl DiagnosticCause {
+    /// True if the diagnostics of cause `other` belong to this cause, the names of the custom causes are compared
+    /// case-insensitively, so that `--suppress_warnings custom:Unsafe-FFI` suppresses `Custom("unsafe-ffi")`
+    pub fn matches(&self, other: &DiagnosticCause) -> bool {
+        match (self, other) {
+            (DiagnosticCause::Custom(name), DiagnosticCause::Custom(other_name)) => {
+                name.eq_ignore_ascii_case(other_name)
+            }
+            _ => self == other,
+        }
+    }
+}
+
+/// The name of a cause in the output formats, i.e., the name of its variant, or the name of a custom cause
+impl fmt::Display for DiagnosticCause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiagnosticCause::Custom(name) => f.write_str(name),
+            cause => fmt::Debug::fmt(cause, f),
+        }
+    }
 }
 
 /// Extract the cause of a diagnostic message from an assertion statement
@@ -42,11 +74,44 @@ impl<O> From<&mir::AssertKind<O>> for DiagnosticCause {
             mir::AssertKind::DivisionByZero(..) | mir::AssertKind::RemainderByZero(..) => {
                 DiagnosticCause::DivZero
             }
-            _ => DiagnosticCause::Other,
+            // Do not use a wildcard here, so that new kinds of assertions in future rustc versions
+            // cannot be silently classified as `Other`
+            mir::AssertKind::ResumedAfterReturn(..)
+            | mir::AssertKind::ResumedAfterPanic(..)
+            | mir::AssertKind::MisalignedPointerDereference { .. } => DiagnosticCause::Other,
         }
     }
 }
 
+/// The content of a diagnosis rendered by `Diagnostic::pretty_print` without emitting it, which does not refer to
+/// the compiler, so it can be compared in tests and serialized by the output formats
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiagnosticRepr {
+    pub message: String,
+    // The file of the primary span, empty if the diagnosis has no span
+    pub file: String,
+    // The line number and the column number of the primary span, both start from 1
+    pub line: usize,
+    pub col: usize,
+    // E.g., `error`, `warning` or `note`
+    pub severity: String,
+    pub cause: DiagnosticCause,
+}
+
+impl DiagnosticRepr {
+    /// Serialize the diagnosis as a JSON object, whose cause is the name of the `DiagnosticCause`, see its `Display`
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "message": self.message,
+            "file": self.file,
+            "line": self.line,
+            "col": self.col,
+            "severity": self.severity,
+            "cause": self.cause.to_string(),
+        })
+    }
+}
+
 /// A diagnosis, which consists of the `DiagnosticBuilder` and more information about it
 // #[derive(Clone)]
 #[derive(Debug)]
@@ -58,10 +123,7 @@ pub struct Diagnostic<'compiler> {
 
 impl Clone for Diagnostic<'_> {
     fn clone(&self) -> Self {
-        let msg = match self.builder.deref().messages.get(0) {
-            Some((msg, _)) => msg.as_str().unwrap_or_default().to_string(),
-            None => String::new(),
-        };
+        let msg = self.message();
         let new_builder = DiagnosticBuilder::new(self.builder.dcx, self.builder.level(), msg);
         Self {
             builder: new_builder,
@@ -92,6 +154,48 @@ impl<'compiler> Diagnostic<'compiler> {
         self.builder.emit();
     }
 
+    /// Returns the primary message of the diagnostic
+    pub fn message(&self) -> String {
+        match self.builder.deref().messages.first() {
+            Some((msg, _)) => msg.as_str().unwrap_or_default().to_string(),
+            None => String::new(),
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.builder.level() == rustc_errors::Level::Error
+    }
+
+    pub fn is_note(&self) -> bool {
+        self.builder.level() == rustc_errors::Level::Note
+    }
+
+    /// Returns the file name, the line number and the column number (both start from 1) of the
+    /// primary span of the diagnostic
+    pub fn location(&self, source_map: &SourceMap) -> Option<(String, usize, usize)> {
+        let span = self.builder.span.primary_spans().first()?;
+        let loc = source_map.lookup_char_pos(span.lo());
+        Some((
+            loc.file.name.prefer_local().to_string(),
+            loc.line,
+            loc.col.0 + 1,
+        ))
+    }
+
+    /// Renders the content of the diagnostic without emitting it, the location is taken from the primary span as in
+    /// `location`, and is `1:1` of an empty file name if the diagnostic has no span
+    pub fn pretty_print(&self, source_map: &SourceMap) -> DiagnosticRepr {
+        let (file, line, col) = self.location(source_map).unwrap_or((String::new(), 1, 1));
+        DiagnosticRepr {
+            message: self.message(),
+            file,
+            line,
+            col,
+            severity: self.builder.level().to_str().to_string(),
+            cause: self.cause.clone(),
+        }
+    }
+
     pub fn compare(x: &Diagnostic<'compiler>, y: &Diagnostic<'compiler>) -> Ordering {
         if x.builder
             .span
@@ -112,10 +216,7 @@ impl<'compiler> Diagnostic<'compiler> {
     }
 
     pub fn upgrade_to_error(&self) -> Self {
-        let msg = match self.builder.deref().messages.get(0) {
-            Some((msg, _)) => msg.as_str().unwrap_or_default().to_string(),
-            None => String::new(),
-        };
+        let msg = self.message();
         let new_builder = DiagnosticBuilder::new(self.builder.dcx, rustc_errors::Level::Error, msg);
         Self {
             builder: new_builder,
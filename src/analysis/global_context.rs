@@ -1,12 +1,21 @@
+use crate::analysis::dataflow::DataFlowGraph;
 use crate::analysis::diagnostics::DiagnosticsForDefId;
+use crate::analysis::memory::path::PathInterner;
 use crate::analysis::memory::symbolic_value::SymbolicValue;
-use crate::analysis::option::AnalysisOption;
+use crate::analysis::option::{self, AnalysisOption};
+use crate::analysis::reachability;
+use crate::analysis::stubs::stub_library::StubLibrary;
+use crate::analysis::summary::FunctionSummary;
 use crate::analysis::wto::Wto;
+use crate::checker::ffi_checker::FfiContract;
 use log::{debug, info};
 use rustc_hir::def::DefKind;
-use rustc_hir::def_id::DefId;
-use rustc_middle::ty::TyCtxt;
+use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_middle::mir;
+use rustc_middle::ty::{Ty, TyCtxt};
 use rustc_session::Session;
+use rustc_span::{Span, Symbol};
+use std::cell::OnceCell;
 use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::rc::Rc;
@@ -42,8 +51,9 @@ pub struct GlobalContext<'tcx, 'compiler> {
     /// Represents the data associated with a compilation session for a single crate
     pub session: &'compiler Session,
 
-    /// The entry function of the analysis
-    pub entry_point: DefId,
+    /// The entry functions of the analysis, i.e., the function specified by `--entry`, and when `--cache-dir`
+    /// is set, the exported functions of the crate whose summaries are stored for other crates
+    pub entry_points: Vec<DefId>,
 
     /// Stores the DefIds that have been already checked, to avoid redundant check
     pub checked_def_ids: HashSet<DefId>,
@@ -62,6 +72,67 @@ pub struct GlobalContext<'tcx, 'compiler> {
 
     /// Generated diagnostic messages for each DefId
     pub diagnostics_for: DiagnosticsForDefId<'compiler>,
+
+    /// Inferred loop invariants, indexed by the function and the loop head basic block
+    /// Each entry stores the span of the loop head and the invariant in a textual form
+    pub loop_invariants: HashMap<(DefId, mir::BasicBlock), (Span, String)>,
+
+    /// Inferred preconditions for each analyzed function, in a textual form
+    pub contracts: HashMap<DefId, String>,
+
+    /// The id of each heap allocation site, i.e., a call to `Box::new` at a location in a function
+    pub heap_allocation_sites: HashMap<(DefId, mir::Location), u32>,
+
+    /// The functions of this crate that are reachable from the entry points, only these are analyzed
+    pub reachable_functions: HashSet<DefId>,
+
+    /// Summaries of the functions in this crate, indexed by the summary key of each function
+    /// A summary is only valid for any arguments, so it is recorded when the function is analyzed with unknown
+    /// arguments, i.e., with `--cache-dir` or `--bottom-up`, beyond `--inline-depth`, or as a recursive function,
+    /// and not when it is analyzed in the context of a call. It is applied at the calls by `FunctionSummary::apply`
+    pub summaries: HashMap<Rc<String>, FunctionSummary>,
+
+    /// Summaries of the functions in the crates that have been analyzed before, loaded from `--cache-dir`
+    pub dependency_summaries: HashMap<String, FunctionSummary>,
+
+    /// The contracts of the C functions, indexed by their names, loaded from `--ffi-stubs`
+    pub ffi_contracts: HashMap<String, FfiContract>,
+
+    /// The types that implement a trait marked with `#[mir_checker::resource]`, see `ResourceTypeStateChecker`
+    pub resource_types: HashSet<DefId>,
+
+    /// The stubs of the standard library functions, see `CallVisitor::handled_by_stub`
+    pub stub_library: StubLibrary,
+
+    /// Interns all the paths created during the analysis of this crate, see `Path::intern`
+    pub path_interner: Rc<PathInterner>,
+
+    /// The number of times that unsupported MIR patterns are skipped, see `TypeVisitor::report_unsupported`
+    pub unsupported_patterns: usize,
+
+    /// The number of the targets of `SwitchInt` terminators that are proved to be never taken, see `DeadBranchChecker`
+    pub pruned_branches: usize,
+
+    /// The index of the mutually recursive component of the call graph that each function belongs to
+    /// The calls between the functions of a component use their summaries instead of their bodies
+    pub recursive_components: HashMap<DefId, usize>,
+
+    /// The mutable statics mentioned by the functions that are not reachable from the entry points, which may write
+    /// them before the analyzed functions run, see `UninitializedGlobalChecker`
+    pub statics_written_elsewhere: HashSet<DefId>,
+
+    /// The data flow graph of each reachable function, only built when `--dump-dataflow` is set
+    pub data_flow_graphs: Option<HashMap<DefId, DataFlowGraph>>,
+
+    /// The taint sources in the `[taint]` section of the configuration, see `taint_sink_checker::resolve_functions`
+    pub taint_source_functions: HashSet<DefId>,
+
+    /// The taint sinks in the `[taint]` section of the configuration, see `TaintSinkChecker`
+    pub taint_sink_functions: HashSet<DefId>,
+
+    /// The strongly connected components of the call graph in bottom-up order, computed when they are first used,
+    /// see `analysis_order`
+    pub analysis_order: OnceCell<Vec<Vec<DefId>>>,
 }
 
 impl<'tcx, 'compiler> fmt::Debug for GlobalContext<'tcx, 'compiler> {
@@ -75,10 +146,14 @@ impl<'tcx, 'compiler> GlobalContext<'tcx, 'compiler> {
         session: &'compiler Session,
         tcx: TyCtxt<'tcx>,
         analysis_options: AnalysisOption,
+        mut entry_points: Vec<DefId>,
     ) -> Option<Self> {
         if analysis_options.show_entries {
             let mut names = HashSet::new();
             for def_id in tcx.iter_local_def_id() {
+                if Self::is_skipped_macro_function(tcx, def_id, &analysis_options) {
+                    continue;
+                }
                 if tcx.def_kind(def_id) == DefKind::Fn || tcx.def_kind(def_id) == DefKind::AssocFn {
                     let name = tcx.item_name(def_id.to_def_id());
                     if !names.contains(&name) {
@@ -92,6 +167,9 @@ impl<'tcx, 'compiler> GlobalContext<'tcx, 'compiler> {
 
         if analysis_options.show_entries_index {
             for def_id in tcx.iter_local_def_id() {
+                if Self::is_skipped_macro_function(tcx, def_id, &analysis_options) {
+                    continue;
+                }
                 if tcx.def_kind(def_id) == DefKind::Fn || tcx.def_kind(def_id) == DefKind::AssocFn {
                     println!("{}", def_id.to_def_id().index.as_u32());
                 }
@@ -100,58 +178,56 @@ impl<'tcx, 'compiler> GlobalContext<'tcx, 'compiler> {
         }
 
         info!("Initializing GlobalContext");
-        let mut entry_func = None;
-
-        // List functions
-        for def_id in tcx.iter_local_def_id() {
-            let def_kind = tcx.def_kind(def_id);
-            // Find the DefId for the entry point, note that the entry point must be a function
-            if def_kind == DefKind::Fn || def_kind == DefKind::AssocFn {
-                // If `entry_def_id_index` flag is provided, find entry point according to the index
-                if let Some(entry_def_id_index) = analysis_options.entry_def_id_index {
-                    let item_name = tcx.item_name(def_id.to_def_id());
-                    if def_id.to_def_id().index.as_u32() == entry_def_id_index {
-                        entry_func = Some(def_id);
-                        debug!("Entry Point: {:?}, DefId: {:?}", item_name, def_id);
-                    } else {
-                        debug!(
-                            "Name: {:?}, DefId: {:?}, DefKind: {:?}",
-                            tcx.item_name(def_id.to_def_id()),
-                            def_id,
-                            def_kind
-                        );
-                    }
-                }
-                // If not, find entry point according to the function name
-                else {
-                    let entry_point = analysis_options.entry_point.clone();
-                    let item_name = tcx.item_name(def_id.to_def_id());
-                    if item_name.to_string() == *entry_point {
-                        entry_func = Some(def_id);
-                        debug!("Entry Point: {:?}, DefId: {:?}", item_name, def_id);
-                    } else {
-                        debug!(
-                            "Name: {:?}, DefId: {:?}, DefKind: {:?}",
-                            tcx.item_name(def_id.to_def_id()),
-                            def_id,
-                            def_kind
-                        );
-                    }
-                }
-            }
+        let found_entry = !entry_points.is_empty();
+        if analysis_options.cache_dir.is_some() {
+            let summarized_functions: Vec<DefId> = tcx
+                .iter_local_def_id()
+                .filter(|def_id| {
+                    !Self::is_skipped_macro_function(tcx, *def_id, &analysis_options)
+                        && Self::is_summarized_function(tcx, *def_id)
+                })
+                .map(|def_id| def_id.to_def_id())
+                .filter(|def_id| !entry_points.contains(def_id))
+                .collect();
+            entry_points.extend(summarized_functions);
         }
 
-        if let Some(entry) = entry_func {
+        if !entry_points.is_empty() {
+            if !found_entry {
+                info!("Entry point not found, only analyze the functions to summarize");
+            }
+            let path_interner = Rc::new(PathInterner::default());
+            PathInterner::set_current(path_interner.clone());
+            let stub_library =
+                StubLibrary::new(tcx.data_layout.pointer_size.bits(), analysis_options.no_std);
             Some(Self {
                 tcx,
                 session,
                 function_name_cache: HashMap::new(),
-                entry_point: entry.to_def_id(),
+                entry_points,
                 checked_def_ids: HashSet::new(),
                 dropped_heaps: HashSet::new(),
                 wto_cache: WtoCache::default(),
                 analysis_options,
                 diagnostics_for: DiagnosticsForDefId::default(),
+                loop_invariants: HashMap::new(),
+                contracts: HashMap::new(),
+                heap_allocation_sites: HashMap::new(),
+                reachable_functions: HashSet::new(),
+                summaries: HashMap::new(),
+                dependency_summaries: HashMap::new(),
+                ffi_contracts: HashMap::new(),
+                resource_types: Self::find_resource_types(tcx),
+                stub_library,
+                path_interner,
+                unsupported_patterns: 0,
+                pruned_branches: 0,
+                recursive_components: HashMap::new(),
+                statics_written_elsewhere: HashSet::new(),
+                data_flow_graphs: None,
+                taint_source_functions: HashSet::new(),
+                taint_sink_functions: HashSet::new(),
+                analysis_order: OnceCell::new(),
             })
         } else {
             error!("Entry point not found");
@@ -159,6 +235,167 @@ impl<'tcx, 'compiler> GlobalContext<'tcx, 'compiler> {
         }
     }
 
+    /// Finds the entry points given by the options, i.e., the functions whose names match one of the patterns of
+    /// `--entry-pattern` if any is given, or otherwise the function given by `--entry_def_id_index` or `--entry`
+    pub fn find_entry_points(tcx: TyCtxt<'tcx>, analysis_options: &AnalysisOption) -> Vec<DefId> {
+        let mut entry_points = Vec::new();
+        for def_id in tcx.iter_local_def_id() {
+            // Functions generated by macros (e.g., `#[derive(Debug)]`) usually do not contain user-written logic
+            if Self::is_skipped_macro_function(tcx, def_id, analysis_options) {
+                debug!("Skip macro-generated function: {:?}", def_id);
+                continue;
+            }
+            let def_kind = tcx.def_kind(def_id);
+            // The entry points must be functions
+            if def_kind != DefKind::Fn && def_kind != DefKind::AssocFn {
+                continue;
+            }
+            let item_name = tcx.item_name(def_id.to_def_id());
+            let is_entry = if !analysis_options.entry_patterns.is_empty() {
+                analysis_options
+                    .entry_patterns
+                    .iter()
+                    .any(|pattern| option::glob_matches(pattern, item_name.as_str()))
+            } else if let Some(entry_def_id_index) = analysis_options.entry_def_id_index {
+                // If `entry_def_id_index` flag is provided, find entry point according to the index
+                def_id.to_def_id().index.as_u32() == entry_def_id_index
+            } else if analysis_options.entry_point.contains("::") {
+                // A path, e.g., `foo::bar`, matches the end of the path of the function, which may be qualified by
+                // the crate or the outer modules
+                let def_path = tcx.def_path_str(def_id.to_def_id());
+                let entry_point = &analysis_options.entry_point;
+                def_path == *entry_point || def_path.ends_with(&format!("::{}", entry_point))
+            } else {
+                // If not, find entry point according to the function name
+                item_name.to_string() == analysis_options.entry_point
+            };
+            if !is_entry {
+                debug!(
+                    "Name: {:?}, DefId: {:?}, DefKind: {:?}",
+                    item_name, def_id, def_kind
+                );
+                continue;
+            }
+            debug!("Entry Point: {:?}, DefId: {:?}", item_name, def_id);
+            // `--entry` names a single function, the last one with the name is used
+            if analysis_options.entry_patterns.is_empty() {
+                entry_points.clear();
+            }
+            entry_points.push(def_id.to_def_id());
+        }
+        entry_points
+    }
+
+    /// Build the call graph of the functions that are reachable from the entry points
+    /// Each function is mapped to the functions of this crate that it may call, where a call through a trait
+    /// object may call any implementation of the method in this crate
+    pub fn build_call_graph(&self) -> HashMap<DefId, HashSet<DefId>> {
+        reachability::call_graph(self.tcx, &self.entry_points)
+    }
+
+    /// The strongly connected components of the call graph of the functions that are reachable from the entry
+    /// points, where the callees of the functions in a component are in the same component or in the components
+    /// before it, see `reachability::bottom_up_order`
+    /// The components are computed once, and are shared by the analyses that visit the callees before the callers
+    pub fn analysis_order(&self) -> &[Vec<DefId>] {
+        self.analysis_order
+            .get_or_init(|| reachability::bottom_up_order(self.tcx, &self.build_call_graph()))
+    }
+
+    /// The functions that are reachable from the entry points, where the callees come before their callers, and the
+    /// functions of a mutually recursive component are adjacent in the order of their paths, see `analysis_order`
+    pub fn functions_in_analysis_order(&self) -> impl Iterator<Item = DefId> + '_ {
+        self.analysis_order().iter().flatten().copied()
+    }
+
+    /// Returns true if `def_id` is generated by a macro expansion and `check-macros` flag is not set
+    fn is_skipped_macro_function(
+        tcx: TyCtxt<'tcx>,
+        def_id: LocalDefId,
+        analysis_options: &AnalysisOption,
+    ) -> bool {
+        !analysis_options.check_macros && tcx.def_span(def_id).from_expansion()
+    }
+
+    /// Returns true if `def_id` is a function that can be called from other crates and returns an integer,
+    /// so that its summary is useful for the crates that depend on this crate
+    fn is_summarized_function(tcx: TyCtxt<'tcx>, def_id: LocalDefId) -> bool {
+        let def_kind = tcx.def_kind(def_id);
+        (def_kind == DefKind::Fn || def_kind == DefKind::AssocFn)
+            && tcx.effective_visibilities(()).is_exported(def_id)
+            && !tcx.generics_of(def_id).requires_monomorphization(tcx)
+            && tcx.is_mir_available(def_id)
+            && tcx
+                .fn_sig(def_id)
+                .skip_binder()
+                .output()
+                .skip_binder()
+                .is_integral()
+    }
+
+    /// Returns true if `def_id` is marked with `#[mir_checker::taint_source]` or listed in `--taint-source`,
+    /// either by its name or by its full path, or in the `[taint]` section of the configuration
+    pub fn is_taint_source(&self, def_id: DefId) -> bool {
+        if self.taint_source_functions.contains(&def_id) {
+            return true;
+        }
+        let attr_path = [
+            Symbol::intern("mir_checker"),
+            Symbol::intern("taint_source"),
+        ];
+        if self
+            .tcx
+            .get_attrs_by_path(def_id, &attr_path)
+            .next()
+            .is_some()
+        {
+            return true;
+        }
+        let taint_sources = &self.analysis_options.taint_sources;
+        !taint_sources.is_empty() && {
+            let name = self.tcx.item_name(def_id);
+            let path = self.tcx.def_path_str(def_id);
+            taint_sources
+                .iter()
+                .any(|source| source.as_str() == name.as_str() || *source == path)
+        }
+    }
+
+    /// Returns true if `def_id` has a precondition, i.e., it is marked with `#[requires(...)]` of `mir-checker-macros`,
+    /// which adds `#[mir_checker::requires]` when the crate is analyzed
+    pub fn has_precondition(&self, def_id: DefId) -> bool {
+        let attr_path = [Symbol::intern("mir_checker"), Symbol::intern("requires")];
+        self.tcx
+            .get_attrs_by_path(def_id, &attr_path)
+            .next()
+            .is_some()
+    }
+
+    /// Returns the types that implement a trait of this crate that is marked with `#[mir_checker::resource]`
+    fn find_resource_types(tcx: TyCtxt<'tcx>) -> HashSet<DefId> {
+        let attr_path = [Symbol::intern("mir_checker"), Symbol::intern("resource")];
+        tcx.iter_local_def_id()
+            .filter(|def_id| {
+                tcx.def_kind(*def_id) == DefKind::Trait
+                    && tcx
+                        .get_attrs_by_path(def_id.to_def_id(), &attr_path)
+                        .next()
+                        .is_some()
+            })
+            .flat_map(|trait_def_id| tcx.all_impls(trait_def_id.to_def_id()))
+            .filter_map(|impl_def_id| {
+                let self_ty = tcx.type_of(impl_def_id).instantiate_identity();
+                self_ty.ty_adt_def().map(|def| def.did())
+            })
+            .collect()
+    }
+
+    /// Returns true if `ty` implements a trait marked with `#[mir_checker::resource]`
+    pub fn is_resource_type(&self, ty: Ty<'tcx>) -> bool {
+        ty.ty_adt_def()
+            .map_or(false, |def| self.resource_types.contains(&def.did()))
+    }
+
     pub fn get_wto(&mut self, def_id: DefId) -> Wto<'tcx> {
         let mir = self.tcx.optimized_mir(def_id);
         let wto;
@@ -179,4 +416,26 @@ impl<'tcx, 'compiler> GlobalContext<'tcx, 'compiler> {
         }
         wto
     }
+
+    /// Returns true if `caller` and `callee` are in the same mutually recursive component of the call graph
+    pub fn is_mutually_recursive(&self, caller: DefId, callee: DefId) -> bool {
+        match (
+            self.recursive_components.get(&caller),
+            self.recursive_components.get(&callee),
+        ) {
+            (Some(caller_component), Some(callee_component)) => {
+                caller_component == callee_component
+            }
+            _ => false,
+        }
+    }
+
+    /// Get the id of the heap allocation site at `location` in `def_id`, assign a new one if not found
+    pub fn get_heap_allocation_id(&mut self, def_id: DefId, location: mir::Location) -> u32 {
+        let next_id = self.heap_allocation_sites.len() as u32;
+        *self
+            .heap_allocation_sites
+            .entry((def_id, location))
+            .or_insert(next_id)
+    }
 }
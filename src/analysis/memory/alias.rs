@@ -0,0 +1,175 @@
+use crate::analysis::memory::path::Path;
+use rustc_middle::mir;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+/// A flow-insensitive points-to analysis over the places of a MIR body
+/// It runs before the fixed-point iteration, and computes which paths may refer to the same memory location
+/// E.g. after `p = &mut x; q = &mut *p`, the paths `*p`, `*q` and `x` are aliases of each other
+#[derive(Clone, Debug, Default)]
+pub struct AliasMap {
+    // The paths that each pointer (a reference or a raw pointer) may point to
+    points_to: HashMap<Rc<Path>, HashSet<Rc<Path>>>,
+
+    // Paths that may refer to the same memory location as the key
+    aliases: HashMap<Rc<Path>, HashSet<Rc<Path>>>,
+
+    // Paths whose addresses flow into raw pointers
+    // Raw pointers are not tracked precisely, so a write through any raw pointer may change them
+    raw_pointer_targets: HashSet<Rc<Path>>,
+}
+
+impl AliasMap {
+    pub fn new(mir: &mir::Body<'_>, offset: usize) -> Self {
+        let mut result = Self::default();
+
+        // Propagate the points-to sets until nothing changes
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for bb_data in mir.basic_blocks.iter() {
+                for stmt in &bb_data.statements {
+                    if let mir::StatementKind::Assign(box (place, rvalue)) = &stmt.kind {
+                        changed |= result.visit_assign(mir, offset, place, rvalue);
+                    }
+                }
+            }
+        }
+
+        // Every pair of paths that may refer to the same target are aliases
+        let mut alias_sets: HashMap<Rc<Path>, HashSet<Rc<Path>>> = HashMap::new();
+        for (pointer, targets) in &result.points_to {
+            for target in targets {
+                let alias_set = alias_sets
+                    .entry(target.clone())
+                    .or_insert_with(|| std::iter::once(target.clone()).collect());
+                alias_set.insert(Path::new_deref(pointer.clone()));
+            }
+        }
+        for alias_set in alias_sets.values() {
+            for path in alias_set {
+                let aliases = result.aliases.entry(path.clone()).or_default();
+                aliases.extend(alias_set.iter().filter(|p| *p != path).cloned());
+            }
+        }
+        debug!("Alias map: {:?}", result.aliases);
+
+        result
+    }
+
+    /// Returns the paths that may refer to the same memory location as `place`
+    pub fn get_aliases(
+        &self,
+        mir: &mir::Body<'_>,
+        offset: usize,
+        place: &mir::Place<'_>,
+    ) -> HashSet<Rc<Path>> {
+        let mut result = HashSet::new();
+        if let Some(paths) = self.resolve_place(mir, offset, place) {
+            for path in paths {
+                if let Some(aliases) = self.aliases.get(&path) {
+                    result.extend(aliases.iter().cloned());
+                }
+                result.insert(path);
+            }
+        }
+        result
+    }
+
+    pub fn get_raw_pointer_targets(&self) -> &HashSet<Rc<Path>> {
+        &self.raw_pointer_targets
+    }
+
+    /// Updates the points-to sets for `place = rvalue`, returns true if anything is changed
+    fn visit_assign(
+        &mut self,
+        mir: &mir::Body<'_>,
+        offset: usize,
+        place: &mir::Place<'_>,
+        rvalue: &mir::Rvalue<'_>,
+    ) -> bool {
+        let (targets, is_raw_pointer) = match rvalue {
+            mir::Rvalue::Ref(_, _, borrowed_place) => {
+                (self.resolve_place(mir, offset, borrowed_place), false)
+            }
+            mir::Rvalue::AddressOf(_, borrowed_place) => {
+                (self.resolve_place(mir, offset, borrowed_place), true)
+            }
+            mir::Rvalue::Use(mir::Operand::Copy(rplace) | mir::Operand::Move(rplace)) => {
+                (self.get_points_to(mir, offset, rplace), false)
+            }
+            mir::Rvalue::Cast(_, mir::Operand::Copy(rplace) | mir::Operand::Move(rplace), ty) => {
+                (self.get_points_to(mir, offset, rplace), ty.is_unsafe_ptr())
+            }
+            _ => (None, false),
+        };
+        let targets = match targets {
+            Some(targets) if !targets.is_empty() => targets,
+            _ => return false,
+        };
+
+        let mut changed = false;
+        if is_raw_pointer {
+            for target in &targets {
+                changed |= self.raw_pointer_targets.insert(target.clone());
+            }
+        }
+        if let Some(pointers) = self.resolve_place(mir, offset, place) {
+            for pointer in pointers {
+                let points_to = self.points_to.entry(pointer).or_default();
+                for target in &targets {
+                    changed |= points_to.insert(target.clone());
+                }
+            }
+        }
+        changed
+    }
+
+    /// Returns the union of the points-to sets of the paths that `place` may refer to
+    fn get_points_to(
+        &self,
+        mir: &mir::Body<'_>,
+        offset: usize,
+        place: &mir::Place<'_>,
+    ) -> Option<HashSet<Rc<Path>>> {
+        let paths = self.resolve_place(mir, offset, place)?;
+        Some(
+            paths
+                .iter()
+                .filter_map(|path| self.points_to.get(path))
+                .flatten()
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Returns the paths that `place` may refer to, where dereferences are resolved with the points-to sets
+    /// Returns `None` if the place contains projections that are not supported, e.g., indices
+    fn resolve_place(
+        &self,
+        mir: &mir::Body<'_>,
+        offset: usize,
+        place: &mir::Place<'_>,
+    ) -> Option<HashSet<Rc<Path>>> {
+        let base_path =
+            Path::new_local_parameter_or_result(place.local.as_usize(), offset, mir.arg_count);
+        let mut paths: HashSet<Rc<Path>> = std::iter::once(base_path).collect();
+        for elem in place.projection.iter() {
+            paths = match elem {
+                mir::ProjectionElem::Deref => paths
+                    .into_iter()
+                    .flat_map(|path| match self.points_to.get(&path) {
+                        Some(targets) if !targets.is_empty() => targets.clone(),
+                        _ => std::iter::once(Path::new_deref(path)).collect(),
+                    })
+                    .collect(),
+                mir::ProjectionElem::Field(field, _) => paths
+                    .into_iter()
+                    .map(|path| Path::new_field(path, field.index()))
+                    .collect(),
+                _ => return None,
+            };
+        }
+        Some(paths)
+    }
+}
@@ -0,0 +1,132 @@
+use crate::analysis::abstract_domain::AbstractDomain;
+use crate::analysis::memory::expression::Expression;
+use crate::analysis::memory::path::{Path, PathEnum, PathSelector};
+use crate::analysis::memory::symbolic_value::SymbolicValue;
+use crate::analysis::numerical::apron_domain::{
+    ApronAbstractDomain, ApronDomainType, GetManagerTrait,
+};
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+/// A flow-insensitive escape analysis of the heap allocations made by a function
+/// An allocation escapes if its address may be stored into a location that outlives the function, i.e., a location
+/// that is not rooted by a local variable, such as the return value, a parameter or a static variable,
+/// or into another allocation that escapes
+/// The values stored at every path in any of the states of the function are treated as if they were stored at
+/// the same time, which is conservative
+#[derive(Debug, Default)]
+pub struct EscapeAnalysis {
+    // The allocations whose addresses are stored in each allocation
+    points_to: HashMap<u32, HashSet<u32>>,
+
+    // The allocations whose addresses are stored in locations that outlive the function
+    escaping: HashSet<u32>,
+}
+
+impl EscapeAnalysis {
+    pub fn new<'a, DomainType>(
+        states: impl IntoIterator<Item = &'a AbstractDomain<DomainType>>,
+    ) -> Self
+    where
+        DomainType: ApronDomainType + 'a,
+        ApronAbstractDomain<DomainType>: GetManagerTrait,
+    {
+        let mut result = Self::default();
+        for state in states {
+            // Addresses are symbolic values, the numerical domain only holds integers
+            for (path, value) in state.symbolic_domain.value_map.iter() {
+                let mut targets = HashSet::new();
+                record_heap_allocations_in_value(value, &mut targets);
+                if targets.is_empty() {
+                    continue;
+                }
+                match &get_root(path).value {
+                    PathEnum::LocalVariable { .. } => {}
+                    PathEnum::HeapAllocation { id } => {
+                        result.points_to.entry(*id).or_default().extend(targets);
+                    }
+                    _ => result.escaping.extend(targets),
+                }
+            }
+        }
+
+        // An allocation that is reachable from an escaping allocation also escapes
+        let mut worklist: Vec<u32> = result.escaping.iter().copied().collect();
+        while let Some(id) = worklist.pop() {
+            if let Some(targets) = result.points_to.get(&id) {
+                for target in targets {
+                    if result.escaping.insert(*target) {
+                        worklist.push(*target);
+                    }
+                }
+            }
+        }
+        debug!("Escaping heap allocations: {:?}", result.escaping);
+        result
+    }
+
+    pub fn escapes(&self, id: u32) -> bool {
+        self.escaping.contains(&id)
+    }
+}
+
+/// Returns the path that `path` is qualified by, e.g., `local_1` for `(*local_1).0`
+pub fn get_root(path: &Rc<Path>) -> &Rc<Path> {
+    match &path.value {
+        PathEnum::QualifiedPath { qualifier, .. } => get_root(qualifier),
+        _ => path,
+    }
+}
+
+/// Adds the ids of the heap allocations that the paths in `path` are rooted by to `result`
+fn record_heap_allocations_in_path(path: &Rc<Path>, result: &mut HashSet<u32>) {
+    match &path.value {
+        PathEnum::HeapAllocation { id } => {
+            result.insert(*id);
+        }
+        PathEnum::QualifiedPath {
+            qualifier,
+            selector,
+            ..
+        } => {
+            record_heap_allocations_in_path(qualifier, result);
+            if let PathSelector::Index(value) | PathSelector::Slice(value) = selector.as_ref() {
+                record_heap_allocations_in_value(value, result);
+            }
+        }
+        PathEnum::Alias { value } | PathEnum::HeapBlock { value } => {
+            record_heap_allocations_in_value(value, result)
+        }
+        _ => {}
+    }
+}
+
+/// Adds the ids of the heap allocations that are referred to by `value` to `result`
+fn record_heap_allocations_in_value(value: &Rc<SymbolicValue>, result: &mut HashSet<u32>) {
+    match &value.expression {
+        Expression::And { left, right }
+        | Expression::Equals { left, right }
+        | Expression::GreaterOrEqual { left, right }
+        | Expression::GreaterThan { left, right }
+        | Expression::Join { left, right }
+        | Expression::LessOrEqual { left, right }
+        | Expression::LessThan { left, right }
+        | Expression::Ne { left, right }
+        | Expression::Or { left, right } => {
+            record_heap_allocations_in_value(left, result);
+            record_heap_allocations_in_value(right, result);
+        }
+        Expression::Cast { operand, .. } | Expression::LogicalNot { operand } => {
+            record_heap_allocations_in_value(operand, result)
+        }
+        Expression::Widen { path, operand } => {
+            record_heap_allocations_in_path(path, result);
+            record_heap_allocations_in_value(operand, result);
+        }
+        Expression::Drop(path)
+        | Expression::Numerical(path)
+        | Expression::Reference(path)
+        | Expression::Variable { path, .. } => record_heap_allocations_in_path(path, result),
+        _ => {}
+    }
+}
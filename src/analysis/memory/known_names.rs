@@ -17,21 +17,73 @@ use std::collections::HashMap;
 pub enum KnownNames {
     /// This is not a known name
     None,
+    BoxNew,
+    CellGet,
+    CellSet,
+    RefCellBorrow,
+    RefCellBorrowMut,
     MirCheckerVerify,
+    MirCheckerRequires,
     RustAlloc,
     RustAllocZeroed,
     RustDealloc,
     RustRealloc,
     StdMemSizeOf,
+    StdMemSwap,
+    PtrAdd,
+    PtrSub,
+    PtrOffset,
+    PtrRead,
+    PtrWrite,
     StdPanickingBeginPanic,
     StdPanickingBeginPanicFmt,
+    OptionUnwrap,
+    ResultUnwrap,
+    OptionBranch,
+    ResultBranch,
+    OptionFromResidual,
+    ResultFromResidual,
+    IntegerCheckedAdd,
+    IntegerCheckedSub,
+    IntegerCheckedMul,
+    IntegerSaturatingAdd,
+    IntegerSaturatingSub,
+    IntegerSaturatingMul,
+    IntoIteratorIntoIter,
+    IteratorNext,
+    IteratorMap,
+    IteratorFilter,
+    IteratorEnumerate,
+    IteratorTake,
+    MutexLock,
+    MpscSyncChannel,
+    MpscSend,
+    MpscRecv,
+    SimdSetEpi32,
+    SimdSetrEpi32,
+    SimdSet1Epi32,
+    SimdI32Gather,
 
     StdIntoVec,
     CoreOpsIndex,
     StdFrom,
     StdAsMutPtr,
+    SliceAsPtr,
 
     VecFromRawParts,
+    VecNew,
+    VecWithCapacity,
+    VecPush,
+    VecPop,
+    VecLen,
+    VecCapacity,
+    VecSetLen,
+    VecDeref,
+    SliceGet,
+    SliceGetUnchecked,
+    StrGetUnchecked,
+    StrIndex,
+    StrSplitAt,
 }
 
 /// An analysis lifetime cache that contains a map from def ids to known names.
@@ -101,10 +153,77 @@ impl KnownNamesCache {
                 _ => KnownNames::None,
             };
 
+        let get_known_name_for_boxed_namespace = |mut def_path_data_iter: Iter<'_>| {
+            def_path_data_iter.next();
+            get_path_data_elem_name(def_path_data_iter.next())
+                .map(|n| match n.as_str() {
+                    "new" => KnownNames::BoxNew,
+                    _ => KnownNames::None,
+                })
+                .unwrap_or(KnownNames::None)
+        };
+
+        // Methods of `UnsafeCell` and `OnceCell` share names with `Cell` and `RefCell`,
+        // so also check the self type of the impl block
+        let get_known_name_for_cell_namespace = |mut def_path_data_iter: Iter<'_>| {
+            def_path_data_iter.next();
+            let self_ty_name = tcx
+                .impl_of_method(def_id)
+                .and_then(|impl_def_id| tcx.type_of(impl_def_id).skip_binder().ty_adt_def())
+                .map(|adt_def| tcx.item_name(adt_def.did()).to_string())
+                .unwrap_or_default();
+            get_path_data_elem_name(def_path_data_iter.next())
+                .map(|n| match (self_ty_name.as_str(), n.as_str()) {
+                    ("Cell", "get") => KnownNames::CellGet,
+                    ("Cell", "set") => KnownNames::CellSet,
+                    ("RefCell", "borrow") => KnownNames::RefCellBorrow,
+                    ("RefCell", "borrow_mut") => KnownNames::RefCellBorrowMut,
+                    _ => KnownNames::None,
+                })
+                .unwrap_or(KnownNames::None)
+        };
+
+        // The iterator traits are defined in `iter::traits::iterator` and `iter::traits::collect`
+        let get_known_name_for_iter_namespace = |mut def_path_data_iter: Iter<'_>| {
+            def_path_data_iter.next();
+            def_path_data_iter.next();
+            let trait_name = get_path_data_elem_name(def_path_data_iter.next());
+            let method_name = get_path_data_elem_name(def_path_data_iter.next());
+            match (trait_name, method_name) {
+                (Some(t), Some(m)) => match (t.as_str(), m.as_str()) {
+                    ("IntoIterator", "into_iter") => KnownNames::IntoIteratorIntoIter,
+                    ("Iterator", "next") => KnownNames::IteratorNext,
+                    ("Iterator", "map") => KnownNames::IteratorMap,
+                    ("Iterator", "filter") => KnownNames::IteratorFilter,
+                    ("Iterator", "enumerate") => KnownNames::IteratorEnumerate,
+                    ("Iterator", "take") => KnownNames::IteratorTake,
+                    _ => KnownNames::None,
+                },
+                _ => KnownNames::None,
+            }
+        };
+
         let get_known_name_for_mem_namespace = |mut def_path_data_iter: Iter<'_>| {
             get_path_data_elem_name(def_path_data_iter.next())
                 .map(|n| match n.as_str() {
                     "size_of" => KnownNames::StdMemSizeOf,
+                    "swap" => KnownNames::StdMemSwap,
+                    _ => KnownNames::None,
+                })
+                .unwrap_or(KnownNames::None)
+        };
+
+        // The methods of the integer types are defined in the impl blocks of `num`
+        let get_known_name_for_num_namespace = |mut def_path_data_iter: Iter<'_>| {
+            def_path_data_iter.next();
+            get_path_data_elem_name(def_path_data_iter.next())
+                .map(|n| match n.as_str() {
+                    "checked_add" => KnownNames::IntegerCheckedAdd,
+                    "checked_sub" => KnownNames::IntegerCheckedSub,
+                    "checked_mul" => KnownNames::IntegerCheckedMul,
+                    "saturating_add" => KnownNames::IntegerSaturatingAdd,
+                    "saturating_sub" => KnownNames::IntegerSaturatingSub,
+                    "saturating_mul" => KnownNames::IntegerSaturatingMul,
                     _ => KnownNames::None,
                 })
                 .unwrap_or(KnownNames::None)
@@ -119,6 +238,33 @@ impl KnownNamesCache {
                 .unwrap_or(KnownNames::None)
         };
 
+        let get_known_name_for_pointer_impl = |mut def_path_data_iter: Iter<'_>| {
+            def_path_data_iter.next();
+            get_path_data_elem_name(def_path_data_iter.next())
+                .map(|n| match n.as_str() {
+                    "add" => KnownNames::PtrAdd,
+                    "sub" => KnownNames::PtrSub,
+                    "offset" => KnownNames::PtrOffset,
+                    "read" => KnownNames::PtrRead,
+                    "write" => KnownNames::PtrWrite,
+                    _ => KnownNames::None,
+                })
+                .unwrap_or(KnownNames::None)
+        };
+
+        // Methods of raw pointers are defined in `ptr::const_ptr` and `ptr::mut_ptr`, and functions such as
+        // `ptr::read` are defined in `ptr` directly
+        let get_known_name_for_ptr_namespace = |mut def_path_data_iter: Iter<'_>| {
+            get_path_data_elem_name(def_path_data_iter.next())
+                .map(|n| match n.as_str() {
+                    "const_ptr" | "mut_ptr" => get_known_name_for_pointer_impl(def_path_data_iter),
+                    "read" => KnownNames::PtrRead,
+                    "write" => KnownNames::PtrWrite,
+                    _ => KnownNames::None,
+                })
+                .unwrap_or(KnownNames::None)
+        };
+
         let get_known_name_for_panicking_namespace = |mut def_path_data_iter: Iter<'_>| {
             get_path_data_elem_name(def_path_data_iter.next())
                 .map(|n| match n.as_str() {
@@ -134,11 +280,89 @@ impl KnownNamesCache {
             get_path_data_elem_name(def_path_data_iter.next())
                 .map(|n| match n.as_str() {
                     "into_vec" => KnownNames::StdIntoVec,
+                    "as_ptr" | "as_mut_ptr" => KnownNames::SliceAsPtr,
+                    "get" | "get_mut" => KnownNames::SliceGet,
+                    "get_unchecked" | "get_unchecked_mut" => KnownNames::SliceGetUnchecked,
+                    _ => KnownNames::None,
+                })
+                .unwrap_or(KnownNames::None)
+        };
+
+        let get_known_name_for_str_namespace =
+            |mut def_path_data_iter: Iter<'_>| match get_path_data_elem_name(
+                def_path_data_iter.next(),
+            ) {
+                // The implementations of `Index` and `IndexMut` for `str` are in `core::str::traits`
+                Some(n) if n.as_str() == "traits" => {
+                    def_path_data_iter.next();
+                    get_path_data_elem_name(def_path_data_iter.next())
+                        .map(|n| match n.as_str() {
+                            "index" | "index_mut" => KnownNames::StrIndex,
+                            _ => KnownNames::None,
+                        })
+                        .unwrap_or(KnownNames::None)
+                }
+                _ => get_path_data_elem_name(def_path_data_iter.next())
+                    .map(|n| match n.as_str() {
+                        "get_unchecked" | "get_unchecked_mut" => KnownNames::StrGetUnchecked,
+                        "split_at" | "split_at_mut" => KnownNames::StrSplitAt,
+                        _ => KnownNames::None,
+                    })
+                    .unwrap_or(KnownNames::None),
+            };
+
+        let get_known_name_for_option_namespace = |mut def_path_data_iter: Iter<'_>| {
+            def_path_data_iter.next();
+            get_path_data_elem_name(def_path_data_iter.next())
+                .map(|n| match n.as_str() {
+                    "unwrap" | "expect" => KnownNames::OptionUnwrap,
+                    "branch" => KnownNames::OptionBranch,
+                    "from_residual" => KnownNames::OptionFromResidual,
+                    _ => KnownNames::None,
+                })
+                .unwrap_or(KnownNames::None)
+        };
+
+        let get_known_name_for_result_namespace = |mut def_path_data_iter: Iter<'_>| {
+            def_path_data_iter.next();
+            get_path_data_elem_name(def_path_data_iter.next())
+                .map(|n| match n.as_str() {
+                    "unwrap" | "expect" => KnownNames::ResultUnwrap,
+                    "branch" => KnownNames::ResultBranch,
+                    "from_residual" => KnownNames::ResultFromResidual,
                     _ => KnownNames::None,
                 })
                 .unwrap_or(KnownNames::None)
         };
 
+        let get_known_name_for_sync_namespace =
+            |mut def_path_data_iter: Iter<'_>| match get_path_data_elem_name(
+                def_path_data_iter.next(),
+            ) {
+                Some(n) if n.as_str() == "mutex" => {
+                    def_path_data_iter.next();
+                    get_path_data_elem_name(def_path_data_iter.next())
+                        .map(|n| match n.as_str() {
+                            "lock" => KnownNames::MutexLock,
+                            _ => KnownNames::None,
+                        })
+                        .unwrap_or(KnownNames::None)
+                }
+                Some(n) if n.as_str() == "mpsc" => {
+                    match get_path_data_elem_name(def_path_data_iter.next()) {
+                        Some(n) if n.as_str() == "sync_channel" => KnownNames::MpscSyncChannel,
+                        _ => get_path_data_elem_name(def_path_data_iter.next())
+                            .map(|n| match n.as_str() {
+                                "send" => KnownNames::MpscSend,
+                                "recv" => KnownNames::MpscRecv,
+                                _ => KnownNames::None,
+                            })
+                            .unwrap_or(KnownNames::None),
+                    }
+                }
+                _ => KnownNames::None,
+            };
+
         let get_known_name_for_convert_namespace = |mut def_path_data_iter: Iter<'_>| {
             def_path_data_iter.next();
             get_path_data_elem_name(def_path_data_iter.next())
@@ -155,6 +379,35 @@ impl KnownNamesCache {
                 .map(|n| match n.as_str() {
                     "as_mut_ptr" => KnownNames::StdAsMutPtr,
                     "from_raw_parts" => KnownNames::VecFromRawParts,
+                    "new" => KnownNames::VecNew,
+                    "with_capacity" => KnownNames::VecWithCapacity,
+                    "push" => KnownNames::VecPush,
+                    "pop" => KnownNames::VecPop,
+                    "len" => KnownNames::VecLen,
+                    "capacity" => KnownNames::VecCapacity,
+                    "set_len" => KnownNames::VecSetLen,
+                    "deref" | "deref_mut" => KnownNames::VecDeref,
+                    _ => KnownNames::None,
+                })
+                .unwrap_or(KnownNames::None)
+        };
+
+        // The intrinsics of `std::arch` are defined in `core_arch::{x86, x86_64}::<target feature>`, only the last
+        // element of the path names the intrinsic
+        let get_known_name_for_core_arch_namespace = |def_path_data_iter: Iter<'_>| {
+            get_path_data_elem_name(def_path_data_iter.last())
+                .map(|n| match n.as_str() {
+                    "_mm_set_epi32" | "_mm256_set_epi32" => KnownNames::SimdSetEpi32,
+                    "_mm_setr_epi32" | "_mm256_setr_epi32" => KnownNames::SimdSetrEpi32,
+                    "_mm_set1_epi32" | "_mm256_set1_epi32" => KnownNames::SimdSet1Epi32,
+                    "_mm_i32gather_epi32"
+                    | "_mm256_i32gather_epi32"
+                    | "_mm_i32gather_epi64"
+                    | "_mm256_i32gather_epi64"
+                    | "_mm_i32gather_ps"
+                    | "_mm256_i32gather_ps"
+                    | "_mm_i32gather_pd"
+                    | "_mm256_i32gather_pd" => KnownNames::SimdI32Gather,
                     _ => KnownNames::None,
                 })
                 .unwrap_or(KnownNames::None)
@@ -164,13 +417,24 @@ impl KnownNamesCache {
             get_path_data_elem_name(def_path_data_iter.next())
                 .map(|n| match n.as_str() {
                     "alloc" => get_known_name_for_alloc_namespace(def_path_data_iter),
+                    "boxed" => get_known_name_for_boxed_namespace(def_path_data_iter),
+                    "cell" => get_known_name_for_cell_namespace(def_path_data_iter),
+                    "core_arch" => get_known_name_for_core_arch_namespace(def_path_data_iter),
+                    "iter" => get_known_name_for_iter_namespace(def_path_data_iter),
                     "mem" => get_known_name_for_mem_namespace(def_path_data_iter),
+                    "num" => get_known_name_for_num_namespace(def_path_data_iter),
                     "ops" => get_known_name_for_ops_namespace(def_path_data_iter),
+                    "option" => get_known_name_for_option_namespace(def_path_data_iter),
+                    "result" => get_known_name_for_result_namespace(def_path_data_iter),
                     "slice" => get_known_name_for_slice_namespace(def_path_data_iter),
+                    "str" => get_known_name_for_str_namespace(def_path_data_iter),
+                    "sync" => get_known_name_for_sync_namespace(def_path_data_iter),
                     "panicking" => get_known_name_for_panicking_namespace(def_path_data_iter),
+                    "ptr" => get_known_name_for_ptr_namespace(def_path_data_iter),
                     "convert" => get_known_name_for_convert_namespace(def_path_data_iter),
                     "vec" => get_known_name_for_vec_namespace(def_path_data_iter),
                     "mir_checker_verify" => KnownNames::MirCheckerVerify,
+                    "mir_checker_requires" => KnownNames::MirCheckerRequires,
                     _ => {
                         debug!("Normal function: {:?}", n.as_str());
                         KnownNames::None
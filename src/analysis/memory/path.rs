@@ -18,6 +18,7 @@ use crate::analysis::numerical::apron_domain::{
 use rustc_hir::def_id::DefId;
 use rustc_middle::ty::{Ty, TyCtxt, TyKind};
 use rustc_target::abi::FieldIdx;
+use std::cell::RefCell;
 use std::collections::hash_map::DefaultHasher;
 use std::collections::HashSet;
 use std::fmt::{Debug, Formatter, Result};
@@ -45,7 +46,9 @@ impl Hash for Path {
 
 impl PartialEq for Path {
     fn eq(&self, other: &Path) -> bool {
-        self.hash == other.hash && self.value == other.value
+        // Interned paths are equal if and only if they are the same allocation, so the structural comparison is
+        // only needed for the paths that are not interned yet, see `Path::intern`
+        std::ptr::eq(self, other) || (self.hash == other.hash && self.value == other.value)
     }
 }
 
@@ -60,7 +63,80 @@ impl From<PathEnum> for Path {
     }
 }
 
+/// Hash-conses paths, so that structurally identical paths share the same `Rc` allocation
+/// Comparing two paths first compares their addresses, which makes the comparison of interned paths, and of the
+/// qualifiers of qualified paths, cheap
+/// Each path is stored once, and is looked up by its value
+#[derive(Default)]
+pub struct PathInterner {
+    paths: RefCell<HashSet<Rc<Path>>>,
+}
+
+thread_local! {
+    // The interner used by the path constructors, which is replaced by the interner of `GlobalContext`
+    // when the analysis of a crate starts
+    // The constructors, e.g., `Path::new_local` or `Path::new_qualified`, are called by the symbolic values, the
+    // numerical domains and the refinement of paths, which have no access to `GlobalContext`. Passing the interner
+    // to each of them would add a parameter to hundreds of calls, so they use this interner instead, and only the
+    // callers that hold `GlobalContext` pass its interner explicitly to `Path::intern`
+    static CURRENT_INTERNER: RefCell<Rc<PathInterner>> =
+        RefCell::new(Rc::new(PathInterner::default()));
+}
+
+impl PathInterner {
+    /// Use `interner` for all the paths created by the current thread from now on
+    pub fn set_current(interner: Rc<PathInterner>) {
+        CURRENT_INTERNER.with(|current| *current.borrow_mut() = interner);
+    }
+
+    /// Returns the interner used by the path constructors
+    pub fn current() -> Rc<PathInterner> {
+        CURRENT_INTERNER.with(|current| current.borrow().clone())
+    }
+
+    /// The number of distinct paths in the interner
+    pub fn len(&self) -> usize {
+        self.paths.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.paths.borrow().is_empty()
+    }
+
+    /// Drop the paths that are only referenced by the interner
+    /// A qualified path holds its qualifier, so removing a path may make its qualifier unused, which is removed by
+    /// the next round
+    pub fn collect_garbage(&self) {
+        let mut paths = self.paths.borrow_mut();
+        loop {
+            let size = paths.len();
+            paths.retain(|path| Rc::strong_count(path) > 1);
+            if paths.len() == size {
+                break;
+            }
+        }
+    }
+}
+
 impl Path {
+    /// Returns the unique path whose value is `value`, creating it in `interner` if it does not exist
+    /// This is the only way to create a path, all the other constructors call it
+    pub fn intern(interner: &PathInterner, value: PathEnum) -> Rc<Path> {
+        let path = Path::from(value);
+        let mut paths = interner.paths.borrow_mut();
+        if let Some(interned) = paths.get(&path) {
+            return interned.clone();
+        }
+        let interned = Rc::new(path);
+        paths.insert(interned.clone());
+        interned
+    }
+
+    /// Intern `value` with the interner of the current thread, see `PathInterner::current`
+    fn intern_current(value: PathEnum) -> Rc<Path> {
+        CURRENT_INTERNER.with(|current| Path::intern(&current.borrow(), value))
+    }
+
     /// Returns a qualified path of the form root.selectors[0].selectors[1]...
     // TODO: This is only used in handling weak updates
     // See whether we really need weak updates, if not, remove this
@@ -72,17 +148,63 @@ impl Path {
         result
     }
 
+    /// Returns the canonical form of a path, so that equivalent paths hash to the same key.
+    /// Unlike `refine_paths`, this does not depend on the abstract state:
+    /// `*(&x)` becomes `x`, `(&x).f` becomes `x.f`, and an alias of the value at `x` becomes `x`.
+    pub fn canonicalize(path: &Rc<Path>) -> Rc<Path> {
+        match &path.value {
+            PathEnum::Alias { value } => match &value.expression {
+                Expression::Variable { path, .. } => Path::canonicalize(path),
+                _ => path.clone(),
+            },
+            PathEnum::QualifiedPath {
+                qualifier,
+                selector,
+                ..
+            } => {
+                let canonical_qualifier = Path::canonicalize(qualifier);
+                // The components of a fat pointer `&x` are not components of `x`
+                if let (false, PathEnum::Alias { value }) = (
+                    selector.is_fat_pointer_component(),
+                    &canonical_qualifier.value,
+                ) {
+                    if let Expression::Reference(target) = &value.expression {
+                        let target = Path::canonicalize(target);
+                        return match selector.as_ref() {
+                            // *&x => x, where a heap block is seen as its first element
+                            PathSelector::Deref => {
+                                if matches!(&target.value, PathEnum::HeapBlock { .. }) {
+                                    Path::new_index(target, Rc::new(0u128.into()))
+                                } else {
+                                    target
+                                }
+                            }
+                            // (&x).f => x.f
+                            _ => Path::new_qualified(target, selector.clone()),
+                        };
+                    }
+                }
+                if Rc::ptr_eq(&canonical_qualifier, qualifier) {
+                    path.clone()
+                } else {
+                    Path::new_qualified(canonical_qualifier, selector.clone())
+                }
+            }
+            _ => path.clone(),
+        }
+    }
+
     /// Requires an abstract value that is an AbstractHeapAddress expression and
     /// returns a path can be used as the root of paths that define the heap value.
     pub fn get_as_path(value: Rc<SymbolicValue>) -> Rc<Path> {
-        Rc::new(match &value.expression {
-            Expression::HeapBlock { .. } => PathEnum::HeapBlock { value }.into(),
+        match &value.expression {
+            Expression::HeapBlock { .. } => Path::intern_current(PathEnum::HeapBlock { value }),
             Expression::Reference(path)
             | Expression::Variable { path, .. }
             | Expression::Numerical(path)
-            | Expression::Widen { path, .. } => path.as_ref().clone(),
-            _ => PathEnum::Alias { value }.into(),
-        })
+            | Expression::Widen { path, .. } => path.clone(),
+            _ => Path::intern_current(PathEnum::Alias { value }),
+        }
     }
 }
 
@@ -103,6 +225,10 @@ pub enum PathEnum {
     /// A dynamically allocated memory block.
     HeapBlock { value: Rc<SymbolicValue> },
 
+    /// An object allocated on the heap by a call like `Box::new`.
+    /// The id is unique for each allocation site, i.e., each call location in each function.
+    HeapAllocation { id: u32 },
+
     /// locals [arg_count+1..] are the local variables and compiler temporaries.
     LocalVariable { ordinal: usize },
 
@@ -143,6 +269,9 @@ impl PartialOrd for PathEnum {
             (PathEnum::HeapBlock { value: v1 }, PathEnum::HeapBlock { value: v2 }) => {
                 v1.partial_cmp(v2)
             }
+            (PathEnum::HeapAllocation { id: i1 }, PathEnum::HeapAllocation { id: i2 }) => {
+                i1.partial_cmp(i2)
+            }
             (PathEnum::LocalVariable { ordinal: o1 }, PathEnum::LocalVariable { ordinal: o2 }) => {
                 o1.partial_cmp(o2)
             }
@@ -208,6 +337,7 @@ impl Debug for PathEnum {
         match self {
             PathEnum::Alias { value } => f.write_fmt(format_args!("alias_{:?}", value)),
             PathEnum::HeapBlock { value } => f.write_fmt(format_args!("<{:?}>", value)),
+            PathEnum::HeapAllocation { id } => f.write_fmt(format_args!("heap_alloc_{}", id)),
             PathEnum::LocalVariable { ordinal } => f.write_fmt(format_args!("local_{}", ordinal)),
             PathEnum::Parameter { ordinal } => f.write_fmt(format_args!("param_{}", ordinal)),
             PathEnum::Result => f.write_str("result"),
@@ -246,7 +376,7 @@ impl Path {
             PathEnum::QualifiedPath { qualifier, .. } => {
                 qualifier.is_rooted_by_abstract_heap_block()
             }
-            PathEnum::HeapBlock { .. } => true,
+            PathEnum::HeapBlock { .. } | PathEnum::HeapAllocation { .. } => true,
             _ => false,
         }
     }
@@ -271,7 +401,12 @@ impl Path {
 
     /// Creates a path that aliases once or more paths contained inside the value.
     pub fn new_alias(value: Rc<SymbolicValue>) -> Rc<Path> {
-        Rc::new(PathEnum::Alias { value }.into())
+        Path::intern_current(PathEnum::Alias { value })
+    }
+
+    /// Creates a path to the object allocated at the allocation site with the given id.
+    pub fn new_heap_allocation(id: u32) -> Rc<Path> {
+        Path::intern_current(PathEnum::HeapAllocation { id })
     }
 
     /// Creates a path to the target memory of a reference value.
@@ -307,39 +442,40 @@ impl Path {
     pub fn new_static(tcx: TyCtxt<'_>, def_id: DefId) -> Rc<Path> {
         let ty = tcx.type_of(def_id);
         let name = utils::summary_key_str(tcx, def_id);
-        Rc::new(
-            PathEnum::StaticVariable {
-                def_id: Some(def_id),
-                summary_cache_key: name,
-                expression_type: ExpressionType::from(ty.skip_binder().kind()),
-            }
-            .into(),
-        )
+        Path::intern_current(PathEnum::StaticVariable {
+            def_id: Some(def_id),
+            summary_cache_key: name,
+            expression_type: ExpressionType::from(ty.skip_binder().kind()),
+        })
+    }
+
+    /// Creates an integer variable that is only known by its name, e.g., a variable read from a checkpoint.
+    /// The path is formatted as `name`, so it can be written back under the same name.
+    pub fn new_named(name: &str) -> Rc<Path> {
+        Path::intern_current(PathEnum::StaticVariable {
+            def_id: None,
+            summary_cache_key: Rc::new(name.to_string()),
+            expression_type: ExpressionType::I128,
+        })
     }
 
     /// Creates a path to the local variable corresponding to the ordinal.
     pub fn new_local(ordinal: usize, offset: usize) -> Rc<Path> {
-        Rc::new(
-            PathEnum::LocalVariable {
-                ordinal: ordinal + offset,
-            }
-            .into(),
-        )
+        Path::intern_current(PathEnum::LocalVariable {
+            ordinal: ordinal + offset,
+        })
     }
 
     /// Creates a path to the local variable corresponding to the ordinal.
     pub fn new_parameter(ordinal: usize, offset: usize) -> Rc<Path> {
-        Rc::new(
-            PathEnum::Parameter {
-                ordinal: ordinal + offset,
-            }
-            .into(),
-        )
+        Path::intern_current(PathEnum::Parameter {
+            ordinal: ordinal + offset,
+        })
     }
 
     /// Creates a path to the local variable corresponding to the ordinal.
     pub fn new_result() -> Rc<Path> {
-        Rc::new(PathEnum::Result.into())
+        Path::intern_current(PathEnum::Result)
     }
 
     /// Creates a path to the local variable, parameter or result local, corresponding to the ordinal.
@@ -357,6 +493,69 @@ impl Path {
         }
     }
 
+    /// Creates a path that selects the data pointer of the fat pointer at the given path.
+    pub fn new_fat_pointer_data(fat_pointer_path: Rc<Path>) -> Rc<Path> {
+        let selector = Rc::new(PathSelector::FatPointerData);
+        Self::new_qualified(fat_pointer_path, selector)
+    }
+
+    /// Creates a path that selects the metadata of the fat pointer at the given path.
+    pub fn new_fat_pointer_meta(fat_pointer_path: Rc<Path>) -> Rc<Path> {
+        let selector = Rc::new(PathSelector::FatPointerMeta);
+        Self::new_qualified(fat_pointer_path, selector)
+    }
+
+    /// Creates a path that summarizes the items of the iterator at the given path.
+    pub fn new_iterator_item(iterator_path: Rc<Path>) -> Rc<Path> {
+        let selector = Rc::new(PathSelector::IteratorItem);
+        Self::new_qualified(iterator_path, selector)
+    }
+
+    /// Creates a path that selects the number of remaining items of the iterator at the given path.
+    pub fn new_iterator_count(iterator_path: Rc<Path>) -> Rc<Path> {
+        let selector = Rc::new(PathSelector::IteratorCount);
+        Self::new_qualified(iterator_path, selector)
+    }
+
+    /// Creates a path that selects the capacity of the vector at the given path.
+    pub fn new_capacity(vector_path: Rc<Path>) -> Rc<Path> {
+        let selector = Rc::new(PathSelector::Capacity);
+        Self::new_qualified(vector_path, selector)
+    }
+
+    /// Creates a path that selects the type-state of the resource at the given path, see `ResourceTypeStateChecker`.
+    pub fn new_resource_state(resource_path: Rc<Path>) -> Rc<Path> {
+        let selector = Rc::new(PathSelector::ResourceState);
+        Self::new_qualified(resource_path, selector)
+    }
+
+    /// Creates a path that selects whether the memory at the given path has been freed, see `DoubleFreeChecker`.
+    pub fn new_freed(pointee_path: Rc<Path>) -> Rc<Path> {
+        let selector = Rc::new(PathSelector::Freed);
+        Self::new_qualified(pointee_path, selector)
+    }
+
+    /// Creates a path that selects whether the static at the given path has been written, see
+    /// `UninitializedGlobalChecker`.
+    pub fn new_written(static_path: Rc<Path>) -> Rc<Path> {
+        let selector = Rc::new(PathSelector::Written);
+        Self::new_qualified(static_path, selector)
+    }
+
+    /// Creates a path that selects the alignment, in bytes, that the address stored in the pointer at the given path
+    /// is known to be a multiple of, see `AlignmentChecker`.
+    pub fn new_alignment(pointer_path: Rc<Path>) -> Rc<Path> {
+        let selector = Rc::new(PathSelector::Alignment);
+        Self::new_qualified(pointer_path, selector)
+    }
+
+    /// Creates a path that selects the value of the parameter at the given path at the entry of the function,
+    /// see `WtoFixPointIterator::record_contract`.
+    pub fn new_entry_value(parameter_path: Rc<Path>) -> Rc<Path> {
+        let selector = Rc::new(PathSelector::EntryValue);
+        Self::new_qualified(parameter_path, selector)
+    }
+
     /// Creates a path the selects the length of the array/slice/string at the given path.
     pub fn new_length(array_path: Rc<Path>) -> Rc<Path> {
         let selector = Rc::new(PathSelector::Field(1));
@@ -372,14 +571,11 @@ impl Path {
         }
         let qualifier_length = qualifier.path_length();
 
-        Rc::new(
-            PathEnum::QualifiedPath {
-                qualifier,
-                selector,
-                length: qualifier_length + 1,
-            }
-            .into(),
-        )
+        Path::intern_current(PathEnum::QualifiedPath {
+            qualifier,
+            selector,
+            length: qualifier_length + 1,
+        })
     }
 
     /// Adds any heap blocks found in embedded index values to the given set.
@@ -551,6 +747,9 @@ where
             } => {
                 let refined_selector = selector.refine_paths(environment);
                 let refined_qualifier = qualifier.refine_paths(environment);
+                if refined_selector.is_fat_pointer_component() {
+                    return Path::new_qualified(refined_qualifier, refined_selector);
+                }
 
                 // The qualifier is now canonical. But in the context of a selector, we
                 // might be able to simplify the qualifier by dropping an explicit dereference
@@ -672,6 +871,41 @@ pub enum PathSelector {
     /// Select the struct field with the given index.
     Field(usize),
 
+    /// Given a path that denotes a fat pointer (e.g., `&[T]`, `&str` or `&dyn Trait`),
+    /// select the thin pointer to its data.
+    FatPointerData,
+
+    /// Given a path that denotes a fat pointer, select its metadata,
+    /// i.e., the length of a slice or a string, or the vtable of a trait object.
+    FatPointerMeta,
+
+    /// Given a path that denotes an iterator, select a value that summarizes all the items
+    /// it may still yield. Iterator adaptors such as `map` are not analyzed field by field,
+    /// so this is how the items of an iterator are tracked instead.
+    IteratorItem,
+
+    /// Given a path that denotes an iterator, select the number of items it may still yield.
+    IteratorCount,
+
+    /// Given a path that denotes a vector, select the number of items its buffer can hold,
+    /// the length of the vector is its `len` field.
+    Capacity,
+
+    /// Given a path that denotes a resource, e.g., a file handle, select whether it is open or closed.
+    ResourceState,
+
+    /// Given a path that denotes the memory a pointer points to, select whether it has been freed.
+    Freed,
+
+    /// Given a path that denotes a mutable static, select whether it has been written.
+    Written,
+
+    /// Given a path that denotes a parameter, select its value at the entry of the function, which is never written.
+    EntryValue,
+
+    /// Given a path that denotes a pointer, select the alignment that its address is known to be a multiple of.
+    Alignment,
+
     /// Select the collection element with the index specified by the abstract value.
     Index(Rc<SymbolicValue>),
 
@@ -703,6 +937,16 @@ impl Debug for PathSelector {
             PathSelector::Deref => f.write_str("deref"),
             PathSelector::Discriminant => f.write_str("discr"),
             PathSelector::Field(index) => index.fmt(f),
+            PathSelector::FatPointerData => f.write_str("data"),
+            PathSelector::FatPointerMeta => f.write_str("meta"),
+            PathSelector::IteratorItem => f.write_str("item"),
+            PathSelector::IteratorCount => f.write_str("count"),
+            PathSelector::Capacity => f.write_str("cap"),
+            PathSelector::ResourceState => f.write_str("state"),
+            PathSelector::Freed => f.write_str("freed"),
+            PathSelector::Written => f.write_str("written"),
+            PathSelector::EntryValue => f.write_str("entry"),
+            PathSelector::Alignment => f.write_str("align"),
             PathSelector::Index(value) => f.write_fmt(format_args!("[{:?}]", value)),
             PathSelector::Slice(value) => f.write_fmt(format_args!("[0..{:?}]", value)),
             PathSelector::ConstantIndex {
@@ -718,6 +962,16 @@ impl Debug for PathSelector {
 }
 
 impl PathSelector {
+    /// True if the selector selects a component of a fat pointer.
+    /// Such a selector applies to the pointer itself, not to the memory it points to,
+    /// so it must not be moved through a reference like other selectors.
+    pub fn is_fat_pointer_component(&self) -> bool {
+        matches!(
+            self,
+            PathSelector::FatPointerData | PathSelector::FatPointerMeta
+        )
+    }
+
     /// Adds any abstract heap addresses found in embedded index values to the given set.
     pub fn record_heap_blocks(&self, result: &mut HashSet<Rc<SymbolicValue>>) {
         match self {
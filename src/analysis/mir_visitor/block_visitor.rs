@@ -12,6 +12,7 @@ use crate::analysis::diagnostics::DiagnosticCause;
 use crate::analysis::memory::constant_value::{ConstantValue, FunctionReference};
 use crate::analysis::memory::expression::{Expression, ExpressionType};
 use crate::analysis::memory::k_limits;
+use crate::analysis::memory::known_names::KnownNames;
 use crate::analysis::memory::path::{Path, PathEnum, PathRefinement, PathSelector};
 use crate::analysis::memory::symbolic_domain::SymbolicDomain;
 use crate::analysis::memory::symbolic_value::{
@@ -24,19 +25,39 @@ use crate::analysis::mir_visitor::type_visitor;
 use crate::analysis::numerical::apron_domain::{
     ApronAbstractDomain, ApronDomainType, ApronOperation, GetManagerTrait,
 };
+use crate::analysis::numerical::float_domain::FloatValue;
+use crate::analysis::numerical::interval::{Bound, Interval};
 use crate::analysis::numerical::linear_constraint::LinearConstraintSystem;
+use crate::analysis::numerical::wrapping_integer::WrappingIntegerDomain;
+use crate::analysis::option::AbstractDomainType;
+use crate::analysis::reachability;
 use crate::analysis::z3_solver::SmtResult;
+use crate::checker::alignment_checker::{AlignedAccess, AlignedOperation};
+use crate::checker::dangling_pointer_checker::{self, DanglingStore};
+use crate::checker::double_free_checker::{self, DoubleFree, FreedState};
+use crate::checker::float_nan_checker::FloatComparison;
+use crate::checker::format_string_checker;
+use crate::checker::int_promotion_checker::SignPromotion;
+use crate::checker::pin_safety_checker::{PinOperation, PinnedAccess};
+use crate::checker::raw_ptr_bounds_checker::RawPointerAccess;
+use crate::checker::resource_type_state_checker::{self, ResourceState};
+use crate::checker::shift_by_const_checker;
+use crate::checker::shift_sign_checker::SignedShift;
+use crate::checker::taint_sink_checker::TaintedSinkCall;
+use crate::checker::uninitialized_global_checker::{self, StaticRead, WrittenState};
 use rug::Integer;
 use rustc_abi::Size;
 use rustc_hir::def_id::DefId;
+use rustc_hir::LangItem;
 use rustc_middle::mir::interpret::{alloc_range, AllocRange, CtfeProvenance, Scalar};
 use rustc_middle::mir::{self, UnwindAction};
 use rustc_middle::query::Key;
 use rustc_middle::ty::{
-    Const, ConstKind, GenericArgsRef, ParamConst, ScalarInt, Ty, TyKind, UserTypeAnnotationIndex,
-    ValTree,
+    Const, ConstKind, FloatTy, GenericArgsRef, ParamConst, ScalarInt, Ty, TyKind,
+    UserTypeAnnotationIndex, ValTree,
 };
 use rustc_span::source_map::Spanned;
+use rustc_span::Span;
 use std::any::Any;
 use std::borrow::Borrow;
 use std::convert::TryFrom;
@@ -44,6 +65,16 @@ use std::convert::TryInto;
 use std::fmt;
 use std::rc::Rc;
 
+/// How an assignment to a raw pointer computes the byte offset of the pointer from the start of its
+/// allocation, see `update_raw_pointer_offset`
+enum RawPointerOffset {
+    // The pointer is derived from a reference, so it points to the start of the allocation
+    Zero,
+    // The pointer is copied or moved from another raw pointer, whose offset is tracked or not
+    CopiedFrom(Rc<Path>, bool),
+    Unknown,
+}
+
 /// This class is used to extract properties from Rust MIR
 /// Initially a pre-condition is given, then the visitor abstractly execute a basic block,
 /// and returns a post-condition.
@@ -103,9 +134,23 @@ where
         self.current_block = bb;
         let mut location = bb.start_location();
         // Visit statements
+        #[cfg(feature = "trace")]
+        let is_traced = self.body_visitor.is_traced();
         for stmt in &self.mir.basic_blocks[bb].statements {
             self.body_visitor.current_location = location;
+            #[cfg(feature = "trace")]
+            let before = is_traced.then(|| format!("{:?}", self.state()));
             self.visit_statement(stmt);
+            #[cfg(feature = "trace")]
+            if let Some(before) = before {
+                self.body_visitor.write_trace(&format!(
+                    "BB{}[stmt {}]: before = {}, after = {:?}",
+                    bb.index(),
+                    location.statement_index,
+                    before,
+                    self.state()
+                ));
+            }
             location.statement_index += 1;
         }
         // Visit terminators
@@ -199,7 +244,15 @@ where
                 destination,
                 target,
                 ..
-            } => self.visit_call(func, args, destination, target),
+            } => {
+                self.forget_written_pointer(destination);
+                self.visit_call_free(func, args, destination);
+                let alignment = self.visit_call_alignment(func, args, destination);
+                self.visit_call(func, args, destination, target);
+                let destination_path = self.visit_place(destination);
+                self.update_known_alignment(&destination_path, alignment);
+                self.visit_call_resource(func, args, destination);
+            }
             mir::TerminatorKind::Assert {
                 cond,
                 expected,
@@ -207,7 +260,21 @@ where
                 target,
                 unwind,
             } => self.visit_assert(cond, *expected, msg, *target, *unwind),
-            mir::TerminatorKind::InlineAsm { .. } => self.visit_inline_asm(),
+            mir::TerminatorKind::InlineAsm { operands, .. } => {
+                for operand in operands.iter() {
+                    if let mir::InlineAsmOperand::Out {
+                        place: Some(place), ..
+                    }
+                    | mir::InlineAsmOperand::InOut {
+                        out_place: Some(place),
+                        ..
+                    } = operand
+                    {
+                        self.forget_written_pointer(place);
+                    }
+                }
+                self.visit_inline_asm()
+            }
 
             // The rest are ignored
             _ => (),
@@ -249,13 +316,22 @@ where
         place: &mir::Place<'tcx>,
         variant_index: rustc_target::abi::VariantIdx,
     ) {
-        let target_path =
-            Path::new_discriminant(self.visit_place(place)).refine_paths(&self.state());
-
+        let enum_path = self.visit_place(place);
         let ty = self
             .body_visitor
             .type_visitor
             .get_rustc_place_type(place, self.body_visitor.current_span);
+        self.set_discriminant(enum_path, ty, variant_index);
+    }
+
+    /// Sets the discriminant of the enum value at `enum_path`, whose type is `ty`, to the one of `variant_index`
+    fn set_discriminant(
+        &mut self,
+        enum_path: Rc<Path>,
+        ty: Ty<'tcx>,
+        variant_index: rustc_target::abi::VariantIdx,
+    ) {
+        let target_path = Path::new_discriminant(enum_path).refine_paths(&self.state());
 
         let param_env = self.body_visitor.type_visitor.get_param_env();
         if let Ok(ty_and_layout) = self.body_visitor.context.tcx.layout_of(param_env.and(ty)) {
@@ -287,6 +363,12 @@ where
                 if self.body_visitor.tainted_variables.contains(&local) {
                     self.body_visitor.tainted_variables.insert(llocal);
                 }
+                if let Some(source) = self.body_visitor.input_tainted_variables.get(&local) {
+                    let source = *source;
+                    self.body_visitor
+                        .input_tainted_variables
+                        .insert(llocal, source);
+                }
             }
         }
     }
@@ -347,9 +429,460 @@ where
             "Current tainted variables: {:?}",
             self.body_visitor.tainted_variables
         );
+        if self
+            .body_visitor
+            .context
+            .analysis_options
+            .check_raw_ptr_bounds
+        {
+            self.record_raw_pointer_accesses(place, rvalue);
+        }
+        let alignment = self.get_assigned_alignment(rvalue);
+        if self.body_visitor.context.analysis_options.check_float_nan {
+            self.record_float_comparison(rvalue);
+            self.update_float_value(place, rvalue);
+        }
+        if self.body_visitor.context.analysis_options.check_pin_safety {
+            self.record_pinned_accesses(rvalue);
+        }
+        // Zero-sized values carry no information, so do not allocate variables for them
+        let ty = self
+            .body_visitor
+            .type_visitor
+            .get_rustc_place_type(place, self.body_visitor.current_span);
+        if self.body_visitor.type_visitor.is_zero_sized(ty) {
+            debug!("Skip assignment to zero-sized place: {:?}", place);
+            return;
+        }
         let path = self.visit_place(place);
         debug!("Get LHS Path: {:?}", path);
-        self.visit_rvalue(path, rvalue);
+        // Compute the metadata before the rvalue is moved out
+        let fat_pointer_meta = if type_visitor::is_fat_pointer(ty.kind()) {
+            Some(self.get_fat_pointer_meta(rvalue))
+        } else {
+            None
+        };
+        let raw_pointer_offset = if ty.is_unsafe_ptr() {
+            Some(self.get_raw_pointer_offset(rvalue))
+        } else {
+            None
+        };
+        let provenance = self.get_pointer_provenance(&path, rvalue, ty.is_any_ptr());
+        self.visit_rvalue(path.clone(), rvalue);
+        if let Some(target) = provenance {
+            self.body_visitor
+                .record_pointer_provenance(path.clone(), target);
+        }
+        if let Some(meta) = fat_pointer_meta {
+            self.update_fat_pointer_components(&path, meta);
+        }
+        if let Some(offset) = raw_pointer_offset {
+            self.update_raw_pointer_offset(&path, offset);
+        }
+        self.update_known_alignment(&path, alignment);
+        let analysis_options = &self.body_visitor.context.analysis_options;
+        let (check_dangling_pointer, check_double_free, check_uninitialized_global) = (
+            analysis_options.check_dangling_pointer,
+            analysis_options.check_double_free,
+            analysis_options.check_use_of_uninitialized_global,
+        );
+        if ty.is_unsafe_ptr() && check_dangling_pointer {
+            self.record_dangling_store(&path);
+        }
+        if ty.is_unsafe_ptr() && check_double_free {
+            self.body_visitor
+                .reset_freed_state(Path::new_deref(path.clone()));
+        }
+        if check_uninitialized_global {
+            self.record_static_write(&path);
+        }
+        self.update_aliases(place, &path);
+    }
+
+    /// Returns the provenance of the value assigned to `path` by `rvalue`, i.e., `Some(Some(target))` for
+    /// `&target` or `&raw target`, or a copy of a pointer to `target`, and `Some(None)` for the other values of
+    /// pointers, or of paths that have provenance, which is then lost. `None` if `path` is not a pointer
+    /// Must be called before `rvalue` is visited, because a move forgets the source
+    fn get_pointer_provenance(
+        &mut self,
+        path: &Rc<Path>,
+        rvalue: &mir::Rvalue<'tcx>,
+        is_pointer: bool,
+    ) -> Option<Option<Rc<Path>>> {
+        let provenance = match rvalue {
+            // The target must denote the same location wherever the pointer is used, so it may not depend on the
+            // values of other variables, e.g., `&a[i]` or `&(*q).0`
+            mir::Rvalue::Ref(_, _, borrowed_place) | mir::Rvalue::AddressOf(_, borrowed_place) => {
+                let target = self.visit_place(borrowed_place);
+                let is_mutable = matches!(
+                    rvalue,
+                    mir::Rvalue::Ref(_, mir::BorrowKind::Mut { .. }, _)
+                        | mir::Rvalue::AddressOf(rustc_hir::Mutability::Mut, _)
+                );
+                // The pointers in the borrowed place may be written through the new pointer
+                if is_mutable {
+                    let borrows_pointer = self
+                        .body_visitor
+                        .type_visitor
+                        .get_rustc_place_type(borrowed_place, self.body_visitor.current_span)
+                        .is_any_ptr();
+                    self.body_visitor
+                        .forget_pointer_provenance(&target, borrows_pointer);
+                }
+                Some(target).filter(is_fixed_location)
+            }
+            mir::Rvalue::Use(mir::Operand::Copy(rplace) | mir::Operand::Move(rplace))
+            | mir::Rvalue::Cast(_, mir::Operand::Copy(rplace) | mir::Operand::Move(rplace), _) => {
+                let source = self.visit_place(rplace);
+                self.body_visitor
+                    .pointer_provenance
+                    .get(&source)
+                    .cloned()
+                    .flatten()
+            }
+            _ => None,
+        };
+        if provenance.is_some()
+            || is_pointer
+            || self.body_visitor.pointer_provenance.contains_key(path)
+        {
+            Some(provenance)
+        } else {
+            None
+        }
+    }
+
+    /// Forgets the provenance of the pointer that is the destination of a call or an output of inline assembly
+    fn forget_written_pointer(&mut self, place: &mir::Place<'tcx>) {
+        let is_pointer = self
+            .body_visitor
+            .type_visitor
+            .get_rustc_place_type(place, self.body_visitor.current_span)
+            .is_any_ptr();
+        let path = self.visit_place(place);
+        self.body_visitor
+            .forget_pointer_provenance(&path, is_pointer);
+    }
+
+    /// Record the raw pointer stored at `path` if it points to a variable of this function and `path` may outlive
+    /// the function, see `DanglingPointerChecker`
+    fn record_dangling_store(&mut self, path: &Rc<Path>) {
+        let location = self.body_visitor.current_location;
+        let local = if dangling_pointer_checker::may_outlive_frame(path) {
+            self.body_visitor
+                .state
+                .value_at(path)
+                .and_then(|value| dangling_pointer_checker::get_referenced_local(&value))
+        } else {
+            None
+        };
+        match local {
+            Some(local) => {
+                let store = DanglingStore {
+                    span: self.body_visitor.current_span,
+                    target: path.clone(),
+                    local,
+                };
+                self.body_visitor.dangling_stores.insert(location, store);
+            }
+            // The pointer stored in an earlier iteration may be overwritten
+            None => {
+                self.body_visitor.dangling_stores.remove(&location);
+            }
+        }
+    }
+
+    /// Returns how `rvalue` computes the byte offset of a raw pointer from the start of its allocation,
+    /// must be called before `rvalue` is visited, because a move renames the offset of the source
+    fn get_raw_pointer_offset(&mut self, rvalue: &mir::Rvalue<'tcx>) -> RawPointerOffset {
+        let source_place = match rvalue {
+            mir::Rvalue::Use(mir::Operand::Copy(rplace) | mir::Operand::Move(rplace))
+            | mir::Rvalue::Cast(_, mir::Operand::Copy(rplace) | mir::Operand::Move(rplace), _) => {
+                *rplace
+            }
+            // `&raw const *p` is `p`, otherwise the pointer points to the start of the borrowed place
+            mir::Rvalue::Ref(_, _, borrowed_place) | mir::Rvalue::AddressOf(_, borrowed_place) => {
+                match borrowed_place.projection.split_last() {
+                    Some((mir::ProjectionElem::Deref, base_projection)) => mir::Place {
+                        local: borrowed_place.local,
+                        projection: self
+                            .body_visitor
+                            .context
+                            .tcx
+                            .mk_place_elems(base_projection),
+                    },
+                    _ => return RawPointerOffset::Zero,
+                }
+            }
+            _ => return RawPointerOffset::Unknown,
+        };
+        let source_ty = self
+            .body_visitor
+            .type_visitor
+            .get_rustc_place_type(&source_place, self.body_visitor.current_span);
+        if source_ty.is_ref() {
+            RawPointerOffset::Zero
+        } else if source_ty.is_unsafe_ptr() {
+            let source_path = self.visit_place(&source_place);
+            let is_tracked = self.state().numerical_domain.contains(&source_path);
+            RawPointerOffset::CopiedFrom(source_path, is_tracked)
+        } else {
+            RawPointerOffset::Unknown
+        }
+    }
+
+    /// A raw pointer is modeled as a byte offset from the start of its allocation in the numerical domain,
+    /// which is then moved by `add`, `sub` and `offset`, see `handle_pointer_offset`
+    fn update_raw_pointer_offset(&mut self, path: &Rc<Path>, offset: RawPointerOffset) {
+        let numerical_domain = &mut self.body_visitor.state.numerical_domain;
+        match offset {
+            RawPointerOffset::Zero => numerical_domain.assign_int(path.clone(), Integer::from(0)),
+            RawPointerOffset::CopiedFrom(source_path, true) => {
+                // If the source is moved, its offset is already renamed to `path`
+                if numerical_domain.contains(&source_path) {
+                    numerical_domain.assign_var(path.clone(), source_path);
+                }
+            }
+            RawPointerOffset::CopiedFrom(_, false) | RawPointerOffset::Unknown => {
+                numerical_domain.forget(path)
+            }
+        }
+    }
+
+    /// Records the dereferences of raw pointers in `place = rvalue`, together with the offsets
+    /// of the pointers before the assignment, so that they can be checked after the fixed-point iteration
+    fn record_raw_pointer_accesses(
+        &mut self,
+        place: &mir::Place<'tcx>,
+        rvalue: &mir::Rvalue<'tcx>,
+    ) {
+        let mut places = vec![*place];
+        match rvalue {
+            mir::Rvalue::Use(operand)
+            | mir::Rvalue::Cast(_, operand, _)
+            | mir::Rvalue::UnaryOp(_, operand)
+            | mir::Rvalue::Repeat(operand, _) => places.extend(operand.place()),
+            mir::Rvalue::BinaryOp(_, box (left_operand, right_operand))
+            | mir::Rvalue::CheckedBinaryOp(_, box (left_operand, right_operand)) => {
+                places.extend(left_operand.place());
+                places.extend(right_operand.place());
+            }
+            _ => (),
+        }
+        let accesses = places
+            .iter()
+            .filter_map(|place| self.get_raw_pointer_access(place))
+            .collect();
+        self.body_visitor
+            .raw_pointer_accesses
+            .insert(self.body_visitor.current_location, accesses);
+    }
+
+    /// Returns the access to memory if `place` dereferences a raw pointer whose offset is tracked
+    fn get_raw_pointer_access(&mut self, place: &mir::Place<'tcx>) -> Option<RawPointerAccess> {
+        let tcx = self.body_visitor.context.tcx;
+        let (pointer_place, pointer_ty) = place.iter_projections().find_map(|(base, elem)| {
+            let base_ty = base.ty(self.mir, tcx).ty;
+            if elem == mir::ProjectionElem::Deref && base_ty.is_unsafe_ptr() {
+                let base_place = mir::Place {
+                    local: base.local,
+                    projection: tcx.mk_place_elems(base.projection),
+                };
+                Some((base_place, base_ty))
+            } else {
+                None
+            }
+        })?;
+        let pointer_path = self.visit_place(&pointer_place);
+        if !self.state().numerical_domain.contains(&pointer_path) {
+            debug!("The allocation of {:?} is unknown", pointer_path);
+            return None;
+        }
+        let offset = self.state().numerical_domain.get_interval(&pointer_path);
+        let access_size = self
+            .body_visitor
+            .type_visitor
+            .get_type_size(type_visitor::get_target_type(pointer_ty))?;
+        let allocation_size = self.get_allocation_size(&pointer_path);
+        Some(RawPointerAccess {
+            span: self.body_visitor.current_span,
+            offset,
+            access_size,
+            allocation_size,
+            lane: None,
+        })
+    }
+
+    /// Returns the number of bytes of the allocation that the raw pointer at `pointer_path` points to
+    /// A pointer returned by `as_ptr` on a slice keeps the length of the slice, see `CallVisitor::handle_from`
+    pub fn get_allocation_size(&mut self, pointer_path: &Rc<Path>) -> Option<Integer> {
+        let span = self.body_visitor.current_span;
+        let length_path = Path::new_fat_pointer_meta(pointer_path.clone());
+        if let Some(length) = self.state().value_at(&length_path) {
+            if let Expression::CompileTimeConstant(ConstantValue::Int(length)) = &length.expression
+            {
+                let pointer_ty = self
+                    .body_visitor
+                    .type_visitor
+                    .get_path_rustc_type(pointer_path, span);
+                let element_size = self
+                    .body_visitor
+                    .type_visitor
+                    .get_type_size(type_visitor::get_target_type(pointer_ty))?;
+                return Some(Integer::from(length * element_size));
+            }
+        }
+        let value = self.state().value_at(pointer_path)?;
+        let target = if let Expression::Reference(target) = &value.expression {
+            target.clone()
+        } else {
+            return None;
+        };
+        if let PathEnum::HeapBlock { .. } = &target.value {
+            // The byte length recorded by `handle_rust_alloc`
+            let length = self.state().value_at(&Path::new_length(target))?;
+            if let Expression::CompileTimeConstant(ConstantValue::Int(length)) = &length.expression
+            {
+                return Some(length.clone());
+            }
+            return None;
+        }
+        let ty = self
+            .body_visitor
+            .type_visitor
+            .get_path_rustc_type(&target, span);
+        let is_unsized = matches!(
+            ty.kind(),
+            TyKind::Slice(..) | TyKind::Str | TyKind::Dynamic(..)
+        );
+        if ty.is_unit() || is_unsized {
+            // The type of the allocation is unknown, or its size is only known at run time
+            return None;
+        }
+        self.body_visitor
+            .type_visitor
+            .get_type_size(ty)
+            .map(Integer::from)
+    }
+
+    /// Returns the metadata of the fat pointer produced by `rvalue`, if it is known
+    /// E.g. the length of `&[T; N] as &[T]` is `N`, and the length of `&*s` is the length of `s`
+    fn get_fat_pointer_meta(&mut self, rvalue: &mir::Rvalue<'tcx>) -> Option<Rc<SymbolicValue>> {
+        match rvalue {
+            mir::Rvalue::Use(mir::Operand::Copy(rplace) | mir::Operand::Move(rplace))
+            | mir::Rvalue::Cast(_, mir::Operand::Copy(rplace) | mir::Operand::Move(rplace), _) => {
+                let source_ty = self
+                    .body_visitor
+                    .type_visitor
+                    .get_rustc_place_type(rplace, self.body_visitor.current_span);
+                if type_visitor::is_fat_pointer(source_ty.kind()) {
+                    let source_path = self.visit_place(rplace);
+                    return self
+                        .state()
+                        .value_at(&Path::new_fat_pointer_meta(source_path));
+                }
+                // Unsizing a pointer to an array
+                if let TyKind::Ref(_, t, _) | TyKind::RawPtr(t, _) = source_ty.kind() {
+                    if let TyKind::Array(_, len) = t.kind() {
+                        return Some(self.visit_constant(None, len));
+                    }
+                }
+                None
+            }
+            mir::Rvalue::Use(mir::Operand::Constant(constant))
+            | mir::Rvalue::Cast(_, mir::Operand::Constant(constant), _) => {
+                self.get_constant_len(constant)
+            }
+            mir::Rvalue::Ref(_, _, borrowed_place) | mir::Rvalue::AddressOf(_, borrowed_place) => {
+                Some(self.get_place_len(borrowed_place))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the length of the string or slice that a constant reference points to, e.g., 5 for `"hello"`
+    /// A byte string literal like `b"hello"` is a reference to an array, whose length is part of its type
+    fn get_constant_len(
+        &mut self,
+        constant: &mir::ConstOperand<'tcx>,
+    ) -> Option<Rc<SymbolicValue>> {
+        let pointee_ty = match constant.const_.ty().kind() {
+            TyKind::Ref(_, t, _) => *t,
+            _ => return None,
+        };
+        let len = match (pointee_ty.kind(), &constant.const_) {
+            (TyKind::Array(_, length), _) => return Some(self.visit_constant(None, length)),
+            (TyKind::Str | TyKind::Slice(..), mir::Const::Ty(literal)) => match literal.kind() {
+                ConstKind::Value(ValTree::Branch(elements)) => elements.len() as u128,
+                _ => return None,
+            },
+            (
+                TyKind::Str | TyKind::Slice(..),
+                mir::Const::Val(mir::ConstValue::Slice { meta, .. }, _),
+            ) => *meta as u128,
+            _ => return None,
+        };
+        Some(self.body_visitor.get_u128_const_val(len))
+    }
+
+    /// Splits the fat pointer at `path` into its data pointer and its metadata
+    fn update_fat_pointer_components(&mut self, path: &Rc<Path>, meta: Option<Rc<SymbolicValue>>) {
+        let data_path = Path::new_fat_pointer_data(path.clone());
+        let meta_path = Path::new_fat_pointer_meta(path.clone());
+        // The data pointer has the same value as the fat pointer in the memory model
+        match self.state().value_at(path) {
+            Some(data) => self.body_visitor.state.update_value_at(data_path, data),
+            None => self.body_visitor.state.remove(&data_path),
+        }
+        match meta {
+            Some(meta) => self.body_visitor.state.update_value_at(meta_path, meta),
+            None => self.body_visitor.state.remove(&meta_path),
+        }
+    }
+
+    /// A write to `place` may also change its aliases, so join their values with the new value
+    /// Writes through raw pointers are not tracked precisely, so all the paths whose addresses
+    /// flow into raw pointers are forgotten
+    fn update_aliases(&mut self, place: &mir::Place<'tcx>, path: &Rc<Path>) {
+        let tcx = self.body_visitor.context.tcx;
+        let through_raw_pointer = place.iter_projections().any(|(base, elem)| {
+            elem == mir::ProjectionElem::Deref && base.ty(self.mir, tcx).ty.is_unsafe_ptr()
+        });
+        if through_raw_pointer {
+            let targets = self
+                .body_visitor
+                .alias_map
+                .get_raw_pointer_targets()
+                .clone();
+            for target in targets {
+                let target = target.refine_paths(self.state());
+                if target == *path {
+                    continue;
+                }
+                for p in self.state().get_paths_iter() {
+                    if p == target || p.is_rooted_by(&target) {
+                        self.body_visitor.state.remove(&p);
+                    }
+                }
+            }
+        }
+
+        if let Some(value) = self.state().value_at(path) {
+            let aliases = self.body_visitor.alias_map.get_aliases(
+                self.mir,
+                self.body_visitor.fresh_variable_offset,
+                place,
+            );
+            for alias in aliases {
+                let alias = alias.refine_paths(self.state());
+                if alias != *path {
+                    debug!("Weak update of {:?}, an alias of {:?}", alias, path);
+                    self.body_visitor
+                        .state
+                        .weak_update_value_at(alias, value.clone());
+                }
+            }
+        }
     }
 
     pub fn visit_function_reference(
@@ -383,7 +916,9 @@ where
             self.body_visitor.fresh_variable_offset
         );
         let place_path = self.get_path_for_place(place);
-        let mut path = place_path.refine_paths(&self.state());
+        let mut path = self
+            .body_visitor
+            .apply_pointer_provenance(&place_path.refine_paths(&self.state()));
         match &path.value {
             PathEnum::QualifiedPath {
                 qualifier,
@@ -442,17 +977,10 @@ where
             }
             _ => (),
         };
-        if !self
-            .body_visitor
+        let path = Path::canonicalize(&path);
+        self.body_visitor
             .type_visitor
-            .path_ty_cache
-            .contains_key(&path)
-        {
-            self.body_visitor
-                .type_visitor
-                .path_ty_cache
-                .insert(path.clone(), ty);
-        }
+            .set_path_rustc_type(&path, ty);
         path
     }
 
@@ -704,6 +1232,21 @@ where
                         return symbolic_value::BOTTOM.into();
                     }
                 }
+                // E.g., the length of `[T; N]` where `const N: usize = FOO + BAR`, which is not normalized,
+                // the value is unknown if it cannot be evaluated, rather than zero
+                ConstKind::Unevaluated(..) | ConstKind::Expr(..) => {
+                    return match self.body_visitor.type_visitor.try_eval_const_int(val) {
+                        Some(scalar_int) => {
+                            let size = scalar_int.size();
+                            let data = scalar_int.assert_bits(size);
+                            Rc::new(
+                                self.get_constant_from_scalar(ty.kind(), data, size.bytes())
+                                    .into(),
+                            )
+                        }
+                        None => symbolic_value::TOP.into(),
+                    };
+                }
                 _ => {
                     unreachable!(
                         "unexpected kind of literal {:?} {:?}",
@@ -820,6 +1363,7 @@ where
                         .body_visitor
                         .type_visitor
                         .get_path_rustc_type(&path_to_scalar, self.body_visitor.current_span);
+                    let scalar_ty = self.body_visitor.type_visitor.unwrap_newtype(scalar_ty);
                     let scalar_val: Rc<SymbolicValue> = Rc::new(
                         self.get_constant_from_scalar(&scalar_ty.kind(), data, size)
                             .clone()
@@ -1093,7 +1637,7 @@ where
                 base_path.refine_paths(&self.state())
             }
         });
-        result
+        Path::canonicalize(&result)
     }
 
     fn visit_projection_elem(
@@ -1137,18 +1681,216 @@ where
 
     fn visit_switch_int(&mut self, discr: &mir::Operand<'tcx>, targets: &mir::SwitchTargets) {
         let mut default_exit_condition = Rc::new(SymbolicValue::new_true());
+        // If the switch is on the discriminant of an enum, the exit conditions also constrain
+        // the discriminant itself, so that the variant is known in each branch
+        let discriminant_path = self.get_switched_discriminant(discr);
+        let switched_value = self.get_switched_value(discr, discriminant_path.as_ref());
+        let discriminant_val = discriminant_path.map(|discriminant_path| {
+            SymbolicValue::make_from(
+                Expression::Variable {
+                    path: discriminant_path,
+                    var_type: ExpressionType::U128,
+                },
+                1,
+            )
+        });
+        // If the switch is on the result of a comparison, the exit conditions also constrain
+        // the compared values, e.g., `x < 10` holds in the `true` branch of `if x < 10`
+        let comparison = self.get_switched_comparison(discr);
         let discr = self.visit_operand(discr);
         for (v, target) in targets.iter() {
             let val: Rc<SymbolicValue> = Rc::new(ConstantValue::Int(Integer::from(v)).into());
-            let cond = discr.equals(val);
-            let not_cond = cond.logical_not();
+            let mut cond = discr.equals(val.clone());
+            let mut not_cond = cond.logical_not();
+            if let Some(discriminant_val) = &discriminant_val {
+                cond = cond.and(discriminant_val.equals(val.clone()));
+                not_cond = not_cond.and(discriminant_val.not_equals(val));
+            }
+            if let Some(comparison) = &comparison {
+                match v {
+                    0 => {
+                        cond = cond.and(comparison.logical_not());
+                        not_cond = not_cond.and(comparison.clone());
+                    }
+                    1 => {
+                        cond = cond.and(comparison.clone());
+                        not_cond = not_cond.and(comparison.logical_not());
+                    }
+                    _ => (),
+                }
+            }
             default_exit_condition = default_exit_condition.and(not_cond);
+            // Several values may switch to the same target, e.g., the patterns of `1 | 2 => ...`
+            if let Some(other_cond) = self.body_visitor.state.exit_conditions.get(&target) {
+                cond = other_cond.or(cond);
+            }
             self.body_visitor.state.exit_conditions.insert(target, cond);
         }
         self.body_visitor
             .state
             .exit_conditions
             .insert(targets.otherwise(), default_exit_condition);
+
+        // If the switched value is a constant in the current state, only the target of that value is taken, so
+        // the other targets are pruned in this iteration, even if their conditions are not linear constraints
+        if let Some(value) = switched_value {
+            let taken = targets.target_for_value(value);
+            for target in targets.all_targets() {
+                if *target != taken {
+                    debug!(
+                        "Prune the branch to {:?}, the switched value is always {}",
+                        target, value
+                    );
+                    self.body_visitor
+                        .state
+                        .exit_conditions
+                        .insert(*target, Rc::new(SymbolicValue::new_false()));
+                }
+            }
+        }
+    }
+
+    /// The value of the operand of a `SwitchInt` if it is a constant in the current state, e.g., the discriminant
+    /// of an enum whose variant is known, or a condition that always holds
+    fn get_switched_value(
+        &mut self,
+        discr: &mir::Operand<'tcx>,
+        discriminant_path: Option<&Rc<Path>>,
+    ) -> Option<u128> {
+        let path = match (discriminant_path, discr.place()) {
+            (Some(discriminant_path), _) => discriminant_path.clone(),
+            (None, Some(place)) => self.visit_place(&place),
+            // A constant operand is already folded into a `Goto` by rustc
+            (None, None) => return None,
+        };
+        if !self.state().numerical_domain.contains(&path) {
+            return None;
+        }
+        let interval = self.state().numerical_domain.get_interval(&path);
+        // The negative values are encoded as their bits in the targets, they are not pruned
+        Integer::try_from(interval).ok()?.to_u128()
+    }
+
+    /// If `discr` is a local that is assigned `Discriminant(place)` in the current block,
+    /// returns the path to the discriminant of `place`
+    fn get_switched_discriminant(&mut self, discr: &mir::Operand<'tcx>) -> Option<Rc<Path>> {
+        let local = discr.place()?.as_local()?;
+        let enum_place = self.mir.basic_blocks[self.current_block]
+            .statements
+            .iter()
+            .rev()
+            .find_map(|statement| match &statement.kind {
+                mir::StatementKind::Assign(box (place, rvalue))
+                    if place.as_local() == Some(local) =>
+                {
+                    match rvalue {
+                        mir::Rvalue::Discriminant(enum_place) => Some(Some(*enum_place)),
+                        _ => Some(None),
+                    }
+                }
+                _ => None,
+            })??;
+        Some(Path::new_discriminant(self.visit_place(&enum_place)).refine_paths(self.state()))
+    }
+
+    /// If `discr` is a local that is assigned `BinaryOp(cmp, left, right)` in the current block, where `cmp` is a
+    /// comparison operator, returns the comparison of the values of `left` and `right`
+    /// The comparison is `None` if the operands are not tracked in the numerical domain
+    fn get_switched_comparison(&mut self, discr: &mir::Operand<'tcx>) -> Option<Rc<SymbolicValue>> {
+        let local = discr.place()?.as_local()?;
+        let (index, bin_op, left_operand, right_operand) = self.mir.basic_blocks
+            [self.current_block]
+            .statements
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(index, statement)| match &statement.kind {
+                mir::StatementKind::Assign(box (place, rvalue))
+                    if place.as_local() == Some(local) =>
+                {
+                    match rvalue {
+                        mir::Rvalue::BinaryOp(bin_op, box (left_operand, right_operand)) => Some(
+                            Some((index, *bin_op, left_operand.clone(), right_operand.clone())),
+                        ),
+                        _ => Some(None),
+                    }
+                }
+                _ => None,
+            })??;
+        if left_operand.constant().is_some() && right_operand.constant().is_some() {
+            return None;
+        }
+        let left = self.get_compared_value(&left_operand, index)?;
+        let right = self.get_compared_value(&right_operand, index)?;
+        match bin_op {
+            mir::BinOp::Eq => Some(left.equals(right)),
+            mir::BinOp::Ge => Some(left.greater_or_equal(right)),
+            mir::BinOp::Gt => Some(left.greater_than(right)),
+            mir::BinOp::Le => Some(left.less_or_equal(right)),
+            mir::BinOp::Lt => Some(left.less_than(right)),
+            mir::BinOp::Ne => Some(left.not_equals(right)),
+            _ => None,
+        }
+    }
+
+    /// The value of an operand of the comparison at `statement_index` in the current block
+    /// The operands are usually temporaries that copy the compared places and are moved into the comparison,
+    /// so they are traced back to the copied places, which must not be assigned again before the block exits
+    fn get_compared_value(
+        &mut self,
+        operand: &mir::Operand<'tcx>,
+        statement_index: usize,
+    ) -> Option<Rc<SymbolicValue>> {
+        let mut place = match operand {
+            mir::Operand::Constant(..) => return Some(self.visit_operand(operand)),
+            mir::Operand::Copy(place) | mir::Operand::Move(place) => *place,
+        };
+        let mut index = statement_index;
+        let statements = &self.mir.basic_blocks[self.current_block].statements;
+        if let Some(local) = place.as_local() {
+            let copy = statements[..index]
+                .iter()
+                .enumerate()
+                .rev()
+                .find_map(|(copy_index, statement)| match &statement.kind {
+                    mir::StatementKind::Assign(box (assigned, rvalue))
+                        if assigned.as_local() == Some(local) =>
+                    {
+                        match rvalue {
+                            mir::Rvalue::Use(
+                                mir::Operand::Copy(copied) | mir::Operand::Move(copied),
+                            ) => Some(Some((copy_index, *copied))),
+                            _ => Some(None),
+                        }
+                    }
+                    _ => None,
+                })
+                .flatten();
+            if let Some((copy_index, copied)) = copy {
+                index = copy_index;
+                place = copied;
+            }
+        }
+        // The value behind a reference may be changed through other references
+        if place.is_indirect() {
+            return None;
+        }
+        let is_assigned_again =
+            statements[index + 1..]
+                .iter()
+                .any(|statement| match &statement.kind {
+                    mir::StatementKind::Assign(box (assigned, _)) => assigned.local == place.local,
+                    _ => false,
+                });
+        if is_assigned_again {
+            return None;
+        }
+        let path = self.visit_place(&place);
+        if self.state().numerical_domain.contains(&path) {
+            Some(SymbolicValue::make_from(Expression::Numerical(path), 1))
+        } else {
+            None
+        }
     }
 
     fn visit_return(&mut self) {
@@ -1271,10 +2013,25 @@ where
             self.body_visitor.type_visitor.generic_argument_map
         );
         debug!("Before visit_call, env: {:?}", self.state());
-        // Store the offset that is about to be used while executing the following call visitor
-        let old_offset = self.body_visitor.next_fresh_variable_offset;
-        // Get `SymbolicValue` from `mir::Operand::Constant`
-        let func_to_call = self.visit_operand(func);
+        // The components of a fat pointer returned by the call are unknown, unless they are
+        // transferred from the callee
+        let destination_ty = self
+            .body_visitor
+            .type_visitor
+            .get_rustc_place_type(destination, self.body_visitor.current_span);
+        if type_visitor::is_fat_pointer(destination_ty.kind()) {
+            let destination_path = self.visit_place(destination);
+            self.body_visitor
+                .state
+                .remove(&Path::new_fat_pointer_data(destination_path.clone()));
+            self.body_visitor
+                .state
+                .remove(&Path::new_fat_pointer_meta(destination_path));
+        }
+        // Store the offset that is about to be used while executing the following call visitor
+        let old_offset = self.body_visitor.next_fresh_variable_offset;
+        // Get `SymbolicValue` from `mir::Operand::Constant`
+        let func_to_call = self.visit_operand(func);
         // Get `FunctionReference` from `SymbolicValue`
         let func_ref = self.get_func_ref(&func_to_call);
         // If the function cannot be reliably analyzed, simply ignore it and return
@@ -1290,6 +2047,13 @@ where
         let callee_def_id = func_ref_to_call
             .def_id
             .expect("callee obtained via operand should have def id");
+        self.visit_call_taint(callee_def_id, args, destination);
+        if self.body_visitor.context.analysis_options.check_float_nan {
+            self.visit_call_float(callee_def_id, args, destination);
+        }
+        if self.body_visitor.context.analysis_options.check_pin_safety {
+            self.visit_call_pin(callee_def_id, args);
+        }
         // The list of generic arguments
         let substs = self
             .body_visitor
@@ -1302,6 +2066,23 @@ where
             .body_visitor
             .type_visitor
             .specialize_substs(substs, &self.body_visitor.type_visitor.generic_argument_map);
+        // If the callee is a trait method, analyze the method of the selected implementation
+        // instead, because the trait method itself usually has no body
+        let (callee_def_id, callee_generic_arguments) = match self
+            .body_visitor
+            .type_visitor
+            .resolve_method_instance(callee_def_id, callee_generic_arguments)
+        {
+            Some(instance) => {
+                debug!(
+                    "Resolved trait method {:?} to {:?}",
+                    callee_def_id,
+                    instance.def_id()
+                );
+                (instance.def_id(), instance.args)
+            }
+            None => (callee_def_id, callee_generic_arguments),
+        };
         let actual_args: Vec<(Rc<Path>, Rc<SymbolicValue>)> = args
             .iter()
             .map(|arg| {
@@ -1351,6 +2132,7 @@ where
         call_visitor.actual_argument_types = &actual_argument_types;
         call_visitor.destination = Some(destination.clone());
         call_visitor.target = target.clone();
+        call_visitor.record_ffi_call();
         call_visitor.callee_fun_val = func_to_call;
         call_visitor.function_constant_args = func_const_args;
         debug!("Calling function {:?}", call_visitor.callee_func_ref);
@@ -1360,12 +2142,51 @@ where
             debug!("Successfully handled as special function call");
             return;
         }
+        call_visitor.forget_mutably_borrowed_vecs();
+
+        // If the callee is defined in a crate that has been analyzed before, use its summary
+        if call_visitor.handled_by_summary() {
+            debug!("Successfully handled by the summary of the callee");
+            call_visitor
+                .block_visitor
+                .assume_statics_written(callee_def_id);
+            return;
+        }
+
+        // If the callee is a standard library function that has a stub, use the stub instead of inlining it
+        if call_visitor.handled_by_stub() {
+            debug!("Successfully handled by the stub of the callee");
+            return;
+        }
+
+        // If the callee is the function being analyzed, use its summary instead of inlining it again
+        if call_visitor.handled_as_recursive_call() {
+            debug!("Successfully handled as a recursive call");
+            call_visitor
+                .block_visitor
+                .assume_statics_written(callee_def_id);
+            return;
+        }
+
+        // If the callee is deeper than `--inline-depth`, use its summary instead of inlining it
+        let exceeds_inline_depth = call_visitor.exceeds_inline_depth();
+        if exceeds_inline_depth && call_visitor.handled_by_inline_summary() {
+            debug!("Successfully handled by the summary of the callee beyond the inline depth");
+            call_visitor
+                .block_visitor
+                .assume_statics_written(callee_def_id);
+            return;
+        }
 
         debug!("Executing call visitor...");
-        // Run the call visitor and get post states
-        let function_post_state = call_visitor
-            .get_function_post_state()
-            .unwrap_or_else(AbstractDomain::default);
+        // Run the call visitor and get post states, a callee that is not inlined and has no summary is not analyzed
+        let function_post_state = if exceeds_inline_depth {
+            None
+        } else {
+            call_visitor.get_function_post_state()
+        };
+        let is_analyzed = function_post_state.is_some();
+        let function_post_state = function_post_state.unwrap_or_else(AbstractDomain::default);
 
         // Here, the offset should have already been reset
 
@@ -1378,12 +2199,718 @@ where
             call_visitor.block_visitor.state()
         );
         call_visitor.transfer_and_refine_normal_return_state(&function_post_state, old_offset);
+        if !is_analyzed {
+            call_visitor
+                .block_visitor
+                .assume_statics_written(callee_def_id);
+        }
         debug!(
             "After handling side-effects, post env {:?}",
             call_visitor.block_visitor.state()
         );
     }
 
+    /// Propagates the taint of untrusted inputs through a call, and records the call if its format
+    /// string argument is tainted, or if it calls a taint sink with a tainted argument
+    fn visit_call_taint(
+        &mut self,
+        callee_def_id: DefId,
+        args: &[Spanned<mir::Operand<'tcx>>],
+        destination: &mir::Place<'tcx>,
+    ) {
+        // The span of the call of the taint source that each argument is derived from, if any
+        let tainted_args: Vec<Option<Span>> = args
+            .iter()
+            .map(|arg| {
+                self.extract_local_from_operand(&arg.node)
+                    .and_then(|locals| {
+                        locals.iter().find_map(|local| {
+                            self.body_visitor
+                                .input_tainted_variables
+                                .get(local)
+                                .copied()
+                        })
+                    })
+            })
+            .collect();
+        let location = self.body_visitor.current_location;
+        let span = self.mir.source_info(location).span;
+        if let Some(index) = self.get_format_string_index(callee_def_id) {
+            if let Some(Some(source)) = tainted_args.get(index) {
+                debug!("Found a format string derived from a taint source");
+                self.body_visitor
+                    .tainted_format_strings
+                    .insert(location, (span, *source));
+            }
+        }
+        if self
+            .body_visitor
+            .context
+            .taint_sink_functions
+            .contains(&callee_def_id)
+        {
+            let tainted_arg = tainted_args
+                .iter()
+                .enumerate()
+                .find_map(|(argument, source)| source.map(|source| (argument, source)));
+            if let Some((argument, source)) = tainted_arg {
+                debug!("Found a taint sink called on an argument derived from a taint source");
+                self.body_visitor.tainted_sink_calls.insert(
+                    location,
+                    TaintedSinkCall {
+                        span,
+                        callee: callee_def_id,
+                        argument,
+                        source,
+                    },
+                );
+            }
+        }
+        // The result of a taint source is tainted, and so is the result of a call on tainted arguments
+        let source = if self.body_visitor.context.is_taint_source(callee_def_id) {
+            Some(span)
+        } else {
+            tainted_args.iter().find_map(|source| *source)
+        };
+        if let Some(source) = source {
+            self.body_visitor
+                .input_tainted_variables
+                .insert(destination.local, source);
+        }
+    }
+
+    /// Returns the alignment of the pointer produced by an assignment, and checks transmutes into
+    /// references, see `AlignmentChecker`
+    /// Must be called before `rvalue` is visited, because a move renames the alignment of the source
+    fn get_assigned_alignment(&mut self, rvalue: &mir::Rvalue<'tcx>) -> Option<u64> {
+        let alignment = match rvalue {
+            mir::Rvalue::Use(operand) | mir::Rvalue::Cast(_, operand, _) => {
+                self.get_known_alignment(operand)
+            }
+            // `&raw const *p` has the alignment of `p`, otherwise the borrowed place is aligned for its type,
+            // e.g., a pointer to a slice element is aligned to `align_of::<ElemType>()`
+            mir::Rvalue::Ref(_, _, borrowed_place) | mir::Rvalue::AddressOf(_, borrowed_place) => {
+                match borrowed_place.projection.as_slice() {
+                    [mir::ProjectionElem::Deref] => {
+                        self.get_known_alignment(&mir::Operand::Copy(borrowed_place.local.into()))
+                    }
+                    _ => {
+                        let ty = self
+                            .body_visitor
+                            .type_visitor
+                            .get_rustc_place_type(borrowed_place, self.body_visitor.current_span);
+                        Some(self.body_visitor.type_visitor.get_type_alignment(ty))
+                    }
+                }
+            }
+            _ => None,
+        };
+        if let mir::Rvalue::Cast(mir::CastKind::Transmute, _, target_ty) = rvalue {
+            if let TyKind::Ref(_, pointee_ty, _) = target_ty.kind() {
+                self.record_aligned_access(AlignedOperation::Transmute, alignment, *pointee_ty);
+            }
+        }
+        alignment
+    }
+
+    /// The alignment of a pointer is kept in the state, so that it is joined at the merges of the control flow,
+    /// and it is forgotten if the pointer is assigned a value whose alignment is unknown
+    fn update_known_alignment(&mut self, pointer_path: &Rc<Path>, alignment: Option<u64>) {
+        let alignment_path = Path::new_alignment(pointer_path.clone());
+        let numerical_domain = &mut self.body_visitor.state.numerical_domain;
+        match alignment {
+            Some(alignment) if alignment > 0 => {
+                numerical_domain.assign_int(alignment_path, Integer::from(alignment))
+            }
+            _ => numerical_domain.forget(&alignment_path),
+        }
+    }
+
+    /// Checks the pointer passed to `ptr::read` and `ptr::write`, and returns the alignment of the pointer
+    /// returned by the call, which must be recorded after the call, see `AlignmentChecker`
+    fn visit_call_alignment(
+        &mut self,
+        func: &mir::Operand<'tcx>,
+        args: &[Spanned<mir::Operand<'tcx>>],
+        destination: &mir::Place<'tcx>,
+    ) -> Option<u64> {
+        let callee_def_id = func.const_fn_def()?.0;
+        let tcx = self.body_visitor.context.tcx;
+        let known_name = self
+            .body_visitor
+            .crate_context
+            .known_names_cache
+            .get(tcx, callee_def_id);
+        let pointer_alignment = args
+            .first()
+            .and_then(|arg| self.get_known_alignment(&arg.node));
+        match known_name {
+            KnownNames::PtrRead | KnownNames::PtrWrite => {
+                let operation = if known_name == KnownNames::PtrRead {
+                    AlignedOperation::Read
+                } else {
+                    AlignedOperation::Write
+                };
+                let pointer_ty = self.get_operand_rustc_type(&args[0].node);
+                if let TyKind::RawPtr(pointee_ty, _) = pointer_ty.kind() {
+                    self.record_aligned_access(operation, pointer_alignment, *pointee_ty);
+                }
+                None
+            }
+            // Moving a pointer to `T` by an unknown number of elements keeps the alignment of `size_of::<T>()`
+            KnownNames::PtrAdd | KnownNames::PtrSub | KnownNames::PtrOffset => {
+                let pointer_ty = self.get_operand_rustc_type(&args[0].node);
+                match (pointer_alignment, pointer_ty.kind()) {
+                    (Some(alignment), TyKind::RawPtr(pointee_ty, _)) => {
+                        match self.body_visitor.type_visitor.get_type_size(*pointee_ty) {
+                            Some(0) => Some(alignment),
+                            Some(size) => Some(alignment.min(1 << size.trailing_zeros())),
+                            None => None,
+                        }
+                    }
+                    _ => None,
+                }
+            }
+            // The pointer to the buffer of a slice or a vector is aligned to `align_of::<ElemType>()`
+            KnownNames::SliceAsPtr | KnownNames::StdAsMutPtr => {
+                let destination_ty = self
+                    .body_visitor
+                    .type_visitor
+                    .get_rustc_place_type(destination, self.body_visitor.current_span);
+                match destination_ty.kind() {
+                    TyKind::RawPtr(pointee_ty, _) => Some(
+                        self.body_visitor
+                            .type_visitor
+                            .get_type_alignment(*pointee_ty),
+                    ),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the alignment that the address in a pointer operand is known to be a multiple of
+    /// A reference is always aligned for its pointee type
+    /// The alignments of the pointers joined at a merge are powers of two, so the lower bound of their join is
+    /// the smallest of them, which all of them are multiples of
+    fn get_known_alignment(&mut self, operand: &mir::Operand<'tcx>) -> Option<u64> {
+        if let mir::Operand::Copy(place) | mir::Operand::Move(place) = operand {
+            let alignment_path = Path::new_alignment(self.visit_place(place));
+            let numerical_domain = &self.state().numerical_domain;
+            if numerical_domain.contains(&alignment_path) {
+                if let Bound::Int(alignment) = numerical_domain.get_interval(&alignment_path).low {
+                    if let Some(alignment) = alignment.to_u64().filter(|alignment| *alignment > 0) {
+                        return Some(alignment);
+                    }
+                }
+            }
+        }
+        match self.get_operand_rustc_type(operand).kind() {
+            TyKind::Ref(_, pointee_ty, _) => Some(
+                self.body_visitor
+                    .type_visitor
+                    .get_type_alignment(*pointee_ty),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Records a use of a pointer that requires alignment for `pointee_ty`, if the alignment of the
+    /// pointer is known
+    fn record_aligned_access(
+        &mut self,
+        operation: AlignedOperation,
+        known_alignment: Option<u64>,
+        pointee_ty: Ty<'tcx>,
+    ) {
+        let known_alignment = if let Some(alignment) = known_alignment {
+            alignment
+        } else {
+            return;
+        };
+        let required_alignment = self
+            .body_visitor
+            .type_visitor
+            .get_type_alignment(pointee_ty);
+        let location = self.body_visitor.current_location;
+        let span = self.mir.source_info(location).span;
+        self.body_visitor.aligned_accesses.insert(
+            location,
+            AlignedAccess {
+                span,
+                operation,
+                required_alignment,
+                known_alignment,
+            },
+        );
+    }
+
+    /// Records the move out of a place, which must not be pinned, and the conversion of a `Pin` into a type that is
+    /// not a `Pin`, see `PinSafetyChecker`
+    fn record_pinned_accesses(&mut self, rvalue: &mir::Rvalue<'tcx>) {
+        let span = self.body_visitor.current_span;
+        let mut accesses = vec![];
+        match rvalue {
+            mir::Rvalue::Use(mir::Operand::Move(moved_place)) => {
+                accesses.push(PinnedAccess {
+                    span,
+                    operation: PinOperation::Move,
+                    path: self.visit_place(moved_place),
+                });
+            }
+            mir::Rvalue::Cast(_, operand, target_ty) => {
+                let operand_ty = self.get_operand_rustc_type(operand);
+                if let Some(pin_place) = operand.place() {
+                    if self.is_pin(operand_ty) && !self.is_pin(*target_ty) {
+                        accesses.push(PinnedAccess {
+                            span,
+                            operation: PinOperation::Unpin,
+                            path: self.visit_place(&pin_place),
+                        });
+                    }
+                }
+            }
+            _ => (),
+        }
+        self.body_visitor
+            .pinned_accesses
+            .insert(self.body_visitor.current_location, accesses);
+    }
+
+    /// Records the values of the operands of a comparison of floats in the current state, see `FloatNanChecker`
+    /// `x != x` is the idiom of `is_nan`, so the comparisons of a variable with itself are not recorded
+    fn record_float_comparison(&mut self, rvalue: &mir::Rvalue<'tcx>) {
+        let (left, right) = match rvalue {
+            mir::Rvalue::BinaryOp(
+                mir::BinOp::Eq
+                | mir::BinOp::Ne
+                | mir::BinOp::Lt
+                | mir::BinOp::Le
+                | mir::BinOp::Gt
+                | mir::BinOp::Ge,
+                box (left, right),
+            ) => (left, right),
+            _ => return,
+        };
+        if !self.get_operand_rustc_type(left).is_floating_point()
+            || (left.place().is_some() && left.place() == right.place())
+        {
+            return;
+        }
+        let comparison = FloatComparison {
+            span: self.body_visitor.current_span,
+            left: self.body_visitor.get_float_value(left),
+            right: self.body_visitor.get_float_value(right),
+        };
+        self.body_visitor
+            .float_comparisons
+            .insert(self.body_visitor.current_location, comparison);
+    }
+
+    /// Computes the value of a float-typed local variable assigned by `rvalue`, see `FloatNanChecker`
+    fn update_float_value(&mut self, place: &mir::Place<'tcx>, rvalue: &mir::Rvalue<'tcx>) {
+        let ty = self
+            .body_visitor
+            .type_visitor
+            .get_rustc_place_type(place, self.body_visitor.current_span);
+        if !place.projection.is_empty() || !ty.is_floating_point() {
+            return;
+        }
+        let value = match rvalue {
+            mir::Rvalue::Use(operand)
+            | mir::Rvalue::Cast(mir::CastKind::FloatToFloat, operand, _) => {
+                self.body_visitor.get_float_value(operand)
+            }
+            mir::Rvalue::BinaryOp(bin_op, box (left, right)) => {
+                let left = self.body_visitor.get_float_value(left);
+                let right = self.body_visitor.get_float_value(right);
+                match bin_op {
+                    mir::BinOp::Add => left.add(&right),
+                    mir::BinOp::Sub => left.sub(&right),
+                    mir::BinOp::Mul => left.mul(&right),
+                    mir::BinOp::Div => left.div(&right),
+                    mir::BinOp::Rem => left.rem(&right),
+                    _ => FloatValue::unknown(),
+                }
+            }
+            mir::Rvalue::UnaryOp(mir::UnOp::Neg, operand) => {
+                self.body_visitor.get_float_value(operand).neg()
+            }
+            // The integer is converted to the nearest float, which is never NaN
+            mir::Rvalue::Cast(mir::CastKind::IntToFloat, operand, _) => {
+                let interval = match operand {
+                    mir::Operand::Copy(operand_place) | mir::Operand::Move(operand_place) => {
+                        let operand_path = self.visit_place(operand_place);
+                        self.body_visitor
+                            .state
+                            .numerical_domain
+                            .get_interval(&operand_path)
+                    }
+                    mir::Operand::Constant(..) => match &self.visit_operand(operand).expression {
+                        Expression::CompileTimeConstant(ConstantValue::Int(value)) => {
+                            Interval::new(Bound::Int(value.clone()), Bound::Int(value.clone()))
+                        }
+                        _ => Interval::top(),
+                    },
+                };
+                FloatValue::from_interval(&interval)
+            }
+            _ => FloatValue::unknown(),
+        };
+        self.set_float_value(place.local, ty, value);
+    }
+
+    /// Computes the value of a float-typed destination of a call of a method of `f32` or `f64`, or of a float
+    /// intrinsic, see `FloatValue::call`
+    /// The result of another call is unknown, and may be NaN if one of its float arguments may be NaN
+    fn visit_call_float(
+        &mut self,
+        callee_def_id: DefId,
+        args: &[Spanned<mir::Operand<'tcx>>],
+        destination: &mir::Place<'tcx>,
+    ) {
+        let ty = self
+            .body_visitor
+            .type_visitor
+            .get_rustc_place_type(destination, self.body_visitor.current_span);
+        if !destination.projection.is_empty() || !ty.is_floating_point() {
+            return;
+        }
+        let arg_values: Vec<FloatValue> = args
+            .iter()
+            .map(|arg| self.body_visitor.get_float_value(&arg.node))
+            .collect();
+        let value = self
+            .get_float_method_name(callee_def_id)
+            .and_then(|name| FloatValue::call(&name, &arg_values))
+            .unwrap_or_else(|| FloatValue {
+                may_be_nan: arg_values.iter().any(|value| value.may_be_nan),
+                ..FloatValue::unknown()
+            });
+        self.set_float_value(destination.local, ty, value);
+    }
+
+    /// The name of a method of `f32` or `f64`, e.g., `sqrt`, or the name of a float intrinsic without the suffix of
+    /// its type, e.g., `sqrt` for `sqrtf64`
+    fn get_float_method_name(&self, def_id: DefId) -> Option<String> {
+        let tcx = self.body_visitor.context.tcx;
+        if let Some(intrinsic) = tcx.intrinsic(def_id) {
+            let name = intrinsic.name.as_str();
+            return name
+                .strip_suffix("f32")
+                .or_else(|| name.strip_suffix("f64"))
+                .map(str::to_owned);
+        }
+        let impl_def_id = tcx.impl_of_method(def_id)?;
+        if tcx
+            .type_of(impl_def_id)
+            .instantiate_identity()
+            .is_floating_point()
+        {
+            Some(tcx.item_name(def_id).to_string())
+        } else {
+            None
+        }
+    }
+
+    /// Assigns `value` to `local` in the current state, the values are joined where the paths merge, and widened at
+    /// the loop heads
+    fn set_float_value(&mut self, local: mir::Local, ty: Ty<'tcx>, value: FloatValue) {
+        let value = if let TyKind::Float(FloatTy::F32) = ty.kind() {
+            value.to_f32()
+        } else {
+            value
+        };
+        self.body_visitor.state.float_values.insert(local, value);
+    }
+
+    /// Records the paths pinned by `Pin::new_unchecked`, and the calls that move a value or unpin a `Pin`, see
+    /// `PinSafetyChecker`
+    fn visit_call_pin(&mut self, callee_def_id: DefId, args: &[Spanned<mir::Operand<'tcx>>]) {
+        let tcx = self.body_visitor.context.tcx;
+        let known_name = self
+            .body_visitor
+            .crate_context
+            .known_names_cache
+            .get(tcx, callee_def_id);
+        let span = self.body_visitor.current_span;
+        let location = self.body_visitor.current_location;
+        let mut accesses = vec![];
+        match known_name {
+            KnownNames::PtrRead | KnownNames::StdMemSwap => {
+                let operation = if known_name == KnownNames::PtrRead {
+                    PinOperation::Read
+                } else {
+                    PinOperation::Swap
+                };
+                // `ptr::read` moves the value that its argument points to, and `mem::swap` moves both values
+                for arg in args {
+                    accesses.extend(self.get_pointee_path(&arg.node).map(|path| PinnedAccess {
+                        span,
+                        operation,
+                        path,
+                    }));
+                }
+            }
+            _ => match self.get_pin_method_name(callee_def_id).as_deref() {
+                Some("new_unchecked") => {
+                    if let Some(path) = self.get_pointee_path(&args[0].node) {
+                        self.body_visitor.pinned_paths.insert(location, path);
+                    }
+                }
+                Some("get_unchecked_mut") | Some("into_inner_unchecked") => {
+                    if let Some(pin_place) = args[0].node.place() {
+                        accesses.push(PinnedAccess {
+                            span,
+                            operation: PinOperation::Unpin,
+                            path: self.visit_place(&pin_place),
+                        });
+                    }
+                }
+                _ => (),
+            },
+        }
+        self.body_visitor.pinned_accesses.insert(location, accesses);
+    }
+
+    /// Updates the type-states of the resources after a call, see `ResourceTypeStateChecker`
+    /// An `open`-style call that returns a resource opens it, and a `close`-style call closes the resource that its
+    /// first argument is or refers to. A resource that is moved into any other call escapes, because the callee
+    /// becomes responsible for closing it
+    fn visit_call_resource(
+        &mut self,
+        func: &mir::Operand<'tcx>,
+        args: &[Spanned<mir::Operand<'tcx>>],
+        destination: &mir::Place<'tcx>,
+    ) {
+        if self.body_visitor.context.resource_types.is_empty() {
+            return;
+        }
+        let callee_def_id = match func.const_fn_def() {
+            Some((def_id, _)) => def_id,
+            None => return,
+        };
+        let name = self.body_visitor.context.tcx.item_name(callee_def_id);
+        let is_close = resource_type_state_checker::is_close_method(name.as_str());
+        for (index, arg) in args.iter().enumerate() {
+            let arg_ty = self.get_operand_rustc_type(&arg.node);
+            let (resource_path, is_moved) = match (&arg.node, arg_ty.kind()) {
+                (mir::Operand::Move(place), _)
+                    if self.body_visitor.context.is_resource_type(arg_ty) =>
+                {
+                    (self.visit_place(place), true)
+                }
+                (_, TyKind::Ref(_, pointee_ty, _))
+                    if is_close
+                        && index == 0
+                        && self.body_visitor.context.is_resource_type(*pointee_ty) =>
+                {
+                    match self.get_pointee_path(&arg.node) {
+                        Some(path) => (path, false),
+                        None => continue,
+                    }
+                }
+                _ => continue,
+            };
+            if is_close && index == 0 {
+                self.body_visitor.state.numerical_domain.assign_int(
+                    Path::new_resource_state(resource_path),
+                    ResourceState::Closed.value(),
+                );
+            } else if is_moved {
+                self.body_visitor.escaped_resources.insert(resource_path);
+            }
+        }
+
+        let destination_ty = self
+            .body_visitor
+            .type_visitor
+            .get_rustc_place_type(destination, self.body_visitor.current_span);
+        if resource_type_state_checker::is_open_method(name.as_str())
+            && self.body_visitor.context.is_resource_type(destination_ty)
+        {
+            let resource_path = self.visit_place(destination);
+            self.body_visitor.state.numerical_domain.assign_int(
+                Path::new_resource_state(resource_path),
+                ResourceState::Open.value(),
+            );
+        }
+    }
+
+    /// Updates the freed states of the memory that a call frees, see `DoubleFreeChecker`
+    /// A call in `FREE_METHODS` frees the memory that its first argument points to, and the call is recorded if the
+    /// memory may have been freed before it. The memory is resolved before the call, which may move the pointer
+    fn visit_call_free(
+        &mut self,
+        func: &mir::Operand<'tcx>,
+        args: &[Spanned<mir::Operand<'tcx>>],
+        destination: &mir::Place<'tcx>,
+    ) {
+        if !self.body_visitor.context.analysis_options.check_double_free {
+            return;
+        }
+        // A pointer returned by a call, e.g., `Box::into_raw`, points to memory that is not freed
+        let destination_ty = self
+            .body_visitor
+            .type_visitor
+            .get_rustc_place_type(destination, self.body_visitor.current_span);
+        if destination_ty.is_unsafe_ptr() {
+            let destination_path = self.visit_place(destination);
+            self.body_visitor
+                .reset_freed_state(Path::new_deref(destination_path));
+        }
+
+        let location = self.body_visitor.current_location;
+        let callee_def_id = match func.const_fn_def() {
+            Some((def_id, _)) => def_id,
+            None => return,
+        };
+        let name = self.body_visitor.context.tcx.item_name(callee_def_id);
+        let pointer = match args.first() {
+            Some(arg)
+                if double_free_checker::is_free_method(name.as_str())
+                    && self.get_operand_rustc_type(&arg.node).is_unsafe_ptr() =>
+            {
+                &arg.node
+            }
+            _ => return,
+        };
+        let pointee_path = match self.get_pointee_path(pointer) {
+            Some(path) => path,
+            None => return,
+        };
+        let freed_path = Path::new_freed(pointee_path);
+        let numerical_domain = &self.body_visitor.state.numerical_domain;
+        let state = if numerical_domain.contains(&freed_path) {
+            FreedState::from_interval(&numerical_domain.get_interval(&freed_path))
+        } else {
+            FreedState::Live
+        };
+        if state == FreedState::Live {
+            // The call recorded in an earlier iteration may not free the memory again after widening or narrowing
+            self.body_visitor.double_frees.remove(&location);
+        } else {
+            let pointer_path = pointer.place().map(|place| self.visit_place(&place));
+            let double_free = DoubleFree {
+                span: self.body_visitor.current_span,
+                callee: name.to_string(),
+                pointer: pointer_path,
+                state,
+            };
+            self.body_visitor.double_frees.insert(location, double_free);
+        }
+        self.body_visitor
+            .state
+            .numerical_domain
+            .assign_int(freed_path, FreedState::Freed.value());
+    }
+
+    /// Marks the mutable static that `path` is rooted by as written, see `UninitializedGlobalChecker`
+    fn record_static_write(&mut self, path: &Rc<Path>) {
+        let tcx = self.body_visitor.context.tcx;
+        let path = path.refine_paths(self.state());
+        if let Some(def_id) = uninitialized_global_checker::get_mutable_static(tcx, &path) {
+            let written_path = Path::new_written(Path::new_static(tcx, def_id));
+            self.body_visitor
+                .state
+                .numerical_domain
+                .assign_int(written_path, WrittenState::Written.value());
+        }
+    }
+
+    /// Records the read of the mutable static that `path` is rooted by if it may not have been written, see
+    /// `UninitializedGlobalChecker`
+    fn record_static_read(&mut self, path: &Rc<Path>) {
+        let tcx = self.body_visitor.context.tcx;
+        let path = path.refine_paths(self.state());
+        let def_id = match uninitialized_global_checker::get_mutable_static(tcx, &path) {
+            Some(def_id) => def_id,
+            None => return,
+        };
+        let location = self.body_visitor.current_location;
+        let written_path = Path::new_written(Path::new_static(tcx, def_id));
+        let numerical_domain = &self.body_visitor.state.numerical_domain;
+        let state = if numerical_domain.contains(&written_path) {
+            WrittenState::from_interval(&numerical_domain.get_interval(&written_path))
+        } else {
+            WrittenState::Unwritten
+        };
+        if state == WrittenState::Written {
+            // The read recorded in an earlier iteration may be preceded by a write after the join of a loop
+            self.body_visitor.static_reads.remove(&location);
+        } else {
+            let read = StaticRead {
+                span: self.body_visitor.current_span,
+                def_id,
+                state,
+            };
+            self.body_visitor.static_reads.insert(location, read);
+        }
+    }
+
+    /// Marks the mutable statics that a call may write as written, if the callee is a local function that is not
+    /// analyzed with the current state, e.g., it is handled by its summary, see `UninitializedGlobalChecker`
+    /// The statics mentioned by the callee and the functions it may call are assumed to be written
+    pub fn assume_statics_written(&mut self, callee_def_id: DefId) {
+        if !self
+            .body_visitor
+            .context
+            .analysis_options
+            .check_use_of_uninitialized_global
+            || !callee_def_id.is_local()
+        {
+            return;
+        }
+        let tcx = self.body_visitor.context.tcx;
+        let callees = reachability::reachable_functions(tcx, &[callee_def_id]);
+        for def_id in reachability::mentioned_mutable_statics(tcx, callees) {
+            let written_path = Path::new_written(Path::new_static(tcx, def_id));
+            self.body_visitor
+                .state
+                .numerical_domain
+                .assign_int(written_path, WrittenState::Written.value());
+        }
+    }
+
+    /// Returns the path of the value that a pointer operand points to
+    fn get_pointee_path(&mut self, operand: &mir::Operand<'tcx>) -> Option<Rc<Path>> {
+        let pointer_path = self.visit_place(&operand.place()?);
+        Some(Path::new_deref(pointer_path).refine_paths(self.state()))
+    }
+
+    /// Returns the name of the callee if it is a method of `Pin`, which is recognized by the `Pin` lang item
+    fn get_pin_method_name(&self, def_id: DefId) -> Option<String> {
+        let tcx = self.body_visitor.context.tcx;
+        let impl_def_id = tcx.impl_of_method(def_id)?;
+        let self_ty = tcx.type_of(impl_def_id).instantiate_identity();
+        if self.is_pin(self_ty) {
+            Some(tcx.item_name(def_id).to_string())
+        } else {
+            None
+        }
+    }
+
+    /// Returns true if `ty` is `Pin<P>`
+    fn is_pin(&self, ty: Ty<'tcx>) -> bool {
+        let tcx = self.body_visitor.context.tcx;
+        ty.ty_adt_def().map_or(false, |def| {
+            tcx.lang_items().get(LangItem::Pin) == Some(def.did())
+        })
+    }
+
+    /// Returns the index of the format string argument if the callee takes one, i.e., a `printf`-like
+    /// C function
+    /// The pieces of the format strings of `format!`-like macros are literals, so they are never tainted
+    fn get_format_string_index(&self, def_id: DefId) -> Option<usize> {
+        let tcx = self.body_visitor.context.tcx;
+        if tcx.is_foreign_item(def_id) {
+            format_string_checker::get_c_format_string_index(tcx.item_name(def_id).as_str())
+        } else {
+            None
+        }
+    }
+
     fn get_operand_rustc_type(&mut self, operand: &mir::Operand<'tcx>) -> Ty<'tcx> {
         match operand {
             mir::Operand::Copy(place) | mir::Operand::Move(place) => self
@@ -1544,11 +3071,44 @@ where
         &mut self,
         cond: &mir::Operand<'tcx>,
         _expected: bool,
-        _msg: &mir::AssertMessage<'tcx>,
+        msg: &mir::AssertMessage<'tcx>,
         _target: mir::BasicBlock,
         _unwind: UnwindAction,
     ) {
-        let cond_value = self.visit_operand(cond);
+        let check_shift_overflow = self
+            .body_visitor
+            .context
+            .analysis_options
+            .check_shift_overflow;
+        let check_shift_by_const = self
+            .body_visitor
+            .context
+            .analysis_options
+            .check_shift_by_const;
+        let cond_value = match msg {
+            // The constant shift amounts are checked before the fixed-point iteration, see `ShiftByConstChecker`,
+            // so the condition is assumed to hold instead of being reported again by the assertion checker
+            mir::AssertKind::Overflow(bin_op @ (mir::BinOp::Shl | mir::BinOp::Shr), lhs, rhs)
+                if check_shift_by_const
+                    && shift_by_const_checker::get_constant_shift(
+                        self.body_visitor.context.tcx,
+                        &self.body_visitor.type_visitor,
+                        *bin_op,
+                        lhs,
+                        rhs,
+                        self.body_visitor.current_span,
+                    )
+                    .is_some() =>
+            {
+                self.body_visitor.get_u128_const_val(1)
+            }
+            mir::AssertKind::Overflow(mir::BinOp::Shl | mir::BinOp::Shr, lhs, rhs)
+                if check_shift_overflow =>
+            {
+                self.get_shift_amount_condition(lhs, rhs)
+            }
+            _ => self.visit_operand(cond),
+        };
         if let Some(place) = cond.place() {
             self.body_visitor
                 .place_to_abstract_value
@@ -1556,6 +3116,88 @@ where
         }
     }
 
+    /// The condition that the shift amount `rhs` is less than the bit width of the shifted value `lhs`, and is
+    /// not negative, which replaces the condition generated by the compiler, i.e., `rhs as u32 < 32`, whose cast
+    /// of a signed shift amount cannot be reasoned about by the numerical domain
+    fn get_shift_amount_condition(
+        &mut self,
+        lhs: &mir::Operand<'tcx>,
+        rhs: &mir::Operand<'tcx>,
+    ) -> Rc<SymbolicValue> {
+        let tcx = self.body_visitor.context.tcx;
+        let type_visitor = &self.body_visitor.type_visitor;
+        let lhs_ty = type_visitor.specialize_generic_argument_type(
+            lhs.ty(self.mir, tcx),
+            &type_visitor.generic_argument_map,
+        );
+        let rhs_ty = type_visitor.specialize_generic_argument_type(
+            rhs.ty(self.mir, tcx),
+            &type_visitor.generic_argument_map,
+        );
+        let bit_width = match type_visitor.get_type_size(lhs_ty) {
+            Some(size) => size * 8,
+            None => return symbolic_value::TOP.into(),
+        };
+        let shift_amount = match rhs {
+            mir::Operand::Constant(constant) => {
+                let param_env = type_visitor.get_param_env();
+                match constant.const_.try_eval_bits(tcx, param_env) {
+                    Some(bits) => self.get_int_const_val(bits, rhs_ty),
+                    None => return symbolic_value::TOP.into(),
+                }
+            }
+            _ => self.visit_operand(rhs),
+        };
+        let bit_width = self.body_visitor.get_u128_const_val(bit_width as u128);
+        let cond = shift_amount.less_than(bit_width);
+        if rhs_ty.is_signed() {
+            let zero = self.body_visitor.get_u128_const_val(0);
+            cond.and(shift_amount.greater_or_equal(zero))
+        } else {
+            cond
+        }
+    }
+
+    /// Record the range of the shifted value `lhs` of a shift of a signed integer, see `ShiftSignChecker`
+    fn record_signed_shift(&mut self, bin_op: mir::BinOp, lhs: &mir::Operand<'tcx>) {
+        use mir::BinOp::*;
+        if !matches!(bin_op, Shl | ShlUnchecked | Shr | ShrUnchecked) {
+            return;
+        }
+        let tcx = self.body_visitor.context.tcx;
+        let type_visitor = &self.body_visitor.type_visitor;
+        let lhs_ty = type_visitor.specialize_generic_argument_type(
+            lhs.ty(self.mir, tcx),
+            &type_visitor.generic_argument_map,
+        );
+        if !lhs_ty.is_signed() {
+            return;
+        }
+        let value = match lhs {
+            mir::Operand::Constant(..) => match &self.visit_operand(lhs).expression {
+                Expression::CompileTimeConstant(ConstantValue::Int(value)) => {
+                    Interval::new(Bound::from(value.clone()), Bound::from(value.clone()))
+                }
+                _ => Interval::top(),
+            },
+            _ => {
+                let lhs_path = self.get_operand_path(lhs);
+                self.body_visitor
+                    .state
+                    .numerical_domain
+                    .get_interval(&lhs_path)
+            }
+        };
+        let shift = SignedShift {
+            span: self.body_visitor.current_span,
+            is_left: matches!(bin_op, Shl | ShlUnchecked),
+            value,
+        };
+        self.body_visitor
+            .signed_shifts
+            .insert(self.body_visitor.current_location, shift);
+    }
+
     fn visit_inline_asm(&mut self) {
         let span = self.body_visitor.current_span;
         let err = self
@@ -1600,6 +3242,9 @@ where
                     "Get RHS Rvalue: BinaryOp({:?}, {:?}, {:?})",
                     bin_op, left_operand, right_operand
                 );
+                if self.body_visitor.context.analysis_options.check_shift_sign {
+                    self.record_signed_shift(*bin_op, left_operand);
+                }
                 self.visit_binary_op(path, *bin_op, left_operand, right_operand);
             }
             mir::Rvalue::CheckedBinaryOp(bin_op, box (left_operand, right_operand)) => {
@@ -1633,8 +3278,20 @@ where
             mir::Rvalue::ThreadLocalRef(def_id) => {
                 self.visit_thread_local_ref(*def_id);
             }
-            mir::Rvalue::ShallowInitBox(operand, ty) => todo!(),
-            mir::Rvalue::CopyForDeref(place) => todo!(),
+            // The value of the box is unknown
+            mir::Rvalue::ShallowInitBox(operand, ty) => {
+                self.body_visitor.type_visitor.report_unsupported(
+                    self.body_visitor.current_span,
+                    &format!("ShallowInitBox({:?}, {:?})", operand, ty),
+                );
+                self.body_visitor
+                    .state
+                    .update_value_at(path, symbolic_value::TOP.into());
+            }
+            mir::Rvalue::CopyForDeref(place) => {
+                debug!("Get RHS Rvalue: CopyForDeref({:?})", place);
+                self.visit_use(path, &mir::Operand::Copy(*place));
+            }
         }
     }
 
@@ -1645,24 +3302,66 @@ where
 
     // operands contains a list of values
     // E.g. Aggregate(Array(i32), [const 1_i32, const 2_i32, const 3_i32, const 4_i32, const 5_i32])
+    // E.g. Aggregate(Adt(Option, 1, [i32]), [move _2]), i.e., `Option::<i32>::Some(move _2)`
     fn visit_aggregate(
         &mut self,
         path: Rc<Path>,
         aggregate_kinds: &mir::AggregateKind<'tcx>,
         operands: &[mir::Operand<'tcx>],
     ) {
-        assert!(matches!(aggregate_kinds, mir::AggregateKind::Array(..)));
-        let length_path = Path::new_length(path.clone()).refine_paths(&self.state());
-        let length_value = self.body_visitor.get_u128_const_val(operands.len() as u128);
-        self.body_visitor
-            .state
-            .update_value_at(length_path, length_value);
+        match aggregate_kinds {
+            mir::AggregateKind::Array(..) => {
+                let length_path = Path::new_length(path.clone()).refine_paths(&self.state());
+                let length_value = self.body_visitor.get_u128_const_val(operands.len() as u128);
+                self.body_visitor
+                    .state
+                    .update_value_at(length_path, length_value);
 
-        // Handle the list of operands
-        for (i, operand) in operands.iter().enumerate() {
-            let index_value = self.body_visitor.get_u128_const_val(i as u128);
-            let index_path = Path::new_index(path.clone(), index_value).refine_paths(&self.state());
-            self.visit_used_operand(index_path, operand);
+                // Handle the list of operands
+                for (i, operand) in operands.iter().enumerate() {
+                    let index_value = self.body_visitor.get_u128_const_val(i as u128);
+                    let index_path =
+                        Path::new_index(path.clone(), index_value).refine_paths(&self.state());
+                    self.visit_used_operand(index_path, operand);
+                }
+            }
+            mir::AggregateKind::Adt(def_id, variant_index, generic_args, _, active_field) => {
+                let tcx = self.body_visitor.context.tcx;
+                let adt_def = tcx.adt_def(*def_id);
+                // The fields of a variant are stored as the fields of the enum value itself,
+                // because `ProjectionElem::Downcast` is ignored when visiting places
+                if adt_def.is_enum() {
+                    let ty = tcx.type_of(*def_id).instantiate(tcx, generic_args);
+                    let ty = self
+                        .body_visitor
+                        .type_visitor
+                        .specialize_generic_argument_type(
+                            ty,
+                            &self.body_visitor.type_visitor.generic_argument_map,
+                        );
+                    self.set_discriminant(path.clone(), ty, *variant_index);
+                }
+                // For a union, the only operand initializes the active field
+                if let Some(active_field) = active_field {
+                    let field_path =
+                        Path::new_field(path, active_field.as_usize()).refine_paths(self.state());
+                    self.visit_used_operand(field_path, &operands[0]);
+                    return;
+                }
+                for (i, operand) in operands.iter().enumerate() {
+                    let field_path = Path::new_field(path.clone(), i).refine_paths(self.state());
+                    self.visit_used_operand(field_path, operand);
+                }
+            }
+            mir::AggregateKind::Tuple
+            | mir::AggregateKind::Closure(..)
+            | mir::AggregateKind::Coroutine(..)
+            | mir::AggregateKind::CoroutineClosure(..) => {
+                for (i, operand) in operands.iter().enumerate() {
+                    let field_path = Path::new_field(path.clone(), i).refine_paths(self.state());
+                    self.visit_used_operand(field_path, operand);
+                }
+            }
         }
     }
 
@@ -1727,6 +3426,8 @@ where
                     &operand_path,
                     &path,
                 );
+                let ty = self.get_operand_rustc_type(operand);
+                self.wrap_integer_result(&path, ty);
             }
             mir::UnOp::Not => {
                 let val = self.visit_operand(operand);
@@ -1737,8 +3438,29 @@ where
         }
     }
 
+    /// path = Discriminant(place)
+    /// The result is equal to the discriminant of `place` in the numerical domain, so that a `SwitchInt` on the
+    /// result also refines the discriminant. An unknown discriminant of an enum is one of the discriminants of its
+    /// variants
     fn visit_discriminant(&mut self, path: Rc<Path>, place: &mir::Place<'tcx>) {
-        let discriminant_path = Path::new_discriminant(self.visit_place(place));
+        let discriminant_path =
+            Path::new_discriminant(self.visit_place(place)).refine_paths(self.state());
+        if self.state().value_at(&discriminant_path).is_none() {
+            if let Some(range) = self.get_discriminant_range(place) {
+                self.body_visitor
+                    .state
+                    .numerical_domain
+                    .set_interval(&discriminant_path, range);
+            }
+        }
+        if self.state().numerical_domain.contains(&discriminant_path) {
+            let discriminant_value =
+                SymbolicValue::make_from(Expression::Numerical(discriminant_path), 1);
+            self.body_visitor
+                .state
+                .update_value_at(path, discriminant_value);
+            return;
+        }
         let discriminant_value = self.body_visitor.lookup_path_and_refine_result(
             discriminant_path,
             self.body_visitor.context.tcx.types.u128,
@@ -1748,6 +3470,27 @@ where
             .update_value_at(path, discriminant_value);
     }
 
+    /// The smallest and the largest discriminants of the variants of the enum at `place`, `None` if it is not an enum
+    fn get_discriminant_range(&mut self, place: &mir::Place<'tcx>) -> Option<Interval> {
+        let tcx = self.body_visitor.context.tcx;
+        let ty = self
+            .body_visitor
+            .type_visitor
+            .get_rustc_place_type(place, self.body_visitor.current_span);
+        let adt_def = ty.ty_adt_def().filter(|adt_def| adt_def.is_enum())?;
+        let discriminants: Vec<Integer> = adt_def
+            .discriminants(tcx)
+            .map(|(_, discr)| self.get_int_const_val(discr.val, discr.ty))
+            .filter_map(|value| match &value.expression {
+                Expression::CompileTimeConstant(ConstantValue::Int(value)) => Some(value.clone()),
+                _ => None,
+            })
+            .collect();
+        let low = discriminants.iter().min()?.clone();
+        let high = discriminants.iter().max()?.clone();
+        Some(Interval::new(Bound::from(low), Bound::from(high)))
+    }
+
     // E.g. Cast(Pointer(Unsize), move _3, std::boxed::Box<[i32]>)
     // Casting `_3` which is originally a pointer, to `std::boxed::Box<[i32]>`
     fn visit_cast(
@@ -1759,6 +3502,12 @@ where
     ) {
         let operand_val = self.visit_operand(operand);
         match cast_kind {
+            mir::CastKind::IntToInt if ty.is_integral() => {
+                if self.body_visitor.context.analysis_options.check_int_promotion {
+                    self.record_sign_promotion(operand, &operand_val, *ty);
+                }
+                self.visit_int_to_int_cast(path, operand_val, *ty);
+            }
             // TODO: do we need to check overflow while casting?
             mir::CastKind::IntToInt
             | mir::CastKind::FloatToInt
@@ -1781,6 +3530,115 @@ where
         }
     }
 
+    /// Record the range of `operand` if it is cast from an unsigned integer into a signed integer type `ty` that is
+    /// at least as wide, see `IntPromotionChecker`
+    fn record_sign_promotion(
+        &mut self,
+        operand: &mir::Operand<'tcx>,
+        operand_val: &Rc<SymbolicValue>,
+        ty: Ty<'tcx>,
+    ) {
+        let tcx = self.body_visitor.context.tcx;
+        let type_visitor = &self.body_visitor.type_visitor;
+        let source_ty = type_visitor.specialize_generic_argument_type(
+            operand.ty(self.mir, tcx),
+            &type_visitor.generic_argument_map,
+        );
+        if !source_ty.is_integral() || source_ty.is_signed() || !ty.is_signed() {
+            return;
+        }
+        let (source_bits, target_bits) = match (
+            type_visitor.get_type_size(source_ty),
+            type_visitor.get_type_size(ty),
+        ) {
+            (Some(source_size), Some(target_size)) => (source_size * 8, target_size * 8),
+            _ => return,
+        };
+        if source_bits == 0 || target_bits < source_bits {
+            return;
+        }
+        let source_range = WrappingIntegerDomain::new(source_bits, false).full_range();
+        let value = match &operand_val.expression {
+            Expression::CompileTimeConstant(ConstantValue::Int(value)) => {
+                Interval::new(Bound::from(value.clone()), Bound::from(value.clone()))
+            }
+            Expression::Numerical(operand_path) => self
+                .body_visitor
+                .state
+                .numerical_domain
+                .get_interval(operand_path),
+            Expression::Variable {
+                path: operand_path,
+                var_type,
+            } if var_type.is_integer() => self
+                .body_visitor
+                .state
+                .numerical_domain
+                .get_interval(operand_path),
+            _ => Interval::top(),
+        };
+        let promotion = SignPromotion {
+            span: self.body_visitor.current_span,
+            source: source_ty.to_string(),
+            target: ty.to_string(),
+            target_bits,
+            value: value.meet(&source_range),
+        };
+        self.body_visitor
+            .sign_promotions
+            .insert(self.body_visitor.current_location, promotion);
+    }
+
+    /// `path = operand as ty`, where `ty` is an integer type
+    /// If all the values of `operand` are in the range of `ty`, e.g., in a widening cast, the value is preserved,
+    /// otherwise the values of `operand` are wrapped around into the range of `ty`, since `as` truncates
+    fn visit_int_to_int_cast(
+        &mut self,
+        path: Rc<Path>,
+        operand_val: Rc<SymbolicValue>,
+        ty: Ty<'tcx>,
+    ) {
+        let operand_path = match &operand_val.expression {
+            Expression::Numerical(operand_path) => operand_path.clone(),
+            Expression::Variable {
+                path: operand_path,
+                var_type,
+            } if var_type.is_integer() => operand_path.clone(),
+            // Constants are cast exactly
+            _ => {
+                let result = operand_val.cast(ExpressionType::from(ty.kind()));
+                self.body_visitor.state.update_value_at(path, result);
+                return;
+            }
+        };
+        let bit_width = match self.body_visitor.type_visitor.get_type_size(ty) {
+            Some(size) => size * 8,
+            None => {
+                self.body_visitor
+                    .state
+                    .update_value_at(path, symbolic_value::TOP.into());
+                return;
+            }
+        };
+        let type_range = WrappingIntegerDomain::new(bit_width, ty.is_signed());
+        let interval = self
+            .body_visitor
+            .state
+            .numerical_domain
+            .get_interval(&operand_path);
+        self.body_visitor.state.update_value_at(
+            path.clone(),
+            SymbolicValue::make_from(Expression::Numerical(operand_path), 1),
+        );
+        if bit_width == 0 || type_range.contains(&interval) {
+            return;
+        }
+        self.body_visitor
+            .state
+            .numerical_domain
+            .set_interval(&path, type_range.wrap(&interval));
+    }
+
     fn bin_op_to_apron_bin_op(&mut self, bin_op: mir::BinOp) -> Option<ApronOperation> {
         let res = match bin_op {
             mir::BinOp::Add | mir::BinOp::AddUnchecked => ApronOperation::Add,
@@ -1857,11 +3715,12 @@ where
                         .apply_bin_op_place_place(op, &left_path, &right_path, &path);
                 }
             }
+            let ty = self.get_operand_rustc_type(left_operand);
+            self.wrap_integer_result(&path, ty);
         }
         // For comparison operators, handle by abstract domain
         else {
-            let left = self.visit_operand(left_operand);
-            let right = self.visit_operand(right_operand);
+            let (left, right) = self.visit_comparison_operands(left_operand, right_operand);
             let result = match bin_op {
                 mir::BinOp::Eq => left.equals(right),
                 mir::BinOp::Ge => left.greater_or_equal(right),
@@ -1877,6 +3736,72 @@ where
         }
     }
 
+    /// In the `wrapping-integer` domain, the result of an arithmetic operation of type `ty` wraps around on overflow,
+    /// i.e., its values are computed modulo `2^N`, where `N` is the bit width of `ty`
+    fn wrap_integer_result(&mut self, path: &Rc<Path>, ty: Ty<'tcx>) {
+        if self.body_visitor.context.analysis_options.domain_type
+            != AbstractDomainType::WrappingInteger
+            || !ty.is_integral()
+        {
+            return;
+        }
+        let bit_width = match self.body_visitor.type_visitor.get_type_size(ty) {
+            Some(size) if size > 0 => size * 8,
+            _ => return,
+        };
+        let wrapping_domain = WrappingIntegerDomain::new(bit_width, ty.is_signed());
+        let numerical_domain = &mut self.body_visitor.state.numerical_domain;
+        let interval = numerical_domain.get_interval(path);
+        // A top interval means that the result is not tracked, which is still sound
+        if !interval.is_top() && !wrapping_domain.contains(&interval) {
+            numerical_domain.set_interval(path, wrapping_domain.wrap(&interval));
+        }
+    }
+
+    /// Raw pointers to the same allocation are compared by their offsets, which are tracked in the
+    /// numerical domain (see `update_raw_pointer_offset`), other operands are compared by their symbolic values
+    fn visit_comparison_operands(
+        &mut self,
+        left_operand: &mir::Operand<'tcx>,
+        right_operand: &mir::Operand<'tcx>,
+    ) -> (Rc<SymbolicValue>, Rc<SymbolicValue>) {
+        if let (Some(left_path), Some(right_path)) = (
+            self.get_tracked_raw_pointer_path(left_operand),
+            self.get_tracked_raw_pointer_path(right_operand),
+        ) {
+            let value_map = &self.state().symbolic_domain.value_map;
+            let left_allocation = value_map.get(&left_path);
+            if left_allocation.is_some() && left_allocation == value_map.get(&right_path) {
+                return (
+                    SymbolicValue::make_from(Expression::Numerical(left_path), 1),
+                    SymbolicValue::make_from(Expression::Numerical(right_path), 1),
+                );
+            }
+        }
+        (
+            self.visit_operand(left_operand),
+            self.visit_operand(right_operand),
+        )
+    }
+
+    /// Returns the path of a raw pointer operand whose offset is tracked in the numerical domain
+    fn get_tracked_raw_pointer_path(&mut self, operand: &mir::Operand<'tcx>) -> Option<Rc<Path>> {
+        let place = operand.place()?;
+        let ty = self
+            .body_visitor
+            .type_visitor
+            .get_rustc_place_type(&place, self.body_visitor.current_span);
+        if !ty.is_unsafe_ptr() {
+            return None;
+        }
+        let path = self.visit_place(&place);
+        if self.state().numerical_domain.contains(&path) {
+            Some(path)
+        } else {
+            None
+        }
+    }
+
     fn visit_checked_binary_op(
         &mut self,
         path: Rc<Path>,
@@ -1924,6 +3849,14 @@ where
 
     fn visit_operand_place(&mut self, place: &mir::Place<'tcx>) -> Rc<SymbolicValue> {
         let path = self.visit_place(place);
+        if self
+            .body_visitor
+            .context
+            .analysis_options
+            .check_use_of_uninitialized_global
+        {
+            self.record_static_read(&path);
+        }
         let rust_place_type = self
             .body_visitor
             .type_visitor
@@ -1967,7 +3900,10 @@ where
             PathEnum::PromotedConstant { .. } => {
                 if let Some(val) = self.state().value_at(&value_path) {
                     if let Expression::HeapBlock { .. } = &val.expression {
-                        let heap_path = Rc::new(PathEnum::HeapBlock { value: val.clone() }.into());
+                        let heap_path = Path::intern(
+                            &self.body_visitor.context.path_interner,
+                            PathEnum::HeapBlock { value: val.clone() },
+                        );
                         SymbolicValue::make_reference(heap_path)
                     } else {
                         SymbolicValue::make_reference(value_path)
@@ -2020,11 +3956,11 @@ where
                 }
                 match &const_value.expression {
                     Expression::HeapBlock { .. } => {
-                        let rpath = Rc::new(
+                        let rpath = Path::intern(
+                            &self.body_visitor.context.path_interner,
                             PathEnum::HeapBlock {
                                 value: const_value.clone(),
-                            }
-                            .into(),
+                            },
                         );
                         self.copy_or_move_elements(path, rpath, rh_type, false);
                     }
@@ -2054,16 +3990,57 @@ where
             .body_visitor
             .type_visitor
             .get_rustc_place_type(place, self.body_visitor.current_span);
-        self.copy_or_move_elements(target_path, rpath, rtype, true);
+        self.copy_or_move_elements(target_path.clone(), rpath.clone(), rtype, true);
+        // Unlike `Copy`, the source place is no longer valid after a `Move`, until it is re-initialized
+        self.body_visitor.state.mark_moved(rpath, &target_path);
     }
 
     // path = Len(place)
+    // The length of an array is part of its type, otherwise the result is equal to the tracked length of the slice,
+    // e.g., the metadata of the fat pointer that it is accessed through
     fn visit_len(&mut self, path: Rc<Path>, place: &mir::Place<'tcx>) {
-        let value_path = self.visit_place(place);
-        let len_value = self.get_len(value_path);
+        let ty = self
+            .body_visitor
+            .type_visitor
+            .get_rustc_place_type(place, self.body_visitor.current_span);
+        let array_len = match ty.kind() {
+            TyKind::Array(_, length) => Some(self.visit_constant(None, length)),
+            _ => None,
+        };
+        let len_value = match array_len {
+            Some(array_len) if !array_len.is_top() => array_len,
+            _ => self.get_place_len(place),
+        };
         self.body_visitor.state.update_value_at(path, len_value);
     }
 
+    /// Returns the length of the array, slice or string at `place`
+    /// The length of `*s`, where `s` is a fat pointer, is the metadata of `s`
+    fn get_place_len(&mut self, place: &mir::Place<'tcx>) -> Rc<SymbolicValue> {
+        if let Some((mir::ProjectionElem::Deref, base_projection)) = place.projection.split_last() {
+            let tcx = self.body_visitor.context.tcx;
+            let base_place = mir::Place {
+                local: place.local,
+                projection: tcx.mk_place_elems(base_projection),
+            };
+            let base_ty = self
+                .body_visitor
+                .type_visitor
+                .get_rustc_place_type(&base_place, self.body_visitor.current_span);
+            if type_visitor::is_fat_pointer(base_ty.kind()) {
+                let base_path = self.visit_place(&base_place);
+                if let Some(meta) = self
+                    .state()
+                    .value_at(&Path::new_fat_pointer_meta(base_path))
+                {
+                    return meta;
+                }
+            }
+        }
+        let value_path = self.visit_place(place);
+        self.get_len(value_path)
+    }
+
     fn get_len(&mut self, path: Rc<Path>) -> Rc<SymbolicValue> {
         let length_path = Path::new_length(path).refine_paths(&self.state());
         self.body_visitor
@@ -2262,9 +4239,10 @@ where
             .body_visitor
             .type_visitor
             .get_elem_type_size(target_type);
-        let length = self
-            .body_visitor
-            .get_u128_const_val(u128::from((to - from) as u64 * elem_size));
+        let length = match elem_size.and_then(|size| ((to - from) as u64).checked_mul(size)) {
+            Some(length) => self.body_visitor.get_u128_const_val(u128::from(length)),
+            None => symbolic_value::TOP.into(),
+        };
         let alignment = Rc::new(1u128.into());
         let slice_value = self
             .body_visitor
@@ -2272,7 +4250,10 @@ where
         self.body_visitor
             .state
             .update_value_at(target_path.clone(), slice_value.clone());
-        let slice_path = Rc::new(PathEnum::HeapBlock { value: slice_value }.into());
+        let slice_path = Path::intern(
+            &self.body_visitor.context.path_interner,
+            PathEnum::HeapBlock { value: slice_value },
+        );
         let slice_len_path = Path::new_length(slice_path);
         let len_value = self.body_visitor.get_u128_const_val(u128::from(to - from));
         self.body_visitor
@@ -2497,3 +4478,23 @@ where
         sat
     }
 }
+
+/// Whether `path` denotes a location that does not depend on the values of variables, i.e., it has no dereference and
+/// its indices are constants
+fn is_fixed_location(path: &Rc<Path>) -> bool {
+    match &path.value {
+        PathEnum::QualifiedPath {
+            qualifier,
+            selector,
+            ..
+        } => {
+            let fixed_selector = match selector.as_ref() {
+                PathSelector::Deref => false,
+                PathSelector::Index(index) => index.is_compile_time_constant(),
+                _ => true,
+            };
+            fixed_selector && is_fixed_location(qualifier)
+        }
+        _ => true,
+    }
+}
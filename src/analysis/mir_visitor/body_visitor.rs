@@ -1,30 +1,69 @@
 use crate::analysis::abstract_domain::AbstractDomain;
+use crate::analysis::checkpoint;
 use crate::analysis::crate_context::CrateContext;
 use crate::analysis::diagnostics::{Diagnostic, DiagnosticCause};
 use crate::analysis::global_context::GlobalContext;
+use crate::analysis::memory::alias::AliasMap;
 use crate::analysis::memory::constant_value::ConstantValue;
+use crate::analysis::memory::escape::EscapeAnalysis;
 use crate::analysis::memory::expression::{Expression, ExpressionType};
 use crate::analysis::memory::k_limits;
+use crate::analysis::memory::known_names::KnownNames;
 use crate::analysis::memory::path::{Path, PathEnum, PathRefinement, PathSelector};
 use crate::analysis::memory::symbolic_value::{self, SymbolicValue, SymbolicValueTrait};
+use crate::analysis::memory::utils;
 use crate::analysis::mir_visitor::block_visitor::BlockVisitor;
 use crate::analysis::mir_visitor::call_visitor::CallVisitor;
 use crate::analysis::mir_visitor::type_visitor::{self, TypeVisitor};
 use crate::analysis::numerical::apron_domain::{
     ApronAbstractDomain, ApronDomainType, GetManagerTrait,
 };
-use crate::analysis::numerical::linear_constraint::LinearConstraintSystem;
-use crate::analysis::wto::{Wto, WtoCircle, WtoVertex, WtoVisitor};
+use crate::analysis::numerical::float_domain::FloatValue;
+use crate::analysis::numerical::interval::{Bound, Interval};
+use crate::analysis::numerical::lattice::LatticeTrait;
+use crate::analysis::numerical::linear_constraint::{LinearConstraint, LinearConstraintSystem};
+use crate::analysis::numerical::predicate_domain::{self, PredicateDomain};
+use crate::analysis::numerical::sparse_domain::SparseApronDomain;
+use crate::analysis::option::AnalysisType;
+use crate::analysis::summary::FunctionSummary;
+use crate::analysis::wto::{Wto, WtoCircle, WtoComponent, WtoVertex, WtoVisitor};
 use crate::analysis::z3_solver::Z3Solver;
+use crate::checker::alignment_checker::{AlignedAccess, AlignmentChecker};
 use crate::checker::assertion_checker::AssertionChecker;
+use crate::checker::channel_capacity_checker::{ChannelCapacityChecker, ChannelSend};
 use crate::checker::checker_trait::CheckerTrait;
+use crate::checker::dangling_pointer_checker::{DanglingPointerChecker, DanglingStore};
+use crate::checker::dead_branch_checker::DeadBranchChecker;
+use crate::checker::dead_code_reporter::DeadCodeReporter;
+use crate::checker::double_free_checker::{DoubleFree, DoubleFreeChecker, FreedState};
+use crate::checker::ffi_checker::{FfiCall, FfiChecker};
+use crate::checker::float_nan_checker::{FloatComparison, FloatNanChecker};
+use crate::checker::format_string_checker::FormatStringChecker;
+use crate::checker::int_promotion_checker::{IntPromotionChecker, SignPromotion};
+use crate::checker::lock_order_checker::{LockAcquisition, LockOrderChecker};
+use crate::checker::padding_uninit_checker::PaddingUninitChecker;
+use crate::checker::panic_reachability_checker::PanicReachabilityChecker;
+use crate::checker::pin_safety_checker::{PinSafetyChecker, PinnedAccess};
+use crate::checker::raw_offset_overflow_checker::{RawOffset, RawOffsetOverflowChecker};
+use crate::checker::raw_ptr_bounds_checker::{RawPointerAccess, RawPtrBoundsChecker};
+use crate::checker::resource_type_state_checker::ResourceTypeStateChecker;
+use crate::checker::shift_by_const_checker::{self, ConstantShift, ShiftByConstChecker};
+use crate::checker::shift_sign_checker::{ShiftSignChecker, SignedShift};
+use crate::checker::str_index_checker::{StrIndex, StrIndexChecker};
+use crate::checker::taint_sink_checker::{TaintSinkChecker, TaintedSinkCall};
+use crate::checker::termination_checker::{self, LoopTransition, TerminationChecker};
+use crate::checker::transmute_safety_checker::TransmuteSafetyChecker;
+use crate::checker::uninitialized_global_checker::{StaticRead, UninitializedGlobalChecker};
+use crate::checker::vec_bounds_checker::{VecAccess, VecBoundsChecker};
 use itertools::Itertools;
 use log::{debug, error, warn};
 use rug::Integer;
 use rustc_errors::Diag as DiagnosticBuilder;
 use rustc_hir::def_id::DefId;
+use rustc_hir::LangItem;
 use rustc_middle::mir;
 use rustc_middle::ty::{Ty, TyKind};
+use rustc_span::source_map::Spanned;
 use rustc_span::Span;
 use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
@@ -63,6 +102,12 @@ where
     // There may be multiple return statements, record them so we can compute the union of the return values
     pub result_blocks: HashSet<mir::BasicBlock>,
 
+    // The basic blocks whose pre-condition is bottom when they are last analyzed, used by the dead code reporter
+    pub unreachable_blocks: HashSet<mir::BasicBlock>,
+
+    // The heads of the loops nested deeper than `--max-loop-depth`, whose fixed-point iteration is abandoned
+    pub abandoned_loops: HashSet<mir::BasicBlock>,
+
     // Helper struct to get information in Rust's type system
     pub type_visitor: TypeVisitor<'tcx>,
 
@@ -73,6 +118,13 @@ where
     // Caveat: we assume each location only allocates once
     pub heap_addresses: HashMap<mir::Location, Rc<SymbolicValue>>,
 
+    // The paths that may refer to the same memory location, computed before the fixed-point iteration
+    pub alias_map: AliasMap,
+
+    // The paths that the pointers created by `&place` and `&raw place` point to, i.e., their provenance
+    // A pointer that is assigned different targets, or other values, in the function has `None`
+    pub pointer_provenance: HashMap<Rc<Path>, Option<Rc<Path>>>,
+
     // Stores the tainted local variables when detecting ownership corruption
     // Variables in this set potentially acquire ownership from other allocated memory
     // So keep track of them and check whether they eventually go to terminators like `Return` or `Drop`
@@ -80,9 +132,81 @@ where
     // We only consider `mir::Local` instead of `mir::Place` for robustness
     pub tainted_variables: HashSet<mir::Local>,
 
+    // Stores the local variables that are derived from the results of taint sources, i.e., untrusted inputs,
+    // together with the span of the call of the taint source. See `GlobalContext::is_taint_source`
+    pub input_tainted_variables: HashMap<mir::Local, Span>,
+
+    // The calls that use a format string derived from a taint source, used by the format string checker
+    // Each call is stored with its span and the span of the call of the taint source
+    pub tainted_format_strings: HashMap<mir::Location, (Span, Span)>,
+
+    // The calls of taint sinks with arguments derived from taint sources, used by the taint sink checker
+    pub tainted_sink_calls: HashMap<mir::Location, TaintedSinkCall>,
+
     // `Place` to `SymbolicValue` Cache, used to extract conditions when analyzing assertions
     pub place_to_abstract_value: HashMap<mir::Place<'tcx>, Rc<SymbolicValue>>,
 
+    // Raw pointer dereferences at each statement, used by the raw pointer bounds checker
+    pub raw_pointer_accesses: HashMap<mir::Location, Vec<RawPointerAccess>>,
+
+    // The values of the operands of the comparisons of floats at each statement, used by the float NaN checker
+    pub float_comparisons: HashMap<mir::Location, FloatComparison>,
+
+    // Uses of pointers that require alignment at each statement, used by the alignment checker
+    pub aligned_accesses: HashMap<mir::Location, AlignedAccess>,
+
+    // The calls of `Mutex::lock` at each statement, used by the lock order checker
+    pub lock_acquisitions: HashMap<mir::Location, LockAcquisition>,
+
+    // The sends on bounded channels at each statement, used by the channel capacity checker
+    pub channel_sends: HashMap<mir::Location, ChannelSend>,
+
+    // The paths pinned by `Pin::new_unchecked` at each statement, used by the pin safety checker
+    pub pinned_paths: HashMap<mir::Location, Rc<Path>>,
+
+    // The moves and unpinning operations at each statement, used by the pin safety checker
+    pub pinned_accesses: HashMap<mir::Location, Vec<PinnedAccess>>,
+
+    // The calls to C functions at each statement, used by the FFI checker
+    pub ffi_calls: HashMap<mir::Location, FfiCall>,
+
+    // The calls of `get_unchecked` and `Vec::set_len` at each statement, used by the vector bounds checker
+    pub vec_accesses: HashMap<mir::Location, VecAccess>,
+
+    // The slicing and splitting of `str`s at each statement, used by the string index checker
+    pub str_indices: HashMap<mir::Location, StrIndex>,
+
+    // The calls of `offset` on raw pointers at each statement, used by the raw offset overflow checker
+    pub raw_offsets: HashMap<mir::Location, RawOffset<'tcx>>,
+
+    // The shifts whose constant shift amounts are out of range at each statement, found before the fixed-point
+    // iteration, used by the shift by const checker
+    pub constant_shifts: HashMap<mir::Location, ConstantShift<'tcx>>,
+
+    // The shifts of signed integers at each statement, used by the shift sign checker
+    pub signed_shifts: HashMap<mir::Location, SignedShift>,
+
+    // The casts of unsigned integers into signed integer types at each statement, used by the int promotion checker
+    pub sign_promotions: HashMap<mir::Location, SignPromotion>,
+
+    // The raw pointers to the variables of this function that are stored into locations that may outlive it at
+    // each statement, used by the dangling pointer checker
+    pub dangling_stores: HashMap<mir::Location, DanglingStore>,
+
+    // The calls that may free a pointer again at each statement, used by the double free checker
+    pub double_frees: HashMap<mir::Location, DoubleFree>,
+
+    // The reads of mutable statics that may not have been written at each statement, used by the uninitialized
+    // global checker
+    pub static_reads: HashMap<mir::Location, StaticRead>,
+
+    // The resources that are moved into calls, which become responsible for closing them, used by the resource
+    // type-state checker
+    pub escaped_resources: HashSet<Rc<Path>>,
+
+    // One iteration of each loop, indexed by the loop head, used by the termination checker
+    pub loop_transitions: HashMap<mir::BasicBlock, LoopTransition<DomainType>>,
+
     // The start index of variables. Because functions may return values that contain local variables, so we
     // increase the index offsets so that returned variables can be distinguished from normal local variables
     pub fresh_variable_offset: usize,
@@ -93,6 +217,17 @@ where
     // The call stack, used to detect recursive calls
     pub call_stack: Vec<DefId>,
 
+    // The predicates of `--predicate` whose variables are all user variables of this function, used by the
+    // predicate abstraction when the states of the predecessors of a basic block are joined
+    pub predicates: Vec<LinearConstraint>,
+
+    // The names of the user variables that the MIR locals hold, including the temporaries copied from them, used
+    // by `get_var_name` with `--verbose-diagnostics`
+    pub local_names: HashMap<mir::Local, String>,
+
+    // The span of the call that this function is analyzed for, `None` if it is an analysis root
+    pub call_site: Option<Span>,
+
     // The Z3 SMT solver
     pub z3_solver: Z3Solver,
 
@@ -100,6 +235,34 @@ where
     pub buffered_diagnostics: Vec<Diagnostic<'compiler>>,
 }
 
+/// The facts that the visitor records for the checkers while it analyzes the basic blocks, which are saved before
+/// `record_loop_transition` analyzes a loop once more and restored after it, so that the extra iteration changes none
+/// of them
+struct RecordedFacts<'tcx> {
+    unreachable_blocks: HashSet<mir::BasicBlock>,
+    abandoned_loops: HashSet<mir::BasicBlock>,
+    result_blocks: HashSet<mir::BasicBlock>,
+    tainted_format_strings: HashMap<mir::Location, (Span, Span)>,
+    tainted_sink_calls: HashMap<mir::Location, TaintedSinkCall>,
+    raw_pointer_accesses: HashMap<mir::Location, Vec<RawPointerAccess>>,
+    float_comparisons: HashMap<mir::Location, FloatComparison>,
+    aligned_accesses: HashMap<mir::Location, AlignedAccess>,
+    lock_acquisitions: HashMap<mir::Location, LockAcquisition>,
+    channel_sends: HashMap<mir::Location, ChannelSend>,
+    pinned_paths: HashMap<mir::Location, Rc<Path>>,
+    pinned_accesses: HashMap<mir::Location, Vec<PinnedAccess>>,
+    ffi_calls: HashMap<mir::Location, FfiCall>,
+    vec_accesses: HashMap<mir::Location, VecAccess>,
+    str_indices: HashMap<mir::Location, StrIndex>,
+    raw_offsets: HashMap<mir::Location, RawOffset<'tcx>>,
+    signed_shifts: HashMap<mir::Location, SignedShift>,
+    sign_promotions: HashMap<mir::Location, SignPromotion>,
+    dangling_stores: HashMap<mir::Location, DanglingStore>,
+    double_frees: HashMap<mir::Location, DoubleFree>,
+    static_reads: HashMap<mir::Location, StaticRead>,
+    escaped_resources: HashSet<Rc<Path>>,
+}
+
 impl<'tcx, 'a, 'compiler, DomainType> WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>
 where
     DomainType: ApronDomainType,
@@ -117,7 +280,9 @@ where
         call_stack: Vec<DefId>,
     ) -> Self {
         let wto = context.get_wto(def_id);
-        let type_visitor = TypeVisitor::new(def_id, wto.get_mir().clone(), context.tcx);
+        let mut type_visitor = TypeVisitor::new(def_id, wto.get_mir().clone(), context.tcx);
+        type_visitor.strict = context.analysis_options.strict;
+        let alias_map = AliasMap::new(wto.get_mir(), fresh_variable_offset);
 
         Self {
             current_span: rustc_span::DUMMY_SP,
@@ -129,14 +294,43 @@ where
             state: AbstractDomain::default(),
             post: HashMap::new(),
             result_blocks: HashSet::new(),
+            unreachable_blocks: HashSet::new(),
+            abandoned_loops: HashSet::new(),
             type_visitor,
             crate_context: CrateContext::default(),
             heap_addresses: HashMap::new(),
+            alias_map,
+            pointer_provenance: HashMap::new(),
             tainted_variables: HashSet::new(),
+            input_tainted_variables: HashMap::new(),
+            tainted_format_strings: HashMap::new(),
+            tainted_sink_calls: HashMap::new(),
             place_to_abstract_value: HashMap::new(),
+            raw_pointer_accesses: HashMap::new(),
+            float_comparisons: HashMap::new(),
+            aligned_accesses: HashMap::new(),
+            lock_acquisitions: HashMap::new(),
+            channel_sends: HashMap::new(),
+            pinned_paths: HashMap::new(),
+            pinned_accesses: HashMap::new(),
+            ffi_calls: HashMap::new(),
+            vec_accesses: HashMap::new(),
+            str_indices: HashMap::new(),
+            raw_offsets: HashMap::new(),
+            constant_shifts: HashMap::new(),
+            signed_shifts: HashMap::new(),
+            sign_promotions: HashMap::new(),
+            dangling_stores: HashMap::new(),
+            double_frees: HashMap::new(),
+            static_reads: HashMap::new(),
+            escaped_resources: HashSet::new(),
+            loop_transitions: HashMap::new(),
             fresh_variable_offset,
             next_fresh_variable_offset: fresh_variable_offset + Self::FRESH_VARIABLE_OFFSET,
             call_stack,
+            predicates: Vec::new(),
+            local_names: HashMap::new(),
+            call_site: None,
             z3_solver: Z3Solver::default(),
             buffered_diagnostics: vec![],
         }
@@ -144,14 +338,216 @@ where
 
     /// Run analysis
     pub fn run(&mut self) {
+        if self.context.analysis_options.check_shift_by_const {
+            self.fold_constant_shifts();
+        }
+        self.predicates = self.resolve_predicates();
+        if self.context.analysis_options.verbose_diagnostics {
+            self.local_names = self.collect_local_names();
+        }
         for comp in self.wto.components() {
             self.visit_component(&comp);
         }
+        self.forget_non_escaping_allocations();
+        self.context.unsupported_patterns += self.type_visitor.unsupported_patterns.get();
+    }
+
+    /// Parses the predicates of `--predicate`, where each variable is the user variable of this function with the
+    /// same name. The predicates that mention other variables are skipped in this function
+    fn resolve_predicates(&self) -> Vec<LinearConstraint> {
+        let mir = self.wto.get_mir();
+        let resolve = |name: &str| {
+            mir.var_debug_info
+                .iter()
+                .find_map(|var_info| match var_info.value {
+                    mir::VarDebugInfoContents::Place(place)
+                        if place.projection.is_empty() && var_info.name.as_str() == name =>
+                    {
+                        Some(Path::new_local_parameter_or_result(
+                            place.local.as_usize(),
+                            self.fresh_variable_offset,
+                            mir.arg_count,
+                        ))
+                    }
+                    _ => None,
+                })
+        };
+        let mut predicates = Vec::new();
+        for predicate in &self.context.analysis_options.predicates {
+            match predicate_domain::parse_predicate(predicate, resolve) {
+                Ok(constraint) => predicates.push(constraint),
+                Err(e) => debug!("Skip `{}` in {:?}: {}", predicate, self.def_id, e),
+            }
+        }
+        predicates
+    }
+
+    /// Maps the MIR locals to the names of the user variables that they hold, i.e., the user variables in the debug
+    /// information, and the temporaries that copy, move or cast them, e.g., `_5 = copy _1` before `_5 + _6`
+    /// A temporary that holds different values in different storage ranges, i.e., between its `StorageLive` and
+    /// `StorageDead`, is not named, since its name would depend on the statement
+    fn collect_local_names(&self) -> HashMap<mir::Local, String> {
+        let mir = self.wto.get_mir();
+        let mut names = HashMap::new();
+        for var_info in &mir.var_debug_info {
+            if let mir::VarDebugInfoContents::Place(place) = var_info.value {
+                if place.projection.is_empty() {
+                    names.insert(place.local, var_info.name.to_ident_string());
+                }
+            }
+        }
+        let mut temporaries: HashMap<mir::Local, Option<String>> = HashMap::new();
+        for block_data in mir.basic_blocks.iter() {
+            for statement in &block_data.statements {
+                let (place, rvalue) = match &statement.kind {
+                    mir::StatementKind::Assign(box (place, rvalue)) => (place, rvalue),
+                    _ => continue,
+                };
+                if !place.projection.is_empty() || names.contains_key(&place.local) {
+                    continue;
+                }
+                let name = match rvalue {
+                    mir::Rvalue::Use(mir::Operand::Copy(source) | mir::Operand::Move(source))
+                    | mir::Rvalue::Cast(
+                        _,
+                        mir::Operand::Copy(source) | mir::Operand::Move(source),
+                        _,
+                    ) if source.projection.is_empty() => names.get(&source.local).cloned(),
+                    _ => None,
+                };
+                temporaries
+                    .entry(place.local)
+                    .and_modify(|existing| {
+                        if *existing != name {
+                            *existing = None;
+                        }
+                    })
+                    .or_insert(name);
+            }
+        }
+        for (local, name) in temporaries {
+            if let Some(name) = name {
+                names.insert(local, name);
+            }
+        }
+        names
+    }
+
+    /// Find the shifts whose shift amounts are constants that are out of range, before the fixed-point iteration
+    /// The constants are evaluated without the abstract state, so the shifts are reported even if they are not
+    /// reachable, see `ShiftByConstChecker`
+    fn fold_constant_shifts(&mut self) {
+        let tcx = self.context.tcx;
+        let type_visitor = &self.type_visitor;
+        for (block, block_data) in type_visitor.mir.basic_blocks.iter_enumerated() {
+            if block_data.is_cleanup {
+                continue;
+            }
+            for (statement_index, statement) in block_data.statements.iter().enumerate() {
+                if let mir::StatementKind::Assign(box (
+                    _,
+                    mir::Rvalue::BinaryOp(bin_op, box (lhs, rhs))
+                    | mir::Rvalue::CheckedBinaryOp(bin_op, box (lhs, rhs)),
+                )) = &statement.kind
+                {
+                    let shift = shift_by_const_checker::get_constant_shift(
+                        tcx,
+                        type_visitor,
+                        *bin_op,
+                        lhs,
+                        rhs,
+                        statement.source_info.span,
+                    );
+                    if let Some(shift) = shift.filter(|shift| shift.is_out_of_range()) {
+                        let location = mir::Location {
+                            block,
+                            statement_index,
+                        };
+                        self.constant_shifts.insert(location, shift);
+                    }
+                }
+            }
+        }
+    }
+
+    /// True if the abstract states of this function are traced, see `--trace-function`
+    #[cfg(feature = "trace")]
+    pub fn is_traced(&self) -> bool {
+        match &self.context.analysis_options.trace_function {
+            Some(name) => {
+                let tcx = self.context.tcx;
+                tcx.def_path_str(self.def_id) == *name
+                    || tcx
+                        .opt_item_name(self.def_id)
+                        .map_or(false, |item_name| item_name.as_str() == name)
+            }
+            None => false,
+        }
+    }
+
+    /// Append a line to the trace file, see `--trace-function`
+    #[cfg(feature = "trace")]
+    pub fn write_trace(&self, line: &str) {
+        use std::io::Write;
+        if let Some(file_name) = self.context.analysis_options.trace_file() {
+            let result = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&file_name)
+                .and_then(|mut file| writeln!(file, "{}", line));
+            if let Err(e) = result {
+                error!("Failed to write the trace to {}: {}", file_name, e);
+            }
+        }
+    }
+
+    /// Remove the heap allocations of this function that do not escape from the exit states, because no caller
+    /// can observe them after the function returns
+    fn forget_non_escaping_allocations(&mut self) {
+        if self.type_visitor.heap_allocation_sites.is_empty() {
+            return;
+        }
+        let escape_analysis = EscapeAnalysis::new(self.post.values());
+        let is_non_escaping = |path: &Rc<Path>| {
+            let mut root = path;
+            while let PathEnum::QualifiedPath { qualifier, .. } = &root.value {
+                root = qualifier;
+            }
+            match &root.value {
+                PathEnum::HeapAllocation { id } => {
+                    self.type_visitor.heap_allocation_sites.contains_key(id)
+                        && !escape_analysis.escapes(*id)
+                }
+                _ => false,
+            }
+        };
+        for bb in self.result_blocks.iter() {
+            if let Some(state) = self.post.get_mut(bb) {
+                let paths: Vec<Rc<Path>> = state
+                    .get_paths_iter()
+                    .into_iter()
+                    .filter(is_non_escaping)
+                    .collect();
+                if !paths.is_empty() {
+                    debug!(
+                        "Forget non-escaping heap allocations at {:?}: {:?}",
+                        bb, paths
+                    );
+                    state.remove_all(&paths);
+                }
+            }
+        }
     }
 
     /// Initialize arguments when analyzing a function
     pub fn init_pre_condition(&mut self, actual_args: Vec<(Rc<Path>, Rc<SymbolicValue>)>) {
         for (i, arg) in actual_args.iter().enumerate() {
+            // Zero-sized arguments carry no information, so do not allocate variables for them
+            if let Some(ty) = self.type_visitor.actual_argument_types.get(i) {
+                if self.type_visitor.is_zero_sized(*ty) {
+                    continue;
+                }
+            }
             // Initialize callee's arguments using caller's values
             // So callee's paths should add an offset to distinguish them from caller's paths
             let new_path = Path::new_parameter(i + 1, self.fresh_variable_offset);
@@ -169,16 +565,148 @@ where
 
         self.context.checked_def_ids.insert(self.def_id);
 
+        if self.context.analysis_options.check_dead_branch {
+            let mut checker = DeadBranchChecker::<DomainType>::new(self);
+            checker.run();
+        }
+
+        if self.context.analysis_options.report_dead_code {
+            let mut checker = DeadCodeReporter::<DomainType>::new(self);
+            checker.run();
+        }
+
+        self.report_abandoned_loops();
+
         let mut checker = AssertionChecker::<DomainType>::new(self);
         checker.run();
 
+        let mut checker = FormatStringChecker::<DomainType>::new(self);
+        checker.run();
+
+        let mut checker = AlignmentChecker::<DomainType>::new(self);
+        checker.run();
+
+        if self.context.analysis_options.check_raw_ptr_bounds {
+            let mut checker = RawPtrBoundsChecker::<DomainType>::new(self);
+            checker.run();
+        }
+
+        if self.context.analysis_options.check_raw_offset_overflow {
+            let mut checker = RawOffsetOverflowChecker::<DomainType>::new(self);
+            checker.run();
+        }
+
+        let analysis_options = &self.context.analysis_options;
+        if analysis_options.check_panic_reachability || analysis_options.panic_freedom {
+            let mut checker = PanicReachabilityChecker::<DomainType>::new(self);
+            checker.run();
+        }
+
+        if self.context.analysis_options.check_lock_order {
+            let mut checker = LockOrderChecker::<DomainType>::new(self);
+            checker.run();
+        }
+
+        if self.context.analysis_options.ffi_stubs.is_some() {
+            let mut checker = FfiChecker::<DomainType>::new(self);
+            checker.run();
+        }
+
+        if self.context.analysis_options.analysis_type == AnalysisType::Taint {
+            let mut checker = TaintSinkChecker::<DomainType>::new(self);
+            checker.run();
+        }
+
+        if self.context.analysis_options.check_pin_safety {
+            let mut checker = PinSafetyChecker::<DomainType>::new(self);
+            checker.run();
+        }
+
+        if self.context.analysis_options.check_vec_bounds {
+            let mut checker = VecBoundsChecker::<DomainType>::new(self);
+            checker.run();
+        }
+
+        if self.context.analysis_options.check_str_index {
+            let mut checker = StrIndexChecker::<DomainType>::new(self);
+            checker.run();
+        }
+
+        if self.context.analysis_options.check_shift_by_const {
+            let mut checker = ShiftByConstChecker::<DomainType>::new(self);
+            checker.run();
+        }
+
+        if self.context.analysis_options.check_shift_sign {
+            let mut checker = ShiftSignChecker::<DomainType>::new(self);
+            checker.run();
+        }
+
+        if self.context.analysis_options.check_int_promotion {
+            let mut checker = IntPromotionChecker::<DomainType>::new(self);
+            checker.run();
+        }
+
+        if self.context.analysis_options.check_dangling_pointer {
+            let mut checker = DanglingPointerChecker::<DomainType>::new(self);
+            checker.run();
+        }
+
+        if self.context.analysis_options.check_double_free {
+            let mut checker = DoubleFreeChecker::<DomainType>::new(self);
+            checker.run();
+        }
+
+        if self
+            .context
+            .analysis_options
+            .check_use_of_uninitialized_global
+        {
+            let mut checker = UninitializedGlobalChecker::<DomainType>::new(self);
+            checker.run();
+        }
+
+        if self.context.analysis_options.check_transmute_safety {
+            let mut checker = TransmuteSafetyChecker::<DomainType>::new(self);
+            checker.run();
+        }
+
+        if self.context.analysis_options.check_padding_uninit {
+            let mut checker = PaddingUninitChecker::<DomainType>::new(self);
+            checker.run();
+        }
+
+        if self.context.analysis_options.check_float_nan {
+            let mut checker = FloatNanChecker::<DomainType>::new(self);
+            checker.run();
+        }
+
+        if !self.context.resource_types.is_empty() {
+            let mut checker = ResourceTypeStateChecker::<DomainType>::new(self);
+            checker.run();
+        }
+
+        if self.context.analysis_options.check_channel_capacity {
+            let mut checker = ChannelCapacityChecker::<DomainType>::new(self);
+            checker.run();
+        }
+
+        if self.context.analysis_options.check_termination {
+            let mut checker = TerminationChecker::<DomainType>::new(self);
+            checker.run();
+        }
+
         // Store diagnostic messages for this function
         self.context
             .diagnostics_for
             .insert(self.def_id, self.buffered_diagnostics.clone());
 
         // Cancel the buffered diagnostics because they have been copied into global context
-        // If not, the compiler will emit a bug when dropping them
+        self.cancel_diagnostics();
+    }
+
+    /// Cancel the buffered diagnostics, if not, the compiler will emit a bug when dropping them
+    pub fn cancel_diagnostics(&mut self) {
         let diags = std::mem::take(&mut self.buffered_diagnostics);
         for diagnostic in diags.into_iter() {
             diagnostic.cancel();
@@ -194,6 +722,22 @@ where
             .fold1(|state1, state2| state1.join(&state2))
     }
 
+    /// Returns the entries of `records` whose basic blocks are reachable, sorted by their locations,
+    /// so that the checkers report them in a deterministic order
+    /// A block is reachable if its pre-condition is not bottom, even if its terminator always panics
+    pub fn reachable_in_order<T: Clone>(
+        &self,
+        records: &HashMap<mir::Location, T>,
+    ) -> Vec<(mir::Location, T)> {
+        let mut entries: Vec<(mir::Location, T)> = records
+            .iter()
+            .filter(|(location, _)| !self.unreachable_blocks.contains(&location.block))
+            .map(|(location, record)| (*location, record.clone()))
+            .collect();
+        entries.sort_by_key(|(location, _)| *location);
+        entries
+    }
+
     pub fn init_promote_constants(&mut self)
     where
         DomainType: ApronDomainType,
@@ -239,8 +783,10 @@ where
                 // The path of the promoted constant in the promoted MIR
                 let mut result_root: Rc<Path> = Path::new_result();
                 // The path of the promoted constant in the current function's MIR
-                let mut promoted_root: Rc<Path> =
-                    Rc::new(PathEnum::PromotedConstant { ordinal }.into());
+                let mut promoted_root: Rc<Path> = Path::intern(
+                    &wto_visitor.context.path_interner,
+                    PathEnum::PromotedConstant { ordinal },
+                );
 
                 // If the promoted constant is a pointer/reference to a collection type
                 // We need to also create its length
@@ -266,11 +812,11 @@ where
                 match &value.expression {
                     // If the promoted constant is a heap allocation
                     Expression::HeapBlock { .. } => {
-                        let heap_root: Rc<Path> = Rc::new(
+                        let heap_root: Rc<Path> = Path::intern(
+                            &wto_visitor.context.path_interner,
                             PathEnum::HeapBlock {
                                 value: value.clone(),
-                            }
-                            .into(),
+                            },
                         );
                         for (path, value) in exit_environment
                             .symbolic_domain
@@ -354,23 +900,28 @@ where
         } else if let TyKind::Ref(_, ty, _) = target_type.kind() {
             // Promoting a reference to a reference.
             ordinal += 99;
-            let value_path: Rc<Path> = Rc::new(PathEnum::PromotedConstant { ordinal }.into());
+            let value_path: Rc<Path> = Path::intern(
+                &self.context.path_interner,
+                PathEnum::PromotedConstant { ordinal },
+            );
             self.promote_reference(environment, *ty, &value_path, local_path, ordinal);
             let promoted_value = SymbolicValue::make_from(Expression::Reference(value_path), 1);
             environment.update_value_at(promoted_root.clone(), promoted_value);
         } else {
             // A composite value needs to get to get promoted to the heap
             // in order to propagate it via function summaries.
-            let byte_size = self.type_visitor.get_type_size(target_type);
-            let byte_size_value = self.get_u128_const_val(byte_size as u128);
+            let byte_size_value = match self.type_visitor.get_type_size(target_type) {
+                Some(byte_size) => self.get_u128_const_val(byte_size as u128),
+                None => symbolic_value::TOP.into(),
+            };
             let elem_size = self
                 .type_visitor
                 .get_type_size(type_visitor::get_element_type(target_type));
             let alignment: Rc<SymbolicValue> = Rc::new(
                 (match elem_size {
-                    0 => 1,
-                    1 | 2 | 4 | 8 => elem_size,
-                    _ => 8,
+                    None | Some(0) => 1,
+                    Some(elem_size @ (1 | 2 | 4 | 8)) => elem_size,
+                    Some(_) => 8,
                 } as u128)
                     .into(),
             );
@@ -438,7 +989,7 @@ where
         let block_path = Path::get_as_path(block.clone());
         self.type_visitor
             .path_ty_cache
-            .insert(block_path.clone(), ty);
+            .insert(Path::canonicalize(&block_path), ty);
         // let layout_path = Path::new_layout(block_path);
         // let layout = SymbolicValue::make_from(
         //     Expression::HeapBlockLayout {
@@ -452,6 +1003,32 @@ where
         block
     }
 
+    /// Returns the path to the object allocated at the current location
+    /// The same path is returned each time the allocation site is visited
+    pub fn get_new_heap_allocation(&mut self) -> Rc<Path> {
+        let id = self
+            .context
+            .get_heap_allocation_id(self.def_id, self.current_location);
+        self.type_visitor
+            .heap_allocation_sites
+            .insert(id, self.current_location);
+        let path = Path::new_heap_allocation(id);
+        // The site allocates new memory each time it is visited, e.g., in a loop
+        self.reset_freed_state(path.clone());
+        path
+    }
+
+    /// Marks the memory at `pointee_path` as not freed if it is tracked by the double free checker, e.g., after a new
+    /// allocation or after the pointer to it is reassigned
+    pub fn reset_freed_state(&mut self, pointee_path: Rc<Path>) {
+        let freed_path = Path::new_freed(pointee_path);
+        if self.state.numerical_domain.contains(&freed_path) {
+            self.state
+                .numerical_domain
+                .assign_int(freed_path, FreedState::Live.value());
+        }
+    }
+
     // TODO: check this
     // When executing: path: local_3, result type: &mut i32, where local_3 is a &(local_1), this function returns local1: Reference
     // It should return &(local_1), fixed.
@@ -467,6 +1044,11 @@ where
             path, result_rustc_type
         );
         let result_type: ExpressionType = (result_rustc_type.kind()).into();
+        // Reading from a moved-out place does not give a valid value
+        if self.state.is_moved(&path) {
+            debug!("{:?} has been moved out", path);
+            return SymbolicValue::make_typed_unknown(result_type);
+        }
         match &path.value {
             PathEnum::Alias { value } => {
                 return value.clone();
@@ -714,9 +1296,55 @@ where
         Rc::new(ConstantValue::Int(Integer::from(val)).into())
     }
 
+    /// The value of a float operand in the current state, i.e., a float constant or a float-typed local variable
+    /// The values of other operands, e.g., fields or dereferences, are unknown but never NaN, see `FloatValue::unknown`
+    pub fn get_float_value(&self, operand: &mir::Operand<'tcx>) -> FloatValue {
+        match operand {
+            mir::Operand::Copy(place) | mir::Operand::Move(place)
+                if place.projection.is_empty() =>
+            {
+                self.state
+                    .float_values
+                    .get(&place.local)
+                    .copied()
+                    .unwrap_or_else(FloatValue::unknown)
+            }
+            mir::Operand::Constant(constant) if constant.ty().is_floating_point() => {
+                let scalar = constant
+                    .const_
+                    .try_eval_scalar_int(self.context.tcx, self.type_visitor.get_param_env());
+                match scalar {
+                    Some(scalar) if scalar.size().bytes() == 4 => FloatValue::constant(
+                        f32::from_bits(scalar.assert_bits(scalar.size()) as u32) as f64,
+                    ),
+                    Some(scalar) if scalar.size().bytes() == 8 => FloatValue::constant(
+                        f64::from_bits(scalar.assert_bits(scalar.size()) as u64),
+                    ),
+                    _ => FloatValue::unknown(),
+                }
+            }
+            _ => FloatValue::unknown(),
+        }
+    }
+
     /// Try to get the symbol name of a variable in debug information
     /// If failed to find the symbol, return a string according to its `Debug` trait implementation
+    /// With `--verbose-diagnostics`, the name is looked up in `local_names`, and a local without a name is shown as
+    /// itself, e.g., `_3`
     pub fn get_var_name(&self, operand: &mir::Operand<'tcx>) -> String {
+        if self.context.analysis_options.verbose_diagnostics {
+            return match operand {
+                mir::Operand::Copy(place) | mir::Operand::Move(place)
+                    if place.projection.is_empty() =>
+                {
+                    self.local_names
+                        .get(&place.local)
+                        .cloned()
+                        .unwrap_or_else(|| format!("{:?}", place))
+                }
+                _ => format!("{:?}", operand),
+            };
+        }
         for var_info in &self.wto.get_mir().var_debug_info {
             match var_info.value {
                 mir::VarDebugInfoContents::Place(place1) => match operand {
@@ -743,73 +1371,520 @@ where
         format!("{:?}", operand)
     }
 
+    /// Try to get the symbol name of a path that denotes a local variable or a parameter
+    /// Return `None` if the path is not a user variable (e.g., compiler temporaries)
+    pub fn get_path_var_name(&self, path: &Rc<Path>) -> Option<String> {
+        let ordinal = match &path.value {
+            PathEnum::LocalVariable { ordinal } | PathEnum::Parameter { ordinal } => {
+                ordinal.checked_sub(self.fresh_variable_offset)?
+            }
+            _ => return None,
+        };
+        self.wto
+            .get_mir()
+            .var_debug_info
+            .iter()
+            .find_map(|var_info| match var_info.value {
+                mir::VarDebugInfoContents::Place(place)
+                    if place.projection.is_empty() && place.local.as_usize() == ordinal =>
+                {
+                    Some(var_info.name.to_ident_string())
+                }
+                _ => None,
+            })
+    }
+
+    /// Record the abstract state at the head of a loop as its loop invariant
+    /// Only the numerical constraints on user variables are kept
+    pub fn record_loop_invariant(
+        &mut self,
+        head_bb: mir::BasicBlock,
+        state: &AbstractDomain<DomainType>,
+    ) {
+        let invariant = if state.is_bottom() {
+            String::from("false")
+        } else {
+            let constraints: Vec<String> = state
+                .numerical_domain
+                .get_paths_iter()
+                .iter()
+                .filter_map(|path| {
+                    let name = self.get_path_var_name(path)?;
+                    let interval = state.numerical_domain.get_interval(path);
+                    match (&interval.low, &interval.high) {
+                        (Bound::Int(low), Bound::Int(high)) if low == high => {
+                            Some(format!("{} == {}", name, low))
+                        }
+                        (Bound::Int(low), Bound::Int(high)) => {
+                            Some(format!("{} in [{}, {}]", name, low, high))
+                        }
+                        (Bound::Int(low), _) => Some(format!("{} >= {}", name, low)),
+                        (_, Bound::Int(high)) => Some(format!("{} <= {}", name, high)),
+                        _ => None,
+                    }
+                })
+                .collect();
+            if constraints.is_empty() {
+                String::from("true")
+            } else {
+                constraints.join(", ")
+            }
+        };
+        let span = self.wto.get_mir().basic_blocks[head_bb]
+            .terminator()
+            .source_info
+            .span;
+        debug!("Loop invariant at {:?}: {}", head_bb, invariant);
+        // A function may be analyzed under several calling contexts, keep the latest one
+        self.context
+            .loop_invariants
+            .insert((self.def_id, head_bb), (span, invariant));
+    }
+
+    /// Analyze a circle that is nested deeper than `--max-loop-depth` without the fixed-point algorithm
+    /// The locals modified in the circle are removed from the state at its head, which then over-approximates the
+    /// states of all the iterations, and the blocks are analyzed once. The circles nested in it are analyzed in
+    /// the same way
+    fn abandon_circle(&mut self, circle: &WtoCircle) {
+        let head_bb = circle.head().node();
+        let mut pre = self.get_state_from_predecessors(head_bb);
+        let mir = self.wto.get_mir();
+        let roots: Vec<Rc<Path>> = termination_checker::get_modified_locals(mir, &circle.blocks())
+            .into_iter()
+            .map(|local| {
+                Path::new_local_parameter_or_result(
+                    local.as_usize(),
+                    self.fresh_variable_offset,
+                    mir.arg_count,
+                )
+            })
+            .collect();
+        let modified: Vec<Rc<Path>> = pre
+            .get_paths_iter()
+            .into_iter()
+            .filter(|path| {
+                roots
+                    .iter()
+                    .any(|root| path == root || path.is_rooted_by(root))
+            })
+            .collect();
+        debug!(
+            "Abandon the fixed-point of loop {:?}, forget {} paths",
+            head_bb,
+            modified.len()
+        );
+        pre.remove_all(&modified);
+        self.analyze_basic_block(head_bb, pre);
+        for comp in circle {
+            match comp {
+                WtoComponent::Vertex(vertex) => self.visit_vertex(vertex),
+                WtoComponent::Circle(nested) => self.abandon_circle(nested),
+            }
+        }
+    }
+
+    /// Notes the loops whose fixed-point iteration is abandoned because they are nested deeper than
+    /// `--max-loop-depth`, at the spans of their heads
+    fn report_abandoned_loops(&mut self) {
+        let max_loop_depth = match self.context.analysis_options.max_loop_depth {
+            Some(max_loop_depth) => max_loop_depth,
+            None => return,
+        };
+        let mut heads: Vec<mir::BasicBlock> = self.abandoned_loops.iter().copied().collect();
+        heads.sort();
+        for head_bb in heads {
+            let span = self.wto.get_mir().basic_blocks[head_bb]
+                .terminator()
+                .source_info
+                .span;
+            let mut note = self.context.session.dcx().struct_span_note(
+                span,
+                "[MirChecker] Imprecision: analysis of this loop was abandoned at max depth",
+            );
+            note.note(format!(
+                "the loop is nested {} levels deep, deeper than `--max-loop-depth {}`, so the variables modified \
+                 in it are unknown",
+                self.wto.circle_depth(head_bb),
+                max_loop_depth
+            ));
+            self.emit_diagnostic(note, false, DiagnosticCause::Other);
+        }
+    }
+
+    /// The facts recorded so far, see `RecordedFacts`
+    fn save_recorded_facts(&self) -> RecordedFacts<'tcx> {
+        RecordedFacts {
+            unreachable_blocks: self.unreachable_blocks.clone(),
+            abandoned_loops: self.abandoned_loops.clone(),
+            result_blocks: self.result_blocks.clone(),
+            tainted_format_strings: self.tainted_format_strings.clone(),
+            tainted_sink_calls: self.tainted_sink_calls.clone(),
+            raw_pointer_accesses: self.raw_pointer_accesses.clone(),
+            float_comparisons: self.float_comparisons.clone(),
+            aligned_accesses: self.aligned_accesses.clone(),
+            lock_acquisitions: self.lock_acquisitions.clone(),
+            channel_sends: self.channel_sends.clone(),
+            pinned_paths: self.pinned_paths.clone(),
+            pinned_accesses: self.pinned_accesses.clone(),
+            ffi_calls: self.ffi_calls.clone(),
+            vec_accesses: self.vec_accesses.clone(),
+            str_indices: self.str_indices.clone(),
+            raw_offsets: self.raw_offsets.clone(),
+            signed_shifts: self.signed_shifts.clone(),
+            sign_promotions: self.sign_promotions.clone(),
+            dangling_stores: self.dangling_stores.clone(),
+            double_frees: self.double_frees.clone(),
+            static_reads: self.static_reads.clone(),
+            escaped_resources: self.escaped_resources.clone(),
+        }
+    }
+
+    fn restore_recorded_facts(&mut self, facts: RecordedFacts<'tcx>) {
+        self.unreachable_blocks = facts.unreachable_blocks;
+        self.abandoned_loops = facts.abandoned_loops;
+        self.result_blocks = facts.result_blocks;
+        self.tainted_format_strings = facts.tainted_format_strings;
+        self.tainted_sink_calls = facts.tainted_sink_calls;
+        self.raw_pointer_accesses = facts.raw_pointer_accesses;
+        self.float_comparisons = facts.float_comparisons;
+        self.aligned_accesses = facts.aligned_accesses;
+        self.lock_acquisitions = facts.lock_acquisitions;
+        self.channel_sends = facts.channel_sends;
+        self.pinned_paths = facts.pinned_paths;
+        self.pinned_accesses = facts.pinned_accesses;
+        self.ffi_calls = facts.ffi_calls;
+        self.vec_accesses = facts.vec_accesses;
+        self.str_indices = facts.str_indices;
+        self.raw_offsets = facts.raw_offsets;
+        self.signed_shifts = facts.signed_shifts;
+        self.sign_promotions = facts.sign_promotions;
+        self.dangling_stores = facts.dangling_stores;
+        self.double_frees = facts.double_frees;
+        self.static_reads = facts.static_reads;
+        self.escaped_resources = facts.escaped_resources;
+    }
+
+    /// Analyze the loop body once more from the loop invariant `pre`, where each loop variable is copied
+    /// into a path that keeps its value at the loop head, so that the state on the back edges relates the
+    /// values of the loop variables before and after an iteration
+    /// The loop variables are the paths of the user variables used in the loop, and the loops with more than
+    /// `TerminationChecker::MAX_LOOP_VARIABLES` of them are not recorded
+    /// The post conditions, the diagnostics and the facts recorded for the checkers in this extra iteration are
+    /// discarded, as well as the transitions and the invariants that it records for the nested loops
+    fn record_loop_transition(&mut self, circle: &WtoCircle, pre: &AbstractDomain<DomainType>) {
+        let head_bb = circle.head().node();
+        let mir = self.wto.get_mir();
+        let roots: Vec<Rc<Path>> = termination_checker::get_used_locals(mir, &circle.blocks())
+            .into_iter()
+            .map(|local| {
+                Path::new_local_parameter_or_result(
+                    local.as_usize(),
+                    self.fresh_variable_offset,
+                    mir.arg_count,
+                )
+            })
+            .filter(|root| self.get_path_var_name(root).is_some())
+            .collect();
+        let variables: Vec<Rc<Path>> = pre
+            .numerical_domain
+            .get_paths_iter()
+            .into_iter()
+            .filter(|path| {
+                roots
+                    .iter()
+                    .any(|root| path == root || path.is_rooted_by(root))
+            })
+            .collect();
+        if variables.is_empty()
+            || variables.len() > TerminationChecker::<DomainType>::MAX_LOOP_VARIABLES
+        {
+            debug!(
+                "Skip the termination check of loop {:?} with {} variables",
+                head_bb,
+                variables.len()
+            );
+            return;
+        }
+
+        // The locals after the ones of the function are never used by the analysis
+        let fresh_local =
+            |i: usize| Path::new_local(mir.local_decls.len() + i, self.fresh_variable_offset);
+        let variables: Vec<(Rc<Path>, Rc<Path>)> = variables
+            .into_iter()
+            .enumerate()
+            .map(|(i, variable)| (variable, fresh_local(i)))
+            .collect();
+        let rank = fresh_local(variables.len());
+        let mut state = pre.clone();
+        for (variable, initial) in &variables {
+            state.numerical_domain.duplicate(variable, initial);
+        }
+
+        let post = self.post.clone();
+        let facts = self.save_recorded_facts();
+        let loop_transitions = std::mem::take(&mut self.loop_transitions);
+        let invariant_keys: Vec<_> = circle
+            .blocks()
+            .into_iter()
+            .map(|bb| (self.def_id, bb))
+            .collect();
+        let loop_invariants: Vec<_> = invariant_keys
+            .iter()
+            .map(|key| self.context.loop_invariants.get(key).cloned())
+            .collect();
+        let diagnostic_count = self.buffered_diagnostics.len();
+        self.analyze_basic_block(head_bb, state);
+        for comp in circle {
+            self.visit_component(comp);
+        }
+        let state = self
+            .get_state_from_back_edges(head_bb)
+            .map_or_else(SparseApronDomain::bottom, |state| state.numerical_domain);
+        self.post = post;
+        self.restore_recorded_facts(facts);
+        // The nested loops are analyzed again in the extra iteration, which records their transitions and invariants
+        // from a different state
+        self.loop_transitions = loop_transitions;
+        for (key, invariant) in invariant_keys.into_iter().zip(loop_invariants) {
+            match invariant {
+                Some(invariant) => self.context.loop_invariants.insert(key, invariant),
+                None => self.context.loop_invariants.remove(&key),
+            };
+        }
+        for diagnostic in self.buffered_diagnostics.split_off(diagnostic_count) {
+            diagnostic.cancel();
+        }
+
+        let span = mir.basic_blocks[head_bb].terminator().source_info.span;
+        self.loop_transitions.insert(
+            head_bb,
+            LoopTransition {
+                span,
+                variables,
+                rank,
+                state,
+            },
+        );
+    }
+
+    /// The paths of the values of the integer parameters at the entry of the function, see `record_contract`
+    fn entry_value_paths(&self) -> Vec<(Rc<Path>, Rc<Path>)> {
+        let mir = self.wto.get_mir();
+        (1..=mir.arg_count)
+            .filter(|i| mir.local_decls[mir::Local::from(*i)].ty.is_integral())
+            .map(|i| {
+                let parameter = Path::new_parameter(i, self.fresh_variable_offset);
+                (Path::new_entry_value(parameter.clone()), parameter)
+            })
+            .collect()
+    }
+
+    /// Copy the values of the integer parameters into paths that are never written, so that the constraints on
+    /// the arguments can still be found at the exit of the function, see `record_contract`
+    pub fn record_entry_values(&mut self) {
+        for (entry_value, parameter) in self.entry_value_paths() {
+            self.init_state
+                .numerical_domain
+                .assign_var(entry_value, parameter);
+        }
+    }
+
+    /// Back-project the exit state onto the values of the parameters at the entry of the function, which gives
+    /// the precondition of the current function, i.e., the arguments that the function returns normally for
+    /// Assertion failures never reach `return`, so the exit state only contains the successful execution paths
+    /// The values at the entry are used because a parameter may be modified in the function
+    pub fn record_contract(&mut self) {
+        let requires = if let Some(exit_state) = self.get_exit_state() {
+            let entry_values: Vec<Rc<Path>> = self
+                .entry_value_paths()
+                .into_iter()
+                .map(|(entry_value, _)| entry_value)
+                .collect();
+            let projected = exit_state.numerical_domain.project(&entry_values);
+            let var_name = |path: &Rc<Path>| {
+                let parameter = match &path.value {
+                    PathEnum::QualifiedPath { qualifier, .. } => qualifier,
+                    _ => path,
+                };
+                self.get_path_var_name(parameter)
+                    .unwrap_or_else(|| format!("{:?}", path))
+            };
+            LinearConstraintSystem::from(&projected)
+                .into_iter()
+                .map(|cst| cst.to_string_with(&var_name))
+                .join(" && ")
+        } else {
+            // The function never returns normally
+            String::from("false")
+        };
+        debug!("Precondition of {:?}: {}", self.def_id, requires);
+        self.context.contracts.insert(self.def_id, requires);
+    }
+
+    /// The range of the return value at the exit of the function, bottom if the function never returns normally
+    pub fn get_return_value(&self) -> Interval {
+        if let Some(exit_state) = self.get_exit_state() {
+            exit_state
+                .numerical_domain
+                .get_interval(&Path::new_result())
+        } else {
+            Interval::bottom()
+        }
+    }
+
+    /// Record the range of the return value as the summary of the function, which is valid for any arguments
+    /// only if the function is analyzed with unknown arguments
+    pub fn record_summary(&mut self) {
+        if !self.wto.get_mir().return_ty().is_integral() {
+            return;
+        }
+        let return_value = self.get_return_value();
+        debug!("Summary of {:?}: returns {:?}", self.def_id, return_value);
+        let summary_key = utils::summary_key_str(self.context.tcx, self.def_id);
+        let domain = self.context.analysis_options.domain_type;
+        self.context
+            .summaries
+            .insert(summary_key, FunctionSummary::new(domain, return_value));
+    }
+
+    /// Write the numerical state of each basic block after the fixed-point is reached into `<dir>/<function>.json`,
+    /// together with the terminator of the block, e.g., `[{"bb": 0, "state": "x <= 10", "terminator": "goto -> bb1"}]`
+    /// The blocks that are never reached have the state `⊥`, see `mir-checker-inspect`
+    pub fn dump_states(&self, dir: &std::path::Path) {
+        let blocks: Vec<serde_json::Value> = self
+            .wto
+            .basic_blocks
+            .iter_enumerated()
+            .map(|(bb, bb_data)| {
+                let state = self.post.get(&bb).map_or_else(
+                    || String::from("⊥"),
+                    |state| format!("{:?}", state.numerical_domain),
+                );
+                serde_json::json!({
+                    "bb": bb.index(),
+                    "state": state,
+                    "terminator": format!("{:?}", bb_data.terminator().kind),
+                })
+            })
+            .collect();
+        self.write_function_json(dir, &serde_json::Value::Array(blocks), "states");
+    }
+
+    /// Write the weak topological order of the basic blocks into `<dir>/<function>.json`, where a loop is an array
+    /// whose first element is the loop head, together with the terminator of each block, e.g.,
+    /// `{"wto": ["BB0", ["BB1", "BB2"], "BB3"], "terminators": {"BB0": "goto -> bb1", ...}}`
+    /// The loop heads are where the widening is applied
+    pub fn dump_wto(&self, dir: &std::path::Path) {
+        let terminators: serde_json::Map<String, serde_json::Value> = self
+            .wto
+            .basic_blocks
+            .iter_enumerated()
+            .map(|(bb, bb_data)| {
+                (
+                    format!("BB{}", bb.index()),
+                    serde_json::Value::String(format!("{:?}", bb_data.terminator().kind)),
+                )
+            })
+            .collect();
+        let wto = serde_json::json!({
+            "function": self.context.tcx.def_path_str(self.def_id),
+            "wto": self.wto.to_json(),
+            "terminators": terminators,
+        });
+        self.write_function_json(dir, &wto, "weak topological order");
+    }
+
+    /// Write `value` into `<dir>/<function>.json`, `what` describes the content in the log
+    fn write_function_json(&self, dir: &std::path::Path, value: &serde_json::Value, what: &str) {
+        let function = self.context.tcx.def_path_str(self.def_id);
+        let output = serde_json::to_string_pretty(value).expect("failed to serialize the JSON");
+        let file_path = dir.join(format!(
+            "{}.json",
+            checkpoint::function_file_name(&function)
+        ));
+        if let Err(e) =
+            std::fs::create_dir_all(dir).and_then(|_| std::fs::write(&file_path, output))
+        {
+            error!("Failed to dump the {} to {:?}: {}", what, file_path, e);
+        } else {
+            debug!("Dumped the {} of {} to {:?}", what, function, file_path);
+        }
+    }
+
     /// Recover the variable name for each assert message
     /// This is used to pretty print the diagnostic messages
     pub fn recover_var_name(&self, assert_kind: &mir::AssertKind<mir::Operand<'tcx>>) -> String {
         use mir::AssertKind::*;
         use mir::BinOp;
 
+        // The names are quoted as strings unless `--verbose-diagnostics` is set, where they are shown as in the code
+        let name = |operand: &mir::Operand<'tcx>| {
+            let name = self.get_var_name(operand);
+            if self.context.analysis_options.verbose_diagnostics {
+                name
+            } else {
+                format!("{:?}", name)
+            }
+        };
+
         // The following code is adapted from the original implementation of the `Debug` trait for `AssertKind`
         match assert_kind {
             BoundsCheck { ref len, ref index } => format!(
-                "index out of bounds: the length is {:?} but the index is {:?}",
-                self.get_var_name(len),
-                self.get_var_name(index)
-            ),
-            OverflowNeg(op) => format!(
-                "attempt to negate `{:#?}`, which would overflow",
-                self.get_var_name(op)
+                "index out of bounds: the length is {} but the index is {}",
+                name(len),
+                name(index)
             ),
+            OverflowNeg(op) => format!("attempt to negate `{}`, which would overflow", name(op)),
             DivisionByZero(op) => {
-                format!("attempt to divide `{:#?}` by zero", self.get_var_name(op))
+                format!("attempt to divide `{}` by zero", name(op))
             }
             RemainderByZero(op) => format!(
-                "attempt to calculate the remainder of `{:#?}` with a divisor of zero",
-                self.get_var_name(op)
+                "attempt to calculate the remainder of `{}` with a divisor of zero",
+                name(op)
             ),
             Overflow(BinOp::Add, l, r) => {
                 format!(
-                    "attempt to compute `{:#?} + {:#?}`, which would overflow",
-                    self.get_var_name(l),
-                    self.get_var_name(r)
+                    "attempt to compute `{} + {}`, which would overflow",
+                    name(l),
+                    name(r)
                 )
             }
             Overflow(BinOp::Sub, l, r) => {
                 format!(
-                    "attempt to compute `{:#?} - {:#?}`, which would overflow",
-                    self.get_var_name(l),
-                    self.get_var_name(r)
+                    "attempt to compute `{} - {}`, which would overflow",
+                    name(l),
+                    name(r)
                 )
             }
             Overflow(BinOp::Mul, l, r) => {
                 format!(
-                    "attempt to compute `{:#?} * {:#?}`, which would overflow",
-                    self.get_var_name(l),
-                    self.get_var_name(r)
+                    "attempt to compute `{} * {}`, which would overflow",
+                    name(l),
+                    name(r)
                 )
             }
             Overflow(BinOp::Div, l, r) => {
                 format!(
-                    "attempt to compute `{:#?} / {:#?}`, which would overflow",
-                    self.get_var_name(l),
-                    self.get_var_name(r)
+                    "attempt to compute `{} / {}`, which would overflow",
+                    name(l),
+                    name(r)
                 )
             }
             Overflow(BinOp::Rem, l, r) => format!(
-                "attempt to compute the remainder of `{:#?} % {:#?}`, which would overflow",
-                self.get_var_name(l),
-                self.get_var_name(r)
+                "attempt to compute the remainder of `{} % {}`, which would overflow",
+                name(l),
+                name(r)
             ),
             Overflow(BinOp::Shr, _, r) => {
                 format!(
-                    "attempt to shift right by `{:#?}`, which would overflow",
-                    self.get_var_name(r)
+                    "attempt to shift right by `{}`, which would overflow",
+                    name(r)
                 )
             }
             Overflow(BinOp::Shl, _, r) => {
                 format!(
-                    "attempt to shift left by `{:#?}`, which would overflow",
-                    self.get_var_name(r)
+                    "attempt to shift left by `{}`, which would overflow",
+                    name(r)
                 )
             }
             _ => format!("{:?}", assert_kind),
@@ -834,6 +1909,8 @@ where
                 return;
             }
         }
+        // In the panic-freedom mode, any panic is as fatal as a memory-safety issue
+        let is_memory_safety = is_memory_safety || self.context.analysis_options.panic_freedom;
         let diagnostic = Diagnostic::new(diagnostic_builder, is_memory_safety, cause);
         self.buffered_diagnostics.push(diagnostic);
     }
@@ -847,11 +1924,13 @@ where
         debug!("Pre-Condition for {:?}: {:?}", bb, pre);
         let post;
         if !pre.is_bottom() {
+            self.unreachable_blocks.remove(&bb);
             let mut visitor = BlockVisitor::new(self, pre);
             visitor.visit_basic_block(bb);
             post = &self.state;
         } else {
             debug!("The precondition is bottom, ignore the analysis for this block");
+            self.unreachable_blocks.insert(bb);
             post = &pre;
         }
         debug!("Finish analyzing basic block: {:?}", bb);
@@ -908,13 +1987,103 @@ where
             before.narrowing_with(&after)
         }
     }
+    /// Records that the pointer at `pointer` points to `target`, or to an unknown path if `target` is `None`
+    /// A pointer that is assigned different targets keeps no provenance
+    pub fn record_pointer_provenance(&mut self, pointer: Rc<Path>, target: Option<Rc<Path>>) {
+        let provenance = match self.pointer_provenance.get(&pointer) {
+            Some(recorded) if *recorded != target => None,
+            _ => target,
+        };
+        debug!("Provenance of {:?}: {:?}", pointer, provenance);
+        self.pointer_provenance.insert(pointer, provenance);
+    }
+
+    /// Forgets the provenance of the pointers at `path` or inside it, which are written by something other than an
+    /// assignment, e.g., as the destination of a call. If `is_pointer`, `path` is marked even if it has no
+    /// provenance yet, so that the targets assigned to it elsewhere in the function are not used either
+    pub fn forget_pointer_provenance(&mut self, path: &Rc<Path>, is_pointer: bool) {
+        let mut pointers: Vec<Rc<Path>> = self
+            .pointer_provenance
+            .keys()
+            .filter(|pointer| *pointer == path || pointer.is_rooted_by(path))
+            .cloned()
+            .collect();
+        if is_pointer {
+            pointers.push(path.clone());
+        }
+        for pointer in pointers {
+            debug!("Forget the provenance of {:?}", pointer);
+            self.pointer_provenance.insert(pointer, None);
+        }
+    }
+
+    /// Replaces each dereference `*p` in `path` by the path that `p` points to, if the state has no value of `p`
+    /// but its provenance is known, e.g., after the reference is joined with an unknown value
+    pub fn apply_pointer_provenance(&self, path: &Rc<Path>) -> Rc<Path> {
+        if self.pointer_provenance.is_empty() {
+            return path.clone();
+        }
+        match &path.value {
+            PathEnum::QualifiedPath {
+                qualifier,
+                selector,
+                ..
+            } => {
+                let refined_qualifier = self.apply_pointer_provenance(qualifier);
+                if **selector == PathSelector::Deref {
+                    let unknown = self
+                        .state
+                        .value_at(&refined_qualifier)
+                        .map_or(true, |value| value.is_top());
+                    if let (true, Some(Some(target))) =
+                        (unknown, self.pointer_provenance.get(&refined_qualifier))
+                    {
+                        return target.clone();
+                    }
+                }
+                if refined_qualifier == *qualifier {
+                    path.clone()
+                } else {
+                    Path::new_qualified(refined_qualifier, selector.clone())
+                }
+            }
+            _ => path.clone(),
+        }
+    }
 
     /// Merge all the predecessors' states
     fn get_state_from_predecessors(&mut self, bb: mir::BasicBlock) -> AbstractDomain<DomainType> {
+        let preds = self.wto.get_mir().basic_blocks.predecessors()[bb].to_vec();
+        self.join_predecessor_states(bb, &preds)
+            .expect("Panic while merging states using fold1")
+    }
+
+    /// Merge the states of the predecessors that are reached from `bb` through a back edge,
+    /// i.e., the predecessors that are dominated by `bb`
+    /// Returns `None` if none of them has been analyzed
+    fn get_state_from_back_edges(
+        &mut self,
+        bb: mir::BasicBlock,
+    ) -> Option<AbstractDomain<DomainType>> {
+        let basic_blocks = &self.wto.get_mir().basic_blocks;
+        let preds: Vec<mir::BasicBlock> = basic_blocks.predecessors()[bb]
+            .iter()
+            .filter(|pred_bb| basic_blocks.dominators().dominates(bb, **pred_bb))
+            .copied()
+            .collect();
+        self.join_predecessor_states(bb, &preds)
+    }
+
+    /// Merge the post conditions of `preds`, each constrained by its exit condition to `bb`
+    fn join_predecessor_states(
+        &mut self,
+        bb: mir::BasicBlock,
+        preds: &[mir::BasicBlock],
+    ) -> Option<AbstractDomain<DomainType>> {
         debug!("Start merging state from predecessors");
         let pred_states: Vec<AbstractDomain<DomainType>> =
             // For all predecessors of bb
-            self.wto.get_mir().basic_blocks.predecessors()[bb]
+            preds
                 .iter()
                 .filter_map(|pred_bb| {
                     // For a predecessor pred_bb, get the post condition
@@ -937,14 +2106,202 @@ where
                     }
                 })
                 .collect();
+        // The predicates that hold in all the predecessors still hold after the join, which may lose them, e.g.,
+        // the join of intervals loses `x + y < 100`
+        let predicate_domain = if self.predicates.is_empty() {
+            None
+        } else {
+            pred_states
+                .iter()
+                .map(|state| {
+                    PredicateDomain::from_numerical(&state.numerical_domain, &self.predicates)
+                })
+                .fold1(|domain1, domain2| domain1.lub(&domain2))
+        };
         // Merge states using the join operator
-        let joined_state = pred_states
+        let mut joined_state = pred_states
             .into_iter()
-            .fold1(|state1, state2| state1.join(&state2))
-            .expect("Panic while merging states using fold1");
+            .fold1(|state1, state2| state1.join(&state2));
+        if let (Some(state), Some(predicate_domain)) = (&mut joined_state, predicate_domain) {
+            debug!("Predicates that hold: {:?}", predicate_domain);
+            state
+                .numerical_domain
+                .add_constraints(predicate_domain.to_constraints(&self.predicates));
+        }
         debug!("Merged state: {:?}", joined_state);
         joined_state
     }
+
+    /// Returns the number of iterations to unroll the loop headed by `head_bb`, if it is at most `unroll_threshold`
+    /// The loop either compares a value with a constant, e.g., `while i < 4`, which is detected from the `SwitchInt`
+    /// terminator of the loop head, or iterates over an integer range whose number of items is known in `pre`, the
+    /// state at the loop head, e.g., `for i in 0..4`, which is detected from the call of `Iterator::next` that
+    /// terminates the loop head
+    fn get_unroll_bound(
+        &mut self,
+        head_bb: mir::BasicBlock,
+        pre: &AbstractDomain<DomainType>,
+    ) -> Option<u128> {
+        let threshold = u128::from(self.context.analysis_options.unroll_threshold);
+        if threshold == 0 {
+            return None;
+        }
+        let block = &self.wto.get_mir().basic_blocks[head_bb];
+        let bound = match &block.terminator().kind {
+            mir::TerminatorKind::SwitchInt { discr, .. } => {
+                self.get_condition_bound(block, discr)?
+            }
+            mir::TerminatorKind::Call { func, args, .. } => {
+                self.get_range_bound(block, func, args, pre)?
+            }
+            _ => return None,
+        };
+        if bound <= threshold {
+            Some(bound)
+        } else {
+            None
+        }
+    }
+
+    /// The number of iterations of a loop whose head `block` compares a value with a constant, e.g., `i < 4`, and
+    /// switches on the result `discr`
+    fn get_condition_bound(
+        &self,
+        block: &mir::BasicBlockData<'tcx>,
+        discr: &mir::Operand<'tcx>,
+    ) -> Option<u128> {
+        let discr = discr.place()?.as_local()?;
+        // Find the comparison that computes the condition in the loop head
+        let (op, operands) = block.statements.iter().rev().find_map(|statement| {
+            if let mir::StatementKind::Assign(box (place, mir::Rvalue::BinaryOp(op, operands))) =
+                &statement.kind
+            {
+                if place.as_local() == Some(discr) {
+                    return Some((*op, operands));
+                }
+            }
+            None
+        })?;
+        if !matches!(
+            op,
+            mir::BinOp::Lt | mir::BinOp::Le | mir::BinOp::Gt | mir::BinOp::Ge | mir::BinOp::Ne
+        ) {
+            return None;
+        }
+        let tcx = self.context.tcx;
+        let param_env = tcx.param_env(self.def_id);
+        let bound = [&operands.0, &operands.1].iter().find_map(|operand| {
+            operand
+                .constant()
+                .and_then(|constant| constant.const_.try_eval_bits(tcx, param_env))
+        })?;
+        // `i <= n` and `n >= i` iterate once more than `i < n`
+        if matches!(op, mir::BinOp::Le | mir::BinOp::Ge) {
+            bound.checked_add(1)
+        } else {
+            Some(bound)
+        }
+    }
+
+    /// The number of iterations of a loop whose head `block` calls `Iterator::next` on an integer range, e.g.,
+    /// `for i in 0..n`, which is the upper bound of the number of the remaining items of the range in `pre`
+    /// The remaining items are counted by the `IteratorCount` path of a `Range` once it is tracked, see
+    /// `CallVisitor::is_tracked_iterator`, otherwise they are computed from the bounds of the range
+    fn get_range_bound(
+        &mut self,
+        block: &mir::BasicBlockData<'tcx>,
+        func: &mir::Operand<'tcx>,
+        args: &[Spanned<mir::Operand<'tcx>>],
+        pre: &AbstractDomain<DomainType>,
+    ) -> Option<u128> {
+        let tcx = self.context.tcx;
+        let callee_def_id = func.const_fn_def()?.0;
+        let known_name = self.crate_context.known_names_cache.get(tcx, callee_def_id);
+        if known_name != KnownNames::IteratorNext {
+            return None;
+        }
+        // The argument is a mutable reference to the range, which is taken in the loop head
+        let reference = args.first()?.node.place()?.as_local()?;
+        let range = block.statements.iter().find_map(|statement| {
+            if let mir::StatementKind::Assign(box (place, mir::Rvalue::Ref(_, _, range))) =
+                &statement.kind
+            {
+                if place.as_local() == Some(reference) {
+                    return range.as_local();
+                }
+            }
+            None
+        })?;
+        let mir = self.wto.get_mir();
+        let lang_items = tcx.lang_items();
+        let (is_inclusive, item_ty) = match mir.local_decls[range].ty.kind() {
+            TyKind::Adt(def, args) if lang_items.get(LangItem::Range) == Some(def.did()) => {
+                (false, args.type_at(0))
+            }
+            TyKind::Adt(def, args)
+                if lang_items.get(LangItem::RangeInclusiveStruct) == Some(def.did()) =>
+            {
+                (true, args.type_at(0))
+            }
+            _ => return None,
+        };
+        if !item_ty.is_integral() {
+            return None;
+        }
+        let range_path = Path::new_local_parameter_or_result(
+            range.as_usize(),
+            self.fresh_variable_offset,
+            mir.arg_count,
+        );
+        let count_path = Path::new_iterator_count(range_path.clone());
+        let count = if !is_inclusive && pre.numerical_domain.contains(&count_path) {
+            pre.numerical_domain.get_interval(&count_path).high
+        } else {
+            let start = pre
+                .numerical_domain
+                .get_interval(&Path::new_field(range_path.clone(), 0));
+            let end = pre
+                .numerical_domain
+                .get_interval(&Path::new_field(range_path, 1));
+            // `start..=end` has one more item than `start..end`
+            let extra = Bound::from(i128::from(is_inclusive));
+            end.high - start.low + extra
+        };
+        match count {
+            Bound::Int(count) => count.to_u128(),
+            _ => None,
+        }
+    }
+
+    /// Analyze the loop headed by `circle` iteration by iteration, at most `bound + 1` times, where the last
+    /// iteration is the one in which the loop condition no longer holds
+    /// Returns the join of the states at the loop head in all the iterations, and whether the back edges become
+    /// unreachable, in which case the join is the exact loop invariant and no fixed-point iteration is needed
+    fn unroll_loop(
+        &mut self,
+        circle: &WtoCircle,
+        entry_state: AbstractDomain<DomainType>,
+        bound: u128,
+    ) -> (AbstractDomain<DomainType>, bool) {
+        let head_bb = circle.head().node();
+        let mut invariant = entry_state.clone();
+        let mut iteration_state = entry_state;
+        for i in 0..=bound {
+            debug!("Unrolling loop {:?} at iteration: {}", head_bb, i);
+            self.analyze_basic_block(head_bb, iteration_state);
+            for comp in circle {
+                self.visit_component(comp);
+            }
+            match self.get_state_from_back_edges(head_bb) {
+                Some(back_edge_state) if !back_edge_state.is_bottom() => {
+                    invariant = invariant.join(&back_edge_state);
+                    iteration_state = back_edge_state;
+                }
+                _ => return (invariant, true),
+            }
+        }
+        (invariant, false)
+    }
 }
 
 /// Implement `visit_vertex` and `visit_circle`
@@ -975,6 +2332,13 @@ where
         let head = circle.head();
         let head_bb = head.node();
         debug!("Analyzing loop {:?} with head: {:?}", circle, head);
+        if let Some(max_loop_depth) = self.context.analysis_options.max_loop_depth {
+            if self.wto.circle_depth(head_bb) > max_loop_depth {
+                self.abandoned_loops.insert(head_bb);
+                self.abandon_circle(circle);
+                return;
+            }
+        }
         // First, find out the precondition of the head node of this circle
         let mut pre = if head.is_entry() {
             // If the head of this circle is the entry block (FIXME: Is it possible?)
@@ -985,6 +2349,28 @@ where
             self.get_state_from_predecessors(head_bb)
         };
 
+        // Unrolling a loop with a small constant bound is more precise than widening
+        if let Some(bound) = self.get_unroll_bound(head_bb, &pre) {
+            let (invariant, exhaustive) = self.unroll_loop(circle, pre, bound);
+            pre = invariant;
+            if exhaustive {
+                debug!(
+                    "Loop {:?} is fully unrolled, skip the fixed-point algorithm",
+                    head_bb
+                );
+                // Analyze the loop once more from the invariant, so that the post conditions of the blocks in the
+                // loop cover all the iterations
+                self.analyze_basic_block(head_bb, pre.clone());
+                for comp in circle {
+                    self.visit_component(comp);
+                }
+                if self.context.analysis_options.export_invariants.is_some() {
+                    self.record_loop_invariant(head_bb, &pre);
+                }
+                return;
+            }
+        }
+
         // Perform the fixed-point algorithm
         loop {
             // Increment iteration counter
@@ -1039,5 +2425,13 @@ where
                 }
             }
         }
+
+        // The state at the loop head is now an over-approximation of all reachable states, i.e., a loop invariant
+        if self.context.analysis_options.export_invariants.is_some() {
+            self.record_loop_invariant(head_bb, &pre);
+        }
+        if self.context.analysis_options.check_termination {
+            self.record_loop_transition(circle, &pre);
+        }
     }
 }
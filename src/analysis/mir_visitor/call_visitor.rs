@@ -10,22 +10,42 @@
 use crate::analysis::abstract_domain::AbstractDomain;
 use crate::analysis::diagnostics::DiagnosticCause;
 use crate::analysis::memory::constant_value::{ConstantValue, FunctionReference};
+use crate::analysis::memory::escape;
 use crate::analysis::memory::expression::{Expression, ExpressionType};
 use crate::analysis::memory::known_names::KnownNames;
-use crate::analysis::memory::path::{Path, PathRefinement};
+use crate::analysis::memory::path::{Path, PathEnum, PathRefinement, PathSelector};
 use crate::analysis::memory::symbolic_value::{self, SymbolicValue, SymbolicValueTrait};
+use crate::analysis::memory::utils;
 use crate::analysis::mir_visitor::block_visitor::BlockVisitor;
 use crate::analysis::mir_visitor::body_visitor::WtoFixPointIterator;
+use crate::analysis::mir_visitor::type_visitor;
 use crate::analysis::numerical::apron_domain::{
-    ApronAbstractDomain, ApronDomainType, GetManagerTrait,
+    ApronAbstractDomain, ApronDomainType, ApronOperation, GetManagerTrait,
 };
+use crate::analysis::numerical::interval::{Bound, Interval};
+use crate::analysis::numerical::lattice::LatticeTrait;
+use crate::analysis::numerical::linear_constraint::{
+    LinearConstraint, LinearConstraintSystem, LinearExpression,
+};
+use crate::analysis::numerical::wrapping_integer::WrappingIntegerDomain;
+use crate::analysis::summary::FunctionSummary;
 use crate::checker::assertion_checker::{AssertionChecker, CheckerResult};
+use crate::checker::channel_capacity_checker::ChannelSend;
 use crate::checker::checker_trait::CheckerTrait;
+use crate::checker::ffi_checker::{FfiArgument, FfiCall};
+use crate::checker::lock_order_checker::LockAcquisition;
+use crate::checker::raw_offset_overflow_checker::RawOffset;
+use crate::checker::raw_ptr_bounds_checker::RawPointerAccess;
+use crate::checker::str_index_checker::{StrIndex, StrOperation};
+use crate::checker::vec_bounds_checker::{VecAccess, VecOperation};
 use itertools::Itertools;
+use rug::Integer;
 use rustc_hir::def_id::DefId;
+use rustc_hir::LangItem;
 use rustc_middle::mir;
 use rustc_middle::ty::{GenericArgsRef, Ty, TyKind};
 use rustc_span::source_map::Spanned;
+use rustc_span::sym;
 use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Formatter, Result};
 use std::rc::Rc;
@@ -149,7 +169,9 @@ where
 
             // let init_abstract_value = self.extract_heap_value(&self.block_visitor.state);
             // TODO: try to include all states of the caller
-            let init_abstract_value = self.block_visitor.state().clone();
+            let mut init_abstract_value = self.block_visitor.state().clone();
+            // The float values are indexed by the locals of the caller, so the callee starts without them
+            init_abstract_value.float_values.clear();
 
             info!("====== Fixed-Point Algorithm Starts ======");
             debug!(
@@ -163,6 +185,7 @@ where
                 self.block_visitor.body_visitor.next_fresh_variable_offset,
                 self.call_stack.clone(),
             );
+            body_visitor.call_site = Some(self.block_visitor.body_visitor.current_span);
             body_visitor.type_visitor.actual_argument_types = self.actual_argument_types.into();
             body_visitor.type_visitor.generic_arguments = self.callee_generic_arguments;
             body_visitor.type_visitor.generic_argument_map =
@@ -297,6 +320,26 @@ where
             None
         };
         match self.callee_known_name {
+            KnownNames::BoxNew => {
+                self.handle_box_new();
+                return true;
+            }
+            KnownNames::CellGet => {
+                self.handle_cell_get();
+                return true;
+            }
+            KnownNames::CellSet => {
+                self.handle_cell_set();
+                return true;
+            }
+            KnownNames::RefCellBorrow | KnownNames::RefCellBorrowMut => {
+                self.handle_refcell_borrow();
+                return true;
+            }
+            KnownNames::PtrAdd | KnownNames::PtrSub | KnownNames::PtrOffset => {
+                self.handle_pointer_offset();
+                return true;
+            }
             KnownNames::VecFromRawParts => {
                 self.handle_from_raw_parts();
                 return true;
@@ -311,6 +354,12 @@ where
                 // self.handle_assume();
                 return true;
             }
+            KnownNames::MirCheckerRequires => {
+                assert!(self.actual_args.len() == 1);
+                debug!("Handling special function MirCheckerRequires");
+                self.handle_requires();
+                return true;
+            }
             KnownNames::RustDealloc => {
                 return true;
             }
@@ -318,6 +367,104 @@ where
                 self.handle_panic();
                 return true;
             }
+            KnownNames::OptionUnwrap => {
+                self.handle_unwrap(LangItem::OptionSome);
+                return true;
+            }
+            KnownNames::ResultUnwrap => {
+                self.handle_unwrap(LangItem::ResultOk);
+                return true;
+            }
+            KnownNames::OptionBranch => {
+                return self.handle_try_branch(LangItem::OptionSome, LangItem::OptionNone);
+            }
+            KnownNames::ResultBranch => {
+                return self.handle_try_branch(LangItem::ResultOk, LangItem::ResultErr);
+            }
+            KnownNames::OptionFromResidual => {
+                return self.handle_from_residual(LangItem::OptionNone);
+            }
+            KnownNames::ResultFromResidual => {
+                return self.handle_from_residual(LangItem::ResultErr);
+            }
+            KnownNames::IntegerCheckedAdd => {
+                return self.handle_checked_arithmetic(ApronOperation::Add);
+            }
+            KnownNames::IntegerCheckedSub => {
+                return self.handle_checked_arithmetic(ApronOperation::Sub);
+            }
+            KnownNames::IntegerCheckedMul => {
+                return self.handle_checked_arithmetic(ApronOperation::Mul);
+            }
+            KnownNames::IntegerSaturatingAdd => {
+                return self.handle_saturating_arithmetic(ApronOperation::Add);
+            }
+            KnownNames::IntegerSaturatingSub => {
+                return self.handle_saturating_arithmetic(ApronOperation::Sub);
+            }
+            KnownNames::IntegerSaturatingMul => {
+                return self.handle_saturating_arithmetic(ApronOperation::Mul);
+            }
+            KnownNames::IntoIteratorIntoIter => {
+                return self.handle_iterator_into_iter();
+            }
+            KnownNames::IteratorNext => {
+                return self.handle_iterator_next();
+            }
+            KnownNames::MutexLock => {
+                self.record_lock_acquisition();
+            }
+            KnownNames::MpscSyncChannel => {
+                return self.handle_sync_channel();
+            }
+            KnownNames::MpscSend => {
+                return self.handle_channel_send();
+            }
+            KnownNames::MpscRecv => {
+                return self.handle_channel_recv();
+            }
+            KnownNames::SimdSetEpi32 | KnownNames::SimdSetrEpi32 | KnownNames::SimdSet1Epi32 => {
+                return self.handle_simd_set();
+            }
+            KnownNames::SimdI32Gather => {
+                return self.handle_simd_gather();
+            }
+            KnownNames::VecNew | KnownNames::VecWithCapacity => {
+                return self.handle_vec_new();
+            }
+            KnownNames::VecPush => {
+                return self.handle_vec_push();
+            }
+            KnownNames::VecPop => {
+                return self.handle_vec_pop();
+            }
+            KnownNames::VecLen | KnownNames::VecCapacity => {
+                return self.handle_vec_len();
+            }
+            KnownNames::VecSetLen => {
+                return self.handle_vec_set_len();
+            }
+            KnownNames::VecDeref => {
+                return self.handle_vec_deref();
+            }
+            KnownNames::SliceGetUnchecked | KnownNames::StrGetUnchecked
+                if self.actual_args.len() == 2
+                    && self.is_integer_range(self.actual_argument_types[1]) =>
+            {
+                return self.handle_get_unchecked_range();
+            }
+            KnownNames::SliceGet | KnownNames::SliceGetUnchecked => {
+                return self.handle_slice_get();
+            }
+            KnownNames::StrIndex | KnownNames::StrSplitAt => {
+                return self.handle_str_index();
+            }
+            KnownNames::IteratorMap
+            | KnownNames::IteratorFilter
+            | KnownNames::IteratorEnumerate
+            | KnownNames::IteratorTake => {
+                return self.handle_iterator_adaptor();
+            }
             KnownNames::StdIntoVec => {
                 self.handle_into_vec();
                 return true;
@@ -326,7 +473,7 @@ where
                 self.handle_index();
                 return true;
             }
-            KnownNames::StdFrom | KnownNames::StdAsMutPtr => {
+            KnownNames::StdFrom | KnownNames::StdAsMutPtr | KnownNames::SliceAsPtr => {
                 self.handle_from();
                 return true;
             }
@@ -352,6 +499,372 @@ where
         false
     }
 
+    /// If the MIR of the callee is not available, but its summary is loaded from `--cache-dir`, assign the
+    /// range of the return value to the destination and return true
+    /// A summary does not describe the side effects of the callee, so it is only used if the arguments are plain
+    /// values, and the statics that a callee of another crate may write are forgotten
+    pub fn handled_by_summary(&mut self) -> bool {
+        if !self.has_plain_arguments() {
+            return false;
+        }
+        let context = &self.block_visitor.body_visitor.context;
+        let summary_key = utils::summary_key_str(context.tcx, self.callee_def_id);
+        let summary = if self.callee_def_id.is_local() {
+            // The summaries of the current crate are computed by the bottom-up analysis
+            // The summaries of the current crate left in the cache by a previous run are never used
+            if !context.analysis_options.bottom_up {
+                return false;
+            }
+            // The precondition of the callee is checked against the state of the caller when it is analyzed
+            if context.has_precondition(self.callee_def_id) {
+                return false;
+            }
+            context.summaries.get(&summary_key)
+        } else {
+            context.dependency_summaries.get(summary_key.as_str())
+        };
+        let summary = match summary {
+            Some(summary) => summary.clone(),
+            None => return false,
+        };
+        if summary.domain != context.analysis_options.domain_type {
+            debug!(
+                "The summary of {} is computed in the {} domain",
+                summary_key,
+                summary.domain.name()
+            );
+        }
+        // The state after a call that never returns is handled by the normal logic
+        if summary.return_value().is_bottom() {
+            return false;
+        }
+        if let Some(dest) = self.destination {
+            debug!("Using summary of {}: {:?}", summary_key, summary);
+            let target_path = self.block_visitor.visit_place(&dest);
+            let state = &mut self.block_visitor.body_visitor.state;
+            summary.apply(state, Some(&target_path));
+            if !self.callee_def_id.is_local() {
+                let static_paths: Vec<Rc<Path>> = state
+                    .get_paths_iter()
+                    .into_iter()
+                    .filter(|path| {
+                        matches!(
+                            escape::get_root(path).value,
+                            PathEnum::StaticVariable { .. }
+                        )
+                    })
+                    .collect();
+                state.remove_all(&static_paths);
+            }
+            true
+        } else {
+            false
+        }
+    }
+
+    /// If the callee is a standard library function that returns an integer and has a stub in `StubLibrary`,
+    /// assign the range computed by the stub from the ranges of the arguments to the destination and return true
+    /// The call is reported if the range computed by the stub exceeds the range of the return type, i.e., the call
+    /// may overflow, or if the callee is a division whose divisor may be zero
+    pub fn handled_by_stub(&mut self) -> bool {
+        if self.callee_def_id.is_local() {
+            return false;
+        }
+        let dest = match self.destination {
+            Some(dest) => dest,
+            None => return false,
+        };
+        let return_type = self
+            .block_visitor
+            .body_visitor
+            .type_visitor
+            .get_rustc_place_type(&dest, self.block_visitor.body_visitor.current_span);
+        if !return_type.is_integral() {
+            return false;
+        }
+        let context = &self.block_visitor.body_visitor.context;
+        let summary_key = utils::summary_key_str(context.tcx, self.callee_def_id);
+        let stub = match context.stub_library.get(&summary_key) {
+            Some(stub) => stub,
+            None => return false,
+        };
+        let is_division = context.stub_library.is_division(&summary_key);
+        let state = &self.block_visitor.body_visitor.state;
+        let args: Vec<Interval> = self
+            .actual_args
+            .iter()
+            .map(|(_, value)| match &value.expression {
+                Expression::CompileTimeConstant(constant) => match constant.try_get_integer() {
+                    Some(integer) => {
+                        Interval::new(Bound::Int(integer.clone()), Bound::Int(integer))
+                    }
+                    None => Interval::top(),
+                },
+                Expression::Numerical(path) | Expression::Variable { path, .. } => {
+                    state.numerical_domain.get_interval(path)
+                }
+                _ => Interval::top(),
+            })
+            .collect();
+        if args.iter().any(Interval::is_bottom) {
+            return false;
+        }
+        let type_visitor = &self.block_visitor.body_visitor.type_visitor;
+        let bit_width = match type_visitor.get_type_size(return_type) {
+            Some(size) => size * 8,
+            None => return false,
+        };
+        // The call panics if the result overflows the return type, unless it wraps around as computed by the stub
+        let range = stub(&args);
+        let result = range
+            .meet(&WrappingIntegerDomain::new(bit_width, return_type.is_signed()).full_range());
+        let function_name = context.tcx.def_path_str(self.callee_def_id);
+        if is_division {
+            let zero = Interval::new(Bound::Int(Integer::from(0)), Bound::Int(Integer::from(0)));
+            if args[1] == zero {
+                self.report_stub_error(
+                    format!(
+                        "[MirChecker] Provably error: `{}` divides by zero",
+                        function_name
+                    ),
+                    DiagnosticCause::DivZero,
+                );
+            } else if !args[1].meet(&zero).is_bottom() {
+                self.report_stub_error(
+                    format!(
+                        "[MirChecker] Possible error: `{}` may divide by zero",
+                        function_name
+                    ),
+                    DiagnosticCause::DivZero,
+                );
+            }
+        }
+        if result.is_bottom() && !range.is_bottom() {
+            self.report_stub_error(
+                format!(
+                    "[MirChecker] Provably error: the result of `{}` overflows",
+                    function_name
+                ),
+                DiagnosticCause::Arithmetic,
+            );
+        } else if result != range {
+            self.report_stub_error(
+                format!(
+                    "[MirChecker] Possible error: the result of `{}` may overflow",
+                    function_name
+                ),
+                DiagnosticCause::Arithmetic,
+            );
+        }
+        // The state after a call that never returns is handled by the normal logic
+        if result.is_bottom() {
+            return false;
+        }
+        debug!("Using stub of {}: {:?} -> {:?}", summary_key, args, result);
+        let target_path = self.block_visitor.visit_place(&dest);
+        let state = &mut self.block_visitor.body_visitor.state;
+        state.remove(&target_path);
+        state.numerical_domain.set_interval(&target_path, result);
+        true
+    }
+
+    fn report_stub_error(&mut self, message: String, cause: DiagnosticCause) {
+        let body_visitor = &mut self.block_visitor.body_visitor;
+        let warning = body_visitor
+            .context
+            .session
+            .dcx()
+            .struct_span_warn(body_visitor.current_span, message);
+        body_visitor.emit_diagnostic(warning, false, cause);
+    }
+
+    /// True if all the arguments are plain values, so that the callee has no side effects on the caller's state
+    fn has_plain_arguments(&self) -> bool {
+        self.actual_argument_types
+            .iter()
+            .all(|ty| ty.is_integral() || ty.is_bool() || ty.is_char() || ty.is_floating_point())
+    }
+
+    /// If the callee is the function being analyzed, i.e., a direct recursive call that returns an integer,
+    /// or a function in the same mutually recursive component, assign the range of the return value in the
+    /// summary of the callee to the destination and return true
+    /// The summary of a direct recursive call is computed by `compute_recursive_summary` when the call is met
+    /// for the first time, the summaries of a mutually recursive component are computed before the analysis
+    pub fn handled_as_recursive_call(&mut self) -> bool {
+        let body_visitor = &self.block_visitor.body_visitor;
+        let is_direct = body_visitor.def_id == self.callee_def_id;
+        if !(is_direct
+            || body_visitor
+                .context
+                .is_mutually_recursive(body_visitor.def_id, self.callee_def_id))
+            || !self.has_plain_arguments()
+        {
+            return false;
+        }
+        let summary_key = utils::summary_key_str(body_visitor.context.tcx, self.callee_def_id);
+        let summary = match body_visitor.context.summaries.get(&summary_key) {
+            Some(summary) => summary.clone(),
+            None if is_direct && body_visitor.wto.get_mir().return_ty().is_integral() => {
+                self.compute_recursive_summary(summary_key.clone())
+            }
+            // Only the functions that return integers have summaries
+            None => return false,
+        };
+        debug!(
+            "Using summary of recursive function {}: {:?}",
+            summary_key, summary
+        );
+        self.apply_summary(&summary);
+        true
+    }
+
+    /// Apply the summary of the callee to the state after the call, the state is bottom if the range of the return
+    /// value is bottom, i.e., the callee never returns, or no base case has been reached yet
+    fn apply_summary(&mut self, summary: &FunctionSummary) {
+        let target_path = self
+            .destination
+            .map(|dest| self.block_visitor.visit_place(&dest));
+        summary.apply(
+            &mut self.block_visitor.body_visitor.state,
+            target_path.as_ref(),
+        );
+    }
+
+    /// Returns true if `--inline-depth` is set, and the callee is not analyzed in the context of this call, because
+    /// the caller is already inlined that deep, or the callee is being analyzed, i.e., a recursive call
+    pub fn exceeds_inline_depth(&self) -> bool {
+        let body_visitor = &self.block_visitor.body_visitor;
+        match body_visitor.context.analysis_options.inline_depth {
+            Some(depth) => {
+                body_visitor.call_stack.len() >= depth as usize
+                    || self.call_stack.contains(&self.callee_def_id)
+            }
+            None => false,
+        }
+    }
+
+    /// If the callee is not inlined, see `exceeds_inline_depth`, assign the range of the return value in its
+    /// summary to the destination and return true
+    /// The summary is computed by `compute_inline_summary` when the callee is met for the first time, and is shared
+    /// by all its calls. Only the callees whose arguments are plain values and that return integers have summaries
+    pub fn handled_by_inline_summary(&mut self) -> bool {
+        let body_visitor = &self.block_visitor.body_visitor;
+        let tcx = body_visitor.context.tcx;
+        if !self.has_plain_arguments()
+            || !tcx.is_mir_available(self.callee_def_id)
+            || !tcx
+                .optimized_mir(self.callee_def_id)
+                .return_ty()
+                .is_integral()
+        {
+            return false;
+        }
+        let summary_key = utils::summary_key_str(tcx, self.callee_def_id);
+        let summary = match body_visitor.context.summaries.get(&summary_key) {
+            Some(summary) => summary.clone(),
+            // A recursive call whose callee has no summary yet returns an unknown value
+            None if self.call_stack.contains(&self.callee_def_id) => return false,
+            None => self.compute_inline_summary(summary_key.clone()),
+        };
+        debug!(
+            "Using summary of {} beyond the inline depth: {:?}",
+            summary_key, summary
+        );
+        self.apply_summary(&summary);
+        true
+    }
+
+    /// Compute the summary of the callee by analyzing it once with unknown arguments, see `--inline-depth`
+    fn compute_inline_summary(&mut self, summary_key: Rc<String>) -> FunctionSummary {
+        info!(
+            "Start Analyzing Function Beyond The Inline Depth: {}",
+            summary_key
+        );
+        let mut call_stack = self.call_stack.clone();
+        call_stack.push(self.callee_def_id);
+        let mut body_visitor = WtoFixPointIterator::new(
+            self.block_visitor.body_visitor.context,
+            self.callee_def_id,
+            AbstractDomain::default(),
+            self.block_visitor.body_visitor.next_fresh_variable_offset,
+            call_stack,
+        );
+        body_visitor.type_visitor.generic_arguments = self.callee_generic_arguments;
+        body_visitor
+            .type_visitor
+            .generic_argument_map
+            .clone_from(&self.callee_generic_argument_map);
+        body_visitor.init_promote_constants();
+        body_visitor.run();
+        // The bugs are reported when the callee is analyzed as a reachable function
+        body_visitor.cancel_diagnostics();
+        body_visitor.record_summary();
+
+        // Update the fresh variable offset for the next call
+        self.block_visitor.body_visitor.next_fresh_variable_offset =
+            body_visitor.next_fresh_variable_offset;
+
+        self.block_visitor.body_visitor.context.summaries[&summary_key].clone()
+    }
+
+    /// Compute the summary of the recursive callee by analyzing it with unknown arguments, where the recursive
+    /// calls in its body use the summary computed so far
+    /// The summary starts at bottom, i.e., only the base cases return, instead of top, which could never be
+    /// refined. Each iteration joins the new range of the return value for the first `widening_delay` iterations,
+    /// and widens it afterwards, until the summary is stable
+    fn compute_recursive_summary(&mut self, summary_key: Rc<String>) -> FunctionSummary {
+        let analysis_options = &self.block_visitor.body_visitor.context.analysis_options;
+        let domain = analysis_options.domain_type;
+        let widening_delay = analysis_options.widening_delay;
+        let mut summary = FunctionSummary::new(domain, Interval::bottom());
+        let mut iteration = 0;
+        loop {
+            iteration += 1;
+            info!(
+                "Start Analyzing Recursive Function: {}, iteration: {}",
+                summary_key, iteration
+            );
+            self.block_visitor
+                .body_visitor
+                .context
+                .summaries
+                .insert(summary_key.clone(), summary.clone());
+            let mut body_visitor = WtoFixPointIterator::new(
+                self.block_visitor.body_visitor.context,
+                self.callee_def_id,
+                AbstractDomain::default(),
+                self.block_visitor.body_visitor.next_fresh_variable_offset,
+                self.call_stack.clone(),
+            );
+            body_visitor.type_visitor.generic_arguments = self.callee_generic_arguments;
+            body_visitor
+                .type_visitor
+                .generic_argument_map
+                .clone_from(&self.callee_generic_argument_map);
+            body_visitor.init_promote_constants();
+            body_visitor.run();
+            // The bugs are reported when the function is checked in its own context
+            body_visitor.cancel_diagnostics();
+            let return_value = body_visitor.get_return_value();
+
+            // Update the fresh variable offset for the next call
+            self.block_visitor.body_visitor.next_fresh_variable_offset =
+                body_visitor.next_fresh_variable_offset;
+
+            let next = FunctionSummary::new(domain, return_value);
+            let next = if iteration <= widening_delay {
+                summary.join(&next)
+            } else {
+                summary.widening_with(&next)
+            };
+            if next == summary {
+                break;
+            }
+            summary = next;
+        }
+        summary
+    }
+
     /// If the function being called is a special function like mirai_annotations.mirai_verify or
     /// std.panicking.begin_panic then report a diagnostic or create a precondition as appropriate.
     fn report_calls_to_special_functions(&mut self) {
@@ -367,6 +880,48 @@ where
         }
     }
 
+    /// Handles the precondition injected by `#[requires(cond)]` at the beginning of a function, see
+    /// `mir-checker-macros`
+    /// If the function is analyzed for a call, the state of the caller must satisfy the precondition, otherwise the
+    /// call is reported. The precondition is then assumed, so that the assertion that follows it always succeeds
+    fn handle_requires(&mut self) {
+        let (_, cond) = &self.actual_args[0];
+        let cond = cond.clone();
+        let body_visitor = &self.block_visitor.body_visitor;
+        let function_name = body_visitor.context.tcx.def_path_str(body_visitor.def_id);
+        let precondition_span = body_visitor.current_span;
+        if let Some(call_site) = body_visitor.call_site {
+            let message = match self.block_visitor.check_condition_value(&cond) {
+                Some(true) => None,
+                Some(false) => Some(format!(
+                    "[MirChecker] Provably error: the precondition of `{}` is violated",
+                    function_name
+                )),
+                None => Some(format!(
+                    "[MirChecker] Possible error: the precondition of `{}` may not hold",
+                    function_name
+                )),
+            };
+            if let Some(message) = message {
+                let body_visitor = &mut self.block_visitor.body_visitor;
+                let mut warning = body_visitor
+                    .context
+                    .session
+                    .dcx()
+                    .struct_span_warn(call_site, message);
+                warning.span_note(precondition_span, "the precondition is declared here");
+                body_visitor.emit_diagnostic(warning, false, DiagnosticCause::Other);
+            }
+        }
+        if let Ok(constraints) = LinearConstraintSystem::try_from(cond) {
+            self.block_visitor
+                .body_visitor
+                .state
+                .numerical_domain
+                .add_constraints(constraints);
+        }
+    }
+
     /// Provides special handling of functions that have no MIR bodies or that need to access
     /// internal MIRAI state in ways that cannot be expressed in normal Rust and therefore
     /// cannot be summarized in the standard_contracts crate.
@@ -425,11 +980,23 @@ where
         let alignment = self.actual_args[1].1.clone();
         let tcx = self.block_visitor.body_visitor.context.tcx;
         let byte_slice = tcx.mk_ty_from_kind(TyKind::Slice(tcx.types.u8));
-        let heap_path = Path::get_as_path(
+        let heap_path = Path::get_as_path(self.block_visitor.body_visitor.get_new_heap_block(
+            length.clone(),
+            alignment,
+            byte_slice,
+        ));
+        // Record the byte length, which bounds the accesses through the returned pointer
+        let state = &mut self.block_visitor.body_visitor.state;
+        state.update_value_at(Path::new_length(heap_path.clone()), length);
+        if let Some(dest) = self.destination {
+            let pointer_path = self.block_visitor.get_path_for_place(&dest);
+            // The returned pointer points to the start of the block
             self.block_visitor
                 .body_visitor
-                .get_new_heap_block(length, alignment, byte_slice),
-        );
+                .state
+                .numerical_domain
+                .assign_int(pointer_path, Integer::from(0));
+        }
         SymbolicValue::make_reference(heap_path)
     }
 
@@ -585,6 +1152,147 @@ where
         }
     }
 
+    /// Moves the argument of `Box::new` into a new heap allocation and returns a reference to it,
+    /// so that `*b` and its fields can be tracked via the allocation path
+    fn handle_box_new(&mut self) {
+        assert!(self.actual_args.len() == 1);
+        let source = &self.actual_args[0].0;
+        let destination_path = if let Some(dest) = self.destination {
+            Some(self.block_visitor.get_path_for_place(&dest))
+        } else {
+            None
+        };
+        assert!(destination_path.is_some());
+        let result = destination_path.as_ref().unwrap();
+
+        let body_visitor = &mut self.block_visitor.body_visitor;
+        let heap_path = body_visitor.get_new_heap_allocation();
+        let rtype = body_visitor
+            .type_visitor
+            .get_path_rustc_type(source, body_visitor.current_span);
+        self.block_visitor
+            .copy_or_move_elements(heap_path.clone(), source.clone(), rtype, true);
+        self.block_visitor
+            .body_visitor
+            .state
+            .update_value_at(result.clone(), SymbolicValue::make_reference(heap_path));
+    }
+
+    /// Returns the path to the value wrapped by the `Cell` or `RefCell` that the first argument refers to,
+    /// i.e., `(*arg).value.value`, and the type of the wrapped value
+    fn get_cell_value_path(&mut self) -> (Rc<Path>, Ty<'tcx>) {
+        let cell_path = Path::new_deref(self.actual_args[0].0.clone())
+            .refine_paths(self.block_visitor.state());
+        let cell_ty = match self.actual_argument_types[0].kind() {
+            TyKind::Ref(_, ty, _) => *ty,
+            _ => unreachable!("the receiver of a cell method should be a reference"),
+        };
+        if let TyKind::Adt(adt_def, args) = cell_ty.kind() {
+            let value_index = adt_def
+                .non_enum_variant()
+                .fields
+                .iter()
+                .position(|field| field.name.as_str() == "value")
+                .expect("cells should have a `value` field");
+            // The `value` field is an `UnsafeCell`, whose only field is the actual value
+            let value_path = Path::new_field(Path::new_field(cell_path, value_index), 0);
+            (value_path, args.type_at(0))
+        } else {
+            unreachable!("{:?} is not a cell type", cell_ty)
+        }
+    }
+
+    /// `Cell::get` returns the current value in the cell
+    fn handle_cell_get(&mut self) {
+        assert!(self.actual_args.len() == 1);
+        assert!(self.destination.is_some());
+        let result = self
+            .block_visitor
+            .get_path_for_place(&self.destination.unwrap());
+        let (value_path, value_ty) = self.get_cell_value_path();
+        self.block_visitor
+            .copy_or_move_elements(result, value_path, value_ty, false);
+    }
+
+    /// `Cell::set` updates the value in the cell
+    fn handle_cell_set(&mut self) {
+        assert!(self.actual_args.len() == 2);
+        let source = self.actual_args[1].0.clone();
+        let (value_path, value_ty) = self.get_cell_value_path();
+        self.block_visitor
+            .copy_or_move_elements(value_path, source, value_ty, true);
+    }
+
+    /// `RefCell::borrow` and `RefCell::borrow_mut` return a reference to the value in the cell
+    /// Writes through `borrow_mut` are not tracked, so the value is forgotten, i.e., becomes top,
+    /// which also invalidates the values read through other borrows of the same cell
+    fn handle_refcell_borrow(&mut self) {
+        assert!(self.actual_args.len() == 1);
+        assert!(self.destination.is_some());
+        let result = self
+            .block_visitor
+            .get_path_for_place(&self.destination.unwrap());
+        let (value_path, _) = self.get_cell_value_path();
+        let state = &mut self.block_visitor.body_visitor.state;
+        if self.callee_known_name == KnownNames::RefCellBorrowMut {
+            for path in state.get_paths_iter() {
+                if path == value_path || path.is_rooted_by(&value_path) {
+                    state.remove(&path);
+                }
+            }
+        }
+        state.update_value_at(result, SymbolicValue::make_reference(value_path));
+    }
+
+    /// `<*T>::add`, `<*T>::sub` and `<*T>::offset` compute the address `base ± count * size_of::<T>()`
+    /// The address is tracked in the numerical domain, so that comparisons between pointers can be reasoned about,
+    /// while the symbolic value still refers to the base, the same as `BinOp::Offset`
+    fn handle_pointer_offset(&mut self) {
+        assert!(self.actual_args.len() == 2);
+        assert!(self.destination.is_some());
+        let result = self
+            .block_visitor
+            .get_path_for_place(&self.destination.unwrap());
+        let (pointee_ty, elem_size) = match self.actual_argument_types[0].kind() {
+            TyKind::RawPtr(ty, _) => (
+                *ty,
+                self.block_visitor
+                    .body_visitor
+                    .type_visitor
+                    .get_type_size(*ty),
+            ),
+            _ => unreachable!("the receiver of a pointer method should be a raw pointer"),
+        };
+        if let (KnownNames::PtrOffset, Some(elem_size)) = (self.callee_known_name, elem_size) {
+            self.record_raw_offset(pointee_ty, elem_size);
+        }
+        let (base_path, base_val) = &self.actual_args[0];
+        let (count_path, count_val) = &self.actual_args[1];
+        let op = if self.callee_known_name == KnownNames::PtrSub {
+            ApronOperation::Sub
+        } else {
+            ApronOperation::Add
+        };
+
+        let state = &mut self.block_visitor.body_visitor.state;
+        state.update_value_at(result.clone(), base_val.clone());
+        let numerical_domain = &mut state.numerical_domain;
+        let elem_size = if let Some(elem_size) = elem_size {
+            elem_size
+        } else {
+            // The address is unknown if the size of the pointee is unknown
+            numerical_domain.forget(&result);
+            return;
+        };
+        if let Expression::CompileTimeConstant(ConstantValue::Int(count)) = &count_val.expression {
+            let byte_offset = Integer::from(count * elem_size);
+            numerical_domain.apply_bin_op_place_const(op, base_path, &byte_offset, &result);
+        } else {
+            let size = Integer::from(elem_size);
+            numerical_domain.apply_pointer_offset(op, base_path, count_path, &size, &result);
+        }
+    }
+
     fn handle_into_vec(&mut self) {
         assert!(self.actual_args.len() == 1);
         let source = &self.actual_args[0].0;
@@ -631,7 +1339,9 @@ where
         assert!(self.actual_args.len() == 1);
         assert!(self.destination.is_none());
         let body_visitor = &mut self.block_visitor.body_visitor;
-        if !body_visitor.state.is_bottom() {
+        // If `--check panic-reachability` is set, the panic is reported by `PanicReachabilityChecker` instead
+        let options = &body_visitor.context.analysis_options;
+        if !body_visitor.state.is_bottom() && !options.check_panic_reachability {
             let warning = body_visitor.context.session.dcx().struct_span_warn(
                 body_visitor.current_span,
                 format!("[MirChecker] Possible error: run into panic code"),
@@ -640,39 +1350,1812 @@ where
         }
     }
 
-    fn handle_from(&mut self) {
-        assert!(self.actual_args.len() == 1);
-        let source = &self.actual_args[0].0;
-        let destination_path = if let Some(dest) = self.destination {
-            Some(self.block_visitor.get_path_for_place(&dest))
+    /// `unwrap` and `expect` of `Option` and `Result` panic unless the discriminant is the one of the variant
+    /// `success_variant`, i.e., `Some` or `Ok`
+    /// A warning is reported if the discriminant is known to be another variant on some path, i.e., it is set by
+    /// `None` or `Err` or constrained by a branch. A value with an unknown discriminant is only reported in the
+    /// panic-freedom mode
+    /// Afterwards the discriminant is assumed to be `success_variant`, and the result is the field of the variant
+    fn handle_unwrap(&mut self, success_variant: LangItem) {
+        assert!(self.actual_args.len() == 1 || self.actual_args.len() == 2);
+        assert!(self.destination.is_some());
+        let destination = self.destination.unwrap();
+        let result = self.block_visitor.get_path_for_place(&destination);
+        let enum_path = self.actual_args[0].0.clone();
+        let discriminant_path = Path::new_discriminant(enum_path.clone())
+            .refine_paths(&self.block_visitor.body_visitor.state);
+
+        let success_discriminant = match self.get_variant_discriminant(success_variant) {
+            Some(discriminant) => discriminant,
+            None => return,
+        };
+        let success_interval = Interval::new(
+            success_discriminant.clone().into(),
+            success_discriminant.clone().into(),
+        );
+        let (always_fails, may_fail) = if success_variant == LangItem::OptionSome {
+            (
+                "[MirChecker] Provably error: unwrap of `None` always panics",
+                "[MirChecker] Possible error: unwrap of an option that may be `None`",
+            )
         } else {
-            None
+            (
+                "[MirChecker] Provably error: unwrap of `Err` always panics",
+                "[MirChecker] Possible error: unwrap of a result that may be `Err`",
+            )
         };
-        assert!(destination_path.is_some());
-        let result = destination_path.as_ref().unwrap();
 
         let body_visitor = &mut self.block_visitor.body_visitor;
-        let rtype = body_visitor
+
+        if !body_visitor.state.is_bottom() {
+            let discriminant_interval = body_visitor
+                .state
+                .numerical_domain
+                .get_interval(&discriminant_path);
+            let is_success = discriminant_interval.equal_to(&success_interval);
+            let message = match is_success {
+                Some(false) => Some(always_fails),
+                None if !discriminant_interval.is_top()
+                    || body_visitor.context.analysis_options.panic_freedom =>
+                {
+                    Some(may_fail)
+                }
+                _ => None,
+            };
+            if let Some(message) = message {
+                let warning = body_visitor
+                    .context
+                    .session
+                    .dcx()
+                    .struct_span_warn(body_visitor.current_span, message);
+                body_visitor.emit_diagnostic(warning, false, DiagnosticCause::Panic);
+            }
+            if is_success == Some(false) {
+                body_visitor.state.numerical_domain.set_to_bottom();
+                return;
+            }
+        }
+        // The discriminant is constrained instead of assigned, so that the constraints that are related to it,
+        // e.g., the range of the result of `checked_add`, are kept
+        let mut succeeds = LinearConstraintSystem::default();
+        succeeds.add(LinearConstraint::Equality(
+            LinearExpression::default() + discriminant_path - success_discriminant,
+        ));
+        body_visitor
+            .state
+            .numerical_domain
+            .add_constraints(succeeds);
+
+        let result_ty = body_visitor
             .type_visitor
-            .get_path_rustc_type(source, body_visitor.current_span);
+            .get_rustc_place_type(&destination, body_visitor.current_span);
+        let value_path =
+            Path::new_field(enum_path, 0).refine_paths(&self.block_visitor.body_visitor.state);
         self.block_visitor
-            .copy_or_move_elements(result.clone(), source.clone(), rtype, true);
+            .copy_or_move_elements(result, value_path, result_ty, true);
     }
 
-    fn handle_index(&mut self) {
-        assert!(self.actual_args.len() == 2);
-        let destination_path = if let Some(dest) = self.destination {
-            Some(self.block_visitor.get_path_for_place(&dest))
-        } else {
-            None
-        };
-        assert!(destination_path.is_some());
-        let state = self.block_visitor.state().clone();
+    /// Records the mutex that `Mutex::lock` is called on for the lock order checker, the call itself is analyzed
+    /// as a normal call
+    fn record_lock_acquisition(&mut self) {
+        if !self
+            .block_visitor
+            .body_visitor
+            .context
+            .analysis_options
+            .check_lock_order
+        {
+            return;
+        }
+        assert!(self.actual_args.len() == 1);
+        let lock =
+            Path::new_deref(self.actual_args[0].0.clone()).refine_paths(self.block_visitor.state());
         let body_visitor = &mut self.block_visitor.body_visitor;
-
-        let array = &self.actual_args[0].0;
-        let array_len = Path::new_length(array.clone()).refine_paths(&body_visitor.state);
-        let array_len_val = SymbolicValue::make_from(
+        let span = body_visitor.current_span;
+        // The receiver of `a.lock()`, or the whole call if it is not a method call
+        let name = match body_visitor
+            .context
+            .session
+            .source_map()
+            .span_to_snippet(span)
+        {
+            Ok(snippet) => snippet
+                .strip_suffix(".lock()")
+                .unwrap_or(&snippet)
+                .to_string(),
+            Err(_) => format!("{:?}", lock),
+        };
+        body_visitor.lock_acquisitions.insert(
+            body_visitor.current_location,
+            LockAcquisition { span, lock, name },
+        );
+    }
+
+    /// `sync_channel(capacity)` returns a `SyncSender` and a `Receiver` that share a buffer, which is modeled as a new
+    /// heap allocation whose first field is the number of buffered items, initially zero, and whose second field is
+    /// the capacity. The first field of each end of the channel refers to the buffer
+    /// Returns false if the channel capacity checker is disabled, and the call is analyzed as a normal call
+    fn handle_sync_channel(&mut self) -> bool {
+        if !self
+            .block_visitor
+            .body_visitor
+            .context
+            .analysis_options
+            .check_channel_capacity
+            || self.destination.is_none()
+        {
+            return false;
+        }
+        assert!(self.actual_args.len() == 1);
+        let channel_path = self
+            .block_visitor
+            .get_path_for_place(&self.destination.unwrap());
+        let capacity = self.actual_args[0].1.clone();
+        let body_visitor = &mut self.block_visitor.body_visitor;
+        let buffer_path = body_visitor.get_new_heap_allocation();
+        let state = &mut body_visitor.state;
+        state
+            .numerical_domain
+            .assign_int(Path::new_field(buffer_path.clone(), 0), Integer::from(0));
+        state.update_value_at(Path::new_field(buffer_path.clone(), 1), capacity);
+        for end in 0..2 {
+            let end_path = Path::new_field(channel_path.clone(), end);
+            state.update_value_at(
+                Path::new_field(end_path, 0),
+                SymbolicValue::make_reference(buffer_path.clone()),
+            );
+        }
+        true
+    }
+
+    /// `SyncSender::send` adds an item to the buffer of the channel, and blocks while the buffer is full until a
+    /// receive in another thread makes room for it, after which the buffer is full again
+    /// The number of buffered items before the send is recorded for the channel capacity checker
+    /// Returns false if the channel is not created by `sync_channel` in the analyzed code, e.g., it is a `Sender`
+    fn handle_channel_send(&mut self) -> bool {
+        let buffer_path = match self.get_channel_buffer() {
+            Some(buffer_path) => buffer_path,
+            None => return false,
+        };
+        let count_path = Path::new_field(buffer_path.clone(), 0);
+        let capacity_path = Path::new_field(buffer_path, 1);
+        self.forget_destination();
+        let body_visitor = &mut self.block_visitor.body_visitor;
+        let numerical_domain = &mut body_visitor.state.numerical_domain;
+        body_visitor.channel_sends.insert(
+            body_visitor.current_location,
+            ChannelSend {
+                span: body_visitor.current_span,
+                buffered: numerical_domain.get_interval(&count_path),
+                capacity: numerical_domain.get_interval(&capacity_path),
+            },
+        );
+        if numerical_domain.is_bottom() {
+            return true;
+        }
+
+        // `count <= capacity - 1`, the item is added to the buffer
+        let mut not_full = numerical_domain.clone();
+        let mut has_room = LinearConstraintSystem::default();
+        has_room.add(LinearConstraint::LessEq(
+            LinearExpression::default() + count_path.clone() - capacity_path.clone()
+                + Integer::from(1),
+        ));
+        not_full.add_constraints(has_room);
+        not_full.apply_bin_op_place_const(
+            ApronOperation::Add,
+            &count_path,
+            &Integer::from(1),
+            &count_path,
+        );
+
+        // `count >= capacity`, the send waits for a receive
+        let mut full = numerical_domain.clone();
+        let mut no_room = LinearConstraintSystem::default();
+        no_room.add(LinearConstraint::LessEq(
+            LinearExpression::default() + capacity_path.clone() - count_path.clone(),
+        ));
+        full.add_constraints(no_room);
+        full.assign_var(count_path, capacity_path);
+
+        *numerical_domain = not_full.join(&full);
+        true
+    }
+
+    /// `Receiver::recv` removes an item from the buffer of the channel, and blocks while the buffer is empty until
+    /// a send in another thread provides an item, which is received without being buffered
+    /// Returns false if the channel is not created by `sync_channel` in the analyzed code
+    fn handle_channel_recv(&mut self) -> bool {
+        let buffer_path = match self.get_channel_buffer() {
+            Some(buffer_path) => buffer_path,
+            None => return false,
+        };
+        let count_path = Path::new_field(buffer_path, 0);
+        self.forget_destination();
+        let numerical_domain = &mut self.block_visitor.body_visitor.state.numerical_domain;
+        if numerical_domain.is_bottom() {
+            return true;
+        }
+
+        // `count >= 1`, an item is removed from the buffer
+        let mut not_empty = numerical_domain.clone();
+        let mut has_item = LinearConstraintSystem::default();
+        has_item.add(LinearConstraint::LessEq(
+            LinearExpression::default() + Integer::from(1) - count_path.clone(),
+        ));
+        not_empty.add_constraints(has_item);
+        not_empty.apply_bin_op_place_const(
+            ApronOperation::Sub,
+            &count_path,
+            &Integer::from(1),
+            &count_path,
+        );
+
+        // `count <= 0`, the receive waits for a send
+        let mut empty = numerical_domain.clone();
+        let mut no_item = LinearConstraintSystem::default();
+        no_item.add(LinearConstraint::LessEq(
+            LinearExpression::default() + count_path.clone(),
+        ));
+        empty.add_constraints(no_item);
+
+        *numerical_domain = not_empty.join(&empty);
+        true
+    }
+
+    /// The buffer of the channel that the first argument refers to, if the channel is created by `sync_channel`
+    fn get_channel_buffer(&mut self) -> Option<Rc<Path>> {
+        if !self
+            .block_visitor
+            .body_visitor
+            .context
+            .analysis_options
+            .check_channel_capacity
+        {
+            return None;
+        }
+        let end_path =
+            Path::new_deref(self.actual_args[0].0.clone()).refine_paths(self.block_visitor.state());
+        let value = self
+            .block_visitor
+            .state()
+            .value_at(&Path::new_field(end_path, 0))?;
+        match &value.expression {
+            Expression::Reference(buffer_path)
+                if matches!(buffer_path.value, PathEnum::HeapAllocation { .. }) =>
+            {
+                Some(buffer_path.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// The vector that the first argument refers to, if the vector bounds checker is enabled
+    /// The length of a vector is the value at its `new_length` path, i.e., its `len` field, and its capacity is the
+    /// value at its `new_capacity` path
+    fn get_vec_path(&mut self) -> Option<Rc<Path>> {
+        if !self
+            .block_visitor
+            .body_visitor
+            .context
+            .analysis_options
+            .check_vec_bounds
+            || self.actual_args.is_empty()
+        {
+            return None;
+        }
+        Some(Path::new_deref(self.actual_args[0].0.clone()).refine_paths(self.block_visitor.state()))
+    }
+
+    /// Forgets the items of the vector, i.e., the paths that index it directly, see `handle_slice_get`, and the
+    /// paths rooted by them, except the items at constant indices below `length`
+    fn forget_vec_items(&mut self, vec_path: &Rc<Path>, length: &Bound) {
+        let state = &mut self.block_visitor.body_visitor.state;
+        let stale_paths: Vec<Rc<Path>> = state
+            .get_paths_iter()
+            .into_iter()
+            .filter(|path| match get_vec_item_selector(path, vec_path) {
+                Some(PathSelector::Index(index)) => match &index.expression {
+                    Expression::CompileTimeConstant(ConstantValue::Int(index)) => {
+                        Bound::Int(index.clone()) >= *length
+                    }
+                    _ => true,
+                },
+                Some(_) => true,
+                None => false,
+            })
+            .collect();
+        state.remove_all(&stale_paths);
+    }
+
+    /// A call that is not modeled may change the capacity, the length and the items of the vectors that are passed
+    /// to it by mutable references, e.g., `Vec::reserve` and `Vec::extend`, so they are forgotten
+    /// Does nothing if the vector bounds checker is disabled
+    pub fn forget_mutably_borrowed_vecs(&mut self) {
+        if !self
+            .block_visitor
+            .body_visitor
+            .context
+            .analysis_options
+            .check_vec_bounds
+        {
+            return;
+        }
+        let tcx = self.block_visitor.body_visitor.context.tcx;
+        let actual_args = self.actual_args;
+        for (arg, arg_ty) in actual_args.iter().zip(self.actual_argument_types) {
+            let is_vec = match arg_ty.kind() {
+                TyKind::Ref(_, ty, rustc_hir::Mutability::Mut) => match ty.kind() {
+                    TyKind::Adt(def, _) => tcx.is_diagnostic_item(sym::Vec, def.did()),
+                    _ => false,
+                },
+                _ => false,
+            };
+            if !is_vec {
+                continue;
+            }
+            let vec_path = Path::new_deref(arg.0.clone()).refine_paths(self.block_visitor.state());
+            self.forget_vec_items(&vec_path, &Bound::Int(Integer::from(0)));
+            let state = &mut self.block_visitor.body_visitor.state;
+            state.remove(&Path::new_capacity(vec_path.clone()));
+            state.remove(&Path::new_length(vec_path));
+        }
+    }
+
+    /// `Vec::new()` and `Vec::with_capacity(capacity)` return an empty vector with the capacity
+    /// Returns false if the vector bounds checker is disabled, and the call is analyzed as a normal call
+    fn handle_vec_new(&mut self) -> bool {
+        if !self
+            .block_visitor
+            .body_visitor
+            .context
+            .analysis_options
+            .check_vec_bounds
+            || self.destination.is_none()
+        {
+            return false;
+        }
+        let vec_path = self
+            .block_visitor
+            .get_path_for_place(&self.destination.unwrap());
+        let capacity = match self.actual_args.first() {
+            Some((_, capacity)) => capacity.clone(),
+            None => self.block_visitor.body_visitor.get_u128_const_val(0),
+        };
+        self.forget_destination();
+        let state = &mut self.block_visitor.body_visitor.state;
+        state
+            .numerical_domain
+            .assign_int(Path::new_length(vec_path.clone()), Integer::from(0));
+        state.update_value_at(Path::new_capacity(vec_path), capacity);
+        true
+    }
+
+    /// `Vec::push` writes the item at the old length and increments the length, and the buffer grows if it is full,
+    /// to an unknown capacity that is at least the new length
+    /// The item is only tracked if the old length is a constant, and the items at unknown indices are forgotten
+    /// Returns false if the vector bounds checker is disabled
+    fn handle_vec_push(&mut self) -> bool {
+        let vec_path = match self.get_vec_path() {
+            Some(vec_path) if self.actual_args.len() == 2 => vec_path,
+            _ => return false,
+        };
+        let length_path = Path::new_length(vec_path.clone());
+        let capacity_path = Path::new_capacity(vec_path.clone());
+        let state = &self.block_visitor.body_visitor.state;
+        if state.numerical_domain.is_bottom() {
+            return true;
+        }
+        let length = state.numerical_domain.get_interval(&length_path);
+        self.forget_vec_items(&vec_path, &length.low);
+        if let (Bound::Int(low), Bound::Int(high)) = (&length.low, &length.high) {
+            if low == high {
+                let index = Rc::new(ConstantValue::Int(low.clone()).into());
+                let item = self.actual_args[1].1.clone();
+                self.block_visitor
+                    .body_visitor
+                    .state
+                    .update_value_at(Path::new_index(vec_path, index), item);
+            }
+        }
+        let numerical_domain = &mut self.block_visitor.body_visitor.state.numerical_domain;
+
+        // `length <= capacity - 1`, the item is added to the buffer
+        let mut has_room = numerical_domain.clone();
+        let mut not_full = LinearConstraintSystem::default();
+        not_full.add(LinearConstraint::LessEq(
+            LinearExpression::default() + length_path.clone() - capacity_path.clone()
+                + Integer::from(1),
+        ));
+        has_room.add_constraints(not_full);
+
+        // `length >= capacity`, the buffer grows
+        let mut grows = numerical_domain.clone();
+        let mut full = LinearConstraintSystem::default();
+        full.add(LinearConstraint::LessEq(
+            LinearExpression::default() + capacity_path.clone() - length_path.clone(),
+        ));
+        grows.add_constraints(full);
+        grows.forget(&capacity_path);
+
+        for state in [&mut has_room, &mut grows] {
+            state.apply_bin_op_place_const(
+                ApronOperation::Add,
+                &length_path,
+                &Integer::from(1),
+                &length_path,
+            );
+        }
+        let mut holds_items = LinearConstraintSystem::default();
+        holds_items.add(LinearConstraint::LessEq(
+            LinearExpression::default() + length_path - capacity_path,
+        ));
+        grows.add_constraints(holds_items);
+        *numerical_domain = has_room.join(&grows);
+        true
+    }
+
+    /// `Vec::pop` returns `None` if the vector is empty, otherwise it decrements the length and returns `Some` of the
+    /// last item, which is not tracked
+    /// Returns false if the vector bounds checker is disabled
+    fn handle_vec_pop(&mut self) -> bool {
+        let vec_path = match (self.get_vec_path(), self.destination) {
+            (Some(vec_path), Some(_)) => vec_path,
+            _ => return false,
+        };
+        let discriminants = (
+            self.get_variant_discriminant(LangItem::OptionSome),
+            self.get_variant_discriminant(LangItem::OptionNone),
+        );
+        let (some_discriminant, none_discriminant) = match discriminants {
+            (Some(some), Some(none)) => (some, none),
+            _ => return false,
+        };
+        let option_path = self
+            .block_visitor
+            .get_path_for_place(&self.destination.unwrap());
+        self.forget_destination();
+        let discriminant_path =
+            Path::new_discriminant(option_path).refine_paths(self.block_visitor.state());
+        let length_path = Path::new_length(vec_path);
+        let numerical_domain = &mut self.block_visitor.body_visitor.state.numerical_domain;
+        if numerical_domain.is_bottom() {
+            return true;
+        }
+
+        // `length >= 1`, the last item is removed
+        let mut not_empty = numerical_domain.clone();
+        let mut has_item = LinearConstraintSystem::default();
+        has_item.add(LinearConstraint::LessEq(
+            LinearExpression::default() + Integer::from(1) - length_path.clone(),
+        ));
+        not_empty.add_constraints(has_item);
+        not_empty.apply_bin_op_place_const(
+            ApronOperation::Sub,
+            &length_path,
+            &Integer::from(1),
+            &length_path,
+        );
+        not_empty.assign_int(discriminant_path.clone(), some_discriminant);
+
+        // `length <= 0`
+        let mut empty = numerical_domain.clone();
+        let mut no_item = LinearConstraintSystem::default();
+        no_item.add(LinearConstraint::LessEq(
+            LinearExpression::default() + length_path,
+        ));
+        empty.add_constraints(no_item);
+        empty.assign_int(discriminant_path, none_discriminant);
+
+        *numerical_domain = not_empty.join(&empty);
+        true
+    }
+
+    /// `Vec::len` and `Vec::capacity` return the tracked length and capacity
+    /// Returns false if the vector bounds checker is disabled
+    fn handle_vec_len(&mut self) -> bool {
+        let vec_path = match (self.get_vec_path(), self.destination) {
+            (Some(vec_path), Some(_)) => vec_path,
+            _ => return false,
+        };
+        let source_path = if self.callee_known_name == KnownNames::VecLen {
+            Path::new_length(vec_path)
+        } else {
+            Path::new_capacity(vec_path)
+        };
+        let result = self
+            .block_visitor
+            .get_path_for_place(&self.destination.unwrap());
+        self.forget_destination();
+        let numerical_domain = &mut self.block_visitor.body_visitor.state.numerical_domain;
+        if numerical_domain.contains(&source_path) {
+            numerical_domain.assign_var(result, source_path);
+        }
+        true
+    }
+
+    /// `Vec::set_len(length)` sets the length, which must be at most the capacity, as recorded for the vector bounds
+    /// checker
+    /// Returns false if the vector bounds checker is disabled
+    fn handle_vec_set_len(&mut self) -> bool {
+        let vec_path = match self.get_vec_path() {
+            Some(vec_path) if self.actual_args.len() == 2 => vec_path,
+            _ => return false,
+        };
+        let capacity_path = Path::new_capacity(vec_path.clone());
+        let length = self.actual_args[1].clone();
+        self.record_vec_access(VecOperation::SetLen, &length, &capacity_path);
+        self.block_visitor
+            .body_visitor
+            .state
+            .update_value_at(Path::new_length(vec_path), length.1);
+        true
+    }
+
+    /// `Vec::deref` returns a slice of the items of the vector, whose metadata is the length of the vector
+    /// The slice refers to the vector, so that the items are indexed in the same way
+    /// Returns false if the vector bounds checker is disabled
+    fn handle_vec_deref(&mut self) -> bool {
+        let vec_path = match (self.get_vec_path(), self.destination) {
+            (Some(vec_path), Some(_)) => vec_path,
+            _ => return false,
+        };
+        let length_path = Path::new_length(vec_path);
+        let slice_path = self
+            .block_visitor
+            .get_path_for_place(&self.destination.unwrap());
+        self.forget_destination();
+        let vec_reference = self.actual_args[0].1.clone();
+        let state = &mut self.block_visitor.body_visitor.state;
+        state.update_value_at(slice_path.clone(), vec_reference);
+        if state.numerical_domain.contains(&length_path) {
+            state
+                .numerical_domain
+                .assign_var(Path::new_fat_pointer_meta(slice_path), length_path);
+        }
+        true
+    }
+
+    /// `get(index)` returns `Some` of a reference to the item if `index < len`, otherwise `None`, and
+    /// `get_unchecked(index)` returns a reference to the item, which is recorded for the vector bounds checker
+    /// The length of the slice is its metadata, see `handle_vec_deref`
+    /// Returns false if the vector bounds checker is disabled, or the index is not an integer, e.g., a range
+    fn handle_slice_get(&mut self) -> bool {
+        if !self
+            .block_visitor
+            .body_visitor
+            .context
+            .analysis_options
+            .check_vec_bounds
+            || self.actual_args.len() != 2
+            || !self.actual_argument_types[1].is_integral()
+            || self.destination.is_none()
+        {
+            return false;
+        }
+        let slice_path = self.actual_args[0].0.clone();
+        let length_path = Path::new_fat_pointer_meta(slice_path.clone());
+        let index = self.actual_args[1].clone();
+        let result = self
+            .block_visitor
+            .get_path_for_place(&self.destination.unwrap());
+        self.forget_destination();
+        let item_path = Path::new_index(
+            Path::new_deref(slice_path).refine_paths(self.block_visitor.state()),
+            index.1.clone(),
+        );
+        let item_reference = SymbolicValue::make_reference(item_path);
+        if self.callee_known_name == KnownNames::SliceGetUnchecked {
+            self.record_vec_access(VecOperation::GetUnchecked, &index, &length_path);
+            self.block_visitor
+                .body_visitor
+                .state
+                .update_value_at(result, item_reference);
+            return true;
+        }
+
+        let discriminants = (
+            self.get_variant_discriminant(LangItem::OptionSome),
+            self.get_variant_discriminant(LangItem::OptionNone),
+        );
+        let (some_discriminant, none_discriminant) = match discriminants {
+            (Some(some), Some(none)) => (some, none),
+            _ => return true,
+        };
+        let discriminant_path =
+            Path::new_discriminant(result.clone()).refine_paths(self.block_visitor.state());
+        let index_expression = self.get_linear_expression(&index);
+        let state = &mut self.block_visitor.body_visitor.state;
+        state.update_value_at(Path::new_field(result, 0), item_reference);
+        let numerical_domain = &mut state.numerical_domain;
+        let index_expression = match index_expression {
+            Some(index_expression) if numerical_domain.contains(&length_path) => index_expression,
+            _ => {
+                numerical_domain.forget(&discriminant_path);
+                return true;
+            }
+        };
+        if numerical_domain.is_bottom() {
+            return true;
+        }
+
+        // `index <= length - 1`
+        let mut in_bounds = numerical_domain.clone();
+        let mut less = LinearConstraintSystem::default();
+        less.add(LinearConstraint::LessEq(
+            index_expression.clone() - length_path.clone() + Integer::from(1),
+        ));
+        in_bounds.add_constraints(less);
+        in_bounds.assign_int(discriminant_path.clone(), some_discriminant);
+
+        // `index >= length`
+        let mut out_of_bounds = numerical_domain.clone();
+        let mut not_less = LinearConstraintSystem::default();
+        not_less.add(LinearConstraint::LessEq(
+            LinearExpression::default() + length_path - index_expression,
+        ));
+        out_of_bounds.add_constraints(not_less);
+        out_of_bounds.assign_int(discriminant_path, none_discriminant);
+
+        *numerical_domain = in_bounds.join(&out_of_bounds);
+        true
+    }
+
+    /// `get_unchecked(start..end)` on a slice or a `str` returns a reference to the subslice, which is recorded for
+    /// the vector bounds checker, since `end` must not exceed the length of the slice or the string, i.e., the
+    /// metadata of the reference, see `handle_vec_deref`
+    /// The result is unknown, and false is returned if the vector bounds checker is disabled
+    fn handle_get_unchecked_range(&mut self) -> bool {
+        if !self
+            .block_visitor
+            .body_visitor
+            .context
+            .analysis_options
+            .check_vec_bounds
+        {
+            return false;
+        }
+        let length_path = Path::new_fat_pointer_meta(self.actual_args[0].0.clone());
+        let end_path = Path::new_field(self.actual_args[1].0.clone(), 1)
+            .refine_paths(self.block_visitor.state());
+        let end = self.get_value_at(&end_path);
+        self.record_vec_access(VecOperation::GetUncheckedRange, &end, &length_path);
+        self.forget_destination();
+        true
+    }
+
+    /// `&s[range]` on a `str` returns the substring between the start and the end of the range, and
+    /// `s.split_at(mid)` returns the substrings before and after `mid`, whose lengths are the metadata of the
+    /// results, i.e., `end - start`, or `mid` and `len - mid`, where `len` is the metadata of `s`
+    /// They are recorded for the string index checker, together with whether `start <= end <= len` or `mid <= len`
+    /// holds in the current state
+    /// Returns false if the string index checker is disabled, or the index is not an integer or a range of integers
+    fn handle_str_index(&mut self) -> bool {
+        if !self
+            .block_visitor
+            .body_visitor
+            .context
+            .analysis_options
+            .check_str_index
+            || self.actual_args.len() != 2
+            || self.destination.is_none()
+        {
+            return false;
+        }
+        let is_split = self.callee_known_name == KnownNames::StrSplitAt;
+        let length_path = Path::new_fat_pointer_meta(self.actual_args[0].0.clone());
+        let length = self.get_value_at(&length_path);
+        let (start, end) = if is_split {
+            if !self.actual_argument_types[1].is_integral() {
+                return false;
+            }
+            (self.actual_args[1].clone(), self.actual_args[1].clone())
+        } else {
+            let range_path = self.actual_args[1].0.clone();
+            match self.get_range_bounds(&range_path, self.actual_argument_types[1], &length) {
+                Some(bounds) => bounds,
+                None => return false,
+            }
+        };
+
+        let operation = if is_split {
+            StrOperation::SplitAt {
+                mid: self.get_value_interval(&end),
+            }
+        } else {
+            StrOperation::Slice {
+                start: self.get_value_interval(&start),
+                end: self.get_value_interval(&end),
+            }
+        };
+        let index = StrIndex {
+            span: self.block_visitor.body_visitor.current_span,
+            operation,
+            length: self.get_value_interval(&length),
+            in_bounds: self.is_range_in_bounds(&start, &end, &length),
+        };
+        let body_visitor = &mut self.block_visitor.body_visitor;
+        body_visitor
+            .str_indices
+            .insert(body_visitor.current_location, index);
+
+        let result = self
+            .block_visitor
+            .get_path_for_place(&self.destination.unwrap());
+        self.forget_destination();
+        if is_split {
+            let zero_value = self.block_visitor.body_visitor.get_u128_const_val(0);
+            let zero = (Path::new_alias(zero_value.clone()), zero_value);
+            let before = Path::new_fat_pointer_meta(Path::new_field(result.clone(), 0));
+            let after = Path::new_fat_pointer_meta(Path::new_field(result, 1));
+            self.assign_operation(ApronOperation::Add, &end, &zero, &before);
+            self.assign_operation(ApronOperation::Sub, &length, &end, &after);
+        } else {
+            let substring_length = Path::new_fat_pointer_meta(result);
+            self.assign_operation(ApronOperation::Sub, &end, &start, &substring_length);
+        }
+        true
+    }
+
+    /// The start and the end of a range of integers at `range_path`, where the start of `..end` is 0 and the end
+    /// of `start..` is `length`
+    /// Returns None if `range_ty` is not `Range`, `RangeFrom`, `RangeTo` or `RangeFull`
+    fn get_range_bounds(
+        &mut self,
+        range_path: &Rc<Path>,
+        range_ty: Ty<'tcx>,
+        length: &(Rc<Path>, Rc<SymbolicValue>),
+    ) -> Option<((Rc<Path>, Rc<SymbolicValue>), (Rc<Path>, Rc<SymbolicValue>))> {
+        let range_def_id = match range_ty.kind() {
+            TyKind::Adt(def, _) => Some(def.did()),
+            _ => return None,
+        };
+        let zero_value = self.block_visitor.body_visitor.get_u128_const_val(0);
+        let zero = (Path::new_alias(zero_value.clone()), zero_value);
+        let field = |index: usize| {
+            let field_path =
+                Path::new_field(range_path.clone(), index).refine_paths(self.block_visitor.state());
+            self.get_value_at(&field_path)
+        };
+        let lang_items = self.block_visitor.body_visitor.context.tcx.lang_items();
+        if lang_items.get(LangItem::Range) == range_def_id {
+            Some((field(0), field(1)))
+        } else if lang_items.get(LangItem::RangeFrom) == range_def_id {
+            Some((field(0), length.clone()))
+        } else if lang_items.get(LangItem::RangeTo) == range_def_id {
+            Some((zero, field(0)))
+        } else if lang_items.get(LangItem::RangeFull) == range_def_id {
+            Some((zero, length.clone()))
+        } else {
+            None
+        }
+    }
+
+    /// Whether `start <= end <= bound` holds in the current state, None if it may not hold, or if any of them is
+    /// not tracked by the numerical domain
+    fn is_range_in_bounds(
+        &self,
+        start: &(Rc<Path>, Rc<SymbolicValue>),
+        end: &(Rc<Path>, Rc<SymbolicValue>),
+        bound: &(Rc<Path>, Rc<SymbolicValue>),
+    ) -> Option<bool> {
+        let numerical_domain = &self.block_visitor.body_visitor.state.numerical_domain;
+        if numerical_domain.is_bottom() {
+            return Some(true);
+        }
+        let expressions = (
+            self.get_linear_expression(start),
+            self.get_linear_expression(end),
+            self.get_linear_expression(bound),
+        );
+        let (start, end, bound) = match expressions {
+            (Some(start), Some(end), Some(bound)) => (start, end, bound),
+            _ => return None,
+        };
+        // Whether `lhs >= rhs + 1` may hold
+        let may_exceed = |lhs: &LinearExpression, rhs: &LinearExpression| {
+            let mut exceeds = numerical_domain.clone();
+            let mut above = LinearConstraintSystem::default();
+            above.add(LinearConstraint::LessEq(
+                rhs.clone() - lhs.clone() + Integer::from(1),
+            ));
+            exceeds.add_constraints(above);
+            !exceeds.is_bottom()
+        };
+        if !may_exceed(&start, &end) && !may_exceed(&end, &bound) {
+            return Some(true);
+        }
+        // `start <= end` and `end <= bound`
+        let mut within = numerical_domain.clone();
+        let mut below = LinearConstraintSystem::default();
+        below.add(LinearConstraint::LessEq(start - end.clone()));
+        below.add(LinearConstraint::LessEq(end - bound));
+        within.add_constraints(below);
+        if within.is_bottom() {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Records a call of `get_unchecked` or `Vec::set_len` for the vector bounds checker, together with whether
+    /// `value < bound` holds in the current state for an index, or `value <= bound` for the end of a range or a
+    /// new length
+    fn record_vec_access(
+        &mut self,
+        operation: VecOperation,
+        value: &(Rc<Path>, Rc<SymbolicValue>),
+        bound_path: &Rc<Path>,
+    ) {
+        let value_expression = self.get_linear_expression(value);
+        let body_visitor = &mut self.block_visitor.body_visitor;
+        let numerical_domain = &body_visitor.state.numerical_domain;
+        // The margin between the largest value in bounds and the bound
+        let margin = Integer::from(if operation == VecOperation::GetUnchecked {
+            1
+        } else {
+            0
+        });
+        let in_bounds = match value_expression {
+            _ if numerical_domain.is_bottom() => Some(true),
+            Some(value_expression) if numerical_domain.contains(bound_path) => {
+                // `value >= bound + 1 - margin`
+                let mut exceeds = numerical_domain.clone();
+                let mut above = LinearConstraintSystem::default();
+                above.add(LinearConstraint::LessEq(
+                    LinearExpression::default() + bound_path.clone() - value_expression.clone()
+                        + Integer::from(1)
+                        - margin.clone(),
+                ));
+                exceeds.add_constraints(above);
+                // `value <= bound - margin`
+                let mut within = numerical_domain.clone();
+                let mut below = LinearConstraintSystem::default();
+                below.add(LinearConstraint::LessEq(
+                    value_expression - bound_path.clone() + margin,
+                ));
+                within.add_constraints(below);
+                if exceeds.is_bottom() {
+                    Some(true)
+                } else if within.is_bottom() {
+                    Some(false)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
+        let value_interval = match &value.1.expression {
+            Expression::CompileTimeConstant(ConstantValue::Int(value)) => {
+                Interval::new(Bound::from(value.clone()), Bound::from(value.clone()))
+            }
+            _ => numerical_domain.get_interval(&value.0),
+        };
+        let access = VecAccess {
+            span: body_visitor.current_span,
+            operation,
+            value: value_interval,
+            bound: numerical_domain.get_interval(bound_path),
+            in_bounds,
+        };
+        body_visitor
+            .vec_accesses
+            .insert(body_visitor.current_location, access);
+    }
+
+    /// Records the call of `offset` on a raw pointer for the raw offset overflow checker, together with the range of
+    /// the number of elements in the current state
+    fn record_raw_offset(&mut self, pointee_ty: Ty<'tcx>, elem_size: u64) {
+        let body_visitor = &mut self.block_visitor.body_visitor;
+        let analysis_options = &body_visitor.context.analysis_options;
+        if !analysis_options.check_raw_offset_overflow {
+            return;
+        }
+        let (count_path, count_val) = &self.actual_args[1];
+        let count = match &count_val.expression {
+            Expression::CompileTimeConstant(ConstantValue::Int(count)) => {
+                Interval::new(Bound::from(count.clone()), Bound::from(count.clone()))
+            }
+            _ => body_visitor.state.numerical_domain.get_interval(count_path),
+        };
+        let offset = RawOffset {
+            span: body_visitor.current_span,
+            pointee_ty,
+            count,
+            elem_size,
+        };
+        body_visitor
+            .raw_offsets
+            .insert(body_visitor.current_location, offset);
+    }
+
+    /// The linear expression of an integer argument, if it is a constant or it is tracked in the numerical domain
+    fn get_linear_expression(
+        &self,
+        argument: &(Rc<Path>, Rc<SymbolicValue>),
+    ) -> Option<LinearExpression> {
+        match &argument.1.expression {
+            Expression::CompileTimeConstant(ConstantValue::Int(value)) => {
+                Some(LinearExpression::default() + value.clone())
+            }
+            _ if self
+                .block_visitor
+                .state()
+                .numerical_domain
+                .contains(&argument.0) =>
+            {
+                Some(LinearExpression::default() + argument.0.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// The path and the value at it, in the form of an argument, where the value is top if it is unknown
+    fn get_value_at(&self, path: &Rc<Path>) -> (Rc<Path>, Rc<SymbolicValue>) {
+        let value = self
+            .block_visitor
+            .state()
+            .value_at(path)
+            .unwrap_or_else(|| Rc::new(symbolic_value::TOP));
+        (path.clone(), value)
+    }
+
+    /// Removes the values of the destination and its fields, so that the result of a call that is not modeled is
+    /// unknown
+    fn forget_destination(&mut self) {
+        let destination_path = match self.destination {
+            Some(destination) => self.block_visitor.get_path_for_place(&destination),
+            None => return,
+        };
+        let state = &mut self.block_visitor.body_visitor.state;
+        let stale_paths: Vec<Rc<Path>> = state
+            .get_paths_iter()
+            .into_iter()
+            .filter(|path| *path == destination_path || path.is_rooted_by(&destination_path))
+            .collect();
+        state.remove_all(&stale_paths);
+    }
+
+    /// `_mm_set_epi32` and the other intrinsics that build a vector of 32-bit integers are modeled lane by lane, the
+    /// value of lane `i` is at the index path `vector[i]`, so that each lane has its own interval
+    /// `_mm_set_epi32` takes the lanes from the highest to the lowest, `_mm_setr_epi32` from the lowest to the
+    /// highest, and `_mm_set1_epi32` broadcasts its argument to every lane
+    fn handle_simd_set(&mut self) -> bool {
+        let destination = match self.destination {
+            Some(destination) => destination,
+            None => return false,
+        };
+        let vector_path = self.block_visitor.get_path_for_place(&destination);
+        let body_visitor = &mut self.block_visitor.body_visitor;
+        let vector_ty = body_visitor
+            .type_visitor
+            .get_rustc_place_type(&destination, body_visitor.current_span);
+        let lane_count = match body_visitor.type_visitor.get_type_size(vector_ty) {
+            Some(vector_size) => (vector_size / 4) as usize,
+            None => return false,
+        };
+        let arguments = self.actual_args.iter().map(|(_, value)| value.clone());
+        let lanes: Vec<Rc<SymbolicValue>> = match self.callee_known_name {
+            KnownNames::SimdSetEpi32 => arguments.rev().collect(),
+            KnownNames::SimdSetrEpi32 => arguments.collect(),
+            _ => arguments.cycle().take(lane_count).collect(),
+        };
+        if lanes.len() != lane_count {
+            return false;
+        }
+        self.forget_destination();
+        for (lane, value) in lanes.into_iter().enumerate() {
+            self.block_visitor
+                .body_visitor
+                .state
+                .update_value_at(Self::get_lane_path(&vector_path, lane), value);
+        }
+        true
+    }
+
+    /// `_mm_i32gather_epi32(pointer, offsets)` and the other gathers with 32-bit offsets load lane `i` of the result
+    /// from `pointer + offsets[i] * SCALE` bytes, where `SCALE` is a const generic argument
+    /// The load of each lane is recorded for the raw pointer bounds checker, with the offset of the pointer plus the
+    /// interval of the lane of `offsets`, see `handle_simd_set`. The lanes of the result are not tracked
+    /// Returns false if the raw pointer bounds checker is disabled, and the call is analyzed as a normal call
+    fn handle_simd_gather(&mut self) -> bool {
+        if !self
+            .block_visitor
+            .body_visitor
+            .context
+            .analysis_options
+            .check_raw_ptr_bounds
+            || self.actual_args.len() != 2
+        {
+            return false;
+        }
+        let scale = self
+            .callee_generic_arguments
+            .and_then(|generic_arguments| generic_arguments.consts().next())
+            .and_then(|scale| scale.try_to_scalar())
+            .and_then(|scale| scale.try_to_int().ok())
+            .and_then(|scale| scale.try_to_i32().ok());
+        let pointer_path = self.actual_args[0].0.clone();
+        let offsets_path = self.actual_args[1].0.clone();
+        let element_ty = type_visitor::get_target_type(self.actual_argument_types[0]);
+        let is_tracked = self
+            .block_visitor
+            .state()
+            .numerical_domain
+            .contains(&pointer_path);
+        let accesses = match (scale, self.destination) {
+            (Some(scale), Some(destination)) if is_tracked => {
+                let body_visitor = &mut self.block_visitor.body_visitor;
+                let span = body_visitor.current_span;
+                let access_size = body_visitor.type_visitor.get_type_size(element_ty);
+                let result_ty = body_visitor
+                    .type_visitor
+                    .get_rustc_place_type(&destination, span);
+                let result_size = body_visitor.type_visitor.get_type_size(result_ty);
+                let (access_size, lane_count) = match (access_size, result_size) {
+                    (Some(access_size), Some(result_size)) => (
+                        access_size,
+                        result_size.checked_div(access_size).unwrap_or(0) as usize,
+                    ),
+                    // No lane is known if the sizes are unknown
+                    _ => (0, 0),
+                };
+                let numerical_domain = &body_visitor.state.numerical_domain;
+                let pointer_offset = numerical_domain.get_interval(&pointer_path);
+                let scale = Bound::from(Integer::from(scale));
+                let scale = Interval::new(scale.clone(), scale);
+                let allocation_size = self.block_visitor.get_allocation_size(&pointer_path);
+                let numerical_domain = &self.block_visitor.state().numerical_domain;
+                (0..lane_count)
+                    .map(|lane| {
+                        let lane_path = Self::get_lane_path(&offsets_path, lane);
+                        let lane_offset = if numerical_domain.contains(&lane_path) {
+                            numerical_domain.get_interval(&lane_path)
+                        } else {
+                            Interval::top()
+                        };
+                        RawPointerAccess {
+                            span,
+                            offset: pointer_offset.clone() + lane_offset * scale.clone(),
+                            access_size,
+                            allocation_size: allocation_size.clone(),
+                            lane: Some(lane),
+                        }
+                    })
+                    .collect()
+            }
+            _ => {
+                debug!("The allocation or the scale of the gather is unknown");
+                vec![]
+            }
+        };
+        let body_visitor = &mut self.block_visitor.body_visitor;
+        body_visitor
+            .raw_pointer_accesses
+            .insert(body_visitor.current_location, accesses);
+        self.forget_destination();
+        true
+    }
+
+    /// The index path of a lane of a SIMD vector
+    fn get_lane_path(vector_path: &Rc<Path>, lane: usize) -> Rc<Path> {
+        let lane = Rc::new(ConstantValue::Int(Integer::from(lane)).into());
+        Path::new_index(vector_path.clone(), lane)
+    }
+
+    /// The `?` operator calls `Try::branch` on an `Option` or a `Result`, which returns `ControlFlow::Continue` of the
+    /// value of the success variant, or `ControlFlow::Break` of the residual, i.e., the `None` or `Err` that is
+    /// returned early
+    /// The state after the call is the join of the state where the argument is `success_variant` and the result is
+    /// `Continue`, and the state where it is `failure_variant` and the result is `Break`. The branch of the match on
+    /// the result that is not possible becomes bottom, e.g., the code after `?` is unreachable if the argument is
+    /// known to be `Err`, and the early return is unreachable if it is known to be `Ok`
+    /// Returns false if the discriminants of the variants are unknown, and the call is analyzed as a normal call
+    fn handle_try_branch(&mut self, success_variant: LangItem, failure_variant: LangItem) -> bool {
+        assert!(self.actual_args.len() == 1);
+        if self.destination.is_none() {
+            return false;
+        }
+        let discriminants = (
+            self.get_variant_discriminant(success_variant),
+            self.get_variant_discriminant(failure_variant),
+            self.get_variant_discriminant(LangItem::ControlFlowContinue),
+            self.get_variant_discriminant(LangItem::ControlFlowBreak),
+        );
+        let (success_discriminant, failure_discriminant, continue_discriminant, break_discriminant) =
+            match discriminants {
+                (Some(success), Some(failure), Some(continue_), Some(break_)) => {
+                    (success, failure, continue_, break_)
+                }
+                _ => return false,
+            };
+        let destination = self.destination.unwrap();
+        let control_flow_path = self.block_visitor.get_path_for_place(&destination);
+        let enum_path = self.actual_args[0].0.clone();
+        let discriminant_path =
+            Path::new_discriminant(enum_path.clone()).refine_paths(self.block_visitor.state());
+        let control_flow_discriminant_path = Path::new_discriminant(control_flow_path.clone())
+            .refine_paths(self.block_visitor.state());
+
+        // The fields of the variants are not distinguished, so the value of `Continue` is the field of the argument,
+        // which is also the value of the residual of `Break`
+        let value_ty = match self.actual_argument_types[0].kind() {
+            TyKind::Adt(_, generic_args) => generic_args.type_at(0),
+            _ => return false,
+        };
+        let value_path = Path::new_field(enum_path, 0).refine_paths(self.block_visitor.state());
+        let continue_value_path =
+            Path::new_field(control_flow_path, 0).refine_paths(self.block_visitor.state());
+        self.block_visitor
+            .copy_or_move_elements(continue_value_path, value_path, value_ty, true);
+
+        let numerical_domain = &mut self.block_visitor.body_visitor.state.numerical_domain;
+        if numerical_domain.is_bottom() {
+            return true;
+        }
+        let variant_state = |discriminant: Integer, control_flow_discriminant: Integer| {
+            let mut state = numerical_domain.clone();
+            let mut is_variant = LinearConstraintSystem::default();
+            is_variant.add(LinearConstraint::Equality(
+                LinearExpression::default() + discriminant_path.clone() - discriminant,
+            ));
+            state.add_constraints(is_variant);
+            state.assign_int(
+                control_flow_discriminant_path.clone(),
+                control_flow_discriminant,
+            );
+            state
+        };
+        let continue_state = variant_state(success_discriminant, continue_discriminant);
+        let break_state = variant_state(failure_discriminant, break_discriminant);
+        *numerical_domain = continue_state.join(&break_state);
+        true
+    }
+
+    /// The early return of the `?` operator converts the residual into the return value with
+    /// `FromResidual::from_residual`, which is always `None` or `Err`, i.e., `failure_variant`
+    /// The error value of `Err` is converted by `From::from`, which is not tracked
+    /// Returns false if the discriminant of the variant is unknown, and the call is analyzed as a normal call
+    fn handle_from_residual(&mut self, failure_variant: LangItem) -> bool {
+        assert!(self.actual_args.len() == 1);
+        let failure_discriminant = match self.get_variant_discriminant(failure_variant) {
+            Some(discriminant) => discriminant,
+            None => return false,
+        };
+        let destination = match self.destination {
+            Some(destination) => destination,
+            None => return false,
+        };
+        let result = self.block_visitor.get_path_for_place(&destination);
+        let discriminant_path =
+            Path::new_discriminant(result).refine_paths(self.block_visitor.state());
+        self.block_visitor
+            .body_visitor
+            .state
+            .numerical_domain
+            .assign_int(discriminant_path, failure_discriminant);
+        true
+    }
+
+    /// `checked_add`, `checked_sub` and `checked_mul` of the integer types return `Some` of the result if it is in
+    /// the range of the type, and `None` otherwise
+    /// The state after the call is the join of the state where the option is `Some` and the result is in the range,
+    /// and the state where it is `None` and the result overflows. A relational domain keeps the constraints of each
+    /// variant related to the discriminant, which are recovered when the option is matched or unwrapped
+    /// Returns false if the arguments are not integers, and the call is analyzed as a normal call
+    fn handle_checked_arithmetic(&mut self, op: ApronOperation) -> bool {
+        assert!(self.actual_args.len() == 2);
+        let ty = self.actual_argument_types[0];
+        if !ty.is_integral() || self.destination.is_none() {
+            return false;
+        }
+        let (some_discriminant, none_discriminant) = match (
+            self.get_variant_discriminant(LangItem::OptionSome),
+            self.get_variant_discriminant(LangItem::OptionNone),
+        ) {
+            (Some(some_discriminant), Some(none_discriminant)) => {
+                (some_discriminant, none_discriminant)
+            }
+            _ => return false,
+        };
+        let option_path = self
+            .block_visitor
+            .get_path_for_place(&self.destination.unwrap());
+        let value_path =
+            Path::new_field(option_path.clone(), 0).refine_paths(self.block_visitor.state());
+        let discriminant_path =
+            Path::new_discriminant(option_path).refine_paths(self.block_visitor.state());
+        let exp_type: ExpressionType = ty.kind().into();
+        let (min, max) = (exp_type.min_value_int(), exp_type.max_value_int());
+
+        // The mathematical result, which is the value of `Some` if it does not overflow
+        self.assign_argument_operation(op, &value_path);
+        let numerical_domain = &mut self.block_visitor.body_visitor.state.numerical_domain;
+        if numerical_domain.is_bottom() {
+            return true;
+        }
+
+        // `min <= value <= max`
+        let mut in_range = LinearConstraintSystem::default();
+        in_range.add(LinearConstraint::LessEq(
+            LinearExpression::default() + value_path.clone() - max.clone(),
+        ));
+        in_range.add(LinearConstraint::LessEq(
+            LinearExpression::default() + min.clone() - value_path.clone(),
+        ));
+        let mut some_state = numerical_domain.clone();
+        some_state.add_constraints(in_range);
+
+        // The overflow condition is a disjunction, which is only added if the result may overflow on one side
+        let mut none_state = numerical_domain.clone();
+        let value = none_state.get_interval(&value_path);
+        let mut overflow = LinearConstraintSystem::default();
+        if value.low >= Bound::Int(min.clone()) {
+            // `value >= max + 1`
+            overflow.add(LinearConstraint::LessEq(
+                LinearExpression::default() + max + Integer::from(1) - value_path.clone(),
+            ));
+        } else if value.high <= Bound::Int(max) {
+            // `value <= min - 1`
+            overflow.add(LinearConstraint::LessEq(
+                LinearExpression::default() + value_path.clone() - min + Integer::from(1),
+            ));
+        }
+        none_state.add_constraints(overflow);
+        // The value of `None` is never read, it is set to the range of the value of `Some`, so that the join keeps
+        // the range of `Some` in a non-relational domain
+        if !some_state.is_bottom() && !none_state.is_bottom() {
+            none_state.set_interval(&value_path, some_state.get_interval(&value_path));
+        }
+
+        some_state.assign_int(discriminant_path.clone(), some_discriminant);
+        none_state.assign_int(discriminant_path, none_discriminant);
+        *numerical_domain = some_state.join(&none_state);
+        true
+    }
+
+    /// `saturating_add`, `saturating_sub` and `saturating_mul` of the integer types return the mathematical result
+    /// clamped to the range of the type
+    /// The result keeps its relation with the arguments if it never saturates, otherwise its range is clamped
+    /// Returns false if the arguments are not integers, and the call is analyzed as a normal call
+    fn handle_saturating_arithmetic(&mut self, op: ApronOperation) -> bool {
+        assert!(self.actual_args.len() == 2);
+        let ty = self.actual_argument_types[0];
+        if !ty.is_integral() || self.destination.is_none() {
+            return false;
+        }
+        let result = self
+            .block_visitor
+            .get_path_for_place(&self.destination.unwrap());
+        let exp_type: ExpressionType = ty.kind().into();
+        let min = Bound::Int(exp_type.min_value_int());
+        let max = Bound::Int(exp_type.max_value_int());
+
+        self.assign_argument_operation(op, &result);
+        let numerical_domain = &mut self.block_visitor.body_visitor.state.numerical_domain;
+        let value = numerical_domain.get_interval(&result);
+        if value.is_bottom() || (value.low >= min && value.high <= max) {
+            return true;
+        }
+        let clamp = |bound: Bound| bound.max(min.clone()).min(max.clone());
+        numerical_domain.set_interval(&result, Interval::new(clamp(value.low), clamp(value.high)));
+        true
+    }
+
+    /// Assigns `lhs op rhs` to `result`, where `lhs` and `rhs` are the two integer arguments of the call
+    fn assign_argument_operation(&mut self, op: ApronOperation, result: &Rc<Path>) {
+        let (lhs, rhs) = (self.actual_args[0].clone(), self.actual_args[1].clone());
+        self.assign_operation(op, &lhs, &rhs, result);
+    }
+
+    /// Assigns `lhs op rhs` to `result`, where `lhs` and `rhs` are integer values at their paths
+    fn assign_operation(
+        &mut self,
+        op: ApronOperation,
+        lhs: &(Rc<Path>, Rc<SymbolicValue>),
+        rhs: &(Rc<Path>, Rc<SymbolicValue>),
+        result: &Rc<Path>,
+    ) {
+        let constant_value = |value: &Rc<SymbolicValue>| match &value.expression {
+            Expression::CompileTimeConstant(constant) => constant.try_get_integer(),
+            _ => None,
+        };
+        let (lhs_constant, rhs_constant) = (constant_value(&lhs.1), constant_value(&rhs.1));
+        let (lhs, rhs) = (&lhs.0, &rhs.0);
+        let numerical_domain = &mut self.block_visitor.body_visitor.state.numerical_domain;
+        match (lhs_constant, rhs_constant) {
+            (Some(lhs), Some(rhs)) => {
+                let value = match op {
+                    ApronOperation::Add => lhs + rhs,
+                    ApronOperation::Sub => lhs - rhs,
+                    ApronOperation::Mul => lhs * rhs,
+                    _ => unreachable!(),
+                };
+                numerical_domain.assign_int(result.clone(), value);
+            }
+            (Some(lhs), None) => numerical_domain.apply_bin_op_const_place(op, &lhs, rhs, result),
+            (None, Some(rhs)) => numerical_domain.apply_bin_op_place_const(op, lhs, &rhs, result),
+            (None, None) => numerical_domain.apply_bin_op_place_place(op, lhs, rhs, result),
+        }
+    }
+
+    /// The discriminant of a variant that is a lang item, e.g., `OptionSome`, `ResultOk` or `ControlFlowBreak`
+    fn get_variant_discriminant(&self, variant: LangItem) -> Option<Integer> {
+        let tcx = self.block_visitor.body_visitor.context.tcx;
+        let variant_def_id = tcx.lang_items().get(variant)?;
+        let enum_def = tcx.adt_def(tcx.parent(variant_def_id));
+        let variant_index = enum_def.variant_index_with_id(variant_def_id);
+        Some(Integer::from(
+            enum_def.discriminant_for_variant(tcx, variant_index).val,
+        ))
+    }
+
+    /// True if `ty` is `Range<T>` where `T` is an integer type, e.g., `0..n`
+    fn is_integer_range(&self, ty: Ty<'tcx>) -> bool {
+        let tcx = self.block_visitor.body_visitor.context.tcx;
+        match ty.kind() {
+            TyKind::Adt(def, args) => {
+                tcx.lang_items().get(LangItem::Range) == Some(def.did())
+                    && args.type_at(0).is_integral()
+            }
+            _ => false,
+        }
+    }
+
+    /// The items of an iterator are summarized by the value at its `IteratorItem` path, and the number of
+    /// the remaining items is the value at its `IteratorCount` path
+    /// Returns true if the iterator at `iterator_path` is tracked by these paths, which are initialized
+    /// from the bounds of the range if the iterator is an integer range
+    fn is_tracked_iterator(&mut self, iterator_path: &Rc<Path>, iterator_ty: Ty<'tcx>) -> bool {
+        let count_path = Path::new_iterator_count(iterator_path.clone());
+        if self
+            .block_visitor
+            .body_visitor
+            .state
+            .numerical_domain
+            .contains(&count_path)
+        {
+            return true;
+        }
+        if !self.is_integer_range(iterator_ty) {
+            return false;
+        }
+        let item_path = Path::new_iterator_item(iterator_path.clone());
+        let numerical_domain = &mut self.block_visitor.body_visitor.state.numerical_domain;
+        let start = numerical_domain.get_interval(&Path::new_field(iterator_path.clone(), 0));
+        let end = numerical_domain.get_interval(&Path::new_field(iterator_path.clone(), 1));
+        let item = Interval::new(start.low.clone(), end.high.clone() - Bound::from(1i128));
+        if item.is_bottom() {
+            // The range is always empty
+            numerical_domain.forget(&item_path);
+            numerical_domain.assign_int(count_path, Integer::from(0));
+        } else {
+            numerical_domain.set_interval(&item_path, item);
+            numerical_domain.set_interval(&count_path, (end - start).meet(&non_negative()));
+        }
+        true
+    }
+
+    /// Copies the item of the iterator at `source_path`, and the paths rooted by it, to `target_item_path`
+    fn copy_iterator_item(&mut self, source_path: &Rc<Path>, target_item_path: Rc<Path>) {
+        let source_item_path = Path::new_iterator_item(source_path.clone());
+        let state = &mut self.block_visitor.body_visitor.state;
+        for path in state.get_paths_iter() {
+            if path == source_item_path {
+                state.duplicate(&path, &target_item_path);
+            } else if path.is_rooted_by(&source_item_path) {
+                let target_path = path.replace_root(&source_item_path, target_item_path.clone());
+                state.duplicate(&path, &target_path);
+            }
+        }
+    }
+
+    /// Records the arguments of a call to a C function, which are checked against its contract by `FfiChecker`
+    pub fn record_ffi_call(&mut self) {
+        let tcx = self.block_visitor.body_visitor.context.tcx;
+        if self
+            .block_visitor
+            .body_visitor
+            .context
+            .analysis_options
+            .ffi_stubs
+            .is_none()
+            || !tcx.is_foreign_item(self.callee_def_id)
+        {
+            return;
+        }
+        let arguments = (0..self.actual_args.len())
+            .map(|index| self.get_ffi_argument(index))
+            .collect();
+        let body_visitor = &mut self.block_visitor.body_visitor;
+        body_visitor.ffi_calls.insert(
+            body_visitor.current_location,
+            FfiCall {
+                span: body_visitor.current_span,
+                function: tcx.item_name(self.callee_def_id).to_string(),
+                arguments,
+            },
+        );
+    }
+
+    /// A reference is never null, and neither is a raw pointer whose offset is tracked, because it is derived from
+    /// a reference or an allocation, while the constant zero is a null pointer
+    fn get_ffi_argument(&self, index: usize) -> FfiArgument {
+        let ty = self.actual_argument_types[index];
+        let (path, value) = &self.actual_args[index];
+        if ty.is_ref() {
+            FfiArgument::Pointer(Some(false))
+        } else if ty.is_unsafe_ptr() {
+            let is_null = match &value.expression {
+                Expression::CompileTimeConstant(constant) => {
+                    constant.try_get_integer().map(|address| address == 0)
+                }
+                Expression::Reference(_) => Some(false),
+                _ if self
+                    .block_visitor
+                    .body_visitor
+                    .state
+                    .numerical_domain
+                    .contains(path) =>
+                {
+                    Some(false)
+                }
+                _ => None,
+            };
+            FfiArgument::Pointer(is_null)
+        } else if ty.is_integral() {
+            FfiArgument::Integer(self.get_argument_interval(index))
+        } else {
+            FfiArgument::Other
+        }
+    }
+
+    /// The range of an integer argument, which is either a constant or a numerical variable
+    fn get_argument_interval(&self, index: usize) -> Interval {
+        self.get_value_interval(&self.actual_args[index])
+    }
+
+    /// The range of an integer value at a path, which is either a constant or a numerical variable
+    fn get_value_interval(&self, (path, value): &(Rc<Path>, Rc<SymbolicValue>)) -> Interval {
+        if let Expression::CompileTimeConstant(constant) = &value.expression {
+            if let Some(n) = constant.try_get_integer() {
+                return Interval::new(n.clone().into(), n.into());
+            }
+        }
+        self.block_visitor
+            .body_visitor
+            .state
+            .numerical_domain
+            .get_interval(path)
+    }
+
+    /// Analyzes the closure at argument `closure_index` with the item at `item_path` as its argument, which
+    /// is passed by reference if the closure takes a reference, e.g., the predicate of `filter`
+    /// Returns the join of the states at the returns of the closure, which is bottom if it never returns,
+    /// or `None` if the argument is not a closure that can be analyzed
+    fn analyze_closure(
+        &mut self,
+        closure_index: usize,
+        item_path: &Rc<Path>,
+    ) -> Option<AbstractDomain<DomainType>> {
+        let tcx = self.block_visitor.body_visitor.context.tcx;
+        let (closure_def_id, closure_args) = match self.actual_argument_types[closure_index].kind()
+        {
+            TyKind::Closure(def_id, args) => (*def_id, *args),
+            _ => return None,
+        };
+        if !tcx.is_mir_available(closure_def_id) || self.call_stack.contains(&closure_def_id) {
+            return None;
+        }
+        let mut call_stack = self.call_stack.clone();
+        call_stack.push(closure_def_id);
+        let mut body_visitor = WtoFixPointIterator::new(
+            self.block_visitor.body_visitor.context,
+            closure_def_id,
+            self.block_visitor.state().clone(),
+            self.block_visitor.body_visitor.next_fresh_variable_offset,
+            call_stack,
+        );
+        if body_visitor.wto.get_mir().arg_count != 2 {
+            return None;
+        }
+        body_visitor.type_visitor.generic_arguments = Some(closure_args);
+        body_visitor.type_visitor.generic_argument_map = self
+            .block_visitor
+            .body_visitor
+            .type_visitor
+            .get_generic_arguments_map(closure_def_id, closure_args, &[]);
+
+        // The closure itself is passed by reference
+        let closure_path = self.actual_args[closure_index].0.clone();
+        let item_ty = body_visitor.wto.get_mir().local_decls[mir::Local::from(2usize)].ty;
+        let item_value = if item_ty.is_ref() {
+            SymbolicValue::make_reference(item_path.clone())
+        } else {
+            SymbolicValue::make_from(
+                Expression::Variable {
+                    path: item_path.clone(),
+                    var_type: item_ty.kind().into(),
+                },
+                1,
+            )
+        };
+        body_visitor.init_pre_condition(vec![
+            (
+                closure_path.clone(),
+                SymbolicValue::make_reference(closure_path),
+            ),
+            (item_path.clone(), item_value),
+        ]);
+        body_visitor.run();
+        body_visitor.run_checker();
+        self.block_visitor.body_visitor.next_fresh_variable_offset =
+            body_visitor.next_fresh_variable_offset;
+
+        Some(body_visitor.get_exit_state().unwrap_or_else(|| {
+            let mut bottom = AbstractDomain::default();
+            bottom.numerical_domain.set_to_bottom();
+            bottom
+        }))
+    }
+
+    /// `IntoIterator::into_iter` returns the iterator itself, together with the paths that track its items,
+    /// which would be lost if the call is inlined, because the callee only receives the value of the iterator
+    /// Returns false if the items of the iterator are not tracked, e.g., the argument is a collection
+    fn handle_iterator_into_iter(&mut self) -> bool {
+        assert!(self.actual_args.len() == 1);
+        let destination = match self.destination {
+            Some(destination) => destination,
+            None => return false,
+        };
+        let iterator_path = self.actual_args[0].0.clone();
+        let iterator_ty = self.actual_argument_types[0];
+        if !self.is_tracked_iterator(&iterator_path, iterator_ty) {
+            return false;
+        }
+        let result = self.block_visitor.get_path_for_place(&destination);
+        self.block_visitor
+            .copy_or_move_elements(result, iterator_path, iterator_ty, true);
+        true
+    }
+
+    /// `Iterator::next` returns `None` if the count of the remaining items may be zero, and `Some` of the item
+    /// if it may be positive, the count is then decremented
+    /// Returns false if the items of the iterator are not tracked, so that `next` is analyzed as usual
+    fn handle_iterator_next(&mut self) -> bool {
+        assert!(self.actual_args.len() == 1);
+        let destination = match self.destination {
+            Some(destination) => destination,
+            None => return false,
+        };
+        let iterator_path =
+            Path::new_deref(self.actual_args[0].0.clone()).refine_paths(self.block_visitor.state());
+        let iterator_ty = type_visitor::get_target_type(self.actual_argument_types[0]);
+        if !self.is_tracked_iterator(&iterator_path, iterator_ty) {
+            return false;
+        }
+        let (none_discriminant, some_discriminant) = match (
+            self.get_variant_discriminant(LangItem::OptionNone),
+            self.get_variant_discriminant(LangItem::OptionSome),
+        ) {
+            (Some(none), Some(some)) => (none, some),
+            _ => return false,
+        };
+        let result = self.block_visitor.get_path_for_place(&destination);
+        let body_visitor = &mut self.block_visitor.body_visitor;
+        let result_ty = body_visitor
+            .type_visitor
+            .get_rustc_place_type(&destination, body_visitor.current_span);
+        let item_ty = match result_ty.kind() {
+            TyKind::Adt(_, args) => args.type_at(0),
+            _ => return false,
+        };
+
+        let count_path = Path::new_iterator_count(iterator_path.clone());
+        let count = body_visitor
+            .state
+            .numerical_domain
+            .get_interval(&count_path);
+        let zero = Bound::from(0i128);
+        let may_be_empty = count.low <= zero;
+        let may_yield = count.high > zero;
+        let discriminant = match (may_be_empty, may_yield) {
+            (true, false) => {
+                Interval::new(none_discriminant.clone().into(), none_discriminant.into())
+            }
+            (false, true) => {
+                Interval::new(some_discriminant.clone().into(), some_discriminant.into())
+            }
+            _ => Interval::new(
+                none_discriminant
+                    .clone()
+                    .min(some_discriminant.clone())
+                    .into(),
+                none_discriminant.max(some_discriminant).into(),
+            ),
+        };
+        let discriminant_path =
+            Path::new_discriminant(result.clone()).refine_paths(&body_visitor.state);
+        body_visitor
+            .state
+            .numerical_domain
+            .set_interval(&discriminant_path, discriminant);
+        let one = Interval::new(Bound::from(1i128), Bound::from(1i128));
+        body_visitor
+            .state
+            .numerical_domain
+            .set_interval(&count_path, (count - one).meet(&non_negative()));
+        // The start of a range is not updated, so it must not be used to initialize the items again
+        if self.is_integer_range(iterator_ty) {
+            let start_path = Path::new_field(iterator_path.clone(), 0);
+            self.block_visitor.body_visitor.state.remove(&start_path);
+        }
+
+        if may_yield {
+            let item_path = Path::new_iterator_item(iterator_path);
+            let value_path = Path::new_field(result, 0).refine_paths(self.block_visitor.state());
+            self.block_visitor
+                .copy_or_move_elements(value_path, item_path, item_ty, false);
+        }
+        true
+    }
+
+    /// The adaptors `map`, `filter`, `enumerate` and `take` are not analyzed, instead the item and the count of
+    /// the returned iterator are derived from the ones of the underlying iterator
+    /// - `map` analyzes the closure with the item as its argument, and the item becomes its return value
+    /// - `filter` analyzes the predicate, and constrains the item by the returned condition
+    /// - `enumerate` pairs the item with an index within `[0, count)`
+    /// - `take(n)` bounds the count by `n`
+    /// Returns false if the items of the underlying iterator are not tracked
+    fn handle_iterator_adaptor(&mut self) -> bool {
+        let destination = match self.destination {
+            Some(destination) => destination,
+            None => return false,
+        };
+        let iterator_path = self.actual_args[0].0.clone();
+        let iterator_ty = self.actual_argument_types[0];
+        if !self.is_tracked_iterator(&iterator_path, iterator_ty) {
+            return false;
+        }
+        let result = self.block_visitor.get_path_for_place(&destination);
+        let item_path = Path::new_iterator_item(iterator_path.clone());
+        let result_item_path = Path::new_iterator_item(result.clone());
+        let count = self
+            .block_visitor
+            .body_visitor
+            .state
+            .numerical_domain
+            .get_interval(&Path::new_iterator_count(iterator_path.clone()));
+        let zero = Interval::new(Bound::from(0i128), Bound::from(0i128));
+
+        let result_count = match self.callee_known_name {
+            KnownNames::IteratorMap => match self.analyze_closure(1, &item_path) {
+                // The closure never returns, so no item is yielded
+                Some(exit_state) if exit_state.is_bottom() => zero,
+                Some(exit_state) => {
+                    let return_value = exit_state
+                        .numerical_domain
+                        .get_interval(&Path::new_result());
+                    self.block_visitor
+                        .body_visitor
+                        .state
+                        .numerical_domain
+                        .set_interval(&result_item_path, return_value);
+                    count
+                }
+                None => count,
+            },
+            KnownNames::IteratorFilter => {
+                self.copy_iterator_item(&iterator_path, result_item_path.clone());
+                let mut filtered_count = Interval::new(Bound::from(0i128), count.high);
+                if let Some(mut exit_state) = self.analyze_closure(1, &item_path) {
+                    if let Some(condition) = exit_state.value_at(&Path::new_result()) {
+                        if let Ok(constraints) = LinearConstraintSystem::try_from(condition) {
+                            exit_state.numerical_domain.add_constraints(constraints);
+                        }
+                    }
+                    let filtered_item = exit_state.numerical_domain.get_interval(&item_path);
+                    let numerical_domain =
+                        &mut self.block_visitor.body_visitor.state.numerical_domain;
+                    // The predicate is never true, or never returns
+                    if exit_state.is_bottom() || filtered_item.is_bottom() {
+                        filtered_count = zero;
+                    } else {
+                        let item = numerical_domain.get_interval(&result_item_path);
+                        numerical_domain.set_interval(&result_item_path, item.meet(&filtered_item));
+                    }
+                }
+                filtered_count
+            }
+            KnownNames::IteratorEnumerate => {
+                self.copy_iterator_item(
+                    &iterator_path,
+                    Path::new_field(result_item_path.clone(), 1),
+                );
+                let index =
+                    Interval::new(Bound::from(0i128), count.high.clone() - Bound::from(1i128));
+                self.block_visitor
+                    .body_visitor
+                    .state
+                    .numerical_domain
+                    .set_interval(
+                        &Path::new_field(result_item_path, 0),
+                        index.meet(&non_negative()).join(&zero),
+                    );
+                count
+            }
+            KnownNames::IteratorTake => {
+                self.copy_iterator_item(&iterator_path, result_item_path);
+                let n = self.get_argument_interval(1);
+                Interval::new(count.low.min(n.low), count.high.min(n.high)).meet(&non_negative())
+            }
+            _ => unreachable!(),
+        };
+        self.block_visitor
+            .body_visitor
+            .state
+            .numerical_domain
+            .set_interval(&Path::new_iterator_count(result), result_count);
+        true
+    }
+
+    fn handle_from(&mut self) {
+        assert!(self.actual_args.len() == 1);
+        let source = &self.actual_args[0].0;
+        let destination_path = if let Some(dest) = self.destination {
+            Some(self.block_visitor.get_path_for_place(&dest))
+        } else {
+            None
+        };
+        assert!(destination_path.is_some());
+        let result = destination_path.as_ref().unwrap();
+
+        let body_visitor = &mut self.block_visitor.body_visitor;
+        let rtype = body_visitor
+            .type_visitor
+            .get_path_rustc_type(source, body_visitor.current_span);
+        self.block_visitor
+            .copy_or_move_elements(result.clone(), source.clone(), rtype, true);
+        if self.callee_known_name == KnownNames::StdAsMutPtr
+            || self.callee_known_name == KnownNames::SliceAsPtr
+        {
+            // The returned pointer points to the start of the slice
+            self.block_visitor
+                .body_visitor
+                .state
+                .numerical_domain
+                .assign_int(result.clone(), Integer::from(0));
+        }
+        if self.callee_known_name == KnownNames::SliceAsPtr {
+            // The pointer keeps the length of the slice, which bounds the accesses through it,
+            // see `BlockVisitor::get_allocation_size`
+            let length = self
+                .block_visitor
+                .state()
+                .value_at(&Path::new_fat_pointer_meta(source.clone()));
+            let length_path = Path::new_fat_pointer_meta(result.clone());
+            let state = &mut self.block_visitor.body_visitor.state;
+            match length {
+                Some(length) => state.update_value_at(length_path, length),
+                None => state.remove(&length_path),
+            }
+        }
+    }
+
+    fn handle_index(&mut self) {
+        assert!(self.actual_args.len() == 2);
+        let destination_path = if let Some(dest) = self.destination {
+            Some(self.block_visitor.get_path_for_place(&dest))
+        } else {
+            None
+        };
+        assert!(destination_path.is_some());
+        let state = self.block_visitor.state().clone();
+        let body_visitor = &mut self.block_visitor.body_visitor;
+
+        let array = &self.actual_args[0].0;
+        let array_len = Path::new_length(array.clone()).refine_paths(&body_visitor.state);
+        let array_len_val = SymbolicValue::make_from(
             Expression::Variable {
                 path: array_len.clone(),
                 var_type: ExpressionType::Usize,
@@ -807,7 +3290,10 @@ where
         function_post_state: &AbstractDomain<DomainType>,
         old_offset: usize,
     ) {
+        // The float values of the post state are indexed by the locals of the callee, so the ones of the caller are kept
+        let float_values = std::mem::take(&mut self.block_visitor.body_visitor.state.float_values);
         self.block_visitor.body_visitor.state = function_post_state.clone();
+        self.block_visitor.body_visitor.state.float_values = float_values;
 
         debug!("Start to transfer and refine normal return state");
         let destination_path = if let Some(dest) = self.destination {
@@ -912,3 +3398,30 @@ where
         }
     }
 }
+
+/// The interval `[0, ∞]`, e.g., the count of the items of an iterator
+fn non_negative() -> Interval {
+    Interval::new(Bound::from(0i128), Bound::INF)
+}
+
+/// The selector of the item of the vector at `vec_path` that `path` is, or is rooted by, if any
+fn get_vec_item_selector<'a>(path: &'a Rc<Path>, vec_path: &Rc<Path>) -> Option<&'a PathSelector> {
+    let mut path = path;
+    while let PathEnum::QualifiedPath {
+        qualifier,
+        selector,
+        ..
+    } = &path.value
+    {
+        if qualifier == vec_path {
+            return match selector.as_ref() {
+                PathSelector::Index(_)
+                | PathSelector::Slice(_)
+                | PathSelector::ConstantIndex { .. } => Some(selector.as_ref()),
+                _ => None,
+            };
+        }
+        path = qualifier;
+    }
+    None
+}
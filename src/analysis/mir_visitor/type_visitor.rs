@@ -13,10 +13,13 @@ use rustc_hir::def_id::DefId;
 use rustc_middle::mir;
 use rustc_middle::ty::ty_kind::TyKind;
 use rustc_middle::ty::{
-    Binder, ExistentialPredicate, ExistentialProjection, ExistentialTraitRef, FnSig, GenericArg,
-    GenericArgKind, GenericArgs, GenericArgsRef, ParamTy, Ty, TyCtxt,
+    AliasKind, Binder, Const, ConstKind, ExistentialPredicate, ExistentialProjection,
+    ExistentialTraitRef, FnSig, GenericArg, GenericArgKind, GenericArgs, GenericArgsRef, Instance,
+    InstanceDef, ParamTy, ScalarInt, Ty, TyCtxt, TypeVisitableExt,
 };
+use rustc_span::{sym, DUMMY_SP};
 use rustc_target::abi::FieldIdx;
+use std::cell::Cell;
 use std::collections::HashMap;
 use std::fmt::{Debug, Formatter, Result};
 use std::rc::Rc;
@@ -26,8 +29,13 @@ pub struct TypeVisitor<'tcx> {
     pub def_id: DefId,
     pub generic_argument_map: Option<HashMap<rustc_span::Symbol, Ty<'tcx>>>,
     pub generic_arguments: Option<GenericArgsRef<'tcx>>,
+    pub heap_allocation_sites: HashMap<u32, mir::Location>,
     pub mir: mir::Body<'tcx>,
     pub path_ty_cache: HashMap<Rc<Path>, Ty<'tcx>>,
+    // Panic on the MIR patterns that are not supported instead of skipping them, see `--strict`
+    pub strict: bool,
+    // The number of times that unsupported MIR patterns are skipped
+    pub unsupported_patterns: Cell<usize>,
     tcx: TyCtxt<'tcx>,
 }
 
@@ -44,20 +52,37 @@ impl<'compilation, 'tcx> TypeVisitor<'tcx> {
             def_id,
             generic_argument_map: None,
             generic_arguments: None,
+            heap_allocation_sites: HashMap::new(),
             mir,
             path_ty_cache: HashMap::new(),
+            strict: false,
+            unsupported_patterns: Cell::new(0),
             tcx,
         }
     }
 
+    /// Report a MIR pattern that is not supported, the caller falls back to an over-approximation
+    /// so that the rest of the crate can still be analyzed, unless `strict` is set
+    pub fn report_unsupported(&self, span: rustc_span::Span, pattern: &str) {
+        if self.strict {
+            panic!("Unsupported MIR pattern at {:?}: {}", span, pattern);
+        }
+        warn!(
+            "Unsupported MIR pattern at {:?}: {}, skip it",
+            span, pattern
+        );
+        self.unsupported_patterns
+            .set(self.unsupported_patterns.get() + 1);
+    }
+
     // TODO: this is only used in `copy_or_move_subslice`, remove this if not necessary
     /// Returns the size in bytes (including padding) or an element of the given collection type.
-    /// If the type is not a collection, it returns one.
-    pub fn get_elem_type_size(&self, ty: Ty<'tcx>) -> u64 {
+    /// If the type is not a collection, it returns one, and None if the size of the element is unknown.
+    pub fn get_elem_type_size(&self, ty: Ty<'tcx>) -> Option<u64> {
         match ty.kind() {
             TyKind::Array(ty, _) | TyKind::Slice(ty) => self.get_type_size(*ty),
             TyKind::RawPtr(t, _) => self.get_type_size(*t),
-            _ => 1,
+            _ => Some(1),
         }
     }
 
@@ -66,6 +91,18 @@ impl<'compilation, 'tcx> TypeVisitor<'tcx> {
         self.tcx.param_env(self.def_id)
     }
 
+    /// Returns the type recorded for the canonical form of the path, if any.
+    pub fn get_cached_path_type(&self, path: &Rc<Path>) -> Option<Ty<'tcx>> {
+        self.path_ty_cache.get(&Path::canonicalize(path)).copied()
+    }
+
+    /// Records the type of the canonical form of the path, unless it is already known.
+    pub fn set_path_rustc_type(&mut self, path: &Rc<Path>, ty: Ty<'tcx>) {
+        self.path_ty_cache
+            .entry(Path::canonicalize(path))
+            .or_insert(ty);
+    }
+
     /// This is a hacky and brittle way to navigate the Rust compiler's type system.
     /// Eventually it should be replaced with a comprehensive and principled mapping.
     pub fn get_path_rustc_type(
@@ -73,9 +110,10 @@ impl<'compilation, 'tcx> TypeVisitor<'tcx> {
         path: &Rc<Path>,
         current_span: rustc_span::Span,
     ) -> Ty<'tcx> {
-        if let Some(ty) = self.path_ty_cache.get(path) {
-            return *ty;
+        if let Some(ty) = self.get_cached_path_type(path) {
+            return ty;
         }
+        let path = &Path::canonicalize(path);
         match &path.value {
             PathEnum::LocalVariable { ordinal } => {
                 if *ordinal > 0 && *ordinal < self.mir.local_decls.len() {
@@ -86,7 +124,7 @@ impl<'compilation, 'tcx> TypeVisitor<'tcx> {
                 }
             }
             PathEnum::Parameter { ordinal } => {
-                if self.actual_argument_types.len() >= *ordinal {
+                if *ordinal > 0 && self.actual_argument_types.len() >= *ordinal {
                     self.actual_argument_types[*ordinal - 1]
                 } else if *ordinal > 0 && *ordinal < self.mir.local_decls.len() {
                     self.mir.local_decls[mir::Local::from(*ordinal)].ty
@@ -110,7 +148,7 @@ impl<'compilation, 'tcx> TypeVisitor<'tcx> {
             } => {
                 let t = self.get_path_rustc_type(qualifier, current_span);
                 match &**selector {
-                    PathSelector::Slice(_) => {
+                    PathSelector::Slice(_) | PathSelector::EntryValue => {
                         return t;
                     }
                     PathSelector::Field(ordinal) => {
@@ -143,11 +181,31 @@ impl<'compilation, 'tcx> TypeVisitor<'tcx> {
                         }
                     }
                     PathSelector::Deref => {
+                        if let Some(pointee_ty) = self.get_smart_pointer_pointee_type(t) {
+                            return pointee_ty;
+                        }
                         return Self::get_dereferenced_type(t);
                     }
                     PathSelector::Discriminant => {
                         return self.tcx.types.i32;
                     }
+                    PathSelector::FatPointerData => {
+                        let elem_ty = match get_target_type(t).kind() {
+                            TyKind::Slice(elem_ty) => *elem_ty,
+                            TyKind::Str => self.tcx.types.u8,
+                            _ => self.tcx.types.unit,
+                        };
+                        return Ty::new_imm_ptr(self.tcx, elem_ty);
+                    }
+                    PathSelector::FatPointerMeta
+                    | PathSelector::IteratorCount
+                    | PathSelector::Capacity
+                    | PathSelector::ResourceState
+                    | PathSelector::Freed
+                    | PathSelector::Written
+                    | PathSelector::Alignment => {
+                        return self.tcx.types.usize;
+                    }
                     // PathSelector::Downcast(_, ordinal) => {
                     //     let t = type_visitor::get_target_type(t);
                     //     if let TyKind::Adt(def, substs) = t.kind() {
@@ -192,6 +250,21 @@ impl<'compilation, 'tcx> TypeVisitor<'tcx> {
                 info!("path.value is {:?}", path.value);
                 self.tcx.types.unit
             }
+            PathEnum::HeapAllocation { id } => {
+                // The allocated object has the type that the allocation site returns a box of
+                if let Some(location) = self.heap_allocation_sites.get(id) {
+                    let terminator = self.mir.basic_blocks[location.block].terminator();
+                    if let mir::TerminatorKind::Call { destination, .. } = &terminator.kind {
+                        let t = self.get_rustc_place_type(destination, current_span);
+                        if t.is_box() {
+                            return t.boxed_ty();
+                        }
+                        return Self::get_dereferenced_type(t);
+                    }
+                }
+                info!("path.value is {:?}", path.value);
+                self.tcx.types.unit
+            }
             _ => {
                 info!("path.value is {:?}", path.value);
                 self.tcx.types.unit
@@ -199,6 +272,21 @@ impl<'compilation, 'tcx> TypeVisitor<'tcx> {
         }
     }
 
+    /// If `ty` is a smart pointer `Box<T>`, `Rc<T>` or `Arc<T>`, returns `T`
+    /// The internals of `Rc` and `Arc` (e.g., `ArcInner`) are private, so we skip them entirely
+    pub fn get_smart_pointer_pointee_type(&self, ty: Ty<'tcx>) -> Option<Ty<'tcx>> {
+        if let TyKind::Adt(def, args) = ty.kind() {
+            if def.is_box() {
+                return Some(args.type_at(0));
+            }
+            match self.tcx.get_diagnostic_name(def.did()) {
+                Some(sym::Rc) | Some(sym::Arc) => return Some(args.type_at(0)),
+                _ => (),
+            }
+        }
+        None
+    }
+
     /// Returns the target type of a reference type.
     fn get_dereferenced_type(ty: Ty<'tcx>) -> Ty<'tcx> {
         match &ty.kind() {
@@ -207,6 +295,60 @@ impl<'compilation, 'tcx> TypeVisitor<'tcx> {
         }
     }
 
+    /// If `ty` is a newtype wrapper, i.e., a struct with exactly one field such as
+    /// `struct Meters(f64)`, returns the type of the wrapped value. Nested wrappers
+    /// are peeled off repeatedly, so the result is never itself a newtype.
+    pub fn unwrap_newtype(&self, ty: Ty<'tcx>) -> Ty<'tcx> {
+        let mut ty = ty;
+        while let TyKind::Adt(adt_def, substs) = ty.kind() {
+            if !adt_def.is_struct() || adt_def.non_enum_variant().fields.len() != 1 {
+                break;
+            }
+            let field = &adt_def.non_enum_variant().fields[FieldIdx::from_usize(0)];
+            let inner_ty = field.ty(self.tcx, substs);
+            if inner_ty == ty {
+                break;
+            }
+            ty = inner_ty;
+        }
+        ty
+    }
+
+    /// If `def_id` is a trait method, resolves the call with the generic arguments `substs` to the
+    /// method of the implementation that is selected for them, and returns its `DefId`.
+    /// Returns `None` if `def_id` is not a trait method, or if the implementation cannot be
+    /// determined statically, e.g., when the call is made through a trait object.
+    pub fn resolve_method_call(
+        &self,
+        def_id: DefId,
+        substs: GenericArgsRef<'tcx>,
+    ) -> Option<DefId> {
+        self.resolve_method_instance(def_id, substs)
+            .map(|instance| instance.def_id())
+    }
+
+    /// Same as `resolve_method_call`, but returns the resolved instance, whose generic arguments
+    /// are those of the implementation method
+    pub fn resolve_method_instance(
+        &self,
+        def_id: DefId,
+        substs: GenericArgsRef<'tcx>,
+    ) -> Option<Instance<'tcx>> {
+        self.tcx.trait_of_item(def_id)?;
+        let substs = self.tcx.erase_regions(substs);
+        if substs.has_param() {
+            return None;
+        }
+        match Instance::resolve(self.tcx, self.get_param_env(), def_id, substs) {
+            Ok(Some(instance))
+                if matches!(instance.def, InstanceDef::Item(..)) && instance.def_id() != def_id =>
+            {
+                Some(instance)
+            }
+            _ => None,
+        }
+    }
+
     /// If Operand corresponds to a compile time constant function, return
     /// the generic parameter substitutions (type arguments) that are used by
     /// the call instruction whose operand this is.
@@ -314,15 +456,17 @@ impl<'compilation, 'tcx> TypeVisitor<'tcx> {
             .iter()
             .fold(base_ty, |base_ty, projection_elem| match projection_elem {
                 mir::ProjectionElem::Deref => match &base_ty.kind() {
-                    TyKind::Adt(..) => base_ty,
+                    TyKind::Adt(..) => self
+                        .get_smart_pointer_pointee_type(base_ty)
+                        .unwrap_or(base_ty),
                     TyKind::RawPtr(ty, _) => *ty,
                     TyKind::Ref(_, ty, _) => *ty,
                     _ => {
-                        debug!(
-                            "span: {:?}\nelem: {:?} type: {:?}",
-                            current_span, projection_elem, base_ty
+                        self.report_unsupported(
+                            current_span,
+                            &format!("dereference of type {:?}", base_ty),
                         );
-                        unreachable!();
+                        self.tcx.types.unit
                     }
                 },
                 mir::ProjectionElem::Field(_, ty) => *ty,
@@ -334,29 +478,97 @@ impl<'compilation, 'tcx> TypeVisitor<'tcx> {
                     TyKind::Ref(_, ty, _) => get_element_type(*ty),
                     TyKind::Slice(ty) => *ty,
                     _ => {
-                        debug!(
-                            "span: {:?}\nelem: {:?} type: {:?}",
-                            current_span, projection_elem, base_ty
+                        self.report_unsupported(
+                            current_span,
+                            &format!("{:?} of type {:?}", projection_elem, base_ty),
                         );
-                        unreachable!();
+                        self.tcx.types.unit
                     }
                 },
                 mir::ProjectionElem::Downcast(..) => base_ty,
-                mir::ProjectionElem::OpaqueCast(_) => todo!(),
-                mir::ProjectionElem::Subtype(_) => todo!(),
+                // Both carry the type that the place is cast to
+                mir::ProjectionElem::OpaqueCast(ty) | mir::ProjectionElem::Subtype(ty) => *ty,
             })
     }
 
-    /// Returns the size in bytes (including padding) of an instance of the given type.
-    pub fn get_type_size(&self, ty: Ty<'tcx>) -> u64 {
+    /// Returns the size in bytes (including padding) of an instance of the given type, or None if it is unknown.
+    pub fn get_type_size(&self, ty: Ty<'tcx>) -> Option<u64> {
+        let param_env = self.get_param_env();
+        if let Ok(ty_and_layout) = self.tcx.layout_of(param_env.and(ty)) {
+            Some(ty_and_layout.layout.size.bytes())
+        } else if let TyKind::Array(elem_ty, length) = ty.kind() {
+            // `layout_of` fails if the length is a `const` expression that is not normalized
+            self.get_array_length(*length)?
+                .checked_mul(self.get_type_size(*elem_ty)?)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the length of an array type, if it can be evaluated, see `try_eval_const_int`
+    pub fn get_array_length(&self, length: Const<'tcx>) -> Option<u64> {
+        self.try_eval_const_int(length)?
+            .try_to_target_usize(self.tcx)
+            .ok()
+    }
+
+    /// Evaluates an integer constant, e.g., the length of an array defined by `const N: usize = FOO + BAR`, which
+    /// is resolved by `const_eval_resolve` with the generic arguments of the current function
+    /// Returns None if the constant cannot be evaluated, e.g., it depends on a const generic parameter
+    pub fn try_eval_const_int(&self, constant: Const<'tcx>) -> Option<ScalarInt> {
+        match constant.kind() {
+            ConstKind::Value(valtree) => valtree.try_to_scalar_int(),
+            ConstKind::Unevaluated(unevaluated) => {
+                let args = self.specialize_substs(unevaluated.args, &self.generic_argument_map);
+                if args.has_non_region_infer() {
+                    return None;
+                }
+                let unevaluated = mir::UnevaluatedConst::new(unevaluated.def, args);
+                self.tcx
+                    .const_eval_resolve(self.get_param_env(), unevaluated, DUMMY_SP)
+                    .ok()?
+                    .try_to_scalar_int()
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the ABI-required alignment of the given type in bytes, or 0 if the layout is unknown
+    pub fn get_type_alignment(&self, ty: Ty<'tcx>) -> u64 {
         let param_env = self.get_param_env();
         if let Ok(ty_and_layout) = self.tcx.layout_of(param_env.and(ty)) {
-            ty_and_layout.layout.size.bytes()
+            ty_and_layout.layout.align.abi.bytes()
         } else {
             0
         }
     }
 
+    /// Returns true if an instance of the given type has zero size.
+    /// Common cases are decided syntactically, `layout_of` is only used for the rest.
+    pub fn is_zero_sized(&self, ty: Ty<'tcx>) -> bool {
+        match ty.kind() {
+            TyKind::Tuple(types) if types.is_empty() => true,
+            TyKind::Never | TyKind::FnDef(..) => true,
+            TyKind::Adt(def, _) if def.is_phantom_data() => true,
+            TyKind::Bool
+            | TyKind::Char
+            | TyKind::Int(..)
+            | TyKind::Uint(..)
+            | TyKind::Float(..)
+            | TyKind::RawPtr(..)
+            | TyKind::Ref(..)
+            | TyKind::FnPtr(..) => false,
+            _ => {
+                let param_env = self.get_param_env();
+                if let Ok(ty_and_layout) = self.tcx.layout_of(param_env.and(ty)) {
+                    ty_and_layout.is_zst()
+                } else {
+                    false
+                }
+            }
+        }
+    }
+
     fn specialize_generic_argument(
         &self,
         gen_arg: GenericArg<'tcx>,
@@ -473,6 +685,22 @@ impl<'compilation, 'tcx> TypeVisitor<'tcx> {
                 }
                 gen_arg_type
             }
+            // An associated type, e.g., `<T as Iterator>::Item`, can be resolved once `T` is known
+            TyKind::Alias(AliasKind::Projection, projection) => {
+                let specialized_ty = Ty::new_projection(
+                    self.tcx,
+                    projection.def_id,
+                    self.specialize_substs(projection.args, map),
+                );
+                // Normalization does not support bound variables, e.g., in the signature of a function pointer
+                if specialized_ty.has_escaping_bound_vars() {
+                    return specialized_ty;
+                }
+                // If the projection is still polymorphic, keep it as is
+                self.tcx
+                    .try_normalize_erasing_regions(self.get_param_env(), specialized_ty)
+                    .unwrap_or(specialized_ty)
+            }
             _ => gen_arg_type,
         }
     }
@@ -552,6 +780,18 @@ pub fn get_target_type(ty: Ty<'_>) -> Ty<'_> {
     }
 }
 
+/// Returns true if a value of this type is a fat pointer, i.e., a pointer with metadata.
+pub fn is_fat_pointer(ty_kind: &TyKind<TyCtxt<'_>>) -> bool {
+    if let TyKind::RawPtr(target, _) | TyKind::Ref(_, target, _) = ty_kind {
+        matches!(
+            target.kind(),
+            TyKind::Slice(..) | TyKind::Str | TyKind::Dynamic(..)
+        )
+    } else {
+        false
+    }
+}
+
 pub fn is_slice_pointer<'tcx>(ty_kind: &TyKind<TyCtxt<'tcx>>) -> bool {
     if let TyKind::RawPtr(target, _) | TyKind::Ref(_, target, _) = ty_kind {
         // Pointers to sized arrays and slice pointers are thin pointers.
@@ -19,6 +19,7 @@ use std::ptr::NonNull;
 use std::rc::Rc;
 
 /// The operators that numerical abstract domain supports
+#[derive(Clone, Copy)]
 pub enum ApronOperation {
     // Binop
     Add,
@@ -71,6 +72,9 @@ pub struct ApronPplLinearCongruences;
 #[derive(Clone)]
 // Apron Reduced Product of NewPolka Convex Polyhedra and PPL Linear Congruences
 pub struct ApronPkgridPolyhedraLinCongruences;
+#[derive(Clone)]
+// Apron Interval, whose integers wrap around on overflow, see `WrappingIntegerDomain`
+pub struct ApronWrappingInteger;
 
 pub trait ApronDomainType: Clone {}
 
@@ -81,6 +85,7 @@ impl ApronDomainType for ApronLinearEqualities {}
 impl ApronDomainType for ApronPplPolyhedra {}
 impl ApronDomainType for ApronPplLinearCongruences {}
 impl ApronDomainType for ApronPkgridPolyhedraLinCongruences {}
+impl ApronDomainType for ApronWrappingInteger {}
 
 /// Different types of abstract domains have different managers
 /// So we define a trait to share the same function name `get_manager`
@@ -309,6 +314,24 @@ impl GetManagerTrait for ApronAbstractDomain<ApronPkgridPolyhedraLinCongruences>
     }
 }
 
+impl GetManagerTrait for ApronAbstractDomain<ApronWrappingInteger> {
+    fn get_manager() -> Rc<ApronManager> {
+        if let Some(apron_man) = unsafe { APRON_MANAGER.clone() } {
+            apron_man
+        } else {
+            unsafe {
+                let apron_man = Rc::new(ApronManager::from_ptr(apron_sys::box_manager_alloc()));
+                APRON_MANAGER = Some(apron_man.clone());
+                apron_man
+            }
+        }
+    }
+
+    fn get_domain_type() -> AbstractDomainType {
+        AbstractDomainType::WrappingInteger
+    }
+}
+
 impl<Type> Default for ApronAbstractDomain<Type>
 where
     Type: ApronDomainType,
@@ -671,6 +694,23 @@ where
         }
     }
 
+    /// Apply the pointer offset statement: `res = base op count * size`, where `op` is `Add` or `Sub`
+    pub fn apply_pointer_offset(
+        &mut self,
+        op: ApronOperation,
+        base: &Rc<Path>,
+        count: &Rc<Path>,
+        size: &Integer,
+        res: &Rc<Path>,
+    ) {
+        if !self.is_bottom() {
+            let base_expr = self.var2texpr(base);
+            let count_expr = self.var2texpr(count);
+            let offset_expr = Self::mul(count_expr, Self::num2texpr(size));
+            self.do_bin_op_expr(op, base_expr, offset_expr, res);
+        }
+    }
+
     /// Apply the unary operation statement: `res = - rhs`, or `res = !rhs`
     pub fn apply_un_op_place(&mut self, op: ApronOperation, rhs: &Rc<Path>, res: &Rc<Path>) {
         if !self.is_bottom() {
@@ -699,33 +739,84 @@ where
 
     /// Remove a path from current abstract domain
     pub fn forget(&mut self, var: &Rc<Path>) {
-        let mut vec_dims = Vec::new();
-        if let Some(dim) = self.get_var_dim(var) {
-            vec_dims.push(dim);
-            self.abstract_state = unsafe {
-                AbstractState::from_ptr(apron_sys::ap_abstract0_forget_array(
-                    Self::get_manager().as_ptr(),
-                    false,
-                    self.abstract_state.as_ptr(),
-                    &mut vec_dims[0] as *mut apron_sys::ap_dim_t,
-                    1,
-                    false,
-                ))
-            };
+        self.forget_all(std::slice::from_ref(var));
+    }
+
+    /// Remove several paths from current abstract domain at once
+    /// The dimensions of all the paths are removed by a single call to Apron, which is much cheaper
+    /// than removing them one by one when the domain has many dimensions
+    pub fn forget_all(&mut self, vars: &[Rc<Path>]) {
+        let mut vec_dims: Vec<apron_sys::ap_dim_t> = vars
+            .iter()
+            .filter_map(|var| self.get_var_dim(var))
+            .collect();
+        if vec_dims.is_empty() {
+            return;
+        }
+        vec_dims.sort_unstable();
+        vec_dims.dedup();
+        self.abstract_state = unsafe {
+            AbstractState::from_ptr(apron_sys::ap_abstract0_forget_array(
+                Self::get_manager().as_ptr(),
+                false,
+                self.abstract_state.as_ptr(),
+                vec_dims.as_mut_ptr(),
+                vec_dims.len(),
+                false,
+            ))
+        };
 
-            let mut new_var_map: BTreeMap<Rc<Path>, apron_sys::ap_dim_t> = BTreeMap::new();
-            // We have to iterate by the dim to preserve the order
-            let mut old_var_map: Vec<(&Rc<Path>, &apron_sys::ap_dim_t)> =
-                self.var_map.iter().collect();
-            old_var_map.sort_by(|a, b| a.1.cmp(b.1));
-            for (var, old_dim) in old_var_map {
-                if dim != *old_dim {
-                    new_var_map.insert(var.clone(), new_var_map.len() as apron_sys::ap_dim_t);
-                }
+        let mut new_var_map: BTreeMap<Rc<Path>, apron_sys::ap_dim_t> = BTreeMap::new();
+        // We have to iterate by the dim to preserve the order
+        let mut old_var_map: Vec<(&Rc<Path>, &apron_sys::ap_dim_t)> = self.var_map.iter().collect();
+        old_var_map.sort_by(|a, b| a.1.cmp(b.1));
+        for (var, old_dim) in old_var_map {
+            if vec_dims.binary_search(old_dim).is_err() {
+                new_var_map.insert(var.clone(), new_var_map.len() as apron_sys::ap_dim_t);
             }
-            self.remove_dimensions(vec_dims);
-            self.var_map = new_var_map;
         }
+        self.remove_dimensions(vec_dims);
+        self.var_map = new_var_map;
+    }
+
+    /// Project the current abstract domain onto `paths`, i.e., eliminate all the other paths
+    pub fn project(&self, paths: &[Rc<Path>]) -> Self {
+        let mut res = self.clone();
+        let eliminated: Vec<Rc<Path>> = self
+            .var_map
+            .keys()
+            .filter(|path| !paths.contains(path))
+            .cloned()
+            .collect();
+        res.forget_all(&eliminated);
+        res
+    }
+
+    /// Serialize the abstract state as the variables it has and the linear constraints over them,
+    /// e.g. `{"variables": ["local_1"], "constraints": [{"op": "<=", "terms": {"local_1": "1"}, "constant": "-10"}]}`
+    /// Each variable is named by `format!("{:?}", path)`
+    pub fn to_json(&self) -> serde_json::Value {
+        let variables: Vec<serde_json::Value> = self
+            .var_map
+            .keys()
+            .map(|path| serde_json::Value::String(format!("{:?}", path)))
+            .collect();
+        serde_json::json!({
+            "variables": variables,
+            "constraints": LinearConstraintSystem::from(self).to_json(),
+        })
+    }
+
+    /// Deserialize an abstract state written by `to_json`, the variables are created by `Path::new_named`
+    pub fn from_json(value: &serde_json::Value) -> Option<Self> {
+        let mut result = Self::top();
+        for name in value.get("variables")?.as_array()? {
+            result.get_var_dim_insert(Path::new_named(name.as_str()?));
+        }
+        result.add_constraints(LinearConstraintSystem::from_json(
+            value.get("constraints")?,
+        )?);
+        Some(result)
     }
 
     /// Add a linear constraint system into current abstract domain
@@ -824,6 +915,10 @@ where
         // Merge two `var_map`
         assert_eq!(lhs.var_map.len(), lhs.get_dims());
         assert_eq!(rhs.var_map.len(), rhs.get_dims());
+        // Both domains already use the same dimensions, no permutation is needed
+        if lhs.var_map == rhs.var_map {
+            return lhs.var_map.clone();
+        }
         let mut vars: Vec<Rc<Path>> = lhs.var_map.keys().cloned().collect();
         for v in rhs.var_map.keys() {
             if !vars.contains(&v) {
@@ -1257,7 +1352,8 @@ where
         }
     }
 
-    fn set_interval(&mut self, v: &Rc<Path>, itv: Interval) {
+    /// Forget the value of `v` and constrain it to be within `itv`
+    pub fn set_interval(&mut self, v: &Rc<Path>, itv: Interval) {
         // Remove variable from abstract domain
         self.forget(v);
 
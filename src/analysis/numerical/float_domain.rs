@@ -0,0 +1,344 @@
+// The values of floating-point numbers, used by the float NaN checker
+
+use crate::analysis::numerical::interval::{Bound, Interval};
+use std::f64::consts::PI;
+
+/// The classes of the values of a float, which form a lattice where the join of two different classes is `Top`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FloatClass {
+    /// No value, e.g., the result of an unreachable operation
+    Bottom,
+    /// Finite values only
+    Normal,
+    /// NaN only
+    Nan,
+    /// `+inf` or `-inf` only
+    Infinite,
+    /// Values of more than one class
+    Top,
+}
+
+/// An abstract float value, i.e., the range of the values that are not NaN and whether the value may be NaN
+/// The bounds of the range may be infinite, and `low > high` if the range is empty, e.g., if the value is always NaN
+/// The operations compute the ranges with the float arithmetic of the analyzer, which rounds in the same way as the
+/// analyzed program, so the bounds are exact for the operations on `f64` and the rounding of `f32` is applied by
+/// `to_f32`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FloatValue {
+    pub low: f64,
+    pub high: f64,
+    pub may_be_nan: bool,
+}
+
+impl FloatValue {
+    pub fn new(low: f64, high: f64, may_be_nan: bool) -> Self {
+        FloatValue {
+            low,
+            high,
+            may_be_nan,
+        }
+    }
+
+    pub fn constant(value: f64) -> Self {
+        if value.is_nan() {
+            Self::nan()
+        } else {
+            Self::new(value, value, false)
+        }
+    }
+
+    pub fn bottom() -> Self {
+        Self::new(f64::INFINITY, f64::NEG_INFINITY, false)
+    }
+
+    pub fn nan() -> Self {
+        Self::new(f64::INFINITY, f64::NEG_INFINITY, true)
+    }
+
+    /// Any finite value, i.e., the value of a float that is not computed by the analyzed function, e.g., a parameter,
+    /// a field or the result of an unknown call
+    /// NaN and infinities are only introduced by the operations that are tracked, so that they are reported where they
+    /// are produced instead of at every use of a parameter
+    pub fn unknown() -> Self {
+        Self::new(f64::MIN, f64::MAX, false)
+    }
+
+    /// The values of the integers in `interval` converted to floats, the unbounded sides are finite
+    pub fn from_interval(interval: &Interval) -> Self {
+        if interval.is_bottom() {
+            return Self::bottom();
+        }
+        let to_f64 = |bound: &Bound| match bound {
+            Bound::NINF => f64::MIN,
+            Bound::Int(value) => value.to_f64(),
+            Bound::INF => f64::MAX,
+        };
+        Self::new(to_f64(&interval.low), to_f64(&interval.high), false)
+    }
+
+    /// True if the range of the values that are not NaN is empty
+    pub fn is_empty_range(&self) -> bool {
+        self.low > self.high || self.low.is_nan() || self.high.is_nan()
+    }
+
+    pub fn may_be_infinite(&self) -> bool {
+        !self.is_empty_range() && (self.low.is_infinite() || self.high.is_infinite())
+    }
+
+    pub fn class(&self) -> FloatClass {
+        match (self.is_empty_range(), self.may_be_nan) {
+            (true, false) => FloatClass::Bottom,
+            (true, true) => FloatClass::Nan,
+            (false, true) => FloatClass::Top,
+            (false, false) if self.low == self.high && self.low.is_infinite() => {
+                FloatClass::Infinite
+            }
+            (false, false) if !self.may_be_infinite() => FloatClass::Normal,
+            (false, false) => FloatClass::Top,
+        }
+    }
+
+    pub fn join(&self, other: &FloatValue) -> FloatValue {
+        if self.is_empty_range() {
+            return Self::new(other.low, other.high, self.may_be_nan || other.may_be_nan);
+        }
+        if other.is_empty_range() {
+            return Self::new(self.low, self.high, self.may_be_nan || other.may_be_nan);
+        }
+        Self::new(
+            self.low.min(other.low),
+            self.high.max(other.high),
+            self.may_be_nan || other.may_be_nan,
+        )
+    }
+
+    /// True if every value of `self` is a value of `other`
+    pub fn leq(&self, other: &FloatValue) -> bool {
+        let range_leq = self.is_empty_range()
+            || (!other.is_empty_range() && other.low <= self.low && self.high <= other.high);
+        range_leq && (!self.may_be_nan || other.may_be_nan)
+    }
+
+    /// Joins `self` with `next`, and moves the bounds that grow to infinity, so that the values of a float that is
+    /// updated in a loop are stable after a bounded number of updates
+    pub fn widen(&self, next: &FloatValue) -> FloatValue {
+        if self.is_empty_range() || next.is_empty_range() {
+            return self.join(next);
+        }
+        let joined = self.join(next);
+        let low = if joined.low < self.low {
+            f64::NEG_INFINITY
+        } else {
+            joined.low
+        };
+        let high = if joined.high > self.high {
+            f64::INFINITY
+        } else {
+            joined.high
+        };
+        Self::new(low, high, joined.may_be_nan)
+    }
+
+    /// The bounds of the range, and the zeros of both signs if the range contains zero
+    /// A binary operation that is monotone on each side of zero reaches its extreme values at these points
+    fn samples(&self) -> Vec<f64> {
+        if self.is_empty_range() {
+            return vec![];
+        }
+        let mut samples = vec![self.low, self.high];
+        if self.low <= 0.0 && self.high >= 0.0 {
+            samples.push(0.0);
+            samples.push(-0.0);
+        }
+        samples
+    }
+
+    /// Applies `operation` to the samples of both operands, the result may be NaN if an operand may be NaN or the
+    /// operation produces NaN for a pair of samples, e.g., `inf - inf`, `0 * inf`, `0 / 0` or `inf / inf`
+    fn apply_binary(&self, other: &FloatValue, operation: impl Fn(f64, f64) -> f64) -> FloatValue {
+        let mut result = Self::new(
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            self.may_be_nan || other.may_be_nan,
+        );
+        for left in self.samples() {
+            for right in other.samples() {
+                result = result.join(&Self::constant(operation(left, right)));
+            }
+        }
+        result
+    }
+
+    pub fn add(&self, other: &FloatValue) -> FloatValue {
+        self.apply_binary(other, |left, right| left + right)
+    }
+
+    pub fn sub(&self, other: &FloatValue) -> FloatValue {
+        self.apply_binary(other, |left, right| left - right)
+    }
+
+    pub fn mul(&self, other: &FloatValue) -> FloatValue {
+        self.apply_binary(other, |left, right| left * right)
+    }
+
+    /// Dividing by a range that contains zero produces infinities of both signs, and NaN if the dividend may be zero
+    pub fn div(&self, other: &FloatValue) -> FloatValue {
+        self.apply_binary(other, |left, right| left / right)
+    }
+
+    /// `x % y` has the sign of `x` and is smaller than `|y|` in magnitude, it is NaN if `x` is infinite or `y` is zero
+    pub fn rem(&self, other: &FloatValue) -> FloatValue {
+        let may_be_nan = self.may_be_nan
+            || other.may_be_nan
+            || self.may_be_infinite()
+            || (!other.is_empty_range() && other.low <= 0.0 && other.high >= 0.0);
+        if self.is_empty_range() || other.is_empty_range() {
+            return Self::new(f64::INFINITY, f64::NEG_INFINITY, may_be_nan);
+        }
+        let magnitude = other.low.abs().max(other.high.abs());
+        let low = if self.low < 0.0 {
+            self.low.max(-magnitude)
+        } else {
+            0.0
+        };
+        let high = if self.high > 0.0 {
+            self.high.min(magnitude)
+        } else {
+            0.0
+        };
+        Self::new(low, high, may_be_nan)
+    }
+
+    pub fn neg(&self) -> FloatValue {
+        Self::new(-self.high, -self.low, self.may_be_nan)
+    }
+
+    pub fn abs(&self) -> FloatValue {
+        if self.is_empty_range() || self.low >= 0.0 {
+            *self
+        } else if self.high <= 0.0 {
+            self.neg()
+        } else {
+            Self::new(0.0, self.high.max(-self.low), self.may_be_nan)
+        }
+    }
+
+    /// Applies a function that is monotone on `[domain_low, domain_high]`, and NaN outside of it
+    fn apply_monotone(
+        &self,
+        domain_low: f64,
+        domain_high: f64,
+        increasing: bool,
+        function: impl Fn(f64) -> f64,
+    ) -> FloatValue {
+        let may_be_nan = self.may_be_nan
+            || (!self.is_empty_range() && (self.low < domain_low || self.high > domain_high));
+        let low = self.low.max(domain_low);
+        let high = self.high.min(domain_high);
+        if low > high {
+            return Self::new(f64::INFINITY, f64::NEG_INFINITY, may_be_nan);
+        }
+        if increasing {
+            Self::new(function(low), function(high), may_be_nan)
+        } else {
+            Self::new(function(high), function(low), may_be_nan)
+        }
+    }
+
+    fn apply_increasing(&self, function: impl Fn(f64) -> f64) -> FloatValue {
+        self.apply_monotone(f64::NEG_INFINITY, f64::INFINITY, true, function)
+    }
+
+    /// Applies a periodic function, which is NaN for infinite arguments, and whose results are in `[-bound, bound]`
+    fn apply_periodic(&self, bound: f64) -> FloatValue {
+        let may_be_nan = self.may_be_nan || self.may_be_infinite();
+        if self.is_empty_range() || (self.low == self.high && self.low.is_infinite()) {
+            Self::new(f64::INFINITY, f64::NEG_INFINITY, may_be_nan)
+        } else {
+            Self::new(-bound, bound, may_be_nan)
+        }
+    }
+
+    /// Rounds the bounds to `f32`, whose finite values beyond the range of `f32` become infinite
+    pub fn to_f32(&self) -> FloatValue {
+        self.apply_increasing(|value| value as f32 as f64)
+    }
+
+    /// The result of calling the method `name` of `f32` or `f64` with the values of the arguments, e.g., `sqrt` of a
+    /// negative number is NaN. `None` if the method is not known
+    pub fn call(name: &str, args: &[FloatValue]) -> Option<FloatValue> {
+        let result = match (name, args) {
+            ("abs" | "fabs", [x]) => x.abs(),
+            ("neg", [x]) => x.neg(),
+            ("sqrt", [x]) => x.apply_monotone(0.0, f64::INFINITY, true, f64::sqrt),
+            ("cbrt", [x]) => x.apply_increasing(f64::cbrt),
+            // The intrinsic of `ln` is `log`
+            ("ln" | "log", [x]) => x.apply_monotone(0.0, f64::INFINITY, true, f64::ln),
+            ("log2", [x]) => x.apply_monotone(0.0, f64::INFINITY, true, f64::log2),
+            ("log10", [x]) => x.apply_monotone(0.0, f64::INFINITY, true, f64::log10),
+            // `x.log(base)` is `x.ln() / base.ln()`
+            ("log", [x, base]) => {
+                let ln_x = x.apply_monotone(0.0, f64::INFINITY, true, f64::ln);
+                let ln_base = base.apply_monotone(0.0, f64::INFINITY, true, f64::ln);
+                ln_x.div(&ln_base)
+            }
+            ("ln_1p", [x]) => x.apply_monotone(-1.0, f64::INFINITY, true, f64::ln_1p),
+            ("exp", [x]) => x.apply_increasing(f64::exp),
+            ("exp2", [x]) => x.apply_increasing(f64::exp2),
+            ("exp_m1", [x]) => x.apply_increasing(f64::exp_m1),
+            ("floor", [x]) => x.apply_increasing(f64::floor),
+            ("ceil", [x]) => x.apply_increasing(f64::ceil),
+            ("round", [x]) => x.apply_increasing(f64::round),
+            ("trunc", [x]) => x.apply_increasing(f64::trunc),
+            ("to_degrees", [x]) => x.apply_increasing(f64::to_degrees),
+            ("to_radians", [x]) => x.apply_increasing(f64::to_radians),
+            ("recip", [x]) => FloatValue::constant(1.0).div(x),
+            ("sin" | "cos", [x]) => x.apply_periodic(1.0),
+            ("tan", [x]) => x.apply_periodic(f64::INFINITY),
+            ("asin", [x]) => x.apply_monotone(-1.0, 1.0, true, f64::asin),
+            ("acos", [x]) => x.apply_monotone(-1.0, 1.0, false, f64::acos),
+            ("atan", [x]) => x.apply_increasing(f64::atan),
+            ("atan2", [y, x]) => Self::new(-PI, PI, x.may_be_nan || y.may_be_nan),
+            ("hypot", [x, y]) => Self::new(0.0, f64::INFINITY, x.may_be_nan || y.may_be_nan),
+            ("signum", [x]) => x.apply_increasing(f64::signum),
+            // `x.powi(n)` is not NaN unless `x` is NaN
+            ("powi", [x, _]) => Self::new(f64::NEG_INFINITY, f64::INFINITY, x.may_be_nan),
+            // A negative base with an exponent that is not an integer is NaN
+            ("powf" | "pow", [x, y]) => Self::new(
+                f64::NEG_INFINITY,
+                f64::INFINITY,
+                x.may_be_nan || y.may_be_nan || (!x.is_empty_range() && x.low < 0.0),
+            ),
+            // `min` and `max` return the other argument if one of them is NaN
+            ("min" | "minnum", [x, y]) => {
+                Self::new(x.low.min(y.low), x.high.min(y.high), false).join_if_nan(x, y)
+            }
+            ("max" | "maxnum", [x, y]) => {
+                Self::new(x.low.max(y.low), x.high.max(y.high), false).join_if_nan(x, y)
+            }
+            _ => return None,
+        };
+        Some(result)
+    }
+
+    /// NaN if both `self` and `other` may be NaN, used for the functions that return NaN only in this case
+    fn meet_nan(&self, other: &FloatValue) -> FloatValue {
+        Self::new(
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            self.may_be_nan && other.may_be_nan,
+        )
+    }
+
+    /// Adds the values of `y` if `x` may be NaN and the values of `x` if `y` may be NaN to the result of `min` or `max`
+    fn join_if_nan(&self, x: &FloatValue, y: &FloatValue) -> FloatValue {
+        let mut result = self.join(&x.meet_nan(y));
+        if x.may_be_nan {
+            result = result.join(&Self::new(y.low, y.high, false));
+        }
+        if y.may_be_nan {
+            result = result.join(&Self::new(x.low, x.high, false));
+        }
+        result
+    }
+}
@@ -231,6 +231,53 @@ impl Interval {
         self.high < self.low
     }
 
+    /// The smallest interval that contains both `self` and `other`
+    pub fn join(&self, other: &Interval) -> Interval {
+        if self.is_bottom() {
+            other.clone()
+        } else if other.is_bottom() {
+            self.clone()
+        } else {
+            Interval::new(
+                self.low.clone().min(other.low.clone()),
+                self.high.clone().max(other.high.clone()),
+            )
+        }
+    }
+
+    /// The largest interval that is contained in both `self` and `other`
+    pub fn meet(&self, other: &Interval) -> Interval {
+        if self.is_bottom() || other.is_bottom() {
+            Interval::bottom()
+        } else {
+            Interval::new(
+                self.low.clone().max(other.low.clone()),
+                self.high.clone().min(other.high.clone()),
+            )
+        }
+    }
+
+    /// Extrapolate the bounds of `self` that are not stable in `other` to infinity
+    pub fn widening_with(&self, other: &Interval) -> Interval {
+        if self.is_bottom() {
+            other.clone()
+        } else if other.is_bottom() {
+            self.clone()
+        } else {
+            let low = if other.low < self.low {
+                Self::NINF
+            } else {
+                self.low.clone()
+            };
+            let high = if other.high > self.high {
+                Self::INF
+            } else {
+                self.high.clone()
+            };
+            Interval::new(low, high)
+        }
+    }
+
     pub fn less_than(&self, other: &Interval) -> Option<bool> {
         if self.is_bottom() || self.is_top() || other.is_bottom() || other.is_top() {
             None
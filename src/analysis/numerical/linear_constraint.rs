@@ -1,10 +1,11 @@
 use crate::analysis::memory::expression::Expression;
-use crate::analysis::memory::path::{Path, PathEnum};
+use crate::analysis::memory::path::{Path, PathEnum, PathInterner};
 use crate::analysis::memory::symbolic_value::SymbolicValue;
 use crate::analysis::numerical::apron_domain::{
     ApronAbstractDomain, ApronDomainType, GetManagerTrait,
 };
 use crate::analysis::numerical::lattice::LatticeTrait;
+use crate::analysis::numerical::sparse_domain::SparseApronDomain;
 use apron_sys;
 use foreign_types::ForeignType;
 use rug::Integer;
@@ -207,8 +208,12 @@ impl<'a> IntoIterator for &'a mut LinearExpression {
     }
 }
 
-impl Debug for LinearExpression {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl LinearExpression {
+    /// Format the expression, using `var_name` to print each variable
+    pub fn to_string_with<F>(&self, var_name: &F) -> String
+    where
+        F: Fn(&Rc<Path>) -> String,
+    {
         let mut res = String::new();
         for (i, (v, n)) in self.cof_map.iter().enumerate() {
             if *n > 0 && i != 0 {
@@ -219,7 +224,7 @@ impl Debug for LinearExpression {
             } else if *n != 1 {
                 res.push_str(format!("{}*", n).as_str());
             }
-            res.push_str(format!("{:?}", v).as_str());
+            res.push_str(var_name(v).as_str());
         }
         if self.cst > 0 && !self.cof_map.is_empty() {
             res.push('+');
@@ -227,7 +232,13 @@ impl Debug for LinearExpression {
         if self.cst != 0 || self.cof_map.is_empty() {
             res.push_str(format!("{}", self.cst).as_str());
         }
-        write!(f, "{}", res)
+        res
+    }
+}
+
+impl Debug for LinearExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_string_with(&|v| format!("{:?}", v)))
     }
 }
 
@@ -268,8 +279,8 @@ fn symbolic_to_expression(val: Rc<SymbolicValue>) -> Result<LinearExpression, &'
             }
         }
         HeapBlock { .. } => {
-            let path: Path = PathEnum::HeapBlock { value: val }.into();
-            expr = expr + Rc::new(path);
+            let path = Path::intern(&PathInterner::current(), PathEnum::HeapBlock { value: val });
+            expr = expr + path;
         }
         Variable { path, var_type } => {
             if var_type.is_integer() {
@@ -376,6 +387,38 @@ impl LinearConstraint {
         }
     }
 
+    /// Format the constraint in Rust syntax, e.g., `x >= 0`, using `var_name` to print each variable
+    pub fn to_string_with<F>(&self, var_name: &F) -> String
+    where
+        F: Fn(&Rc<Path>) -> String,
+    {
+        if self.is_contradiction() {
+            return String::from("false");
+        } else if self.is_tautology() {
+            return String::from("true");
+        }
+        let (expr, op) = match self {
+            LinearConstraint::Equality(expr) => (expr, "=="),
+            LinearConstraint::Inequality(expr) => (expr, "!="),
+            LinearConstraint::LessEq(expr) => (expr, "<="),
+            LinearConstraint::LessThan(expr) => (expr, "<"),
+        };
+        let mut e = expr.clone() - expr.constant();
+        let mut c = -expr.constant();
+        let mut op = op;
+        // Prefer `x >= 0` to `-x <= 0`
+        if (&e).into_iter().all(|(_, n)| *n < 0) {
+            e = -e;
+            c = -c;
+            op = match op {
+                "<=" => ">=",
+                "<" => ">",
+                _ => op,
+            };
+        }
+        format!("{} {} {}", e.to_string_with(var_name), op, c)
+    }
+
     pub fn negate(&self) -> Self {
         if self.is_tautology() {
             Self::new_false()
@@ -390,6 +433,48 @@ impl LinearConstraint {
             }
         }
     }
+
+    /// Serialize the constraint as `{"op": op, "terms": {name: coefficient}, "constant": n}`,
+    /// standing for `sum(coefficient * name) + n op 0`, where each variable is named by `format!("{:?}", path)`
+    /// Integers are written as strings so that they are not truncated
+    pub fn to_json(&self) -> serde_json::Value {
+        let (expr, op) = match self {
+            LinearConstraint::Equality(expr) => (expr, "=="),
+            LinearConstraint::Inequality(expr) => (expr, "!="),
+            LinearConstraint::LessEq(expr) => (expr, "<="),
+            LinearConstraint::LessThan(expr) => (expr, "<"),
+        };
+        let terms: serde_json::Map<String, serde_json::Value> = expr
+            .into_iter()
+            .map(|(path, n)| {
+                (
+                    format!("{:?}", path),
+                    serde_json::Value::String(n.to_string()),
+                )
+            })
+            .collect();
+        serde_json::json!({
+            "op": op,
+            "terms": terms,
+            "constant": expr.constant().to_string(),
+        })
+    }
+
+    /// Deserialize a constraint written by `to_json`, the variables are created by `Path::new_named`
+    pub fn from_json(value: &serde_json::Value) -> Option<Self> {
+        let mut expr =
+            LinearExpression::from(value.get("constant")?.as_str()?.parse::<Integer>().ok()?);
+        for (name, n) in value.get("terms")?.as_object()? {
+            expr.add_term(Path::new_named(name), n.as_str()?.parse::<Integer>().ok()?);
+        }
+        match value.get("op")?.as_str()? {
+            "==" => Some(LinearConstraint::Equality(expr)),
+            "!=" => Some(LinearConstraint::Inequality(expr)),
+            "<=" => Some(LinearConstraint::LessEq(expr)),
+            "<" => Some(LinearConstraint::LessThan(expr)),
+            _ => None,
+        }
+    }
 }
 
 impl From<LinearConstraint> for LinearConstraintSystem {
@@ -619,6 +704,19 @@ impl LinearConstraintSystem {
     pub fn is_true(&self) -> bool {
         self.csts.is_empty()
     }
+
+    /// Serialize the constraints as an array, see `LinearConstraint::to_json`
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::Value::Array(self.csts.iter().map(LinearConstraint::to_json).collect())
+    }
+
+    pub fn from_json(value: &serde_json::Value) -> Option<Self> {
+        let mut result = Self::default();
+        for cst in value.as_array()? {
+            result.add(LinearConstraint::from_json(cst)?);
+        }
+        Some(result)
+    }
 }
 
 impl IntoIterator for LinearConstraintSystem {
@@ -695,6 +793,31 @@ where
     }
 }
 
+impl<Type> From<&SparseApronDomain<Type>> for LinearConstraintSystem
+where
+    Type: ApronDomainType,
+    ApronAbstractDomain<Type>: GetManagerTrait,
+{
+    fn from(inv: &SparseApronDomain<Type>) -> Self {
+        let dense = inv.get_dense();
+        let mut cst_system = if dense.is_top() && !inv.is_top() {
+            Self::default()
+        } else {
+            Self::from(dense)
+        };
+        if !inv.is_bottom() {
+            // Each constant `x == n` is represented as `x - n == 0`
+            for path in inv.get_paths_iter() {
+                if let Some(n) = inv.get_constant(&path) {
+                    let expr = LinearExpression::default() + path - n.clone();
+                    cst_system.add(LinearConstraint::Equality(expr));
+                }
+            }
+        }
+        cst_system
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
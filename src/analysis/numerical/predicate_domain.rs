@@ -0,0 +1,223 @@
+use crate::analysis::memory::path::Path;
+use crate::analysis::numerical::apron_domain::{
+    ApronAbstractDomain, ApronDomainType, GetManagerTrait,
+};
+use crate::analysis::numerical::lattice::LatticeTrait;
+use crate::analysis::numerical::linear_constraint::{
+    LinearConstraint, LinearConstraintSystem, LinearExpression,
+};
+use crate::analysis::numerical::sparse_domain::SparseApronDomain;
+use rug::Integer;
+use std::collections::BTreeSet;
+use std::rc::Rc;
+
+/// Parses a predicate given by `--predicate`, i.e., a linear inequality `lhs op rhs` where `op` is one of `<`, `<=`,
+/// `>`, `>=`, `==`, `!=`, and each side is a sum of terms of the form `n`, `x`, `n * x` or `x * n`, e.g.,
+/// `x + 2 * y < 100`
+/// Each variable is resolved into a path by `resolve`, which returns `None` if the variable is not known
+pub fn parse_predicate<F>(text: &str, mut resolve: F) -> Result<LinearConstraint, String>
+where
+    F: FnMut(&str) -> Option<Rc<Path>>,
+{
+    let operators = ["<=", ">=", "==", "!=", "<", ">"];
+    let (position, operator) = operators
+        .iter()
+        .filter_map(|operator| text.find(operator).map(|position| (position, *operator)))
+        .min_by_key(|(position, operator)| (*position, usize::MAX - operator.len()))
+        .ok_or_else(|| format!("no comparison operator in `{}`", text))?;
+    if text[..position].trim().is_empty() || text[position + operator.len()..].trim().is_empty() {
+        return Err(format!(
+            "`{}` has no expression on one side of `{}`",
+            text, operator
+        ));
+    }
+    let lhs = parse_linear_expression(&text[..position], &mut resolve)?;
+    let rhs = parse_linear_expression(&text[position + operator.len()..], &mut resolve)?;
+    Ok(match operator {
+        "<=" => LinearConstraint::LessEq(lhs - rhs),
+        ">=" => LinearConstraint::LessEq(rhs - lhs),
+        "==" => LinearConstraint::Equality(lhs - rhs),
+        "!=" => LinearConstraint::Inequality(lhs - rhs),
+        "<" => LinearConstraint::LessThan(lhs - rhs),
+        _ => LinearConstraint::LessThan(rhs - lhs),
+    })
+}
+
+/// Parses a sum of terms of the form `n`, `x`, `n * x` or `x * n`, separated by `+` or `-`
+fn parse_linear_expression<F>(text: &str, resolve: &mut F) -> Result<LinearExpression, String>
+where
+    F: FnMut(&str) -> Option<Rc<Path>>,
+{
+    let mut expression = LinearExpression::default();
+    let mut sign = 1;
+    let mut term = String::new();
+    let mut add_term = |term: &str, sign: i32| -> Result<(), String> {
+        let (coefficient, variable) = match term.split_once('*') {
+            Some((left, right)) if left.trim().parse::<Integer>().is_ok() => {
+                (left.trim(), Some(right.trim()))
+            }
+            Some((left, right)) => (right.trim(), Some(left.trim())),
+            None if term.starts_with(|c: char| c.is_ascii_digit()) => (term, None),
+            None => ("1", Some(term)),
+        };
+        let coefficient = coefficient
+            .parse::<Integer>()
+            .map_err(|_| format!("`{}` is not an integer", coefficient))?
+            * sign;
+        match variable {
+            Some(variable) => {
+                let is_identifier = variable.starts_with(|c: char| c.is_alphabetic() || c == '_')
+                    && variable.chars().all(|c| c.is_alphanumeric() || c == '_');
+                if !is_identifier {
+                    return Err(format!("`{}` is not a variable", variable));
+                }
+                let path =
+                    resolve(variable).ok_or_else(|| format!("unknown variable `{}`", variable))?;
+                expression.add_term(path, coefficient);
+            }
+            None => expression = expression.clone() + coefficient,
+        }
+        Ok(())
+    };
+    for c in text.chars().chain(std::iter::once('+')) {
+        match c {
+            '+' | '-' => {
+                let trimmed = term.trim();
+                if trimmed.is_empty() {
+                    // A unary sign, e.g., `-x`
+                    if c == '-' {
+                        sign = -sign;
+                    }
+                    continue;
+                }
+                add_term(trimmed, sign)?;
+                term.clear();
+                sign = if c == '-' { -1 } else { 1 };
+            }
+            _ => term.push(c),
+        }
+    }
+    if !term.trim().is_empty() {
+        return Err(format!("`{}` is not a linear expression", text.trim()));
+    }
+    Ok(expression)
+}
+
+/// The predicate abstraction over the predicates given by `--predicate`, which tracks the subset of the predicates
+/// that hold in an abstract state, by their indices in the list of the predicates
+/// The join of two states keeps the predicates that hold in both, and the meet keeps the predicates that hold in
+/// either. There are finitely many subsets, so the widening is the join
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PredicateDomain {
+    // `None` if the state is unreachable, where every predicate holds
+    holds: Option<BTreeSet<usize>>,
+}
+
+impl LatticeTrait for PredicateDomain {
+    fn top() -> Self {
+        Self {
+            holds: Some(BTreeSet::new()),
+        }
+    }
+
+    fn is_top(&self) -> bool {
+        matches!(&self.holds, Some(holds) if holds.is_empty())
+    }
+
+    fn set_to_top(&mut self) {
+        *self = Self::top();
+    }
+
+    fn bottom() -> Self {
+        Self { holds: None }
+    }
+
+    fn is_bottom(&self) -> bool {
+        self.holds.is_none()
+    }
+
+    fn set_to_bottom(&mut self) {
+        *self = Self::bottom();
+    }
+
+    fn lub(&self, other: &Self) -> Self {
+        match (&self.holds, &other.holds) {
+            (Some(holds1), Some(holds2)) => Self {
+                holds: Some(holds1.intersection(holds2).copied().collect()),
+            },
+            (None, _) => other.clone(),
+            (_, None) => self.clone(),
+        }
+    }
+
+    fn widening_with(&self, other: &Self) -> Self {
+        self.lub(other)
+    }
+}
+
+impl PredicateDomain {
+    /// The predicates of `predicates` that are entailed by `numerical_domain`, i.e., whose negations contradict it
+    pub fn from_numerical<Type>(
+        numerical_domain: &SparseApronDomain<Type>,
+        predicates: &[LinearConstraint],
+    ) -> Self
+    where
+        Type: ApronDomainType,
+        ApronAbstractDomain<Type>: GetManagerTrait,
+    {
+        if numerical_domain.is_bottom() {
+            return Self::bottom();
+        }
+        let entails = |constraint: LinearConstraint| {
+            let mut negated = numerical_domain.clone();
+            negated.add_constraints(LinearConstraintSystem::from(constraint.negate()));
+            negated.is_bottom()
+        };
+        let holds = predicates
+            .iter()
+            .enumerate()
+            .filter(|(_, predicate)| match predicate {
+                // The negation `e != 0` is not convex, so `e == 0` holds if both `e < 0` and `e > 0` contradict
+                LinearConstraint::Equality(expr) => {
+                    entails(LinearConstraint::LessEq(expr.clone()))
+                        && entails(LinearConstraint::LessEq(-expr.clone()))
+                }
+                _ => entails((*predicate).clone()),
+            })
+            .map(|(index, _)| index)
+            .collect();
+        Self { holds: Some(holds) }
+    }
+
+    /// Keeps the predicates that hold in either state
+    pub fn meet(&self, other: &Self) -> Self {
+        match (&self.holds, &other.holds) {
+            (Some(holds1), Some(holds2)) => Self {
+                holds: Some(holds1.union(holds2).copied().collect()),
+            },
+            _ => Self::bottom(),
+        }
+    }
+
+    /// Whether the predicate at `index` holds
+    pub fn holds(&self, index: usize) -> bool {
+        match &self.holds {
+            Some(holds) => holds.contains(&index),
+            None => true,
+        }
+    }
+
+    /// The predicates of `predicates` that hold, as constraints that can be added into a numerical domain
+    pub fn to_constraints(&self, predicates: &[LinearConstraint]) -> LinearConstraintSystem {
+        let mut constraints = LinearConstraintSystem::default();
+        match &self.holds {
+            Some(holds) => {
+                for index in holds {
+                    constraints.add(predicates[*index].clone());
+                }
+            }
+            None => constraints.add(LinearConstraint::new_false()),
+        }
+        constraints
+    }
+}
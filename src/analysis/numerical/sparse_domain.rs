@@ -0,0 +1,484 @@
+// A sparse representation of numerical abstract states on top of Apron domains
+
+use crate::analysis::memory::path::Path;
+use crate::analysis::numerical::apron_domain::{
+    ApronAbstractDomain, ApronDomainType, ApronOperation, GetManagerTrait,
+};
+use crate::analysis::numerical::interval::{Bound, Interval};
+use crate::analysis::numerical::lattice::LatticeTrait;
+use crate::analysis::numerical::linear_constraint::{
+    LinearConstraint, LinearConstraintSystem, LinearExpression,
+};
+use rug::Integer;
+use std::collections::BTreeMap;
+use std::fmt::{self, Debug};
+use std::rc::Rc;
+
+/// A numerical abstract domain that only keeps the paths with non-trivial values in the Apron element
+/// Most paths in a function are assigned constants and never related to other paths, e.g., the
+/// discriminants, the lengths of arrays or the temporaries holding literals. They are stored in `constants`
+/// instead, so that join and widening, whose costs grow quickly with the number of dimensions, only work on
+/// the paths that really need a relational domain
+/// A path is added to the Apron element lazily, when it is used by an operation that establishes
+/// a relation with other paths, and a path that is in neither part is unconstrained
+pub struct SparseApronDomain<Type>
+where
+    Type: ApronDomainType,
+    ApronAbstractDomain<Type>: GetManagerTrait,
+{
+    // The paths that are related to other paths, or whose values are not constants
+    dense: ApronAbstractDomain<Type>,
+    // The paths that are known to be constants, which never appear in `dense`
+    constants: BTreeMap<Rc<Path>, Integer>,
+}
+
+impl<Type> Clone for SparseApronDomain<Type>
+where
+    Type: ApronDomainType,
+    ApronAbstractDomain<Type>: GetManagerTrait,
+{
+    fn clone(&self) -> Self {
+        Self {
+            dense: self.dense.clone(),
+            constants: self.constants.clone(),
+        }
+    }
+}
+
+impl<Type> Default for SparseApronDomain<Type>
+where
+    Type: ApronDomainType,
+    ApronAbstractDomain<Type>: GetManagerTrait,
+{
+    fn default() -> Self {
+        Self::top()
+    }
+}
+
+impl<Type> LatticeTrait for SparseApronDomain<Type>
+where
+    Type: ApronDomainType,
+    ApronAbstractDomain<Type>: GetManagerTrait,
+{
+    fn top() -> Self {
+        Self {
+            dense: ApronAbstractDomain::top(),
+            constants: BTreeMap::new(),
+        }
+    }
+
+    fn bottom() -> Self {
+        Self {
+            dense: ApronAbstractDomain::bottom(),
+            constants: BTreeMap::new(),
+        }
+    }
+
+    fn set_to_top(&mut self) {
+        *self = Self::top();
+    }
+
+    fn set_to_bottom(&mut self) {
+        *self = Self::bottom();
+    }
+
+    fn is_top(&self) -> bool {
+        self.constants.is_empty() && self.dense.is_top()
+    }
+
+    fn is_bottom(&self) -> bool {
+        self.dense.is_bottom()
+    }
+
+    fn lub(&self, other: &Self) -> Self {
+        self.join(other)
+    }
+
+    fn widening_with(&self, other: &Self) -> Self {
+        self.widening_with(other)
+    }
+}
+
+impl<Type> SparseApronDomain<Type>
+where
+    Type: ApronDomainType,
+    ApronAbstractDomain<Type>: GetManagerTrait,
+{
+    /// Determine whether `lhs <= rhs`, with respect to the partial ordering defined by lattice
+    pub fn leq(&self, other: &Self) -> bool {
+        if self.is_bottom() {
+            true
+        } else if other.is_bottom() {
+            false
+        } else {
+            let (lhs, rhs, _) = Self::unify(self, other);
+            lhs.leq(&rhs)
+        }
+    }
+
+    /// Used to handle move assignments: `new_path = old_path;`
+    pub fn rename(&mut self, old_path: &Rc<Path>, new_path: &Rc<Path>) {
+        if self.contains(old_path) {
+            self.assign_var(new_path.clone(), old_path.clone());
+            self.forget(old_path);
+        }
+    }
+
+    /// Used to handle copy assignments: `new_path = old_path;`
+    pub fn duplicate(&mut self, old_path: &Rc<Path>, new_path: &Rc<Path>) {
+        if self.contains(old_path) {
+            self.assign_var(new_path.clone(), old_path.clone());
+        }
+    }
+
+    /// Get a list of paths that are in the current domain, in order
+    pub fn get_paths_iter(&self) -> Vec<Rc<Path>> {
+        let mut paths = self.dense.get_paths_iter();
+        paths.extend(self.constants.keys().cloned());
+        paths.sort();
+        paths
+    }
+
+    /// Determine whether `path` is in the current domain
+    pub fn contains(&self, path: &Rc<Path>) -> bool {
+        self.constants.contains_key(path) || self.dense.contains(path)
+    }
+
+    /// Get the constant value of `path`, if it is not in the Apron element
+    pub fn get_constant(&self, path: &Rc<Path>) -> Option<&Integer> {
+        self.constants.get(path)
+    }
+
+    /// Get the Apron element, which does not contain the paths that are constants
+    pub fn get_dense(&self) -> &ApronAbstractDomain<Type> {
+        &self.dense
+    }
+
+    /// Get abstract value according to the given path, and transform it to an interval
+    pub fn get_interval(&self, var: &Rc<Path>) -> Interval {
+        if self.is_bottom() {
+            Interval::bottom()
+        } else if let Some(n) = self.constants.get(var) {
+            Interval::new(Bound::Int(n.clone()), Bound::Int(n.clone()))
+        } else {
+            self.dense.get_interval(var)
+        }
+    }
+
+    /// Handle assignment `var = n` where n is a constant integer
+    pub fn assign_int(&mut self, var: Rc<Path>, n: Integer) {
+        if !self.is_bottom() {
+            self.dense.forget(&var);
+            self.constants.insert(var, n);
+        }
+    }
+
+    /// Handle assignment `var = rvalue` where `rvalue` is a path
+    pub fn assign_var(&mut self, var: Rc<Path>, rvalue: Rc<Path>) {
+        if let Some(n) = self.constants.get(&rvalue).cloned() {
+            self.assign_int(var, n);
+        } else if !self.is_bottom() {
+            self.constants.remove(&var);
+            self.dense.assign_var(var, rvalue);
+        }
+    }
+
+    /// Forget the value of `v` and constrain it to be within `itv`
+    pub fn set_interval(&mut self, v: &Rc<Path>, itv: Interval) {
+        match (&itv.low, &itv.high) {
+            (Bound::Int(low), Bound::Int(high)) if low == high => {
+                let n = low.clone();
+                self.assign_int(v.clone(), n);
+            }
+            _ => {
+                self.constants.remove(v);
+                self.dense.set_interval(v, itv);
+            }
+        }
+    }
+
+    /// Compute narrowing
+    pub fn narrowing_with(&self, rhs: &Self) -> Self {
+        if self.is_bottom() || rhs.is_bottom() {
+            Self::bottom()
+        } else {
+            let (lhs, rhs, constants) = Self::unify(self, rhs);
+            Self::from_parts(lhs.narrowing_with(&rhs), constants)
+        }
+    }
+
+    /// Compute widening
+    pub fn widening_with(&self, rhs: &Self) -> Self {
+        if self.is_bottom() {
+            rhs.clone()
+        } else if rhs.is_bottom() {
+            self.clone()
+        } else {
+            let (lhs, rhs, constants) = Self::unify(self, rhs);
+            Self::from_parts(lhs.widening_with(&rhs), constants)
+        }
+    }
+
+    /// Compute the least upper bound
+    pub fn join(&self, rhs: &Self) -> Self {
+        if self.is_bottom() {
+            rhs.clone()
+        } else if rhs.is_bottom() {
+            self.clone()
+        } else {
+            let (lhs, rhs, constants) = Self::unify(self, rhs);
+            Self::from_parts(lhs.join(&rhs), constants)
+        }
+    }
+
+    /// Compute the greatest lower bound
+    pub fn meet(&self, rhs: &Self) -> Self {
+        if self.is_bottom() || rhs.is_bottom() {
+            Self::bottom()
+        } else {
+            let (lhs, rhs, constants) = Self::unify(self, rhs);
+            Self::from_parts(lhs.meet(&rhs), constants)
+        }
+    }
+
+    /// Apply the binary operation statement: `res = lhs op rhs`
+    pub fn apply_bin_op_place_place(
+        &mut self,
+        op: ApronOperation,
+        lhs: &Rc<Path>,
+        rhs: &Rc<Path>,
+        res: &Rc<Path>,
+    ) {
+        self.apply_with_operands(&[lhs, rhs], res, |dense| {
+            dense.apply_bin_op_place_place(op, lhs, rhs, res)
+        });
+    }
+
+    /// Apply the binary operation statement: `res = cst op rhs`
+    pub fn apply_bin_op_const_place(
+        &mut self,
+        op: ApronOperation,
+        cst: &Integer,
+        rhs: &Rc<Path>,
+        res: &Rc<Path>,
+    ) {
+        self.apply_with_operands(&[rhs], res, |dense| {
+            dense.apply_bin_op_const_place(op, cst, rhs, res)
+        });
+    }
+
+    /// Apply the binary operation statement: `res = lhs op cst`
+    pub fn apply_bin_op_place_const(
+        &mut self,
+        op: ApronOperation,
+        lhs: &Rc<Path>,
+        cst: &Integer,
+        res: &Rc<Path>,
+    ) {
+        self.apply_with_operands(&[lhs], res, |dense| {
+            dense.apply_bin_op_place_const(op, lhs, cst, res)
+        });
+    }
+
+    /// Apply the pointer offset statement: `res = base op count * size`, where `op` is `Add` or `Sub`
+    pub fn apply_pointer_offset(
+        &mut self,
+        op: ApronOperation,
+        base: &Rc<Path>,
+        count: &Rc<Path>,
+        size: &Integer,
+        res: &Rc<Path>,
+    ) {
+        self.apply_with_operands(&[base, count], res, |dense| {
+            dense.apply_pointer_offset(op, base, count, size, res)
+        });
+    }
+
+    /// Apply the unary operation statement: `res = - rhs`, or `res = !rhs`
+    pub fn apply_un_op_place(&mut self, op: ApronOperation, rhs: &Rc<Path>, res: &Rc<Path>) {
+        self.apply_with_operands(&[rhs], res, |dense| dense.apply_un_op_place(op, rhs, res));
+    }
+
+    /// Remove a path from current abstract domain
+    pub fn forget(&mut self, var: &Rc<Path>) {
+        if self.constants.remove(var).is_none() {
+            self.dense.forget(var);
+        }
+    }
+
+    /// Remove several paths at once, see `ApronAbstractDomain::forget_all`
+    pub fn forget_all(&mut self, vars: &[Rc<Path>]) {
+        let dense_vars: Vec<Rc<Path>> = vars
+            .iter()
+            .filter(|var| self.constants.remove(*var).is_none())
+            .cloned()
+            .collect();
+        self.dense.forget_all(&dense_vars);
+    }
+
+    /// Project the current abstract domain onto `paths`, i.e., eliminate all the other paths
+    pub fn project(&self, paths: &[Rc<Path>]) -> Self {
+        let mut constants = self.constants.clone();
+        constants.retain(|path, _| paths.contains(path));
+        Self::from_parts(self.dense.project(paths), constants)
+    }
+
+    /// Add a linear constraint system into current abstract domain
+    /// The constants are substituted into the constraints first, so that only the paths in the Apron
+    /// element are constrained, and a constraint that only involves constants is decided directly
+    pub fn add_constraints(&mut self, conds: LinearConstraintSystem) {
+        if self.is_bottom() {
+            return;
+        }
+        let mut csts = LinearConstraintSystem::default();
+        for cst in conds {
+            let cst = match cst {
+                LinearConstraint::Equality(expr) => {
+                    LinearConstraint::Equality(self.substitute(expr))
+                }
+                LinearConstraint::Inequality(expr) => {
+                    LinearConstraint::Inequality(self.substitute(expr))
+                }
+                LinearConstraint::LessEq(expr) => LinearConstraint::LessEq(self.substitute(expr)),
+                LinearConstraint::LessThan(expr) => {
+                    LinearConstraint::LessThan(self.substitute(expr))
+                }
+            };
+            if cst.is_contradiction() {
+                self.set_to_bottom();
+                return;
+            } else if !cst.is_tautology() {
+                csts.add(cst);
+            }
+        }
+        self.dense.add_constraints(csts);
+        if self.dense.is_bottom() {
+            self.constants.clear();
+        }
+    }
+
+    /// Get an Apron element that contains all the paths of the current domain
+    pub fn to_dense(&self) -> ApronAbstractDomain<Type> {
+        let mut dense = self.dense.clone();
+        for (path, n) in &self.constants {
+            dense.assign_int(path.clone(), n.clone());
+        }
+        dense
+    }
+
+    fn from_parts(
+        dense: ApronAbstractDomain<Type>,
+        constants: BTreeMap<Rc<Path>, Integer>,
+    ) -> Self {
+        if dense.is_bottom() {
+            Self::bottom()
+        } else {
+            Self { dense, constants }
+        }
+    }
+
+    /// Move the constants that do not have the same value in both domains into their Apron elements,
+    /// returning the two Apron elements and the constants that are shared by both domains
+    fn unify(
+        lhs: &Self,
+        rhs: &Self,
+    ) -> (
+        ApronAbstractDomain<Type>,
+        ApronAbstractDomain<Type>,
+        BTreeMap<Rc<Path>, Integer>,
+    ) {
+        let mut lhs_dense = lhs.dense.clone();
+        let mut rhs_dense = rhs.dense.clone();
+        let mut shared = BTreeMap::new();
+        for (path, n) in &lhs.constants {
+            if rhs.constants.get(path) == Some(n) {
+                shared.insert(path.clone(), n.clone());
+            } else {
+                lhs_dense.assign_int(path.clone(), n.clone());
+            }
+        }
+        for (path, n) in &rhs.constants {
+            if !shared.contains_key(path) {
+                rhs_dense.assign_int(path.clone(), n.clone());
+            }
+        }
+        (lhs_dense, rhs_dense, shared)
+    }
+
+    /// Apply `apply` to compute `res` from `operands`
+    /// If all the operands are constants, the operation is evaluated on an Apron element that only contains
+    /// the operands, and `res` remains sparse if its value is a constant
+    /// Otherwise, the operands that are constants are added to the Apron element before applying `apply` to it
+    fn apply_with_operands<F>(&mut self, operands: &[&Rc<Path>], res: &Rc<Path>, apply: F)
+    where
+        F: Fn(&mut ApronAbstractDomain<Type>),
+    {
+        if self.is_bottom() {
+            return;
+        }
+        if operands
+            .iter()
+            .all(|path| self.constants.contains_key(*path))
+        {
+            let mut scratch = ApronAbstractDomain::<Type>::top();
+            for path in operands {
+                scratch.assign_int((*path).clone(), self.constants[*path].clone());
+            }
+            apply(&mut scratch);
+            if scratch.is_bottom() {
+                self.set_to_bottom();
+                return;
+            }
+            let itv = scratch.get_interval(res);
+            if let (Bound::Int(low), Bound::Int(high)) = (&itv.low, &itv.high) {
+                if low == high {
+                    let n = low.clone();
+                    self.assign_int(res.clone(), n);
+                    return;
+                }
+            }
+        }
+        for path in operands {
+            if let Some(n) = self.constants.remove(*path) {
+                self.dense.assign_int((*path).clone(), n);
+            }
+        }
+        self.constants.remove(res);
+        apply(&mut self.dense);
+        if self.dense.is_bottom() {
+            self.constants.clear();
+        }
+    }
+
+    /// Replace the paths that are constants in `expr` by their values
+    fn substitute(&self, expr: LinearExpression) -> LinearExpression {
+        let mut res = LinearExpression::from(expr.constant());
+        for (path, coeff) in expr {
+            if let Some(n) = self.constants.get(&path) {
+                res = res + Integer::from(&coeff * n);
+            } else {
+                res.add_term(path, coeff);
+            }
+        }
+        res
+    }
+}
+
+impl<Type> Debug for SparseApronDomain<Type>
+where
+    Type: ApronDomainType,
+    ApronAbstractDomain<Type>: GetManagerTrait,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut res = String::new();
+        if self.is_bottom() {
+            res.push('⊥');
+        } else if self.is_top() {
+            res.push('⊤');
+        } else {
+            let constraint_system = LinearConstraintSystem::from(self);
+            res.push_str(format!("{:?}", constraint_system).as_str());
+        }
+        write!(f, "{}", res)
+    }
+}
@@ -0,0 +1,88 @@
+// The values of machine integers with wraparound semantics, used by the `wrapping-integer` abstract domain
+
+use crate::analysis::numerical::interval::{Bound, Interval};
+use rug::Integer;
+
+/// The values of an integer type of `bit_width` bits, which are computed modulo `2^bit_width`
+/// The numerical domain computes the mathematical result of an arithmetic operation, which is then mapped
+/// back into the range of the type by `wrap`, so that an overflow wraps around instead of being reported
+/// An interval that crosses a bound of the range is split into two parts, one on each side of the bound,
+/// whose join is the result, i.e., the intervals cannot represent a range that wraps around
+pub struct WrappingIntegerDomain {
+    bit_width: u64,
+    signed: bool,
+}
+
+impl WrappingIntegerDomain {
+    pub fn new(bit_width: u64, signed: bool) -> Self {
+        WrappingIntegerDomain { bit_width, signed }
+    }
+
+    /// `2^bit_width`, i.e., the number of the values of the type
+    fn modulus(&self) -> Integer {
+        Integer::from(1) << (self.bit_width as u32)
+    }
+
+    /// The smallest value of the type
+    pub fn min(&self) -> Integer {
+        if self.signed {
+            -(self.modulus() >> 1u32)
+        } else {
+            Integer::from(0)
+        }
+    }
+
+    /// The largest value of the type
+    pub fn max(&self) -> Integer {
+        if self.signed {
+            (self.modulus() >> 1u32) - 1
+        } else {
+            self.modulus() - 1
+        }
+    }
+
+    /// All the values of the type
+    pub fn full_range(&self) -> Interval {
+        Interval::new(Bound::Int(self.min()), Bound::Int(self.max()))
+    }
+
+    /// Whether all the values of `interval` are in the range of the type, i.e., no value wraps around
+    pub fn contains(&self, interval: &Interval) -> bool {
+        interval.is_bottom() || self.full_range().meet(interval) == *interval
+    }
+
+    /// The values of `interval` modulo `2^bit_width`, in the range of the type
+    pub fn wrap(&self, interval: &Interval) -> Interval {
+        if self.contains(interval) {
+            return interval.clone();
+        }
+        match (&interval.low, &interval.high) {
+            (Bound::Int(low), Bound::Int(high)) => {
+                let modulus = self.modulus();
+                if Integer::from(high - low) >= modulus {
+                    return self.full_range();
+                }
+                // Shift both bounds by the same multiple of the modulus, so that `low` is in the range of the type
+                let (quotient, _) = (low - self.min()).div_rem_floor(modulus.clone());
+                let shift = quotient * &modulus;
+                let low = Integer::from(low - &shift);
+                let high = Integer::from(high - &shift);
+                if high <= self.max() {
+                    Interval::new(Bound::Int(low), Bound::Int(high))
+                } else {
+                    let wrapped_high = high - modulus;
+                    self.join(
+                        &Interval::new(Bound::Int(low), Bound::Int(self.max())),
+                        &Interval::new(Bound::Int(self.min()), Bound::Int(wrapped_high)),
+                    )
+                }
+            }
+            _ => self.full_range(),
+        }
+    }
+
+    /// The smallest interval in the range of the type that contains both `left` and `right` modulo `2^bit_width`
+    pub fn join(&self, left: &Interval, right: &Interval) -> Interval {
+        self.wrap(left).join(&self.wrap(right))
+    }
+}
@@ -1,7 +1,10 @@
+use crate::analysis::checkpoint;
 use crate::analysis::diagnostics::DiagnosticCause;
-use log::warn;
+use crate::analysis::memory::path::Path;
+use crate::analysis::numerical::predicate_domain;
+use crate::checker::{ffi_checker, taint_sink_checker};
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum AbstractDomainType {
     Interval,
     Octagon,
@@ -10,147 +13,803 @@ pub enum AbstractDomainType {
     PplPolyhedra,
     PplLinearCongruences,
     PkgridPolyhedraLinCongruences,
+    WrappingInteger,
 }
 
-#[derive(Clone, Debug)]
-pub struct AnalysisOption {
-    pub entry_point: String,
-    pub entry_def_id_index: Option<u32>,
-    pub domain_type: AbstractDomainType,
-    pub widening_delay: u32,
-    pub cleaning_delay: usize,
-    pub narrowing_iteration: u32,
-    pub show_entries: bool,
-    pub show_entries_index: bool,
-    pub deny_warnings: bool,
-    pub memory_safety_only: bool,
-    pub suppressed_warnings: Option<Vec<DiagnosticCause>>,
+impl AbstractDomainType {
+    /// The name of the domain, as it is given to the `domain` option
+    pub fn name(&self) -> &'static str {
+        match self {
+            AbstractDomainType::Interval => "interval",
+            AbstractDomainType::Octagon => "octagon",
+            AbstractDomainType::Polyhedra => "polyhedra",
+            AbstractDomainType::LinearEqualities => "linear_equalities",
+            AbstractDomainType::PplPolyhedra => "ppl_polyhedra",
+            AbstractDomainType::PplLinearCongruences => "ppl_linear_congruences",
+            AbstractDomainType::PkgridPolyhedraLinCongruences => {
+                "pkgrid_polyhedra_linear_congruences"
+            }
+            AbstractDomainType::WrappingInteger => "wrapping-integer",
+        }
+    }
+
+    /// Whether the domain can represent arbitrary linear inequalities between variables
+    pub fn is_polyhedra(&self) -> bool {
+        matches!(
+            self,
+            AbstractDomainType::Polyhedra
+                | AbstractDomainType::PplPolyhedra
+                | AbstractDomainType::PkgridPolyhedraLinCongruences
+        )
+    }
+}
+
+/// All the abstract domains, in the order they are listed in error messages
+pub const DOMAIN_TYPES: [AbstractDomainType; 8] = [
+    AbstractDomainType::Interval,
+    AbstractDomainType::Octagon,
+    AbstractDomainType::Polyhedra,
+    AbstractDomainType::LinearEqualities,
+    AbstractDomainType::PplPolyhedra,
+    AbstractDomainType::PplLinearCongruences,
+    AbstractDomainType::PkgridPolyhedraLinCongruences,
+    AbstractDomainType::WrappingInteger,
+];
+
+/// The options that take a value, e.g., `--domain interval`
+const VALUE_OPTIONS: [&str; 30] = [
+    "check",
+    "taint-source",
+    "domain",
+    "entry",
+    "entry_def_id_index",
+    "entry-pattern",
+    "widening_delay",
+    "narrowing_iteration",
+    "suppress_warnings",
+    "output-format",
+    "output-file",
+    "output-crate-name",
+    "unroll-threshold",
+    "inline-depth",
+    "max-loop-depth",
+    "predicate",
+    "cleaning_delay",
+    "export-invariants",
+    "export-contracts",
+    "cache-dir",
+    "dump-callgraph",
+    "trace-function",
+    "checkpoint-dir",
+    "dump-states",
+    "dump-wto",
+    "dump-dataflow",
+    "analysis-type",
+    "config",
+    "termination-depth",
+    "ffi-stubs",
+];
+
+/// The prefix of the kinds of suppressed warnings that name the causes of custom checkers, e.g., `custom:unsafe-ffi`
+const CUSTOM_CAUSE_PREFIX: &str = "custom:";
+
+/// The checkers that are enabled by the `check` option
+const CHECKERS: [&str; 20] = [
+    "raw-ptr-bounds",
+    "raw-offset-overflow",
+    "panic-reachability",
+    "shift-overflow",
+    "shift-by-const",
+    "shift-sign",
+    "int-promotion",
+    "dangling-pointer",
+    "double-free",
+    "use-of-uninitialized-global",
+    "termination",
+    "lock-order",
+    "channel-capacity",
+    "pin-safety",
+    "vec-bounds",
+    "str-index",
+    "transmute-safety",
+    "padding-uninit",
+    "float-nan",
+    "dead-branch",
+];
+
+/// The kind of analysis that is run, the numerical analysis runs in every kind
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AnalysisType {
+    // The numerical analysis and the enabled checkers
+    Numerical,
+    // Also report the taint sinks called on data derived from the taint sources, see `TaintSinkChecker`
+    Taint,
+}
+
+impl AnalysisType {
+    /// The name of the analysis type, as it is given to the `analysis-type` option
+    pub fn name(&self) -> &'static str {
+        match self {
+            AnalysisType::Numerical => "numerical",
+            AnalysisType::Taint => "taint",
+        }
+    }
+}
+
+/// All the analysis types, in the order they are listed in error messages
+const ANALYSIS_TYPES: [AnalysisType; 2] = [AnalysisType::Numerical, AnalysisType::Taint];
+
+/// How the diagnostics are printed
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OutputFormat {
+    // The default format of rustc
+    Human,
+    // Workflow commands that are turned into annotations by GitHub Actions
+    GithubActions,
+    // A code quality report of GitLab CI
+    Gitlab,
+    // GCC-style lines that are recognized by the `$gcc` problem matcher of VS Code
+    Vscode,
+    // Rows appended to a SQLite database, see `sqlite_export`
+    Sqlite,
+}
+
+impl OutputFormat {
+    /// The name of the format, as it is given to the `output-format` option
+    pub fn name(&self) -> &'static str {
+        match self {
+            OutputFormat::Human => "human",
+            OutputFormat::GithubActions => "github-actions",
+            OutputFormat::Gitlab => "gitlab",
+            OutputFormat::Vscode => "vscode",
+            OutputFormat::Sqlite => "sqlite",
+        }
+    }
+}
+
+/// All the output formats, in the order they are listed in error messages
+const OUTPUT_FORMATS: [OutputFormat; 5] = [
+    OutputFormat::Human,
+    OutputFormat::GithubActions,
+    OutputFormat::Gitlab,
+    OutputFormat::Vscode,
+    OutputFormat::Sqlite,
+];
+
+/// The kinds of warnings that can be suppressed, i.e., the character that represents each kind in the
+/// `suppress_warnings` option, the cause of the warnings and its description
+const WARNING_KINDS: [(char, DiagnosticCause, &str); 14] = [
+    ('a', DiagnosticCause::Arithmetic, "arithmetic overflow"),
+    ('b', DiagnosticCause::Bitwise, "bit-wise overflow"),
+    ('s', DiagnosticCause::Assembly, "inline assembly"),
+    ('c', DiagnosticCause::Comparison, "comparison operations"),
+    ('d', DiagnosticCause::DivZero, "division by zero"),
+    ('m', DiagnosticCause::Memory, "memory-safety issues"),
+    ('p', DiagnosticCause::Panic, "run into panic code"),
+    ('i', DiagnosticCause::Index, "out-of-bounds access"),
+    ('f', DiagnosticCause::FormatString, "tainted format strings"),
+    ('u', DiagnosticCause::DeadBranch, "unreachable code"),
+    ('t', DiagnosticCause::Termination, "non-terminating loops"),
+    ('l', DiagnosticCause::Deadlock, "inconsistent lock orders"),
+    ('k', DiagnosticCause::Channel, "blocked channel sends"),
+    ('n', DiagnosticCause::TaintSink, "tainted sink arguments"),
+];
+
+/// Declares `AnalysisOption` together with the default value of each field, so that the JSON schema of the
+/// configuration is generated from the same declarations, with the doc comment of each field as its description
+macro_rules! declare_analysis_options {
+    ($($(#[doc = $doc:literal])+ $field:ident: $ty:ty = $default:expr,)*) => {
+        #[derive(Clone, Debug)]
+        pub struct AnalysisOption {
+            $($(#[doc = $doc])+ pub $field: $ty,)*
+        }
+
+        impl Default for AnalysisOption {
+            fn default() -> Self {
+                Self {
+                    $($field: $default,)*
+                }
+            }
+        }
+
+        impl AnalysisOption {
+            /// The JSON schema of each field, together with its name
+            fn field_schemas() -> Vec<(&'static str, serde_json::Value)> {
+                let default = Self::default();
+                vec![$((
+                    stringify!($field),
+                    field_schema(&default.$field, &[$($doc),+]),
+                ),)*]
+            }
+        }
+    };
+}
+
+declare_analysis_options! {
+    /// The name of the entry function, e.g., `main` or `foo::bar`
+    entry_point: String = String::from("main"),
+    /// The index of the DefId of the entry function, as listed by `show_entries_index`
+    entry_def_id_index: Option<u32> = None,
+    /// The glob patterns of the names of the entry functions, e.g., `test_*`, where `*` matches any sequence of
+    /// characters and `?` matches one character, all the functions that match any of them are entry points
+    entry_patterns: Vec<String> = Vec::new(),
+    /// The numerical abstract domain
+    domain_type: AbstractDomainType = AbstractDomainType::Interval,
+    /// The number of iterations before triggering widening
+    widening_delay: u32 = 5,
+    /// The number of iterations before the abstract states are cleaned
+    cleaning_delay: usize = 5,
+    /// The maximum number of narrowing operations that may improve the result
+    narrowing_iteration: u32 = 5,
+    /// Print the functions that can be used as entry points
+    show_entries: bool = false,
+    /// Print the functions that can be used as entry points, together with the indices of their DefIds
+    show_entries_index: bool = false,
+    /// Turn the warnings into errors
+    deny_warnings: bool = false,
+    /// Only report memory-safety issues
+    memory_safety_only: bool = false,
+    /// Also analyze the functions generated by macros, e.g., `#[derive(Debug)]`
+    check_macros: bool = false,
+    /// Warn about raw pointer dereferences that may be out of the bounds of the pointed allocation
+    check_raw_ptr_bounds: bool = false,
+    /// Warn about the calls of `offset` on raw pointers whose byte offsets may overflow `isize`
+    check_raw_offset_overflow: bool = false,
+    /// Warn about the calls that panic or abort in the reachable basic blocks
+    check_panic_reachability: bool = false,
+    /// Check that the shift amounts are less than the bit widths of the shifted values, in the numerical domain
+    check_shift_overflow: bool = false,
+    /// Report the shifts whose shift amounts are constants that are negative or not less than the bit widths of the
+    /// shifted values, without the numerical domain
+    check_shift_by_const: bool = false,
+    /// Warn about the left shifts of signed integers that may be negative, and note the right shifts of them
+    check_shift_sign: bool = false,
+    /// Warn about the casts of unsigned integers into signed integer types of the same or a larger width that may
+    /// make the values negative
+    check_int_promotion: bool = false,
+    /// Warn about the loops that no linear ranking function is found for, which requires a polyhedra domain
+    check_termination: bool = false,
+    /// The largest absolute value of the coefficients of the ranking functions that are searched for each loop
+    termination_depth: u32 = 1,
+    /// Warn about the mutexes that may be acquired in different orders in a function, which may deadlock
+    check_lock_order: bool = false,
+    /// Warn about the sends on a bounded channel created by `sync_channel` that may block because the channel is full
+    check_channel_capacity: bool = false,
+    /// Warn about the values pinned by `Pin::new_unchecked` that are moved afterwards, and the `Pin`s that are
+    /// converted into pointers that are not pinned
+    check_pin_safety: bool = false,
+    /// Warn about the raw pointers to the variables of a function that are stored into the return value, a static
+    /// variable, the memory pointed to by a parameter or an escaping heap allocation
+    check_dangling_pointer: bool = false,
+    /// Warn about the calls that free a pointer, e.g., `drop_in_place` or `Box::from_raw`, after it may have been
+    /// freed on a path that reaches them
+    check_double_free: bool = false,
+    /// Warn about the reads of mutable statics that may not be preceded by a write on the paths that reach them
+    check_use_of_uninitialized_global: bool = false,
+    /// Track the lengths and the capacities of vectors, and warn about the `get_unchecked` that may be out of
+    /// bounds, including the ranges on slices and strings, and the `set_len` that may exceed the capacity
+    check_vec_bounds: bool = false,
+    /// Track the lengths of string slices, and warn about the `&s[start..end]` and `s.split_at(mid)` on a `str`
+    /// that may be out of bounds, and the `split_at` at an unknown index that may not be a character boundary
+    check_str_index: bool = false,
+    /// Warn about the transmutes between types of different sizes, and the transmutes between pointers that may
+    /// produce misaligned pointers
+    check_transmute_safety: bool = false,
+    /// Warn about the transmutes of values that have padding bytes into arrays of bytes, which expose the
+    /// uninitialized padding
+    check_padding_uninit: bool = false,
+    /// Track whether the float-typed local variables may be NaN or infinite, and warn about the comparisons with
+    /// values that may be NaN
+    check_float_nan: bool = false,
+    /// Note the targets of `SwitchInt` terminators that are never taken, e.g., the match arms of the variants that
+    /// the matched value is proved not to be
+    check_dead_branch: bool = false,
+    /// The kind of analysis, `taint` also reports the taint sinks called on data derived from the taint sources
+    analysis_type: AnalysisType = AnalysisType::Numerical,
+    /// The configuration file that the taint sources and sinks are read from by `--analysis-type taint`
+    config: String = String::from("mir-checker.toml"),
+    /// The TOML file of the contracts of the C functions, which the arguments of the calls to them are checked against
+    ffi_stubs: Option<String> = None,
+    /// Report every site that may panic, including the reachable panic calls and the unwraps that are not proved
+    /// to succeed, as memory-safety issues, and fail if any of them is reported
+    panic_freedom: bool = false,
+    /// Report the basic blocks that are unreachable according to the abstract interpretation, as notes
+    report_dead_code: bool = false,
+    /// Show the names of the user variables in the diagnostics instead of the MIR locals, e.g., `count + offset`
+    /// instead of `_3 + _5`
+    verbose_diagnostics: bool = false,
+    /// The functions that are sources of untrusted data
+    taint_sources: Vec<String> = Vec::new(),
+    /// The linear inequalities over the variables of the analyzed functions, e.g., `x + y < 100`, which are kept
+    /// when the states of the predecessors of a basic block are joined if they hold in all of them
+    predicates: Vec<String> = Vec::new(),
+    /// The kinds of warnings that are not reported, each character represents a kind of warning, e.g., `ai`
+    suppressed_warnings: Option<Vec<DiagnosticCause>> = None,
+    /// How the diagnostics are printed
+    output_format: OutputFormat = OutputFormat::Human,
+    /// The file that the diagnostics are written into, required by `--output-format sqlite`
+    output_file: Option<String> = None,
+    /// The crate name stored with the diagnostics by `--output-format sqlite`, the name of the analyzed crate by
+    /// default. It is not called `--crate-name`, which is the option of rustc that names the crate
+    output_crate_name: Option<String> = None,
+    /// The file that the inferred loop invariants are written into
+    export_invariants: Option<String> = None,
+    /// The file that the inferred preconditions are written into
+    export_contracts: Option<String> = None,
+    /// The directory that the function summaries shared by the crates of a workspace are stored in
+    cache_dir: Option<String> = None,
+    /// The file that the call graph is written into, in the Graphviz dot format
+    dump_callgraph: Option<String> = None,
+    /// Analyze the functions in the bottom-up order of the call graph and reuse their summaries
+    bottom_up: bool = false,
+    /// Print the functions reachable from the entry point in the bottom-up order of the call graph, i.e., the
+    /// callees before their callers, without running the analysis
+    dump_analysis_order: bool = false,
+    /// Model the primitives of `core` that `no_std` crates call directly by stubs, and analyze the crates whose
+    /// paths look like those of the sysroot, e.g., `libcore` built by `-Zbuild-std`
+    no_std: bool = false,
+    /// The function whose abstract state around each statement is written into `<name>.trace`
+    trace_function: Option<String> = None,
+    /// The directory that the state of each analyzed function is written into, see `mir-checker-replay`
+    checkpoint_dir: Option<String> = None,
+    /// The directory that the abstract state of each basic block of each analyzed function is written into,
+    /// see `mir-checker-inspect`
+    dump_states: Option<String> = None,
+    /// The directory that the weak topological order of the basic blocks of each analyzed function is written into
+    dump_wto: Option<String> = None,
+    /// The file that the data flow graph of each reachable function is written into, in the Graphviz dot format
+    dump_dataflow: Option<String> = None,
+    /// Panic on the MIR patterns that are not supported, instead of skipping them with a warning
+    strict: bool = false,
+    /// Unroll the loops whose bound is a constant that is at most this value, `0` disables unrolling
+    unroll_threshold: u32 = 4,
+    /// The depth up to which the callees are analyzed in the context of each call, the deeper callees and the
+    /// recursive calls use the summaries of the callees. Every callee is analyzed in context if it is not set
+    inline_depth: Option<u32> = None,
+    /// The loops nested deeper than this depth, counted from the function body, are not iterated to a fixed point,
+    /// the variables modified in them become unknown and a note is emitted. Every loop is iterated if it is not set
+    max_loop_depth: Option<usize> = None,
+}
+
+/// Whether `name` matches the glob `pattern`, where `*` matches any sequence of characters, including the empty one,
+/// and `?` matches exactly one character
+pub fn glob_matches(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // The position of the last `*` in the pattern, and the position in the name that it is matched up to
+    let mut backtrack = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            // Let the last `*` match one more character
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    n = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Describe a list of valid values, e.g., "one of `human`, `vscode`"
+fn one_of<'a>(names: impl Iterator<Item = &'a str>) -> String {
+    let names: Vec<String> = names.map(|name| format!("`{}`", name)).collect();
+    format!("one of {}", names.join(", "))
+}
+
+/// The type of an option, as it is described in the JSON schema of the configuration
+trait OptionType {
+    /// The schema of the values of this type
+    fn schema() -> serde_json::Value;
+
+    /// The value, as it is written in the configuration
+    fn to_json(&self) -> serde_json::Value;
+}
+
+impl OptionType for bool {
+    fn schema() -> serde_json::Value {
+        serde_json::json!({ "type": "boolean" })
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!(self)
+    }
+}
+
+impl OptionType for u32 {
+    fn schema() -> serde_json::Value {
+        serde_json::json!({ "type": "integer", "minimum": 0 })
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!(self)
+    }
+}
+
+impl OptionType for usize {
+    fn schema() -> serde_json::Value {
+        serde_json::json!({ "type": "integer", "minimum": 0 })
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!(self)
+    }
+}
+
+impl OptionType for String {
+    fn schema() -> serde_json::Value {
+        serde_json::json!({ "type": "string" })
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!(self)
+    }
+}
+
+impl OptionType for Vec<String> {
+    fn schema() -> serde_json::Value {
+        serde_json::json!({ "type": "array", "items": { "type": "string" } })
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!(self)
+    }
+}
+
+impl OptionType for AbstractDomainType {
+    fn schema() -> serde_json::Value {
+        let names: Vec<&str> = DOMAIN_TYPES.iter().map(AbstractDomainType::name).collect();
+        serde_json::json!({ "type": "string", "enum": names })
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!(self.name())
+    }
+}
+
+impl OptionType for AnalysisType {
+    fn schema() -> serde_json::Value {
+        let names: Vec<&str> = ANALYSIS_TYPES.iter().map(AnalysisType::name).collect();
+        serde_json::json!({ "type": "string", "enum": names })
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!(self.name())
+    }
 }
 
-impl Default for AnalysisOption {
-    fn default() -> Self {
-        Self {
-            entry_point: String::from("main"),
-            entry_def_id_index: None,
-            domain_type: AbstractDomainType::Interval,
-            widening_delay: 5,
-            cleaning_delay: 5,
-            narrowing_iteration: 5,
-            show_entries: false,
-            show_entries_index: false,
-            deny_warnings: false,
-            memory_safety_only: false,
-            suppressed_warnings: None,
+impl OptionType for OutputFormat {
+    fn schema() -> serde_json::Value {
+        let names: Vec<&str> = OUTPUT_FORMATS.iter().map(OutputFormat::name).collect();
+        serde_json::json!({ "type": "string", "enum": names })
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!(self.name())
+    }
+}
+
+// Suppressed warnings are written as in the `suppress_warnings` option, e.g., `ai` or `ai,custom:unsafe-ffi`
+impl OptionType for Vec<DiagnosticCause> {
+    fn schema() -> serde_json::Value {
+        let codes: String = WARNING_KINDS.iter().map(|(code, ..)| *code).collect();
+        let part = format!("([{}]+|{}[^,]+)", codes, CUSTOM_CAUSE_PREFIX);
+        serde_json::json!({ "type": "string", "pattern": format!("^{}(,{})*$", part, part) })
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        let codes: String = self
+            .iter()
+            .filter_map(|cause| {
+                WARNING_KINDS
+                    .iter()
+                    .find(|(_, kind, _)| kind == cause)
+                    .map(|(code, ..)| *code)
+            })
+            .collect();
+        let parts: Vec<String> = std::iter::once(codes)
+            .filter(|codes| !codes.is_empty())
+            .chain(self.iter().filter_map(|cause| match cause {
+                DiagnosticCause::Custom(name) => Some(format!("{}{}", CUSTOM_CAUSE_PREFIX, name)),
+                _ => None,
+            }))
+            .collect();
+        serde_json::json!(parts.join(","))
+    }
+}
+
+// An option that is not set by default may also be `null`
+impl<T: OptionType> OptionType for Option<T> {
+    fn schema() -> serde_json::Value {
+        let mut schema = T::schema();
+        if let Some(ty) = schema.get("type").cloned() {
+            schema["type"] = serde_json::json!([ty, "null"]);
+        }
+        if let Some(serde_json::Value::Array(values)) = schema.get_mut("enum") {
+            values.push(serde_json::Value::Null);
         }
+        schema
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        self.as_ref()
+            .map_or(serde_json::Value::Null, OptionType::to_json)
     }
 }
 
+/// The schema of a field with the default value `default`, described by the lines of its doc comment
+fn field_schema<T: OptionType>(default: &T, doc: &[&str]) -> serde_json::Value {
+    let mut schema = T::schema();
+    schema["default"] = default.to_json();
+    schema["description"] = serde_json::json!(doc
+        .iter()
+        .map(|line| line.trim())
+        .collect::<Vec<_>>()
+        .join(" "));
+    schema
+}
+
 impl AnalysisOption {
-    pub fn from_args(args: &mut Vec<String>) -> Self {
+    /// The file that the trace of `trace_function` is written into, i.e., `<name>.trace` where the characters
+    /// that cannot appear in a file name are replaced by `_`
+    pub fn trace_file(&self) -> Option<String> {
+        self.trace_function
+            .as_ref()
+            .map(|name| format!("{}.trace", checkpoint::function_file_name(name)))
+    }
+
+    /// Parse the options of MirChecker and remove them from `args`, the remaining arguments are passed to rustc
+    /// All the invalid options are reported at once, each error names the flag and the values it accepts
+    pub fn from_args(args: &mut Vec<String>) -> Result<Self, Vec<String>> {
         let mut indeices_to_remove = vec![];
+        let mut errors = vec![];
         let mut res = Self::default();
         for (i, arg) in args.iter().enumerate() {
-            if arg.starts_with("--") {
-                match &arg[2..] {
-                    "show_entries" => {
-                        res.show_entries = true;
-                        indeices_to_remove.push(i);
-                    }
-                    "show_entries_index" => {
-                        res.show_entries_index = true;
-                        indeices_to_remove.push(i);
-                    }
-                    "deny_warnings" => {
-                        res.deny_warnings = true;
-                        indeices_to_remove.push(i);
-                    }
-                    "memory_safety_only" => {
-                        res.memory_safety_only = true;
-                        indeices_to_remove.push(i);
-                    }
-                    "domain" => {
-                        if let Some(domain_type) = Self::get_domain_type(&args[i + 1]) {
-                            res.domain_type = domain_type;
-                        } else {
-                            warn!("Unknown domain type, use interval as default");
-                        }
-                        indeices_to_remove.push(i);
-                        indeices_to_remove.push(i + 1);
-                    }
-                    "entry" => {
-                        res.entry_point = args[i + 1].clone();
-                        indeices_to_remove.push(i);
-                        indeices_to_remove.push(i + 1);
-                    }
-                    "entry_def_id_index" => {
-                        if let Ok(def_id_index) = args[i + 1].parse() {
-                            res.entry_def_id_index = Some(def_id_index);
-                        } else {
-                            warn!("Invalid entry DefId index, use None as default");
-                        }
-                        indeices_to_remove.push(i);
-                        indeices_to_remove.push(i + 1);
-                    }
-                    "widening_delay" => {
-                        if let Ok(widening_delay) = args[i + 1].parse() {
-                            res.widening_delay = widening_delay;
-                        } else {
-                            warn!("Invalid widening delay, use 5 as default");
-                        }
-                        indeices_to_remove.push(i);
-                        indeices_to_remove.push(i + 1);
-                    }
-                    "narrowing_iteration" => {
-                        if let Ok(narrowing_iteration) = args[i + 1].parse() {
-                            res.narrowing_iteration = narrowing_iteration;
-                        } else {
-                            warn!("Invalid narrowing iteration, use 5 as default");
-                        }
-                        indeices_to_remove.push(i);
-                        indeices_to_remove.push(i + 1);
-                    }
-                    "suppress_warnings" => {
-                        if let Some(suppressed_warnings) =
-                            Self::get_suppressed_warnings(&args[i + 1])
-                        {
-                            res.suppressed_warnings = Some(suppressed_warnings);
-                        } else {
-                            warn!("Invalid suppressed warning types, will not suppress any warnings by default");
-                        }
-                        indeices_to_remove.push(i);
-                        indeices_to_remove.push(i + 1);
-                    }
-                    "cleaning_delay" => {
-                        if let Ok(cleaning_delay) = args[i + 1].parse() {
-                            res.cleaning_delay = cleaning_delay;
-                        } else {
-                            warn!("Invalid cleaning delay, use 5 as default");
+            if !arg.starts_with("--") {
+                continue;
+            }
+            let flag = &arg[2..];
+            match flag {
+                "show_entries" => res.show_entries = true,
+                "show_entries_index" => res.show_entries_index = true,
+                "deny_warnings" => res.deny_warnings = true,
+                "memory_safety_only" => res.memory_safety_only = true,
+                "check-macros" => res.check_macros = true,
+                "bottom-up" => res.bottom_up = true,
+                "dump-analysis-order" => res.dump_analysis_order = true,
+                "no-std" => res.no_std = true,
+                "strict" => res.strict = true,
+                "panic-freedom" => res.panic_freedom = true,
+                "report-dead-code" => res.report_dead_code = true,
+                "verbose-diagnostics" => res.verbose_diagnostics = true,
+                _ if VALUE_OPTIONS.contains(&flag) => {
+                    let value = match args.get(i + 1) {
+                        Some(value) if !value.starts_with("--") => value,
+                        _ => {
+                            errors.push(format!("`--{}` requires a value", flag));
+                            indeices_to_remove.push(i);
+                            continue;
                         }
-                        indeices_to_remove.push(i);
-                        indeices_to_remove.push(i + 1);
+                    };
+                    if let Err(expected) = res.set_option(flag, value) {
+                        errors.push(format!(
+                            "invalid value `{}` for `--{}`, expected {}",
+                            value, flag, expected
+                        ));
                     }
-                    _ => {}
+                    indeices_to_remove.push(i);
+                    indeices_to_remove.push(i + 1);
+                    continue;
                 }
+                _ => continue,
             }
+            indeices_to_remove.push(i);
+        }
+        // The ranking functions relate the values of the loop variables before and after an iteration, which
+        // cannot be represented in a non-relational domain
+        if res.check_termination && !res.domain_type.is_polyhedra() {
+            errors.push(String::from(
+                "`--check termination` requires a polyhedra domain, e.g., `--domain polyhedra`",
+            ));
+        }
+        if res.analysis_type == AnalysisType::Taint {
+            if let Err(e) = taint_sink_checker::load_taint_config(&res.config) {
+                errors.push(format!(
+                    "`--analysis-type taint` requires the `[taint]` section in `{}` ({})",
+                    res.config, e
+                ));
+            }
+        }
+        if res.output_format == OutputFormat::Sqlite && res.output_file.is_none() {
+            errors.push(String::from(
+                "`--output-format sqlite` requires the database file, e.g., `--output-file analysis.db`",
+            ));
         }
         indeices_to_remove.reverse();
         Self::remove_multiple(args, &indeices_to_remove);
-        res
+        if errors.is_empty() {
+            Ok(res)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Set the option `flag` to `value`, returns the description of the valid values if `value` is invalid
+    fn set_option(&mut self, flag: &str, value: &str) -> Result<(), String> {
+        let non_negative = || String::from("a non-negative integer");
+        match flag {
+            "check" => match value {
+                "raw-ptr-bounds" => self.check_raw_ptr_bounds = true,
+                "raw-offset-overflow" => self.check_raw_offset_overflow = true,
+                "panic-reachability" => self.check_panic_reachability = true,
+                "shift-overflow" => self.check_shift_overflow = true,
+                "shift-by-const" => self.check_shift_by_const = true,
+                "shift-sign" => self.check_shift_sign = true,
+                "int-promotion" => self.check_int_promotion = true,
+                "termination" => self.check_termination = true,
+                "lock-order" => self.check_lock_order = true,
+                "channel-capacity" => self.check_channel_capacity = true,
+                "pin-safety" => self.check_pin_safety = true,
+                "dangling-pointer" => self.check_dangling_pointer = true,
+                "double-free" => self.check_double_free = true,
+                "use-of-uninitialized-global" => self.check_use_of_uninitialized_global = true,
+                "vec-bounds" => self.check_vec_bounds = true,
+                "str-index" => self.check_str_index = true,
+                "transmute-safety" => self.check_transmute_safety = true,
+                "padding-uninit" => self.check_padding_uninit = true,
+                "float-nan" => self.check_float_nan = true,
+                "dead-branch" => self.check_dead_branch = true,
+                _ => return Err(one_of(CHECKERS.into_iter())),
+            },
+            "taint-source" => self.taint_sources.extend(
+                value
+                    .split(',')
+                    .filter(|name| !name.is_empty())
+                    .map(String::from),
+            ),
+            "domain" => {
+                self.domain_type = Self::get_domain_type(value)
+                    .ok_or_else(|| one_of(DOMAIN_TYPES.iter().map(AbstractDomainType::name)))?
+            }
+            "entry" => {
+                if !Self::is_valid_path(value) {
+                    return Err(String::from(
+                        "a path to a function, e.g., `main` or `foo::bar`",
+                    ));
+                }
+                self.entry_point = value.to_string();
+            }
+            "entry_def_id_index" => {
+                self.entry_def_id_index = Some(value.parse().map_err(|_| non_negative())?)
+            }
+            "entry-pattern" => {
+                let is_pattern = !value.is_empty()
+                    && value
+                        .chars()
+                        .all(|c| c.is_alphanumeric() || c == '_' || c == '*' || c == '?');
+                if !is_pattern {
+                    return Err(String::from(
+                        "a glob pattern of function names, e.g., `test_*` or `fuzz_?`",
+                    ));
+                }
+                self.entry_patterns.push(value.to_string());
+            }
+            "widening_delay" => self.widening_delay = value.parse().map_err(|_| non_negative())?,
+            "narrowing_iteration" => {
+                self.narrowing_iteration = value.parse().map_err(|_| non_negative())?
+            }
+            "cleaning_delay" => self.cleaning_delay = value.parse().map_err(|_| non_negative())?,
+            "unroll-threshold" => {
+                self.unroll_threshold = value.parse().map_err(|_| non_negative())?
+            }
+            "inline-depth" => self.inline_depth = Some(value.parse().map_err(|_| non_negative())?),
+            "max-loop-depth" => {
+                self.max_loop_depth = Some(value.parse().map_err(|_| non_negative())?)
+            }
+            "predicate" => {
+                predicate_domain::parse_predicate(value, |name| Some(Path::new_named(name)))
+                    .map_err(|e| format!("a linear inequality, e.g., `x + 2 * y < 100` ({})", e))?;
+                self.predicates.push(value.to_string());
+            }
+            "termination-depth" => {
+                self.termination_depth = match value.parse() {
+                    Ok(depth) if depth > 0 => depth,
+                    _ => return Err(String::from("a positive integer")),
+                }
+            }
+            "suppress_warnings" => {
+                self.suppressed_warnings =
+                    Some(Self::get_suppressed_warnings(value).ok_or_else(|| {
+                        let kinds: Vec<String> = WARNING_KINDS
+                            .iter()
+                            .map(|(code, _, description)| format!("`{}` ({})", code, description))
+                            .collect();
+                        format!(
+                            "a combination of {}, or `{}<name>`, separated by `,`",
+                            kinds.join(", "),
+                            CUSTOM_CAUSE_PREFIX
+                        )
+                    })?)
+            }
+            "output-format" => {
+                self.output_format = Self::get_output_format(value)
+                    .ok_or_else(|| one_of(OUTPUT_FORMATS.iter().map(OutputFormat::name)))?
+            }
+            "analysis-type" => {
+                self.analysis_type = Self::get_analysis_type(value)
+                    .ok_or_else(|| one_of(ANALYSIS_TYPES.iter().map(AnalysisType::name)))?
+            }
+            "config" => self.config = value.to_string(),
+            "output-file" => self.output_file = Some(value.to_string()),
+            "output-crate-name" => self.output_crate_name = Some(value.to_string()),
+            "export-invariants" => self.export_invariants = Some(value.to_string()),
+            "export-contracts" => self.export_contracts = Some(value.to_string()),
+            "cache-dir" => self.cache_dir = Some(value.to_string()),
+            "dump-callgraph" => self.dump_callgraph = Some(value.to_string()),
+            "trace-function" => self.trace_function = Some(value.to_string()),
+            "checkpoint-dir" => self.checkpoint_dir = Some(value.to_string()),
+            "dump-states" => self.dump_states = Some(value.to_string()),
+            "dump-wto" => self.dump_wto = Some(value.to_string()),
+            "dump-dataflow" => self.dump_dataflow = Some(value.to_string()),
+            "ffi-stubs" => {
+                ffi_checker::load_ffi_stubs(value)
+                    .map_err(|e| format!("a TOML file of the contracts of C functions ({})", e))?;
+                self.ffi_stubs = Some(value.to_string());
+            }
+            _ => unreachable!("`--{}` does not take a value", flag),
+        }
+        Ok(())
     }
 
+    /// Whether `path` is a Rust path made of identifiers, e.g., `main`, `foo::bar` or `::foo::bar`
+    fn is_valid_path(path: &str) -> bool {
+        let is_identifier = |segment: &str| {
+            let mut chars = segment.chars();
+            match chars.next() {
+                Some(first) if first.is_alphabetic() || first == '_' => {
+                    segment != "_" && chars.all(|c| c.is_alphanumeric() || c == '_')
+                }
+                _ => false,
+            }
+        };
+        path.strip_prefix("::")
+            .unwrap_or(path)
+            .split("::")
+            .all(is_identifier)
+    }
+
+    /// Parses the kinds of warnings separated by `,`, each of which is either the codes of `WARNING_KINDS`, e.g.,
+    /// `ai`, or the name of the cause of a custom checker, e.g., `custom:unsafe-ffi`, which is case-insensitive
     fn get_suppressed_warnings(arg: &str) -> Option<Vec<DiagnosticCause>> {
         let mut res = Vec::new();
-        for ch in arg.chars() {
-            match ch {
-                'a' => res.push(DiagnosticCause::Arithmetic), // Arithmetic overflow
-                'b' => res.push(DiagnosticCause::Bitwise),    // Bit-wise overflow
-                's' => res.push(DiagnosticCause::Assembly),   // Inline assembly
-                'c' => res.push(DiagnosticCause::Comparison), // Comparison operations
-                'd' => res.push(DiagnosticCause::DivZero), // Division by zero / remainder by zero
-                'm' => res.push(DiagnosticCause::Memory),  // Memory-safety issues
-                'p' => res.push(DiagnosticCause::Panic),   // Run into panic code
-                'i' => res.push(DiagnosticCause::Index),   // Out-of-bounds access
-                _ => return None,                          // Invalid flags
+        for part in arg.split(',').map(str::trim) {
+            let prefix_len = CUSTOM_CAUSE_PREFIX.len();
+            let is_custom = part.get(..prefix_len).map_or(false, |prefix| {
+                prefix.eq_ignore_ascii_case(CUSTOM_CAUSE_PREFIX)
+            });
+            if is_custom {
+                let name = &part[prefix_len..];
+                if name.is_empty() {
+                    return None;
+                }
+                res.push(DiagnosticCause::Custom(name.to_lowercase()));
+                continue;
+            }
+            for ch in part.chars() {
+                let (_, cause, _) = WARNING_KINDS.iter().find(|(code, ..)| *code == ch)?;
+                res.push(cause.clone());
             }
         }
         if res.is_empty() {
@@ -160,19 +819,41 @@ impl AnalysisOption {
         }
     }
 
-    fn get_domain_type(arg: &str) -> Option<AbstractDomainType> {
-        match arg {
-            "interval" => Some(AbstractDomainType::Interval),
-            "octagon" => Some(AbstractDomainType::Octagon),
-            "polyhedra" => Some(AbstractDomainType::Polyhedra),
-            "linear_equalities" => Some(AbstractDomainType::LinearEqualities),
-            "ppl_polyhedra" => Some(AbstractDomainType::PplPolyhedra),
-            "ppl_linear_congruences" => Some(AbstractDomainType::PplLinearCongruences),
-            "pkgrid_polyhedra_linear_congruences" => {
-                Some(AbstractDomainType::PkgridPolyhedraLinCongruences)
-            }
-            _ => None,
-        }
+    pub fn get_domain_type(arg: &str) -> Option<AbstractDomainType> {
+        DOMAIN_TYPES
+            .iter()
+            .find(|domain_type| domain_type.name() == arg)
+            .copied()
+    }
+
+    fn get_analysis_type(arg: &str) -> Option<AnalysisType> {
+        ANALYSIS_TYPES
+            .iter()
+            .find(|analysis_type| analysis_type.name() == arg)
+            .copied()
+    }
+
+    fn get_output_format(arg: &str) -> Option<OutputFormat> {
+        OUTPUT_FORMATS
+            .iter()
+            .find(|output_format| output_format.name() == arg)
+            .copied()
+    }
+
+    /// The JSON schema of the configuration, i.e., every field of `AnalysisOption` with its type,
+    /// default value and description, which can be used by editors to validate `mir-checker.toml`
+    pub fn config_schema() -> serde_json::Value {
+        let properties: serde_json::Map<String, serde_json::Value> = Self::field_schemas()
+            .into_iter()
+            .map(|(name, schema)| (name.to_string(), schema))
+            .collect();
+        serde_json::json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "title": "MirChecker configuration",
+            "type": "object",
+            "properties": properties,
+            "additionalProperties": false,
+        })
     }
 
     // Remove a list of indices from a vector
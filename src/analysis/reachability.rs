@@ -0,0 +1,313 @@
+use log::debug;
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::DefId;
+use rustc_hir::Mutability;
+use rustc_middle::mir::interpret::{ConstValue, GlobalAlloc, Scalar};
+use rustc_middle::mir::visit::{TyContext, Visitor};
+use rustc_middle::mir::{self, ConstOperand};
+use rustc_middle::ty::{
+    ConstKind, GenericArgKind, GenericArgsRef, Instance, InstanceDef, Ty, TyCtxt, TyKind, ValTree,
+};
+use std::collections::{HashMap, HashSet};
+
+/// Computes the functions of the local crate that are transitively reachable from `entry_points`
+/// A function is reachable if it is mentioned in the MIR of a reachable function, i.e., it is called directly,
+/// or used as a function pointer or a closure, so indirect calls are handled conservatively
+/// Calls through trait objects and calls of trait methods that cannot be resolved statically reach all the
+/// implementations of the method in the local crate
+/// Functions of other crates are not visited, but they may call the methods of the traits in their bounds, e.g.,
+/// `slice::sort` calls `Ord::cmp`, so a call of such a function reaches all the local implementations of these traits
+/// and of their supertraits
+pub fn reachable_functions(tcx: TyCtxt<'_>, entry_points: &[DefId]) -> HashSet<DefId> {
+    call_graph(tcx, entry_points).into_keys().collect()
+}
+
+/// Builds the call graph of the functions of the local crate that are reachable from `entry_points`,
+/// mapping each reachable function to the local functions it may call, see `reachable_functions`
+/// Edges to the functions of other crates are not included
+pub fn call_graph(tcx: TyCtxt<'_>, entry_points: &[DefId]) -> HashMap<DefId, HashSet<DefId>> {
+    let mut graph = HashMap::new();
+    let mut worklist: Vec<DefId> = entry_points.to_vec();
+    while let Some(def_id) = worklist.pop() {
+        if !def_id.is_local() || !is_function(tcx, def_id) || graph.contains_key(&def_id) {
+            continue;
+        }
+        let mut collector = FunctionCollector {
+            tcx,
+            param_env: tcx.param_env(def_id),
+            functions: Vec::new(),
+        };
+        collector.visit_body(tcx.optimized_mir(def_id));
+        debug!(
+            "Functions mentioned in {:?}: {:?}",
+            def_id, collector.functions
+        );
+        let callees: HashSet<DefId> = collector
+            .functions
+            .into_iter()
+            .filter(|callee| callee.is_local() && is_function(tcx, *callee))
+            .collect();
+        worklist.extend(callees.iter().copied());
+        graph.insert(def_id, callees);
+    }
+    graph
+}
+
+/// Computes the strongly connected components of `call_graph` in bottom-up order, i.e., the callees of the
+/// functions in a component are either in the same component or in one of the components before it
+/// A component is recursive if it has more than one function, or its only function calls itself
+/// The order is deterministic, the functions are visited in the order of their paths
+pub fn bottom_up_order(
+    tcx: TyCtxt<'_>,
+    call_graph: &HashMap<DefId, HashSet<DefId>>,
+) -> Vec<Vec<DefId>> {
+    let mut functions: Vec<(String, DefId)> = call_graph
+        .keys()
+        .map(|def_id| (tcx.def_path_str(*def_id), *def_id))
+        .collect();
+    functions.sort_by(|a, b| a.0.cmp(&b.0));
+    let order: HashMap<DefId, usize> = functions
+        .iter()
+        .enumerate()
+        .map(|(i, (_, def_id))| (*def_id, i))
+        .collect();
+    // The callees of each function, indexed by the positions in `functions`
+    let successors: Vec<Vec<usize>> = functions
+        .iter()
+        .map(|(_, def_id)| {
+            let mut callees: Vec<usize> = call_graph[def_id]
+                .iter()
+                .filter_map(|callee| order.get(callee).copied())
+                .collect();
+            callees.sort_unstable();
+            callees
+        })
+        .collect();
+
+    let mut tarjan = Tarjan {
+        successors: &successors,
+        index: vec![None; functions.len()],
+        low_link: vec![0; functions.len()],
+        on_stack: vec![false; functions.len()],
+        stack: Vec::new(),
+        next_index: 0,
+        components: Vec::new(),
+    };
+    for node in 0..functions.len() {
+        if tarjan.index[node].is_none() {
+            tarjan.visit(node);
+        }
+    }
+    tarjan
+        .components
+        .into_iter()
+        .map(|component| component.into_iter().map(|i| functions[i].1).collect())
+        .collect()
+}
+
+/// Tarjan's algorithm, which emits a strongly connected component after all the components it reaches
+struct Tarjan<'a> {
+    successors: &'a [Vec<usize>],
+    index: Vec<Option<usize>>,
+    low_link: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    next_index: usize,
+    components: Vec<Vec<usize>>,
+}
+
+impl<'a> Tarjan<'a> {
+    fn visit(&mut self, node: usize) {
+        self.index[node] = Some(self.next_index);
+        self.low_link[node] = self.next_index;
+        self.next_index += 1;
+        self.stack.push(node);
+        self.on_stack[node] = true;
+
+        for &succ in self.successors[node].iter() {
+            match self.index[succ] {
+                None => {
+                    self.visit(succ);
+                    self.low_link[node] = self.low_link[node].min(self.low_link[succ]);
+                }
+                Some(succ_index) if self.on_stack[succ] => {
+                    self.low_link[node] = self.low_link[node].min(succ_index);
+                }
+                _ => {}
+            }
+        }
+
+        if Some(self.low_link[node]) == self.index[node] {
+            let mut component = Vec::new();
+            while let Some(member) = self.stack.pop() {
+                self.on_stack[member] = false;
+                component.push(member);
+                if member == node {
+                    break;
+                }
+            }
+            component.sort_unstable();
+            self.components.push(component);
+        }
+    }
+}
+
+/// Returns all the functions of the local crate that have MIR bodies
+pub fn local_functions(tcx: TyCtxt<'_>) -> Vec<DefId> {
+    tcx.iter_local_def_id()
+        .map(|def_id| def_id.to_def_id())
+        .filter(|def_id| is_function(tcx, *def_id))
+        .collect()
+}
+
+fn is_function(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    matches!(
+        tcx.def_kind(def_id),
+        DefKind::Fn | DefKind::AssocFn | DefKind::Closure
+    ) && tcx.is_mir_available(def_id)
+}
+
+/// Collects the functions that are mentioned by the types in a MIR body
+/// Function items, closures and trait methods all have their own types, and every callee, function pointer
+/// or closure in the body is an operand whose type is one of them
+struct FunctionCollector<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    param_env: rustc_middle::ty::ParamEnv<'tcx>,
+    functions: Vec<DefId>,
+}
+
+impl<'tcx> Visitor<'tcx> for FunctionCollector<'tcx> {
+    fn visit_ty(&mut self, ty: Ty<'tcx>, _: TyContext) {
+        for arg in ty.walk() {
+            if let GenericArgKind::Type(ty) = arg.unpack() {
+                match ty.kind() {
+                    TyKind::FnDef(def_id, args) => self.add_callees(*def_id, args),
+                    TyKind::Closure(def_id, _) | TyKind::Coroutine(def_id, _) => {
+                        self.functions.push(*def_id)
+                    }
+                    // The destructor is called implicitly when a value of the type is dropped
+                    TyKind::Adt(adt_def, _) => {
+                        if let Some(destructor) = adt_def.destructor(self.tcx) {
+                            self.functions.push(destructor.did);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+impl<'tcx> FunctionCollector<'tcx> {
+    fn add_callees(&mut self, def_id: DefId, args: GenericArgsRef<'tcx>) {
+        match Instance::resolve(self.tcx, self.param_env, def_id, args) {
+            Ok(Some(instance)) if !matches!(instance.def, InstanceDef::Virtual(..)) => {
+                self.functions.push(instance.def_id());
+                if !instance.def_id().is_local() {
+                    self.add_trait_implementations(instance.def_id());
+                }
+            }
+            _ => {
+                // A call through a trait object, or the generic arguments are not known yet
+                self.functions.push(def_id);
+                if !def_id.is_local() {
+                    self.add_trait_implementations(def_id);
+                }
+                if let Some(trait_def_id) = self.tcx.trait_of_item(def_id) {
+                    let tcx = self.tcx;
+                    self.functions
+                        .extend(tcx.all_impls(trait_def_id).filter_map(|impl_def_id| {
+                            tcx.impl_item_implementor_ids(impl_def_id)
+                                .get(&def_id)
+                                .copied()
+                        }));
+                }
+            }
+        }
+    }
+
+    /// Adds the methods of the local implementations of the traits in the bounds of `def_id`, which is a function of
+    /// another crate, and of their supertraits, since the function is not visited, see `reachable_functions`
+    /// The bounds of the impl of a method are included, e.g., `T: Display` of `impl<T: Display> ToString for T`
+    fn add_trait_implementations(&mut self, def_id: DefId) {
+        let tcx = self.tcx;
+        let predicates = tcx
+            .predicates_of(def_id)
+            .instantiate_identity(tcx)
+            .predicates;
+        let mut traits: Vec<DefId> = predicates
+            .into_iter()
+            .filter_map(|clause| clause.as_trait_clause())
+            .map(|trait_clause| trait_clause.def_id())
+            .collect();
+        let mut visited = HashSet::new();
+        while let Some(trait_def_id) = traits.pop() {
+            if !visited.insert(trait_def_id) {
+                continue;
+            }
+            traits.extend(
+                tcx.super_predicates_of(trait_def_id)
+                    .predicates
+                    .iter()
+                    .filter_map(|(clause, _)| clause.as_trait_clause())
+                    .map(|trait_clause| trait_clause.def_id()),
+            );
+            for impl_def_id in tcx
+                .all_impls(trait_def_id)
+                .filter(|impl_def_id| impl_def_id.is_local())
+            {
+                self.functions
+                    .extend(tcx.associated_item_def_ids(impl_def_id).iter().copied());
+            }
+        }
+    }
+}
+
+/// Returns the mutable statics that are mentioned in the MIR of `functions`, i.e., the statics that they may read or
+/// write, since both are accesses through a constant pointer to the static
+pub fn mentioned_mutable_statics(
+    tcx: TyCtxt<'_>,
+    functions: impl IntoIterator<Item = DefId>,
+) -> HashSet<DefId> {
+    let mut collector = StaticCollector {
+        tcx,
+        statics: HashSet::new(),
+    };
+    for def_id in functions {
+        if def_id.is_local() && is_function(tcx, def_id) {
+            collector.visit_body(tcx.optimized_mir(def_id));
+        }
+    }
+    collector.statics
+}
+
+/// Collects the mutable statics that the constants in a MIR body point to
+struct StaticCollector<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    statics: HashSet<DefId>,
+}
+
+impl<'tcx> Visitor<'tcx> for StaticCollector<'tcx> {
+    fn visit_constant(&mut self, constant: &ConstOperand<'tcx>, _: mir::Location) {
+        let scalar = match constant.const_ {
+            mir::Const::Val(ConstValue::Scalar(scalar), _) => scalar,
+            mir::Const::Ty(literal) => match literal.kind() {
+                ConstKind::Value(ValTree::Leaf(scalar_int)) => Scalar::from(scalar_int),
+                _ => return,
+            },
+            _ => return,
+        };
+        let alloc_id = match scalar.to_pointer(&self.tcx) {
+            Ok(pointer) => match pointer.into_parts().0 {
+                Some(provenance) => provenance.alloc_id(),
+                None => return,
+            },
+            Err(_) => return,
+        };
+        if let Some(GlobalAlloc::Static(def_id)) = self.tcx.try_get_global_alloc(alloc_id) {
+            if self.tcx.static_mutability(def_id) == Some(Mutability::Mut) {
+                self.statics.insert(def_id);
+            }
+        }
+    }
+}
@@ -0,0 +1,108 @@
+// Archive the diagnostics into a SQLite database, see `--output-format sqlite`
+
+use rusqlite::{params, Connection};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The environment variable that holds the id of the analysis run, which is shared by all the crates analyzed by
+/// one invocation of `cargo mir-checker`. CI can also set it, e.g., to the number of the pipeline
+pub const RUN_ID_ENV: &str = "MIR_CHECKER_RUN_ID";
+
+/// Each row is a diagnostic, the diagnostics of one analysis run have the same `run_id` and `run_timestamp`, which
+/// is the number of seconds since the Unix epoch, e.g., `datetime(run_timestamp, 'unixepoch')`
+const CREATE_TABLE: &str = "CREATE TABLE IF NOT EXISTS diagnostics (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    run_id TEXT NOT NULL,
+    crate_name TEXT NOT NULL,
+    file TEXT NOT NULL,
+    line INTEGER NOT NULL,
+    col INTEGER NOT NULL,
+    cause TEXT NOT NULL,
+    is_memory_safety INTEGER NOT NULL,
+    message TEXT NOT NULL,
+    run_timestamp INTEGER NOT NULL
+)";
+
+const CREATE_INDEX: &str = "CREATE INDEX IF NOT EXISTS diagnostics_run_id ON diagnostics (run_id)";
+
+/// How long to wait for another process that writes into the same database, e.g., when cargo analyzes two targets
+/// of a crate in parallel
+const BUSY_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The analysis run that the exported diagnostics belong to
+#[derive(Clone, Debug)]
+pub struct AnalysisRun {
+    pub run_id: String,
+    pub crate_name: String,
+    pub timestamp: u64,
+}
+
+impl AnalysisRun {
+    /// The run of the current process, whose id is taken from `RUN_ID_ENV`, or a new id if the variable is not set,
+    /// e.g., when `mir-checker` is called directly
+    pub fn current(crate_name: String) -> Self {
+        AnalysisRun {
+            run_id: std::env::var(RUN_ID_ENV).unwrap_or_else(|_| new_run_id()),
+            crate_name,
+            timestamp: now(),
+        }
+    }
+}
+
+/// The number of seconds since the Unix epoch
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+/// A new id of an analysis run, made of the current time and the process id, e.g., `1700000000-4242`
+pub fn new_run_id() -> String {
+    format!("{}-{}", now(), std::process::id())
+}
+
+/// A diagnostic as it is stored in the database
+#[derive(Clone, Debug)]
+pub struct DiagnosticRecord {
+    pub file: String,
+    pub line: usize,
+    pub col: usize,
+    pub cause: String,
+    pub is_memory_safety: bool,
+    pub message: String,
+}
+
+/// Appends `records` to the `diagnostics` table of the database `file_name`, which is created if it does not exist
+/// All the records are inserted in one transaction, returns the number of the inserted records
+pub fn export_diagnostics(
+    file_name: &str,
+    run: &AnalysisRun,
+    records: &[DiagnosticRecord],
+) -> rusqlite::Result<usize> {
+    let mut connection = Connection::open(file_name)?;
+    connection.busy_timeout(BUSY_TIMEOUT)?;
+    connection.execute(CREATE_TABLE, [])?;
+    connection.execute(CREATE_INDEX, [])?;
+    let transaction = connection.transaction()?;
+    {
+        let mut statement = transaction.prepare(
+            "INSERT INTO diagnostics \
+             (run_id, crate_name, file, line, col, cause, is_memory_safety, message, run_timestamp) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        )?;
+        for record in records {
+            statement.execute(params![
+                run.run_id,
+                run.crate_name,
+                record.file,
+                record.line as i64,
+                record.col as i64,
+                record.cause,
+                record.is_memory_safety,
+                record.message,
+                run.timestamp as i64,
+            ])?;
+        }
+    }
+    transaction.commit()?;
+    Ok(records.len())
+}
@@ -0,0 +1,37 @@
+// Stubs of the generic functions of `core::cmp`, `core::convert` and `core::hint`, which are called with integers
+
+use crate::analysis::numerical::interval::Interval;
+use crate::analysis::stubs::stub_library::StubLibrary;
+
+/// `[min(a.low, b.low), min(a.high, b.high)]`
+pub fn min(a: &Interval, b: &Interval) -> Interval {
+    Interval::new(
+        a.low.clone().min(b.low.clone()),
+        a.high.clone().min(b.high.clone()),
+    )
+}
+
+/// `[max(a.low, b.low), max(a.high, b.high)]`
+pub fn max(a: &Interval, b: &Interval) -> Interval {
+    Interval::new(
+        a.low.clone().max(b.low.clone()),
+        a.high.clone().max(b.high.clone()),
+    )
+}
+
+pub fn register(library: &mut StubLibrary) {
+    for key in ["core.cmp.min", "core.cmp.Ord.min"] {
+        library.register(key.to_owned(), Box::new(|args| min(&args[0], &args[1])));
+    }
+    for key in ["core.cmp.max", "core.cmp.Ord.max"] {
+        library.register(key.to_owned(), Box::new(|args| max(&args[0], &args[1])));
+    }
+    // `x.clamp(low, high)` is `min(max(x, low), high)`, it panics if `low > high`
+    library.register(
+        "core.cmp.Ord.clamp".to_owned(),
+        Box::new(|args| min(&max(&args[0], &args[1]), &args[2])),
+    );
+    for key in ["core.convert.identity", "core.hint.black_box"] {
+        library.register(key.to_owned(), Box::new(|args| args[0].clone()));
+    }
+}
@@ -0,0 +1,459 @@
+// Stubs of the methods of the integer types, which are defined in the impl blocks of `core::num`
+
+use crate::analysis::numerical::interval::{Bound, Interval};
+use crate::analysis::numerical::wrapping_integer::WrappingIntegerDomain;
+use crate::analysis::stubs::stub_library::StubLibrary;
+use rug::ops::Pow;
+use rug::Integer;
+use std::convert::TryFrom;
+
+/// The largest exponent for which `pow` is computed exactly, the result of a larger exponent overflows
+/// any integer type unless the base is `-1`, `0` or `1`
+const MAX_EXACT_EXPONENT: u32 = 128;
+
+/// An integer type, e.g., `u8` is `IntegerType { bits: 8, signed: false }`
+#[derive(Clone, Copy)]
+struct IntegerType {
+    bits: u64,
+    signed: bool,
+}
+
+impl IntegerType {
+    fn range(self) -> WrappingIntegerDomain {
+        WrappingIntegerDomain::new(self.bits, self.signed)
+    }
+
+    /// `[0, bits]`, the range of the methods that count bits, e.g., `count_ones`
+    fn bit_count_range(self) -> Interval {
+        Interval::new(
+            Bound::Int(Integer::from(0)),
+            Bound::Int(Integer::from(self.bits)),
+        )
+    }
+
+    /// The value of `n` as an unsigned integer of the same width, i.e., its two's complement representation
+    fn to_unsigned(self, n: &Integer) -> Integer {
+        if *n < 0 {
+            n + (Integer::from(1) << (self.bits as u32))
+        } else {
+            n.clone()
+        }
+    }
+
+    /// `[max(x.low, low), min(x.high, max)]` as integers, or `None` if `x` has no value in `[low, max]`
+    fn clamp(self, x: &Interval, low: Integer) -> Option<(Integer, Integer)> {
+        let clamped = x.meet(&Interval::new(
+            Bound::Int(low),
+            Bound::Int(self.range().max()),
+        ));
+        match (clamped.low, clamped.high) {
+            (Bound::Int(low), Bound::Int(high)) if low <= high => Some((low, high)),
+            _ => None,
+        }
+    }
+}
+
+fn constant(n: Integer) -> Interval {
+    Interval::new(Bound::Int(n.clone()), Bound::Int(n))
+}
+
+fn zero() -> Bound {
+    Bound::Int(Integer::from(0))
+}
+
+fn neg(x: &Interval) -> Interval {
+    constant(Integer::from(0)) - x.clone()
+}
+
+pub fn abs(x: &Interval) -> Interval {
+    if x.is_bottom() || x.low >= zero() {
+        x.clone()
+    } else if x.high <= zero() {
+        neg(x)
+    } else {
+        Interval::new(zero(), x.high.clone().max(neg(x).high))
+    }
+}
+
+/// The values of `x` that are out of the range of `ty` are replaced by the closest bound of the range
+fn saturate(x: &Interval, ty: IntegerType) -> Interval {
+    if x.is_bottom() {
+        return x.clone();
+    }
+    let range = ty.range();
+    let clamp = |bound: &Bound| {
+        bound
+            .clone()
+            .max(Bound::Int(range.min()))
+            .min(Bound::Int(range.max()))
+    };
+    Interval::new(clamp(&x.low), clamp(&x.high))
+}
+
+fn pow_bound(bound: &Bound, exp: u32) -> Bound {
+    match bound {
+        Bound::Int(n) => Bound::Int(n.clone().pow(exp)),
+        Bound::INF => Bound::INF,
+        Bound::NINF if exp % 2 == 0 => Bound::INF,
+        Bound::NINF => Bound::NINF,
+    }
+}
+
+fn pow(base: &Interval, exp: &Interval) -> Interval {
+    if base.is_bottom() || exp.is_bottom() {
+        return Interval::bottom();
+    }
+    match Integer::try_from(exp).ok().and_then(|exp| exp.to_u32()) {
+        Some(0) => constant(Integer::from(1)),
+        // `x^exp` is monotonic in `|x|` if `exp` is even, and in `x` if `exp` is odd
+        Some(exp) if exp <= MAX_EXACT_EXPONENT => {
+            let base = if exp % 2 == 0 {
+                abs(base)
+            } else {
+                base.clone()
+            };
+            Interval::new(pow_bound(&base.low, exp), pow_bound(&base.high, exp))
+        }
+        _ if base.low >= zero() => Interval::new(zero(), Bound::INF),
+        _ => Interval::top(),
+    }
+}
+
+/// `a / b`, rounding towards zero, the values of `b` other than zero are divided separately by sign
+pub fn div(a: &Interval, b: &Interval) -> Interval {
+    let negative = b.meet(&Interval::new(Bound::NINF, Bound::Int(Integer::from(-1))));
+    let positive = b.meet(&Interval::new(Bound::Int(Integer::from(1)), Bound::INF));
+    let mut result = Interval::bottom();
+    for divisor in [negative, positive] {
+        if !a.is_bottom() && !divisor.is_bottom() {
+            result = result.join(&(a.clone() / divisor));
+        }
+    }
+    result
+}
+
+/// `a % b`, whose sign is the sign of `a` and whose magnitude is smaller than `|b|`
+pub fn rem(a: &Interval, b: &Interval) -> Interval {
+    let divisor = abs(b).meet(&Interval::new(Bound::Int(Integer::from(1)), Bound::INF));
+    if a.is_bottom() || divisor.is_bottom() {
+        return Interval::bottom();
+    }
+    let max_magnitude = divisor.high - Bound::Int(Integer::from(1));
+    let low = if a.low >= zero() {
+        zero()
+    } else {
+        a.low.clone().max(zero() - max_magnitude.clone())
+    };
+    let high = if a.high <= zero() {
+        zero()
+    } else {
+        a.high.clone().min(max_magnitude)
+    };
+    Interval::new(low, high)
+}
+
+/// `a.rem_euclid(b)`, which is never negative and smaller than `|b|`, and is `a % b` if `a` is not negative
+/// A negative `a` has a remainder of `a + |b|` or more, e.g., `(-1).rem_euclid(3)` is 2, so it is only bounded by `|b|`
+fn rem_euclid(a: &Interval, b: &Interval) -> Interval {
+    let divisor = abs(b).meet(&Interval::new(Bound::Int(Integer::from(1)), Bound::INF));
+    if a.is_bottom() || divisor.is_bottom() {
+        return Interval::bottom();
+    }
+    let max_remainder = divisor.high - Bound::Int(Integer::from(1));
+    let high = if a.low >= zero() {
+        a.high.clone().min(max_remainder)
+    } else {
+        max_remainder
+    };
+    Interval::new(zero(), high)
+}
+
+/// `floor(log(n, base))`, where `n >= 1` and `base >= 2`
+fn ilog(n: &Integer, base: &Integer) -> Integer {
+    let mut n = n.clone();
+    let mut result = Integer::from(0);
+    while n >= *base {
+        n /= base;
+        result += 1;
+    }
+    result
+}
+
+/// The smallest power of two that is at least `n`
+fn next_power_of_two(n: &Integer) -> Integer {
+    if *n <= 1 {
+        Integer::from(1)
+    } else {
+        Integer::from(1) << Integer::from(n - 1).significant_bits()
+    }
+}
+
+/// `log(x, base)`, which panics if `x` is not positive
+fn ilog_range(x: &Interval, base: &Integer, ty: IntegerType) -> Interval {
+    match ty.clamp(x, Integer::from(1)) {
+        Some((low, high)) => {
+            Interval::new(Bound::Int(ilog(&low, base)), Bound::Int(ilog(&high, base)))
+        }
+        None => Interval::bottom(),
+    }
+}
+
+/// The methods that rearrange the bits of an integer map zero to zero, and any other value to any value
+fn rearrange_bits(x: &Interval, ty: IntegerType) -> Interval {
+    match Integer::try_from(x) {
+        Ok(n) if n == 0 => constant(n),
+        _ => ty.range().full_range(),
+    }
+}
+
+fn leading_zeros(x: &Interval, ty: IntegerType) -> Interval {
+    if x.high < zero() {
+        return constant(Integer::from(0));
+    }
+    if x.low < zero() {
+        return ty.bit_count_range();
+    }
+    match ty.clamp(x, Integer::from(0)) {
+        Some((low, high)) => Interval::new(
+            Bound::Int(Integer::from(ty.bits) - high.significant_bits()),
+            Bound::Int(Integer::from(ty.bits) - low.significant_bits()),
+        ),
+        None => Interval::bottom(),
+    }
+}
+
+fn trailing_zeros(x: &Interval, ty: IntegerType) -> Interval {
+    match Integer::try_from(x) {
+        Ok(n) => constant(Integer::from(n.find_one(0).map_or(ty.bits, u64::from))),
+        Err(_) => ty.bit_count_range(),
+    }
+}
+
+fn count_ones(x: &Interval, ty: IntegerType) -> Interval {
+    match Integer::try_from(x)
+        .ok()
+        .and_then(|n| ty.to_unsigned(&n).count_ones())
+    {
+        Some(count) => constant(Integer::from(count)),
+        None => ty.bit_count_range(),
+    }
+}
+
+fn leading_ones(x: &Interval, ty: IntegerType) -> Interval {
+    // The highest bit of a non-negative signed integer is zero, and that of a negative one is one
+    if ty.signed && x.low >= zero() {
+        constant(Integer::from(0))
+    } else if ty.signed && x.high < zero() {
+        Interval::new(
+            Bound::Int(Integer::from(1)),
+            Bound::Int(Integer::from(ty.bits)),
+        )
+    } else {
+        ty.bit_count_range()
+    }
+}
+
+fn signum_bound(bound: &Bound) -> Bound {
+    match bound {
+        Bound::Int(n) => Bound::Int(Integer::from(n.cmp0() as i32)),
+        Bound::INF => Bound::Int(Integer::from(1)),
+        Bound::NINF => Bound::Int(Integer::from(-1)),
+    }
+}
+
+fn register_method<F>(library: &mut StubLibrary, type_name: &str, method: &str, stub: F)
+where
+    F: Fn(&[Interval]) -> Interval + 'static,
+{
+    library.register(
+        format!("core.num.implement_{}.{}", type_name, method),
+        Box::new(stub),
+    );
+}
+
+/// Registers the stub of a method whose second argument is a divisor, see `StubLibrary::is_division`
+fn register_division_method<F>(library: &mut StubLibrary, type_name: &str, method: &str, stub: F)
+where
+    F: Fn(&[Interval]) -> Interval + 'static,
+{
+    library.register_division(
+        format!("core.num.implement_{}.{}", type_name, method),
+        Box::new(stub),
+    );
+}
+
+/// Registers the stubs of the methods that are shared by the signed and unsigned integer types
+fn register_common_methods(library: &mut StubLibrary, name: &str, ty: IntegerType) {
+    register_method(library, name, "min_value", move |_| {
+        constant(ty.range().min())
+    });
+    register_method(library, name, "max_value", move |_| {
+        constant(ty.range().max())
+    });
+    register_method(library, name, "pow", |args| pow(&args[0], &args[1]));
+    register_method(library, name, "saturating_pow", move |args| {
+        saturate(&pow(&args[0], &args[1]), ty)
+    });
+    register_division_method(library, name, "saturating_div", move |args| {
+        saturate(&div(&args[0], &args[1]), ty)
+    });
+    register_method(library, name, "wrapping_add", move |args| {
+        ty.range().wrap(&(args[0].clone() + args[1].clone()))
+    });
+    register_method(library, name, "wrapping_sub", move |args| {
+        ty.range().wrap(&(args[0].clone() - args[1].clone()))
+    });
+    register_method(library, name, "wrapping_mul", move |args| {
+        ty.range().wrap(&(args[0].clone() * args[1].clone()))
+    });
+    register_method(library, name, "wrapping_neg", move |args| {
+        ty.range().wrap(&neg(&args[0]))
+    });
+    register_division_method(library, name, "wrapping_div", move |args| {
+        ty.range().wrap(&div(&args[0], &args[1]))
+    });
+    register_division_method(library, name, "wrapping_rem", |args| {
+        rem(&args[0], &args[1])
+    });
+    register_method(library, name, "wrapping_pow", move |args| {
+        ty.range().wrap(&pow(&args[0], &args[1]))
+    });
+    register_method(library, name, "wrapping_shl", move |_| {
+        ty.range().full_range()
+    });
+    // Shifting right moves the value towards zero, or towards `-1` if it is negative
+    register_method(library, name, "wrapping_shr", |args| {
+        let x = &args[0];
+        Interval::new(x.low.clone().min(zero()), x.high.clone().max(zero()))
+    });
+    register_method(library, name, "abs_diff", |args| {
+        abs(&(args[0].clone() - args[1].clone()))
+    });
+    // The Euclidean division differs from the division rounding towards zero by at most one
+    register_division_method(library, name, "div_euclid", |args| {
+        let (a, b) = (&args[0], &args[1]);
+        let quotient = div(a, b);
+        if a.low >= zero() || quotient.is_bottom() {
+            quotient
+        } else {
+            let one = Bound::Int(Integer::from(1));
+            Interval::new(quotient.low - one.clone(), quotient.high + one)
+        }
+    });
+    register_division_method(library, name, "rem_euclid", |args| {
+        rem_euclid(&args[0], &args[1])
+    });
+    register_method(library, name, "ilog2", move |args| {
+        ilog_range(&args[0], &Integer::from(2), ty)
+    });
+    register_method(library, name, "ilog10", move |args| {
+        ilog_range(&args[0], &Integer::from(10), ty)
+    });
+    register_method(library, name, "ilog", move |args| {
+        match Integer::try_from(&args[1]) {
+            Ok(base) if base >= 2 => ilog_range(&args[0], &base, ty),
+            _ => Interval::new(zero(), Bound::Int(Integer::from(ty.bits - 1))),
+        }
+    });
+    register_method(library, name, "count_ones", move |args| {
+        count_ones(&args[0], ty)
+    });
+    register_method(library, name, "count_zeros", move |args| {
+        constant(Integer::from(ty.bits)) - count_ones(&args[0], ty)
+    });
+    register_method(library, name, "leading_zeros", move |args| {
+        leading_zeros(&args[0], ty)
+    });
+    register_method(library, name, "trailing_zeros", move |args| {
+        trailing_zeros(&args[0], ty)
+    });
+    register_method(library, name, "leading_ones", move |args| {
+        leading_ones(&args[0], ty)
+    });
+    register_method(library, name, "trailing_ones", move |_| {
+        ty.bit_count_range()
+    });
+    for method in [
+        "rotate_left",
+        "rotate_right",
+        "swap_bytes",
+        "reverse_bits",
+        "to_be",
+        "to_le",
+        "from_be",
+        "from_le",
+    ] {
+        register_method(library, name, method, move |args| {
+            rearrange_bits(&args[0], ty)
+        });
+    }
+}
+
+fn register_signed_methods(library: &mut StubLibrary, name: &str, ty: IntegerType) {
+    register_method(library, name, "abs", |args| abs(&args[0]));
+    register_method(library, name, "unsigned_abs", |args| abs(&args[0]));
+    register_method(library, name, "wrapping_abs", move |args| {
+        ty.range().wrap(&abs(&args[0]))
+    });
+    register_method(library, name, "saturating_abs", move |args| {
+        saturate(&abs(&args[0]), ty)
+    });
+    register_method(library, name, "saturating_neg", move |args| {
+        saturate(&neg(&args[0]), ty)
+    });
+    register_method(library, name, "signum", |args| {
+        Interval::new(signum_bound(&args[0].low), signum_bound(&args[0].high))
+    });
+}
+
+fn register_unsigned_methods(library: &mut StubLibrary, name: &str, ty: IntegerType) {
+    register_method(library, name, "next_power_of_two", move |args| {
+        match ty.clamp(&args[0], Integer::from(0)) {
+            Some((low, high)) => Interval::new(
+                Bound::Int(next_power_of_two(&low)),
+                Bound::Int(next_power_of_two(&high)),
+            ),
+            None => Interval::bottom(),
+        }
+    });
+    // `a.div_ceil(b)` is `a / b` or `a / b + 1`
+    register_division_method(library, name, "div_ceil", |args| {
+        let quotient = div(&args[0], &args[1]);
+        if quotient.is_bottom() {
+            quotient
+        } else {
+            Interval::new(quotient.low, quotient.high + Bound::Int(Integer::from(1)))
+        }
+    });
+    register_method(library, name, "saturating_add_signed", move |args| {
+        saturate(&(args[0].clone() + args[1].clone()), ty)
+    });
+}
+
+/// Registers the stubs of the methods of all the integer types, `pointer_width` is the bit width of `isize` and
+/// `usize`
+pub fn register(library: &mut StubLibrary, pointer_width: u64) {
+    let types = [
+        ("i8", 8, true),
+        ("i16", 16, true),
+        ("i32", 32, true),
+        ("i64", 64, true),
+        ("i128", 128, true),
+        ("isize", pointer_width, true),
+        ("u8", 8, false),
+        ("u16", 16, false),
+        ("u32", 32, false),
+        ("u64", 64, false),
+        ("u128", 128, false),
+        ("usize", pointer_width, false),
+    ];
+    for (name, bits, signed) in types {
+        let ty = IntegerType { bits, signed };
+        register_common_methods(library, name, ty);
+        if signed {
+            register_signed_methods(library, name, ty);
+        } else {
+            register_unsigned_methods(library, name, ty);
+        }
+    }
+}
@@ -0,0 +1,101 @@
+// Stubs of the primitives of `core` that `no_std` crates call directly, i.e., the intrinsics, the queries of the
+// sizes of slices and the iterator methods that count items, which are only registered with `--no-std`
+
+use crate::analysis::numerical::interval::{Bound, Interval};
+use crate::analysis::stubs::integer::{div, rem};
+use crate::analysis::stubs::stub_library::StubLibrary;
+use rug::Integer;
+
+/// The bit width of the widest integer type, the intrinsics that count bits are generic over the integer types,
+/// and the width of the argument is not known from its range
+const MAX_BIT_WIDTH: u32 = 128;
+
+/// The largest alignment of a type that rustc accepts
+const MAX_ALIGNMENT: u32 = 1 << 29;
+
+fn range(low: Integer, high: Integer) -> Interval {
+    Interval::new(Bound::Int(low), Bound::Int(high))
+}
+
+/// `[0, bits]`, where `bits` is the number of significant bits of the upper bound of `x` if `x` is not negative,
+/// since a value cannot have more ones than significant bits
+fn count_ones(x: &Interval) -> Interval {
+    match &x.high {
+        Bound::Int(high) if x.low >= Bound::Int(Integer::from(0)) => {
+            range(Integer::from(0), Integer::from(high.significant_bits()))
+        }
+        _ => range(Integer::from(0), Integer::from(MAX_BIT_WIDTH)),
+    }
+}
+
+fn register_intrinsic<F>(library: &mut StubLibrary, name: &str, stub: F)
+where
+    F: Fn(&[Interval]) -> Interval + 'static,
+{
+    library.register(format!("core.intrinsics.{}", name), Box::new(stub));
+}
+
+fn register_division_intrinsic<F>(library: &mut StubLibrary, name: &str, stub: F)
+where
+    F: Fn(&[Interval]) -> Interval + 'static,
+{
+    library.register_division(format!("core.intrinsics.{}", name), Box::new(stub));
+}
+
+/// Registers the stubs of the primitives, `pointer_width` is the bit width of `isize` and `usize`
+pub fn register(library: &mut StubLibrary, pointer_width: u64) {
+    // The unchecked operations are undefined behavior if they overflow or divide by zero, which is reported, and the
+    // result is in the range of the return type, which the range computed by the stub is intersected with
+    register_intrinsic(library, "unchecked_add", |args| {
+        args[0].clone() + args[1].clone()
+    });
+    register_intrinsic(library, "unchecked_sub", |args| {
+        args[0].clone() - args[1].clone()
+    });
+    register_intrinsic(library, "unchecked_mul", |args| {
+        args[0].clone() * args[1].clone()
+    });
+    for name in ["unchecked_div", "exact_div"] {
+        register_division_intrinsic(library, name, |args| div(&args[0], &args[1]));
+    }
+    register_division_intrinsic(library, "unchecked_rem", |args| rem(&args[0], &args[1]));
+    register_intrinsic(library, "ctpop", |args| count_ones(&args[0]));
+    for name in ["ctlz", "cttz", "ctlz_nonzero", "cttz_nonzero"] {
+        register_intrinsic(library, name, |_| {
+            range(Integer::from(0), Integer::from(MAX_BIT_WIDTH))
+        });
+    }
+
+    // The size of a value, e.g., of a slice, is at most `isize::MAX`
+    let max_size = (Integer::from(1) << (pointer_width as u32 - 1)) - 1;
+    for key in ["core.mem.size_of_val", "core.intrinsics.size_of_val"] {
+        let max_size = max_size.clone();
+        library.register(
+            key.to_owned(),
+            Box::new(move |_| range(Integer::from(0), max_size.clone())),
+        );
+    }
+    for key in ["core.mem.align_of_val", "core.intrinsics.min_align_of_val"] {
+        library.register(
+            key.to_owned(),
+            Box::new(|_| range(Integer::from(1), Integer::from(MAX_ALIGNMENT))),
+        );
+    }
+    // The distance between two pointers into the same slice
+    register_intrinsic(library, "ptr_offset_from_unsigned", move |_| {
+        range(Integer::from(0), max_size.clone())
+    });
+
+    // The number of items of an iterator is a `usize`
+    let max_count = (Integer::from(1) << (pointer_width as u32)) - 1;
+    for key in [
+        "core.iter.traits.iterator.Iterator.count",
+        "core.iter.traits.exact_size.ExactSizeIterator.len",
+    ] {
+        let max_count = max_count.clone();
+        library.register(
+            key.to_owned(),
+            Box::new(move |_| range(Integer::from(0), max_count.clone())),
+        );
+    }
+}
@@ -0,0 +1,62 @@
+use crate::analysis::numerical::interval::Interval;
+use crate::analysis::stubs::{cmp, integer, primitives};
+use std::collections::{HashMap, HashSet};
+
+/// Computes the range of the return value of a function from the ranges of its arguments
+pub type Stub = Box<dyn Fn(&[Interval]) -> Interval>;
+
+/// The stubs of the standard library functions, indexed by the summary key of each function, see `summary_key_str`
+/// A call to a function that has a stub assigns the range computed by the stub to the destination instead of
+/// analyzing the body of the function, which is usually less precise or not available at all
+/// The range computed by a stub may exceed the range of the return type, e.g., when the call overflows, so the call
+/// is reported and the range is intersected with the range of the return type by `CallVisitor::handled_by_stub`
+pub struct StubLibrary {
+    stubs: HashMap<String, Stub>,
+    /// The functions whose second argument is a divisor, which panic or are undefined behavior if it is zero
+    divisions: HashSet<String>,
+}
+
+impl StubLibrary {
+    /// Creates the library of all the stubs, `pointer_width` is the bit width of `isize` and `usize`
+    /// The stubs of the primitives of `core` are only registered if `no_std` is set, a crate that uses `std` calls
+    /// them through the methods that have their own stubs, e.g., `u32::count_ones`
+    pub fn new(pointer_width: u64, no_std: bool) -> Self {
+        let mut library = Self {
+            stubs: HashMap::new(),
+            divisions: HashSet::new(),
+        };
+        cmp::register(&mut library);
+        integer::register(&mut library, pointer_width);
+        if no_std {
+            primitives::register(&mut library, pointer_width);
+        }
+        library
+    }
+
+    pub fn register(&mut self, summary_key: String, stub: Stub) {
+        self.stubs.insert(summary_key, stub);
+    }
+
+    /// Registers the stub of a function whose second argument is a divisor, see `is_division`
+    pub fn register_division(&mut self, summary_key: String, stub: Stub) {
+        self.divisions.insert(summary_key.clone());
+        self.register(summary_key, stub);
+    }
+
+    pub fn get(&self, summary_key: &str) -> Option<&Stub> {
+        self.stubs.get(summary_key)
+    }
+
+    /// True if the second argument of the function is a divisor, so that the call is reported if it may be zero
+    pub fn is_division(&self, summary_key: &str) -> bool {
+        self.divisions.contains(summary_key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.stubs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stubs.is_empty()
+    }
+}
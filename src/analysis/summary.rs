@@ -0,0 +1,202 @@
+use crate::analysis::abstract_domain::AbstractDomain;
+use crate::analysis::memory::path::Path;
+use crate::analysis::numerical::apron_domain::{
+    ApronAbstractDomain, ApronDomainType, GetManagerTrait,
+};
+use crate::analysis::numerical::interval::{Bound, Interval};
+use crate::analysis::numerical::lattice::LatticeTrait;
+use crate::analysis::option::{AbstractDomainType, AnalysisOption};
+use log::{debug, error, info};
+use rug::Integer;
+use std::collections::{BTreeMap, HashMap};
+use std::rc::Rc;
+
+/// The name of the return value in the variables of a summary
+pub const RETURN_VARIABLE: &str = "return";
+
+/// The summary of a function that is analyzed with unknown arguments
+/// Summaries are stored in `--cache-dir` after a crate is analyzed, so that the crates of a workspace that are
+/// analyzed later can use them instead of analyzing the callees from other crates again
+/// A stored summary is self-describing: it records the abstract domain it was computed in, and names the
+/// variables it constrains, so that a summary can be applied by an analysis that uses a different domain
+#[derive(Clone, Debug, PartialEq)]
+pub struct FunctionSummary {
+    // The abstract domain in which the function is analyzed
+    pub domain: AbstractDomainType,
+    // The ranges of the variables at the exit of the function, indexed by their names
+    // The range of `RETURN_VARIABLE` is bottom if the function never returns normally
+    pub variables: BTreeMap<String, Interval>,
+}
+
+impl FunctionSummary {
+    pub fn new(domain: AbstractDomainType, return_value: Interval) -> Self {
+        let mut variables = BTreeMap::new();
+        variables.insert(RETURN_VARIABLE.to_owned(), return_value);
+        Self { domain, variables }
+    }
+
+    /// The range of the return value, top if it is not recorded
+    pub fn return_value(&self) -> Interval {
+        self.variables
+            .get(RETURN_VARIABLE)
+            .cloned()
+            .unwrap_or_else(Interval::top)
+    }
+
+    /// Apply the summary to the state `state` after a call of the function, i.e., assign the range of the return
+    /// value to the destination `target_path` of the call, the state is bottom if the function never returns
+    /// The other variables of the summary are not related to the caller, so they are ignored
+    pub fn apply<Type>(&self, state: &mut AbstractDomain<Type>, target_path: Option<&Rc<Path>>)
+    where
+        Type: ApronDomainType,
+        ApronAbstractDomain<Type>: GetManagerTrait,
+    {
+        let return_value = self.return_value();
+        if return_value.is_bottom() {
+            state.numerical_domain.set_to_bottom();
+        } else if let Some(target_path) = target_path {
+            state.remove(target_path);
+            state
+                .numerical_domain
+                .set_interval(target_path, return_value);
+        }
+    }
+
+    /// Combine the ranges of each variable with `f`, a variable missing from one side is top
+    fn combine(&self, other: &Self, f: impl Fn(&Interval, &Interval) -> Interval) -> Self {
+        let variables = self
+            .variables
+            .iter()
+            .filter_map(|(name, interval)| {
+                other
+                    .variables
+                    .get(name)
+                    .map(|other_interval| (name.clone(), f(interval, other_interval)))
+            })
+            .collect();
+        Self {
+            domain: self.domain,
+            variables,
+        }
+    }
+
+    pub fn join(&self, other: &Self) -> Self {
+        self.combine(other, Interval::join)
+    }
+
+    pub fn widening_with(&self, other: &Self) -> Self {
+        self.combine(other, Interval::widening_with)
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        let bound_to_json = |bound: &Bound| match bound {
+            Bound::Int(n) => serde_json::Value::String(n.to_string()),
+            Bound::INF | Bound::NINF => serde_json::Value::Null,
+        };
+        let variables: serde_json::Map<String, serde_json::Value> = self
+            .variables
+            .iter()
+            .map(|(name, interval)| {
+                let value = if interval.is_bottom() {
+                    serde_json::Value::Null
+                } else {
+                    serde_json::json!([bound_to_json(&interval.low), bound_to_json(&interval.high)])
+                };
+                (name.clone(), value)
+            })
+            .collect();
+        serde_json::json!({
+            "domain": self.domain.name(),
+            "variables": variables,
+        })
+    }
+
+    fn from_json(value: &serde_json::Value) -> Option<Self> {
+        let domain = AnalysisOption::get_domain_type(value.get("domain")?.as_str()?)?;
+        let mut variables = BTreeMap::new();
+        for (name, interval) in value.get("variables")?.as_object()? {
+            let interval = match interval {
+                serde_json::Value::Null => Interval::bottom(),
+                serde_json::Value::Array(bounds) if bounds.len() == 2 => {
+                    let low = match &bounds[0] {
+                        serde_json::Value::String(n) => Bound::Int(n.parse::<Integer>().ok()?),
+                        _ => Bound::NINF,
+                    };
+                    let high = match &bounds[1] {
+                        serde_json::Value::String(n) => Bound::Int(n.parse::<Integer>().ok()?),
+                        _ => Bound::INF,
+                    };
+                    Interval::new(low, high)
+                }
+                _ => return None,
+            };
+            variables.insert(name.clone(), interval);
+        }
+        Some(Self { domain, variables })
+    }
+}
+
+/// Load the summaries of all the crates that have been analyzed into `cache_dir`,
+/// indexed by the summary key of each function, see `utils::summary_key_str`
+pub fn load_summaries(cache_dir: &str) -> HashMap<String, FunctionSummary> {
+    let mut summaries = HashMap::new();
+    let entries = match std::fs::read_dir(cache_dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            debug!("No summaries are loaded from {}: {}", cache_dir, e);
+            return summaries;
+        }
+    };
+    for entry in entries.flatten() {
+        let file_path = entry.path();
+        if file_path.extension().map_or(true, |ext| ext != "json") {
+            continue;
+        }
+        let content = match std::fs::read_to_string(&file_path) {
+            Ok(content) => content,
+            Err(e) => {
+                error!("Failed to read summaries from {:?}: {}", file_path, e);
+                continue;
+            }
+        };
+        match serde_json::from_str::<serde_json::Value>(&content) {
+            Ok(serde_json::Value::Object(functions)) => {
+                for (key, value) in functions.iter() {
+                    if let Some(summary) = FunctionSummary::from_json(value) {
+                        summaries.insert(key.clone(), summary);
+                    } else {
+                        debug!(
+                            "Ignore the ill-formed summary of {} in {:?}",
+                            key, file_path
+                        );
+                    }
+                }
+            }
+            _ => error!("Ill-formed summaries in {:?}", file_path),
+        }
+    }
+    info!("Loaded {} summaries from {}", summaries.len(), cache_dir);
+    summaries
+}
+
+/// Store the summaries of the functions in the current crate into `<cache_dir>/<file_name>.json`
+pub fn store_summaries(
+    cache_dir: &str,
+    file_name: &str,
+    summaries: &HashMap<Rc<String>, FunctionSummary>,
+) {
+    let functions: serde_json::Map<String, serde_json::Value> = summaries
+        .iter()
+        .map(|(key, summary)| (key.to_string(), summary.to_json()))
+        .collect();
+    let output = serde_json::to_string_pretty(&serde_json::Value::Object(functions))
+        .expect("failed to serialize summaries");
+    let file_path = std::path::Path::new(cache_dir).join(format!("{}.json", file_name));
+    if let Err(e) =
+        std::fs::create_dir_all(cache_dir).and_then(|_| std::fs::write(&file_path, output))
+    {
+        error!("Failed to store summaries to {:?}: {}", file_path, e);
+    } else {
+        info!("Summaries have been stored to {:?}", file_path);
+    }
+}
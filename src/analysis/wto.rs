@@ -16,6 +16,21 @@ pub enum WtoComponent {
     Circle(WtoCircle),
 }
 
+impl WtoComponent {
+    /// Serializes a vertex as the name of its basic block, e.g., `"BB3"`, and a circle as an array whose first
+    /// element is its head, e.g., `["BB1", "BB2", ["BB3", "BB4"]]`
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            WtoComponent::Vertex(v) => serde_json::Value::String(format!("BB{}", v.node().index())),
+            WtoComponent::Circle(c) => {
+                let mut components = vec![WtoComponent::Vertex(c.head()).to_json()];
+                components.extend(c.component.iter().map(WtoComponent::to_json));
+                serde_json::Value::Array(components)
+            }
+        }
+    }
+}
+
 impl Debug for WtoComponent {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -124,6 +139,18 @@ impl WtoCircle {
     pub fn get_iter_num(&self) -> u32 {
         *self.num_iter.borrow()
     }
+
+    /// All the basic blocks in the circle, including the ones in nested circles
+    pub fn blocks(&self) -> Vec<BasicBlock> {
+        let mut blocks = vec![self.head];
+        for comp in &self.component {
+            match comp {
+                WtoComponent::Vertex(v) => blocks.push(v.node()),
+                WtoComponent::Circle(c) => blocks.extend(c.blocks()),
+            }
+        }
+        blocks
+    }
 }
 
 /// The weak topological order of a CFG
@@ -198,6 +225,22 @@ impl<'tcx> Wto<'tcx> {
         self.wto_components.clone()
     }
 
+    /// The nesting depth of the circle whose head is `head`, relative to the function body, e.g., 1 for a loop that
+    /// is not nested in another loop, and 2 for a loop nested in it
+    pub fn circle_depth(&self, head: BasicBlock) -> usize {
+        let outer_circles = self
+            .nesting_map
+            .get(&WtoVertex::new(head))
+            .map_or(0, |nesting| nesting.wto_nesting.len());
+        outer_circles + 1
+    }
+
+    /// Serializes the components as an array, e.g., `["BB0", ["BB1", "BB2"], "BB3"]`, see `WtoComponent::to_json`
+    pub fn to_json(&self) -> serde_json::Value {
+        let components = self.wto_components.iter().map(WtoComponent::to_json);
+        serde_json::Value::Array(components.collect())
+    }
+
     fn component(&mut self, vertex: BasicBlock) -> WtoCircle {
         let mut partition = Vec::new();
         for succ in self.cfg.basic_blocks.successors(vertex) {
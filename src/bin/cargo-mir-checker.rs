@@ -1,15 +1,20 @@
 /// This file is derived from miri:
 /// https://github.com/rust-lang/miri/blob/master/src/bin/cargo-miri.rs
+use rust_mir_checker::analysis::sqlite_export::{self, RUN_ID_ENV};
 use rust_mir_checker::utils;
 use serde_json;
 use std::ffi::OsString;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 const CARGO_MIR_CHECKER_HELP: &str = r#"Static analysis tool for Rust programs
 
 Usage:
-    cargo mir-checker
+    cargo mir-checker [--workspace] [<cargo options>...] [-- <mir-checker options>...]
+
+Options:
+    --workspace    Analyze all the crates of the workspace in dependency order, sharing function
+                   summaries between them through `--cache-dir` (default: target/mir-checker-cache)
 "#;
 
 fn show_help() {
@@ -59,23 +64,28 @@ fn get_arg_flag_value(name: &str) -> Option<String> {
     }
 }
 
-// Get the top level crate that we need to analyze
-fn current_crate() -> cargo_metadata::Package {
-    // We need to get the manifest, and then the metadata, to enumerate targets.
-
-    // Path to the `Cargo.toml` file
-    let manifest_path =
-        get_arg_flag_value("--manifest-path").map(|m| Path::new(&m).canonicalize().unwrap());
+// Path to the `Cargo.toml` file, if specified by `--manifest-path`
+fn manifest_path() -> Option<PathBuf> {
+    get_arg_flag_value("--manifest-path").map(|m| Path::new(&m).canonicalize().unwrap())
+}
 
+fn metadata(manifest_path: &Option<PathBuf>) -> cargo_metadata::Metadata {
     let mut cmd = cargo_metadata::MetadataCommand::new();
     if let Some(ref manifest_path) = manifest_path {
         cmd.manifest_path(manifest_path);
     }
-    let mut metadata = if let Ok(metadata) = cmd.exec() {
+    if let Ok(metadata) = cmd.exec() {
         metadata
     } else {
         show_error("Could not obtain Cargo metadata; likely an ill-formed manifest".to_string());
-    };
+    }
+}
+
+// Get the top level crate that we need to analyze
+fn current_crate() -> cargo_metadata::Package {
+    // We need to get the manifest, and then the metadata, to enumerate targets.
+    let manifest_path = manifest_path();
+    let mut metadata = metadata(&manifest_path);
 
     let current_dir = std::env::current_dir();
 
@@ -98,7 +108,8 @@ fn current_crate() -> cargo_metadata::Package {
         })
         .unwrap_or_else(|| {
             show_error(
-                "This seems to be a workspace, which is not supported by cargo-miri".to_string(),
+                "This seems to be a workspace, use `--workspace` to analyze all of its crates"
+                    .to_string(),
             )
         });
     let package = metadata.packages.remove(package_index);
@@ -106,6 +117,47 @@ fn current_crate() -> cargo_metadata::Package {
     package
 }
 
+// Get the crates of the workspace and the target directory
+// The crates are sorted so that each crate comes after the crates of the workspace that it depends on
+fn workspace_crates() -> (Vec<cargo_metadata::Package>, PathBuf) {
+    let metadata = metadata(&manifest_path());
+    let members: Vec<cargo_metadata::Package> =
+        metadata.workspace_packages().into_iter().cloned().collect();
+
+    // Depth-first search on the dependency graph, dev-dependencies are ignored because they may form cycles
+    fn visit(
+        index: usize,
+        members: &[cargo_metadata::Package],
+        visited: &mut [bool],
+        sorted: &mut Vec<usize>,
+    ) {
+        if visited[index] {
+            return;
+        }
+        visited[index] = true;
+        for dependency in members[index].dependencies.iter() {
+            if dependency.kind == cargo_metadata::DependencyKind::Development {
+                continue;
+            }
+            if let Some(dependency_index) = members.iter().position(|p| p.name == dependency.name) {
+                visit(dependency_index, members, visited, sorted);
+            }
+        }
+        sorted.push(index);
+    }
+
+    let mut visited = vec![false; members.len()];
+    let mut sorted = Vec::new();
+    for index in 0..members.len() {
+        visit(index, &members, &mut visited, &mut sorted);
+    }
+    let crates = sorted
+        .into_iter()
+        .map(|index| members[index].clone())
+        .collect();
+    (crates, metadata.target_directory.into_std_path_buf())
+}
+
 fn mir_checker() -> Command {
     let mut path = std::env::current_exe().expect("current executable path invalid");
     path.set_file_name("mir-checker");
@@ -159,11 +211,47 @@ fn main() {
 // `MIR_CHECKER_ARGS` is set to the user-provided arguments for `mir-checker`
 // `MIR_CHEKCER_TOP_CRATE_NAME` is set to the name of the crate being analyzed
 // `MIR_CHECKER_VERBOSE` is set if `-v` is provided
+// `MIR_CHECKER_RUN_ID` is set to the same id for all the crates, unless it is already set
+// With `--workspace`, this is done for each crate of the workspace in dependency order
 fn in_cargo_mir_checker() {
     let verbose = has_arg_flag("-v");
+    // The diagnostics of all the crates exported by `--output-format sqlite` belong to one run
+    if std::env::var_os(RUN_ID_ENV).is_none() {
+        std::env::set_var(RUN_ID_ENV, sqlite_export::new_run_id());
+    }
 
-    let current_crate = current_crate();
+    if has_arg_flag("--workspace") {
+        let (crates, target_directory) = workspace_crates();
+        // Summaries are stored into the cache directory after a crate is analyzed,
+        // and loaded when analyzing the crates that depend on it
+        let mut extra_args = Vec::new();
+        if !std::env::args()
+            .skip_while(|val| val != "--")
+            .any(|val| val == "--cache-dir")
+        {
+            let cache_dir = target_directory.join("mir-checker-cache");
+            extra_args.push("--cache-dir".to_string());
+            extra_args.push(cache_dir.to_string_lossy().to_string());
+        }
+        for current_crate in crates.into_iter() {
+            if verbose {
+                eprintln!("Analyzing crate {}", current_crate.name);
+            }
+            analyze_crate(current_crate, true, &extra_args, verbose);
+        }
+    } else {
+        analyze_crate(current_crate(), false, &[], verbose);
+    }
+}
 
+// Run `cargo check` for each target of `current_crate`, `extra_args` are appended to the arguments for `mir-checker`
+// If `in_workspace` is set, the crate is selected by `--package`
+fn analyze_crate(
+    current_crate: cargo_metadata::Package,
+    in_workspace: bool,
+    extra_args: &[String],
+    verbose: bool,
+) {
     // Now run the command.
     for target in current_crate.targets.into_iter() {
         let mut args = std::env::args().skip(2);
@@ -186,12 +274,19 @@ fn in_cargo_mir_checker() {
             }
             _ => continue,
         }
+        if in_workspace {
+            cmd.arg("--package").arg(&current_crate.name);
+        }
 
         // Add cargo args until first `--`.
         while let Some(arg) = args.next() {
             if arg == "--" {
                 break;
             }
+            // `--workspace` is handled by ourselves, passing it to cargo would check all the crates at once
+            if arg == "--workspace" {
+                continue;
+            }
             cmd.arg(arg);
         }
 
@@ -203,7 +298,7 @@ fn in_cargo_mir_checker() {
         // We also add `MIR_CHEKCER_TOP_CRATE_NAME` to specify the top-level
         // crate name that we want to analyze, by doing this we can dispatch
         // dependencies to the real `rustc` and top-level crate to `mir-checker`
-        let args_vec: Vec<String> = args.collect();
+        let args_vec: Vec<String> = args.chain(extra_args.iter().cloned()).collect();
         cmd.env(
             "MIR_CHECKER_ARGS",
             serde_json::to_string(&args_vec).expect("failed to serialize args"),
@@ -0,0 +1,65 @@
+use rust_mir_checker::analysis::diagnostic_diff::{self, DiagnosticEntry};
+
+const MIR_CHECKER_DIFF_HELP: &str = r#"Compare the diagnostics of two runs written by `--output-format gitlab`
+
+Usage:
+    mir-checker-diff <old-report> <new-report>
+
+A diagnostic of the new run matches a diagnostic of the old run if they have the same file and
+cause, and their lines differ by at most 2. The exit code is 1 if the new run has diagnostics
+that do not match any diagnostic of the old run, and 2 if a report cannot be read.
+"#;
+
+/// The exit code of an invalid report, which is distinguished from the exit code of a regression
+const EXIT_INVALID_REPORT: i32 = 2;
+
+fn show_error(msg: String) -> ! {
+    eprintln!("fatal error: {}", msg);
+    std::process::exit(EXIT_INVALID_REPORT)
+}
+
+fn load_report(file_path: &str) -> Vec<DiagnosticEntry> {
+    let text = std::fs::read_to_string(file_path)
+        .unwrap_or_else(|e| show_error(format!("cannot read {}: {}", file_path, e)));
+    diagnostic_diff::parse_report(&text)
+        .unwrap_or_else(|e| show_error(format!("cannot parse {}: {}", file_path, e)))
+}
+
+fn print_entries<'a>(title: &str, entries: impl ExactSizeIterator<Item = &'a DiagnosticEntry>) {
+    println!("{} diagnostics ({}):", title, entries.len());
+    for entry in entries {
+        println!(
+            "    {}:{}: [{}] {}",
+            entry.file, entry.line, entry.cause, entry.message
+        );
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", MIR_CHECKER_DIFF_HELP);
+        return;
+    }
+    let (old_path, new_path) = match args.as_slice() {
+        [old_path, new_path] => (old_path, new_path),
+        _ => show_error(format!("expected two reports\n{}", MIR_CHECKER_DIFF_HELP)),
+    };
+
+    let old = load_report(old_path);
+    let new = load_report(new_path);
+    let diff = diagnostic_diff::diff(&old, &new);
+
+    print_entries("New", diff.new.iter());
+    print_entries("Fixed", diff.fixed.iter());
+    // The unchanged diagnostics are printed at their lines in the new run
+    print_entries(
+        "Unchanged",
+        diff.unchanged.iter().map(|(_, new_entry)| new_entry),
+    );
+
+    // Only the regressions fail, so that the diagnostics that already exist do not block a change
+    if !diff.new.is_empty() {
+        std::process::exit(1);
+    }
+}
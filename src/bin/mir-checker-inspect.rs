@@ -0,0 +1,90 @@
+const MIR_CHECKER_INSPECT_HELP: &str = r#"Query the abstract states written by `--dump-states`
+
+Usage:
+    mir-checker-inspect <file>            list the basic blocks and their terminators
+    mir-checker-inspect <file> --bb <N>   print the state and the terminator of basic block N
+"#;
+
+fn show_error(msg: String) -> ! {
+    eprintln!("fatal error: {}", msg);
+    std::process::exit(1)
+}
+
+/// The state of a basic block, i.e., an element of the dumped array
+struct BlockState {
+    bb: u64,
+    state: String,
+    terminator: String,
+}
+
+fn load_states(file_path: &str) -> Result<Vec<BlockState>, String> {
+    let content = std::fs::read_to_string(file_path).map_err(|e| e.to_string())?;
+    let value = serde_json::from_str::<serde_json::Value>(&content).map_err(|e| e.to_string())?;
+    let blocks = value
+        .as_array()
+        .ok_or_else(|| String::from("expected an array of basic blocks"))?;
+    blocks
+        .iter()
+        .map(|block| {
+            let bb = block.get("bb").and_then(|bb| bb.as_u64());
+            let state = block.get("state").and_then(|state| state.as_str());
+            let terminator = block.get("terminator").and_then(|term| term.as_str());
+            match (bb, state, terminator) {
+                (Some(bb), Some(state), Some(terminator)) => Ok(BlockState {
+                    bb,
+                    state: state.to_string(),
+                    terminator: terminator.to_string(),
+                }),
+                _ => Err(format!("ill-formed basic block {}", block)),
+            }
+        })
+        .collect()
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", MIR_CHECKER_INSPECT_HELP);
+        return;
+    }
+    let (file_path, bb) = match args.as_slice() {
+        [file_path] => (file_path, None),
+        [file_path, flag, bb] if flag == "--bb" => {
+            // Accept both `3` and `bb3`, as the basic blocks are printed in MIR
+            let index = bb
+                .trim_start_matches("bb")
+                .parse::<u64>()
+                .unwrap_or_else(|_| show_error(format!("invalid basic block `{}`", bb)));
+            (file_path, Some(index))
+        }
+        _ => show_error(format!(
+            "expected `<file>` or `<file> --bb <N>`\n{}",
+            MIR_CHECKER_INSPECT_HELP
+        )),
+    };
+
+    let blocks = load_states(file_path)
+        .unwrap_or_else(|e| show_error(format!("cannot load {}: {}", file_path, e)));
+    match bb {
+        Some(index) => {
+            let block = blocks
+                .iter()
+                .find(|block| block.bb == index)
+                .unwrap_or_else(|| {
+                    show_error(format!(
+                        "basic block {} is not in {}, which has {} basic blocks",
+                        index,
+                        file_path,
+                        blocks.len()
+                    ))
+                });
+            println!("bb{}: {}", block.bb, block.terminator);
+            println!("{}", block.state);
+        }
+        None => {
+            for block in &blocks {
+                println!("bb{}: {}", block.bb, block.terminator);
+            }
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use rust_mir_checker::analysis::checkpoint::Checkpoint;
+use rust_mir_checker::checker::assertion_checker::CheckerResult;
+
+const MIR_CHECKER_REPLAY_HELP: &str = r#"Run the assertion checker again on a checkpoint written by `--checkpoint-dir`
+
+Usage:
+    mir-checker-replay --checkpoint <file>
+"#;
+
+fn show_error(msg: String) -> ! {
+    eprintln!("fatal error: {}", msg);
+    std::process::exit(1)
+}
+
+fn main() {
+    pretty_env_logger::init();
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", MIR_CHECKER_REPLAY_HELP);
+        return;
+    }
+    let file_path = match args.as_slice() {
+        [flag, file_path] if flag == "--checkpoint" => file_path,
+        _ => show_error(format!(
+            "expected `--checkpoint <file>`\n{}",
+            MIR_CHECKER_REPLAY_HELP
+        )),
+    };
+
+    let checkpoint = Checkpoint::load(file_path)
+        .unwrap_or_else(|e| show_error(format!("cannot load {}: {}", file_path, e)));
+    let results = checkpoint
+        .replay()
+        .unwrap_or_else(|e| show_error(format!("cannot replay {}: {}", file_path, e)));
+
+    // Diagnostics are printed as the `vscode` output format does
+    let mut count = 0;
+    for (assertion, result) in results {
+        let kind = match result {
+            CheckerResult::Safe => continue,
+            CheckerResult::Unsafe => "Provably error",
+            CheckerResult::Warning => "Possible error",
+        };
+        println!(
+            "{}: warning: [MirChecker] {}: {:?}",
+            assertion.location, kind, assertion.message
+        );
+        count += 1;
+    }
+    println!(
+        "{}: {} diagnostics in {} assertions",
+        checkpoint.function,
+        count,
+        checkpoint.assertions.len()
+    );
+}
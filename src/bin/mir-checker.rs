@@ -31,6 +31,16 @@ fn main() -> ! {
     let result = rustc_driver::catch_fatal_errors(move || {
         let mut rustc_args = args::raw_args(&early_dcx)?;
 
+        // Print the JSON schema of the configuration for editor tooling, without compiling anything
+        if rustc_args.iter().any(|arg| arg == "--dump-config-schema") {
+            let schema = option::AnalysisOption::config_schema();
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&schema).expect("failed to serialize the schema")
+            );
+            return Ok(());
+        }
+
         if let Some(sysroot) = utils::compile_time_sysroot() {
             let sysroot_flag = "--sysroot";
             if !rustc_args.iter().any(|e| e == sysroot_flag) {
@@ -63,7 +73,17 @@ fn main() -> ! {
             // Disable unwind to simplify the CFG
             rustc_args.push("-Cpanic=abort".to_owned());
 
-            let analysis_options = option::AnalysisOption::from_args(&mut rustc_args);
+            // Report all the invalid options before the compiler starts
+            let analysis_options = match option::AnalysisOption::from_args(&mut rustc_args) {
+                Ok(analysis_options) => analysis_options,
+                Err(errors) => {
+                    let mut guar = None;
+                    for error in errors {
+                        guar = Some(early_dcx.early_err(error));
+                    }
+                    return Err(guar.expect("at least one error is reported"));
+                }
+            };
             info!("Analysis Option: {:?}", analysis_options);
 
             let mut callbacks = analysis::callback::MirCheckerCallbacks::new(analysis_options);
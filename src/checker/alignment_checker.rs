@@ -0,0 +1,104 @@
+use crate::analysis::diagnostics::DiagnosticCause;
+use crate::analysis::mir_visitor::body_visitor::WtoFixPointIterator;
+use crate::analysis::numerical::apron_domain::{
+    ApronAbstractDomain, ApronDomainType, GetManagerTrait,
+};
+use crate::checker::checker_trait::CheckerTrait;
+use log::debug;
+use rustc_span::Span;
+
+/// The operations that require their pointer operand to be aligned for the pointee type
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AlignedOperation {
+    // `mem::transmute` from a pointer or a reference into a reference
+    Transmute,
+    // `ptr::read` or `<*const T>::read`
+    Read,
+    // `ptr::write` or `<*mut T>::write`
+    Write,
+}
+
+/// A use of a pointer that requires alignment, recorded during the fixed-point iteration
+#[derive(Clone, Debug)]
+pub struct AlignedAccess {
+    pub span: Span,
+    pub operation: AlignedOperation,
+    // The alignment of the pointee type, in bytes
+    pub required_alignment: u64,
+    // The alignment that the address of the pointer is known to be a multiple of, in bytes
+    pub known_alignment: u64,
+}
+
+/// Checks whether pointers used by `transmute`, `ptr::read` and `ptr::write` are aligned for the target type
+/// Only pointers whose alignment is known are checked, i.e., pointers derived from a reference or from
+/// a slice, possibly casted or moved by `add`, `sub` or `offset`
+pub struct AlignmentChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>,
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> CheckerTrait<'tcx, 'a, 'b, 'compiler, DomainType>
+    for AlignmentChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn new(body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>) -> Self {
+        Self { body_visitor }
+    }
+
+    fn run(&mut self) {
+        info!("====== Alignment Checker starts ======");
+        let accesses = self
+            .body_visitor
+            .reachable_in_order(&self.body_visitor.aligned_accesses);
+        for (_, access) in accesses {
+            self.check_access(&access);
+        }
+        info!("====== Alignment Checker ends ======");
+    }
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> AlignmentChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn check_access(&mut self, access: &AlignedAccess) {
+        debug!("Checking aligned access: {:?}", access);
+        // Alignments are powers of two, so the requirement is met if it divides the known alignment
+        if access.required_alignment == 0 || access.known_alignment % access.required_alignment == 0
+        {
+            return;
+        }
+        let (message, note) = match access.operation {
+            AlignedOperation::Transmute => (
+                "[MirChecker] Possible error: transmute to a reference of a misaligned pointer",
+                "consider copying the value with `ptr::read_unaligned` instead",
+            ),
+            AlignedOperation::Read => (
+                "[MirChecker] Possible error: read through a misaligned pointer",
+                "consider using `ptr::read_unaligned` instead",
+            ),
+            AlignedOperation::Write => (
+                "[MirChecker] Possible error: write through a misaligned pointer",
+                "consider using `ptr::write_unaligned` instead",
+            ),
+        };
+        let mut warning = self
+            .body_visitor
+            .context
+            .session
+            .dcx()
+            .struct_span_warn(access.span, message);
+        warning.note(format!(
+            "the pointer is only known to be aligned to {} byte(s), but {} byte(s) are required, {}",
+            access.known_alignment, access.required_alignment, note
+        ));
+        self.body_visitor
+            .emit_diagnostic(warning, true, DiagnosticCause::Memory);
+    }
+}
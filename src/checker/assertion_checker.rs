@@ -11,6 +11,7 @@ use crate::analysis::numerical::apron_domain::{
 use crate::analysis::numerical::linear_constraint::{
     LinearConstraint, LinearConstraintSystem, LinearExpression,
 };
+use crate::analysis::option::AbstractDomainType;
 use crate::analysis::z3_solver::SmtResult;
 use crate::analysis::z3_solver::Z3Solver;
 use crate::checker::checker_trait::CheckerTrait;
@@ -46,8 +47,11 @@ where
         for (bb, bb_data) in basic_blocks.iter_enumerated() {
             let term = bb_data.terminator();
             let post = self.body_visitor.post.clone();
+            // The blocks whose state is bottom are never reached, e.g., the targets of dead branches
             if let Some(s) = post.get(&bb) {
-                self.run_terminator(term, s);
+                if !s.is_bottom() {
+                    self.run_terminator(term, s);
+                }
             }
         }
         info!("====== Assertion Checker ends ======");
@@ -85,11 +89,46 @@ where
             );
             debug!("Current state: {:?}", abstract_value);
 
+            // The assertions that a coroutine is not resumed after it returns or panics have constant conditions,
+            // they are only reported in the panic-freedom mode, where any reachable panic is an error
+            if matches!(
+                **msg,
+                mir::AssertKind::ResumedAfterReturn(..) | mir::AssertKind::ResumedAfterPanic(..)
+            ) && self.body_visitor.context.analysis_options.panic_freedom
+            {
+                let warning = self.body_visitor.context.session.dcx().struct_span_warn(
+                    span,
+                    format!(
+                        "[MirChecker] Possible error: {:?}",
+                        self.body_visitor.recover_var_name(msg)
+                    ),
+                );
+                self.body_visitor
+                    .emit_diagnostic(warning, false, DiagnosticCause::from(&**msg));
+                return;
+            }
+
             if let Some(place) = cond.place() {
                 if let Some(cond_val) = self.body_visitor.place_to_abstract_value.get(&place) {
                     debug!("place: {:?}, cond_val: {:?}", place, cond_val);
                     let cond_val = cond_val.clone();
+                    let wraps_on_overflow = self.body_visitor.context.analysis_options.domain_type
+                        == AbstractDomainType::WrappingInteger;
                     let check_result = match **msg {
+                        // In the `wrapping-integer` domain, arithmetic overflow is the intended behavior
+                        mir::AssertKind::Overflow(
+                            mir::BinOp::Add | mir::BinOp::Sub | mir::BinOp::Mul,
+                            ..,
+                        )
+                        | mir::AssertKind::OverflowNeg(..)
+                            if wraps_on_overflow =>
+                        {
+                            CheckerResult::Safe
+                        }
+                        // The condition of a shift is a comparison of the shift amount, not an overflow bit
+                        mir::AssertKind::Overflow(mir::BinOp::Shl | mir::BinOp::Shr, ..) => {
+                            self.check_assert_condition(cond_val, *expected, abstract_value)
+                        }
                         mir::AssertKind::Overflow(..) => {
                             self.check_overflow(cond_val.clone(), *expected, abstract_value)
                         }
@@ -259,10 +298,12 @@ where
                             unreachable!("selector is not field 1");
                         } else {
                             let new_path = Path::new_field(qualifier.clone(), 0);
-                            if let Some(rustc_type) =
-                                self.body_visitor.type_visitor.path_ty_cache.get(&new_path)
+                            if let Some(rustc_type) = self
+                                .body_visitor
+                                .type_visitor
+                                .get_cached_path_type(&new_path)
                             {
-                                self.check_within_range(new_path, *rustc_type, abstract_value)
+                                self.check_within_range(new_path, rustc_type, abstract_value)
                             } else {
                                 unreachable!(
                                     "Value that we want to test does not have type infomation"
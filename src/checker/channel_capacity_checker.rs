@@ -0,0 +1,98 @@
+use crate::analysis::diagnostics::DiagnosticCause;
+use crate::analysis::mir_visitor::body_visitor::WtoFixPointIterator;
+use crate::analysis::numerical::apron_domain::{
+    ApronAbstractDomain, ApronDomainType, GetManagerTrait,
+};
+use crate::analysis::numerical::interval::Interval;
+use crate::checker::assertion_checker::CheckerResult;
+use crate::checker::checker_trait::CheckerTrait;
+use log::debug;
+use rustc_span::Span;
+
+/// A call of `SyncSender::send` on a channel created by `sync_channel`, recorded during the fixed-point iteration
+/// A channel is modeled as the number of items in its buffer, which is incremented by `send` and decremented by
+/// `recv`, see `CallVisitor::handle_sync_channel`
+#[derive(Clone, Debug)]
+pub struct ChannelSend {
+    pub span: Span,
+    // The number of items in the buffer before the send
+    pub buffered: Interval,
+    // The capacity passed to `sync_channel`
+    pub capacity: Interval,
+}
+
+/// Checks whether a send on a bounded channel may block because its buffer is full
+/// Only the sends and receives in the function being analyzed are counted, so a send that waits for a receive in
+/// another thread is reported, which is still a point where the sender may block
+pub struct ChannelCapacityChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>,
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> CheckerTrait<'tcx, 'a, 'b, 'compiler, DomainType>
+    for ChannelCapacityChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn new(body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>) -> Self {
+        Self { body_visitor }
+    }
+
+    fn run(&mut self) {
+        info!("====== Channel Capacity Checker starts ======");
+        let channel_sends = self
+            .body_visitor
+            .reachable_in_order(&self.body_visitor.channel_sends);
+        for (_, send) in channel_sends {
+            self.check_send(&send);
+        }
+        info!("====== Channel Capacity Checker ends ======");
+    }
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType>
+    ChannelCapacityChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn check_send(&mut self, send: &ChannelSend) {
+        debug!("Checking channel send: {:?}", send);
+        let message = match Self::check_capacity(send) {
+            CheckerResult::Safe => return,
+            CheckerResult::Unsafe => {
+                "[MirChecker] Provably error: the send blocks, the buffer of the channel is full"
+            }
+            CheckerResult::Warning => {
+                "[MirChecker] Possible error: the send may block, the buffer of the channel may be full"
+            }
+        };
+        let warning = self
+            .body_visitor
+            .context
+            .session
+            .dcx()
+            .struct_span_warn(send.span, message);
+        self.body_visitor
+            .emit_diagnostic(warning, false, DiagnosticCause::Channel);
+    }
+
+    /// The send does not block if `buffered < capacity`
+    fn check_capacity(send: &ChannelSend) -> CheckerResult {
+        if send.buffered.is_bottom() || send.capacity.is_bottom() {
+            // Unreachable
+            return CheckerResult::Safe;
+        }
+        if send.buffered.high < send.capacity.low {
+            CheckerResult::Safe
+        } else if send.buffered.low >= send.capacity.high {
+            CheckerResult::Unsafe
+        } else {
+            CheckerResult::Warning
+        }
+    }
+}
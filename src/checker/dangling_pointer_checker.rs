@@ -0,0 +1,145 @@
+use crate::analysis::diagnostics::DiagnosticCause;
+use crate::analysis::memory::escape::{self, EscapeAnalysis};
+use crate::analysis::memory::expression::Expression;
+use crate::analysis::memory::path::{Path, PathEnum, PathSelector};
+use crate::analysis::memory::symbolic_value::SymbolicValue;
+use crate::analysis::mir_visitor::body_visitor::WtoFixPointIterator;
+use crate::analysis::numerical::apron_domain::{
+    ApronAbstractDomain, ApronDomainType, GetManagerTrait,
+};
+use crate::checker::checker_trait::CheckerTrait;
+use log::debug;
+use rustc_span::Span;
+use std::rc::Rc;
+
+/// A raw pointer to a variable of the current function that is stored into a location that may outlive the
+/// function, recorded during the fixed-point iteration
+#[derive(Clone, Debug)]
+pub struct DanglingStore {
+    pub span: Span,
+    // The location that the pointer is stored into
+    pub target: Rc<Path>,
+    // The variable that the pointer points to
+    pub local: Rc<Path>,
+}
+
+/// Whether `path` contains a dereference, e.g., `(*param_1).0`
+fn contains_deref(path: &Rc<Path>) -> bool {
+    match &path.value {
+        PathEnum::QualifiedPath {
+            qualifier,
+            selector,
+            ..
+        } => **selector == PathSelector::Deref || contains_deref(qualifier),
+        _ => false,
+    }
+}
+
+/// Whether `path` is in the frame of the current function, i.e., a local variable or a parameter, but not the
+/// memory that a parameter points to
+fn is_in_frame(path: &Rc<Path>) -> bool {
+    match &escape::get_root(path).value {
+        PathEnum::LocalVariable { .. } => true,
+        PathEnum::Parameter { .. } => !contains_deref(path),
+        _ => false,
+    }
+}
+
+/// Whether the location `path` may outlive the current function, i.e., the return value, a static variable, the
+/// memory that a parameter points to, or a heap allocation, which outlives the function only if it escapes
+pub fn may_outlive_frame(path: &Rc<Path>) -> bool {
+    match &escape::get_root(path).value {
+        PathEnum::Result | PathEnum::StaticVariable { .. } | PathEnum::HeapAllocation { .. } => {
+            true
+        }
+        PathEnum::Parameter { .. } => contains_deref(path),
+        _ => false,
+    }
+}
+
+/// The variable of the current function that `value` may point to, if any
+pub fn get_referenced_local(value: &Rc<SymbolicValue>) -> Option<Rc<Path>> {
+    match &value.expression {
+        Expression::Reference(path) if is_in_frame(path) => Some(path.clone()),
+        Expression::Join { left, right } => {
+            get_referenced_local(left).or_else(|| get_referenced_local(right))
+        }
+        Expression::Cast { operand, .. } => get_referenced_local(operand),
+        _ => None,
+    }
+}
+
+/// Checks the raw pointers to the variables of the current function that are stored into locations that outlive
+/// it, see `--check dangling-pointer`
+/// Such a pointer dangles after the function returns. The borrow checker rejects the references that outlive
+/// the variables they borrow, but not the raw pointers, e.g., `&x as *const i32` or `&raw const x`
+pub struct DanglingPointerChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>,
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> CheckerTrait<'tcx, 'a, 'b, 'compiler, DomainType>
+    for DanglingPointerChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn new(body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>) -> Self {
+        Self { body_visitor }
+    }
+
+    fn run(&mut self) {
+        info!("====== Dangling Pointer Checker starts ======");
+        let escape_analysis = EscapeAnalysis::new(self.body_visitor.post.values());
+        let dangling_stores = self
+            .body_visitor
+            .reachable_in_order(&self.body_visitor.dangling_stores);
+        for (_, store) in dangling_stores {
+            // A heap allocation that does not escape is freed before the function returns
+            if let PathEnum::HeapAllocation { id } = &escape::get_root(&store.target).value {
+                if !escape_analysis.escapes(*id) {
+                    continue;
+                }
+            }
+            self.check_store(&store);
+        }
+        info!("====== Dangling Pointer Checker ends ======");
+    }
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType>
+    DanglingPointerChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn check_store(&mut self, store: &DanglingStore) {
+        debug!("Checking dangling store: {:?}", store);
+        let local = self
+            .body_visitor
+            .get_path_var_name(escape::get_root(&store.local))
+            .map_or_else(
+                || String::from("a local variable"),
+                |name| format!("`{}`", name),
+            );
+        let target = match &escape::get_root(&store.target).value {
+            PathEnum::Result => "the return value",
+            PathEnum::StaticVariable { .. } => "a static variable",
+            PathEnum::Parameter { .. } => "the memory pointed to by a parameter",
+            _ => "a heap allocation that outlives the function",
+        };
+        let mut warning = self.body_visitor.context.session.dcx().struct_span_warn(
+            store.span,
+            format!(
+                "[MirChecker] Possible error: a pointer to {} is stored into {}",
+                local, target
+            ),
+        );
+        warning.note("the pointer dangles after the current function returns");
+        self.body_visitor
+            .emit_diagnostic(warning, true, DiagnosticCause::Memory);
+    }
+}
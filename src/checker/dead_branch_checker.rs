@@ -0,0 +1,107 @@
+use crate::analysis::diagnostics::DiagnosticCause;
+use crate::analysis::mir_visitor::body_visitor::WtoFixPointIterator;
+use crate::analysis::numerical::apron_domain::{
+    ApronAbstractDomain, ApronDomainType, GetManagerTrait,
+};
+use crate::analysis::numerical::lattice::LatticeTrait;
+use crate::analysis::numerical::linear_constraint::LinearConstraintSystem;
+use crate::checker::checker_trait::CheckerTrait;
+use rustc_middle::mir;
+use rustc_span::Span;
+use std::collections::HashSet;
+
+/// Reports the targets of `SwitchInt` terminators that can never be taken, i.e., the state at the
+/// terminator becomes bottom once it is constrained by the condition of the target, e.g., a match arm
+/// for a variant that the discriminant is proved not to be
+/// The fixed-point iteration already propagates bottom along these edges, so the blocks that are only
+/// reachable through them are not analyzed and have no other diagnostics
+/// Only the functions of the local crate are checked, and the number of the dead targets is counted
+/// in `GlobalContext::pruned_branches`
+pub struct DeadBranchChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>,
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> CheckerTrait<'tcx, 'a, 'b, 'compiler, DomainType>
+    for DeadBranchChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn new(body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>) -> Self {
+        Self { body_visitor }
+    }
+
+    fn run(&mut self) {
+        info!("====== Dead Branch Checker starts ======");
+        if self.body_visitor.def_id.is_local() {
+            let dead_branches = self.find_dead_branches();
+            info!("Pruned {} branches", dead_branches.len());
+            self.body_visitor.context.pruned_branches += dead_branches.len();
+            for span in dead_branches {
+                // Branches introduced by desugaring, e.g., of `?` and `for`, are not written by the user
+                if span.from_expansion() {
+                    continue;
+                }
+                let note = self.body_visitor.context.session.dcx().struct_span_note(
+                    span,
+                    "[MirChecker] Unreachable branch: the switched value never matches this branch",
+                );
+                self.body_visitor
+                    .emit_diagnostic(note, false, DiagnosticCause::DeadBranch);
+            }
+        }
+        info!("====== Dead Branch Checker ends ======");
+    }
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> DeadBranchChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    /// The spans of the targets of `SwitchInt` terminators that are never taken
+    fn find_dead_branches(&self) -> Vec<Span> {
+        let mut dead_branches: Vec<Span> = Vec::new();
+        for (bb, bb_data) in self.body_visitor.wto.basic_blocks.iter_enumerated() {
+            if bb_data.is_cleanup {
+                continue;
+            }
+            let term = bb_data.terminator();
+            let targets = match &term.kind {
+                mir::TerminatorKind::SwitchInt { targets, .. } => targets,
+                _ => continue,
+            };
+            let state = match self.body_visitor.post.get(&bb) {
+                Some(state) if !state.is_bottom() => state,
+                _ => continue,
+            };
+            let mut visited = HashSet::new();
+            for target in targets.all_targets() {
+                if !visited.insert(*target) {
+                    continue;
+                }
+                let condition = match state.exit_conditions.get(target) {
+                    Some(condition) => condition.clone(),
+                    None => continue,
+                };
+                if let Ok(constraints) = LinearConstraintSystem::try_from(condition) {
+                    let mut target_state = state.numerical_domain.clone();
+                    target_state.add_constraints(constraints);
+                    if target_state.is_bottom() {
+                        let target_data = &self.body_visitor.wto.basic_blocks[*target];
+                        let span = match target_data.statements.first() {
+                            Some(statement) => statement.source_info.span,
+                            None => target_data.terminator().source_info.span,
+                        };
+                        dead_branches.push(span);
+                    }
+                }
+            }
+        }
+        dead_branches
+    }
+}
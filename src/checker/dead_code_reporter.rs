@@ -0,0 +1,79 @@
+use crate::analysis::diagnostics::DiagnosticCause;
+use crate::analysis::mir_visitor::body_visitor::WtoFixPointIterator;
+use crate::analysis::numerical::apron_domain::{
+    ApronAbstractDomain, ApronDomainType, GetManagerTrait,
+};
+use crate::checker::checker_trait::CheckerTrait;
+use rustc_middle::mir;
+use rustc_span::Span;
+use std::collections::HashSet;
+
+/// Reports the basic blocks whose state at the entry is bottom after the fixed-point iteration, i.e., the code
+/// that is never executed according to the abstract interpretation, see `--report-dead-code`
+/// The unwinding blocks, the `unreachable` terminators inserted by the compiler, e.g., after an exhaustive match,
+/// and the blocks generated by macro expansion are not reported
+/// Several blocks of the same statement are reported once
+pub struct DeadCodeReporter<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>,
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> CheckerTrait<'tcx, 'a, 'b, 'compiler, DomainType>
+    for DeadCodeReporter<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn new(body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>) -> Self {
+        Self { body_visitor }
+    }
+
+    fn run(&mut self) {
+        info!("====== Dead Code Reporter starts ======");
+        if self.body_visitor.def_id.is_local() {
+            let dead_code = self.find_dead_code();
+            info!("Found {} unreachable blocks", dead_code.len());
+            for span in dead_code {
+                let note = self.body_visitor.context.session.dcx().struct_span_note(
+                    span,
+                    "[MirChecker] Dead code: this code is unreachable according to the abstract interpretation",
+                );
+                self.body_visitor
+                    .emit_diagnostic(note, false, DiagnosticCause::DeadBranch);
+            }
+        }
+        info!("====== Dead Code Reporter ends ======");
+    }
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> DeadCodeReporter<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    /// The spans of the first statements of the basic blocks that are never reached, in the order of the blocks
+    fn find_dead_code(&self) -> Vec<Span> {
+        let mut visited = HashSet::new();
+        let mut dead_code = Vec::new();
+        for (bb, bb_data) in self.body_visitor.wto.basic_blocks.iter_enumerated() {
+            if bb_data.is_cleanup || !self.body_visitor.unreachable_blocks.contains(&bb) {
+                continue;
+            }
+            let span = match bb_data.statements.first() {
+                Some(statement) => statement.source_info.span,
+                None if matches!(bb_data.terminator().kind, mir::TerminatorKind::Unreachable) => {
+                    continue
+                }
+                None => bb_data.terminator().source_info.span,
+            };
+            if span.from_expansion() || !visited.insert(span) {
+                continue;
+            }
+            dead_code.push(span);
+        }
+        dead_code
+    }
+}
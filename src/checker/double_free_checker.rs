@@ -0,0 +1,142 @@
+use crate::analysis::diagnostics::DiagnosticCause;
+use crate::analysis::memory::escape;
+use crate::analysis::memory::path::Path;
+use crate::analysis::mir_visitor::body_visitor::WtoFixPointIterator;
+use crate::analysis::numerical::apron_domain::{
+    ApronAbstractDomain, ApronDomainType, GetManagerTrait,
+};
+use crate::analysis::numerical::interval::{Bound, Interval};
+use crate::checker::checker_trait::CheckerTrait;
+use log::debug;
+use rug::Integer;
+use rustc_span::Span;
+use std::rc::Rc;
+
+/// The names of the calls that free the memory that their first argument points to, or take the ownership of it,
+/// e.g., `Box::from_raw`, whose result frees the memory when it is dropped
+/// `from_raw_parts` is not included, since `slice::from_raw_parts` only borrows the memory
+const FREE_METHODS: [&str; 4] = ["drop_in_place", "from_raw", "dealloc", "free"];
+
+pub fn is_free_method(name: &str) -> bool {
+    FREE_METHODS.contains(&name)
+}
+
+/// Whether the memory that a pointer points to has been freed, which is stored as an integer at its
+/// `Path::new_freed` path, so that the states of the branches are joined in the numerical domain, where an
+/// interval that contains both `Live` and `Freed` is `Top`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FreedState {
+    Live,
+    Freed,
+    Top,
+}
+
+impl FreedState {
+    /// The integer that the state is stored as, `Top` is never stored but computed by the join
+    pub fn value(self) -> Integer {
+        match self {
+            FreedState::Live => Integer::from(0),
+            FreedState::Freed => Integer::from(1),
+            FreedState::Top => unreachable!("`Top` is the join of the other states"),
+        }
+    }
+
+    pub fn from_interval(interval: &Interval) -> Self {
+        let freed = Interval::new(
+            Bound::from(FreedState::Freed.value()),
+            Bound::from(FreedState::Freed.value()),
+        );
+        if interval.meet(&freed).is_bottom() {
+            FreedState::Live
+        } else if interval.equal_to(&freed) == Some(true) {
+            FreedState::Freed
+        } else {
+            FreedState::Top
+        }
+    }
+}
+
+/// A call that frees a pointer whose memory may have been freed, recorded during the fixed-point iteration
+#[derive(Clone, Debug)]
+pub struct DoubleFree {
+    pub span: Span,
+    // The name of the call, e.g., `from_raw`
+    pub callee: String,
+    // The path of the freed pointer, `None` if it is a constant
+    pub pointer: Option<Rc<Path>>,
+    // The state of the memory before the call, either `Freed` or `Top`
+    pub state: FreedState,
+}
+
+/// Checks the calls in `FREE_METHODS` that free a pointer again, see `--check double-free`
+/// The pointers are distinguished by their abstract values, i.e., the memory they point to is resolved in the
+/// symbolic domain, so that the copies of a pointer share the same state, and the pointers to different
+/// allocations do not. A call frees a pointer again if the memory is freed on all the paths that reach it, or
+/// possibly if it is freed on some of them, e.g., in one branch of an `if` before the join point
+pub struct DoubleFreeChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>,
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> CheckerTrait<'tcx, 'a, 'b, 'compiler, DomainType>
+    for DoubleFreeChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn new(body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>) -> Self {
+        Self { body_visitor }
+    }
+
+    fn run(&mut self) {
+        info!("====== Double Free Checker starts ======");
+        let double_frees = self
+            .body_visitor
+            .reachable_in_order(&self.body_visitor.double_frees);
+        for (_, double_free) in double_frees {
+            self.check_double_free(&double_free);
+        }
+        info!("====== Double Free Checker ends ======");
+    }
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> DoubleFreeChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn check_double_free(&mut self, double_free: &DoubleFree) {
+        debug!("Checking double free: {:?}", double_free);
+        let pointer = double_free
+            .pointer
+            .as_ref()
+            .and_then(|path| self.body_visitor.get_path_var_name(escape::get_root(path)))
+            .map_or_else(|| String::from("the pointer"), |name| format!("`{}`", name));
+        let session = self.body_visitor.context.session;
+        let warning = match double_free.state {
+            FreedState::Freed => session.dcx().struct_span_warn(
+                double_free.span,
+                format!(
+                    "[MirChecker] Provably error: double free, {} is freed by `{}` after it has been freed",
+                    pointer, double_free.callee
+                ),
+            ),
+            _ => {
+                let mut warning = session.dcx().struct_span_warn(
+                    double_free.span,
+                    format!(
+                        "[MirChecker] Possible error: double free, {} may have been freed before `{}`",
+                        pointer, double_free.callee
+                    ),
+                );
+                warning.note("the pointer is freed on some of the paths that reach this call");
+                warning
+            }
+        };
+        self.body_visitor
+            .emit_diagnostic(warning, true, DiagnosticCause::Memory);
+    }
+}
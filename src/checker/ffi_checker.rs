@@ -0,0 +1,211 @@
+use crate::analysis::config_file::{self, ConfigValue};
+use crate::analysis::diagnostics::DiagnosticCause;
+use crate::analysis::mir_visitor::body_visitor::WtoFixPointIterator;
+use crate::analysis::numerical::apron_domain::{
+    ApronAbstractDomain, ApronDomainType, GetManagerTrait,
+};
+use crate::analysis::numerical::interval::{Bound, Interval};
+use crate::checker::assertion_checker::CheckerResult;
+use crate::checker::checker_trait::CheckerTrait;
+use log::debug;
+use rug::Integer;
+use rustc_span::Span;
+use std::collections::HashMap;
+
+/// The contract of a C function, i.e., the indices of the arguments that must be non-null pointers, positive
+/// integers, e.g., the size of a buffer, and non-negative integers
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FfiContract {
+    pub non_null: Vec<usize>,
+    pub positive: Vec<usize>,
+    pub non_negative: Vec<usize>,
+}
+
+/// Parses the contracts of the C functions in a stub file, which has a TOML table for each function, e.g.,
+///
+/// ```toml
+/// [memcpy]
+/// non_null = [0, 1]
+/// positive = [2]
+/// ```
+///
+/// The file is parsed by `config_file::parse_config`, only arrays of argument indices are supported, and the error
+/// names the line of the first invalid key
+pub fn parse_ffi_stubs(content: &str) -> Result<HashMap<String, FfiContract>, String> {
+    let mut contracts: HashMap<String, FfiContract> = HashMap::new();
+    for table in config_file::parse_config(content)? {
+        let name = match table.name {
+            Some(name) => name,
+            None => match table.entries.first() {
+                Some(entry) => {
+                    return Err(format!(
+                        "line {}: the key is not in the table of a function",
+                        entry.line
+                    ))
+                }
+                None => continue,
+            },
+        };
+        let contract = contracts.entry(name).or_default();
+        for entry in table.entries {
+            let error = |message: &str| format!("line {}: {}", entry.line, message);
+            let items = match entry.value {
+                ConfigValue::Array(items) => items,
+                _ => {
+                    return Err(error(
+                        "expected an array of argument indices, e.g., `[0, 1]`",
+                    ))
+                }
+            };
+            let indices = items
+                .iter()
+                .map(|item| match item {
+                    ConfigValue::Integer(index) => usize::try_from(*index).ok(),
+                    _ => None,
+                })
+                .collect::<Option<Vec<usize>>>()
+                .ok_or_else(|| error("the argument indices must be non-negative integers"))?;
+            match entry.key.as_str() {
+                "non_null" => contract.non_null = indices,
+                "positive" => contract.positive = indices,
+                "non_negative" => contract.non_negative = indices,
+                key => {
+                    return Err(error(&format!(
+                        "unknown key `{}`, expected one of `non_null`, `positive`, `non_negative`",
+                        key
+                    )))
+                }
+            }
+        }
+    }
+    Ok(contracts)
+}
+
+/// Reads and parses the stub file at `file_path`, see `parse_ffi_stubs`
+pub fn load_ffi_stubs(file_path: &str) -> Result<HashMap<String, FfiContract>, String> {
+    let content = std::fs::read_to_string(file_path).map_err(|e| e.to_string())?;
+    parse_ffi_stubs(&content)
+}
+
+/// An argument of a call to a C function, as far as the contracts are concerned
+#[derive(Clone, Debug)]
+pub enum FfiArgument {
+    // A reference or a raw pointer, with whether it is null, if known
+    Pointer(Option<bool>),
+    // An integer, with its range
+    Integer(Interval),
+    Other,
+}
+
+/// A call to a C function, recorded during the fixed-point iteration
+#[derive(Clone, Debug)]
+pub struct FfiCall {
+    pub span: Span,
+    // The name of the foreign item, which is the key of its contract
+    pub function: String,
+    pub arguments: Vec<FfiArgument>,
+}
+
+/// Checks the arguments of the calls to C functions against the contracts in `--ffi-stubs`
+/// A raw pointer is known to be non-null if it is derived from a reference or an allocation, so that its offset
+/// is tracked, and known to be null if it is the constant zero, otherwise it may be null
+/// The functions without a contract are not checked
+pub struct FfiChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>,
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> CheckerTrait<'tcx, 'a, 'b, 'compiler, DomainType>
+    for FfiChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn new(body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>) -> Self {
+        Self { body_visitor }
+    }
+
+    fn run(&mut self) {
+        info!("====== FFI Checker starts ======");
+        let ffi_calls = self
+            .body_visitor
+            .reachable_in_order(&self.body_visitor.ffi_calls);
+        for (_, call) in ffi_calls {
+            if let Some(contract) = self.body_visitor.context.ffi_contracts.get(&call.function) {
+                let contract = contract.clone();
+                self.check_call(&call, &contract);
+            }
+        }
+        info!("====== FFI Checker ends ======");
+    }
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> FfiChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn check_call(&mut self, call: &FfiCall, contract: &FfiContract) {
+        debug!("Checking FFI call: {:?} against {:?}", call, contract);
+        let mut violations = vec![];
+        for index in &contract.non_null {
+            if let Some(FfiArgument::Pointer(is_null)) = call.arguments.get(*index) {
+                let result = match is_null {
+                    Some(false) => CheckerResult::Safe,
+                    Some(true) => CheckerResult::Unsafe,
+                    None => CheckerResult::Warning,
+                };
+                violations.push((result, *index, "a null pointer", String::new()));
+            }
+        }
+        let requirements = [
+            (&contract.positive, 1, "zero or negative"),
+            (&contract.non_negative, 0, "negative"),
+        ];
+        for (indices, minimum, description) in requirements {
+            for index in indices {
+                if let Some(FfiArgument::Integer(range)) = call.arguments.get(*index) {
+                    let result = Self::check_minimum(range, Integer::from(minimum));
+                    let range = format!(", the range is {:?}", range);
+                    violations.push((result, *index, description, range));
+                }
+            }
+        }
+        for (result, index, description, range) in violations {
+            let message = match result {
+                CheckerResult::Safe => continue,
+                CheckerResult::Unsafe => format!(
+                    "[MirChecker] Provably error: argument {} of `{}` is {}{}",
+                    index, call.function, description, range
+                ),
+                CheckerResult::Warning => format!(
+                    "[MirChecker] Possible error: argument {} of `{}` may be {}{}",
+                    index, call.function, description, range
+                ),
+            };
+            let warning = self
+                .body_visitor
+                .context
+                .session
+                .dcx()
+                .struct_span_warn(call.span, message);
+            self.body_visitor
+                .emit_diagnostic(warning, true, DiagnosticCause::Memory);
+        }
+    }
+
+    /// Whether every value in `range` is at least `minimum`
+    fn check_minimum(range: &Interval, minimum: Integer) -> CheckerResult {
+        let minimum = Bound::Int(minimum);
+        if range.is_bottom() || range.low >= minimum {
+            CheckerResult::Safe
+        } else if range.high < minimum {
+            CheckerResult::Unsafe
+        } else {
+            CheckerResult::Warning
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use crate::analysis::diagnostics::DiagnosticCause;
+use crate::analysis::mir_visitor::body_visitor::WtoFixPointIterator;
+use crate::analysis::numerical::apron_domain::{
+    ApronAbstractDomain, ApronDomainType, GetManagerTrait,
+};
+use crate::analysis::numerical::float_domain::{FloatClass, FloatValue};
+use crate::checker::checker_trait::CheckerTrait;
+use log::debug;
+use rustc_span::Span;
+
+/// The values of the operands of a comparison of floats, recorded during the fixed-point iteration
+#[derive(Clone, Debug)]
+pub struct FloatComparison {
+    pub span: Span,
+    pub left: FloatValue,
+    pub right: FloatValue,
+}
+
+/// Checks the comparisons of floats in the reachable basic blocks, i.e., `==`, `!=`, `<`, `<=`, `>` and `>=`
+/// A comparison with NaN is false, except `!=` which is true, so a NaN produced by an earlier operation, e.g., `0.0 /
+/// 0.0`, `sqrt` of a negative number or `inf - inf`, silently takes a wrong branch
+/// The values of the float-typed local variables are tracked in the state by `BlockVisitor::update_float_value`, so
+/// each comparison is checked with the values that reach it
+/// `x != x` is the idiom of `is_nan`, so the comparisons of a variable with itself are not checked
+pub struct FloatNanChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>,
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> CheckerTrait<'tcx, 'a, 'b, 'compiler, DomainType>
+    for FloatNanChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn new(body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>) -> Self {
+        Self { body_visitor }
+    }
+
+    fn run(&mut self) {
+        info!("====== Float NaN Checker starts ======");
+        let comparisons = self
+            .body_visitor
+            .reachable_in_order(&self.body_visitor.float_comparisons);
+        for (_, comparison) in comparisons {
+            self.check_comparison(&comparison);
+        }
+        info!("====== Float NaN Checker ends ======");
+    }
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> FloatNanChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn check_comparison(&mut self, comparison: &FloatComparison) {
+        let left_value = comparison.left;
+        let right_value = comparison.right;
+        debug!(
+            "Checking comparison of {:?} and {:?}",
+            left_value, right_value
+        );
+        let message =
+            if left_value.class() == FloatClass::Nan || right_value.class() == FloatClass::Nan {
+                "[MirChecker] Provably error: comparison with NaN"
+            } else if left_value.may_be_nan || right_value.may_be_nan {
+                "[MirChecker] Possible error: comparison with a value that may be NaN"
+            } else {
+                return;
+            };
+        let mut warning = self
+            .body_visitor
+            .context
+            .session
+            .dcx()
+            .struct_span_warn(comparison.span, message);
+        warning.note(
+            "a comparison with NaN is false, except `!=` which is true, consider checking `is_nan` first",
+        );
+        self.body_visitor
+            .emit_diagnostic(warning, false, DiagnosticCause::Comparison);
+    }
+}
@@ -0,0 +1,55 @@
+use crate::analysis::diagnostics::DiagnosticCause;
+use crate::analysis::mir_visitor::body_visitor::WtoFixPointIterator;
+use crate::analysis::numerical::apron_domain::{
+    ApronAbstractDomain, ApronDomainType, GetManagerTrait,
+};
+use crate::checker::checker_trait::CheckerTrait;
+
+/// Reports calls whose format string is derived from a taint source
+/// The calls are recorded during the fixed-point iteration, see `BlockVisitor::visit_call_taint`
+pub struct FormatStringChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>,
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> CheckerTrait<'tcx, 'a, 'b, 'compiler, DomainType>
+    for FormatStringChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn new(body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>) -> Self {
+        Self { body_visitor }
+    }
+
+    fn run(&mut self) {
+        info!("====== Format String Checker starts ======");
+        let calls = self
+            .body_visitor
+            .reachable_in_order(&self.body_visitor.tainted_format_strings);
+        for (_, (span, source)) in calls {
+            let mut warning = self.body_visitor.context.session.dcx().struct_span_warn(
+                span,
+                "[MirChecker] Possible error: format string is derived from a taint source",
+            );
+            warning.span_note(source, "the taint source is called here");
+            self.body_visitor
+                .emit_diagnostic(warning, true, DiagnosticCause::FormatString);
+        }
+        info!("====== Format String Checker ends ======");
+    }
+}
+
+/// Returns the index of the format string argument of the C functions in the `printf` family
+pub fn get_c_format_string_index(name: &str) -> Option<usize> {
+    match name {
+        "printf" | "vprintf" => Some(0),
+        "fprintf" | "dprintf" | "sprintf" | "vfprintf" | "vdprintf" | "vsprintf" | "syslog"
+        | "vsyslog" => Some(1),
+        "snprintf" | "vsnprintf" => Some(2),
+        _ => None,
+    }
+}
@@ -0,0 +1,96 @@
+use crate::analysis::diagnostics::DiagnosticCause;
+use crate::analysis::mir_visitor::body_visitor::WtoFixPointIterator;
+use crate::analysis::numerical::apron_domain::{
+    ApronAbstractDomain, ApronDomainType, GetManagerTrait,
+};
+use crate::analysis::numerical::interval::{Bound, Interval};
+use crate::analysis::numerical::wrapping_integer::WrappingIntegerDomain;
+use crate::checker::checker_trait::CheckerTrait;
+use log::debug;
+use rustc_span::Span;
+
+/// A cast of an unsigned integer into a signed integer type that is at least as wide, recorded during the
+/// fixed-point iteration
+#[derive(Clone, Debug)]
+pub struct SignPromotion {
+    pub span: Span,
+    // The names of the source and the target types, e.g., `u32` and `i32`
+    pub source: String,
+    pub target: String,
+    // The bit width of the target type
+    pub target_bits: u64,
+    // The range of the cast value
+    pub value: Interval,
+}
+
+/// Checks the casts of unsigned integers into signed integer types of the same or a larger width, see
+/// `--check int-promotion`
+/// The values above the positive maximum of the target type, e.g., `i32::MAX` for `u32 as i32`, become negative
+/// after the cast. A cast into a wider type, e.g., `u32 as i64`, keeps all the values, so it is never reported
+pub struct IntPromotionChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>,
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> CheckerTrait<'tcx, 'a, 'b, 'compiler, DomainType>
+    for IntPromotionChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn new(body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>) -> Self {
+        Self { body_visitor }
+    }
+
+    fn run(&mut self) {
+        info!("====== Int Promotion Checker starts ======");
+        let sign_promotions = self
+            .body_visitor
+            .reachable_in_order(&self.body_visitor.sign_promotions);
+        for (_, promotion) in sign_promotions {
+            self.check_promotion(&promotion);
+        }
+        info!("====== Int Promotion Checker ends ======");
+    }
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> IntPromotionChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn check_promotion(&mut self, promotion: &SignPromotion) {
+        debug!("Checking sign promotion: {:?}", promotion);
+        let max = WrappingIntegerDomain::new(promotion.target_bits, true).max();
+        let max_bound = Bound::from(max.clone());
+        if promotion.value.is_bottom() || promotion.value.high <= max_bound {
+            return;
+        }
+        let message = if promotion.value.low > max_bound {
+            format!(
+                "[MirChecker] Provably error: the cast from `{}` to `{}` makes the value negative, the value is {:?} but `{}` only holds values up to {}",
+                promotion.source, promotion.target, promotion.value, promotion.target, max
+            )
+        } else {
+            format!(
+                "[MirChecker] Possible error: the cast from `{}` to `{}` may make the value negative, the value is {:?} but `{}` only holds values up to {}",
+                promotion.source, promotion.target, promotion.value, promotion.target, max
+            )
+        };
+        let mut warning = self
+            .body_visitor
+            .context
+            .session
+            .dcx()
+            .struct_span_warn(promotion.span, message);
+        warning.note(format!(
+            "use `{}::try_from` to handle the values that do not fit",
+            promotion.target
+        ));
+        self.body_visitor
+            .emit_diagnostic(warning, false, DiagnosticCause::Arithmetic);
+    }
+}
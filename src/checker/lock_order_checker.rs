@@ -0,0 +1,230 @@
+use crate::analysis::diagnostics::DiagnosticCause;
+use crate::analysis::memory::path::Path;
+use crate::analysis::mir_visitor::body_visitor::WtoFixPointIterator;
+use crate::analysis::numerical::apron_domain::{
+    ApronAbstractDomain, ApronDomainType, GetManagerTrait,
+};
+use crate::checker::checker_trait::CheckerTrait;
+use rustc_middle::mir;
+use rustc_span::Span;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::rc::Rc;
+
+/// A call of `Mutex::lock`, recorded during the fixed-point iteration
+#[derive(Clone, Debug)]
+pub struct LockAcquisition {
+    pub span: Span,
+    // The path of the mutex, which identifies the lock
+    pub lock: Rc<Path>,
+    // The mutex as it is written in the source code, e.g., `a` in `a.lock()`
+    pub name: String,
+}
+
+/// The locks that may be held at a program point
+#[derive(Clone, Default, PartialEq)]
+struct HeldLocks {
+    // Each lock that may be held, with the location where it is acquired
+    locks: BTreeMap<Rc<Path>, mir::Location>,
+    // The locals that may contain the guard of a lock, e.g., the `LockResult` returned by `lock`, or the
+    // `MutexGuard` that is unwrapped from it
+    guards: BTreeMap<mir::Local, Rc<Path>>,
+}
+
+impl HeldLocks {
+    fn join(&mut self, other: &HeldLocks) {
+        for (lock, location) in &other.locks {
+            let held = self.locks.entry(lock.clone()).or_insert(*location);
+            *held = (*held).min(*location);
+        }
+        for (local, lock) in &other.guards {
+            self.guards.entry(*local).or_insert_with(|| lock.clone());
+        }
+    }
+
+    /// Releases the lock whose guard is in `local`
+    fn release(&mut self, local: mir::Local) {
+        if let Some(lock) = self.guards.remove(&local) {
+            if !self.guards.values().any(|other| *other == lock) {
+                self.locks.remove(&lock);
+            }
+        }
+    }
+}
+
+/// Reports the pairs of mutexes that may be acquired in different orders in a function, i.e., one path acquires
+/// `a` and then `b` while `a` is held, and another path acquires `b` and then `a` while `b` is held, so that two
+/// threads running them may deadlock
+/// The mutexes are identified by their paths, and a lock is held from `Mutex::lock` until its guard is dropped,
+/// either by a `Drop` terminator or by passing it to a function that returns `()`, e.g., `mem::drop`
+/// The held locks are over-approximated by joining the paths that reach the same block, so the checker may report
+/// orders that never happen at run time
+pub struct LockOrderChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>,
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> CheckerTrait<'tcx, 'a, 'b, 'compiler, DomainType>
+    for LockOrderChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn new(body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>) -> Self {
+        Self { body_visitor }
+    }
+
+    fn run(&mut self) {
+        info!("====== Lock Order Checker starts ======");
+        if self.body_visitor.def_id.is_local() && self.body_visitor.lock_acquisitions.len() >= 2 {
+            let orders = self.find_lock_orders();
+            for ((first, second), (first_location, second_location)) in &orders {
+                // Each conflicting pair is reported once, for the order that acquires the smaller path first
+                if first > second {
+                    continue;
+                }
+                let (reversed_first, reversed_second) =
+                    match orders.get(&(second.clone(), first.clone())) {
+                        Some(locations) => locations,
+                        None => continue,
+                    };
+                let acquisition = |location: &mir::Location| {
+                    self.body_visitor.lock_acquisitions[location].clone()
+                };
+                let (a, b) = (acquisition(first_location), acquisition(second_location));
+                let (reversed_b, reversed_a) =
+                    (acquisition(reversed_first), acquisition(reversed_second));
+                let mut warning = self.body_visitor.context.session.dcx().struct_span_warn(
+                    b.span,
+                    format!(
+                        "[MirChecker] Possible error: deadlock, the locks are acquired in conflicting orders: `{}` then `{}` here, and `{}` then `{}` elsewhere",
+                        a.name, b.name, b.name, a.name
+                    ),
+                );
+                warning.span_note(a.span, format!("`{}` is acquired first here", a.name));
+                warning.span_note(
+                    reversed_a.span,
+                    format!(
+                        "`{}` is acquired while `{}` is held here",
+                        reversed_a.name, reversed_b.name
+                    ),
+                );
+                warning.span_note(
+                    reversed_b.span,
+                    format!("`{}` is acquired first here", reversed_b.name),
+                );
+                self.body_visitor
+                    .emit_diagnostic(warning, false, DiagnosticCause::Deadlock);
+            }
+        }
+        info!("====== Lock Order Checker ends ======");
+    }
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> LockOrderChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    /// The pairs of locks `(a, b)` such that `b` may be acquired while `a` is held, together with the locations
+    /// where `a` and `b` are acquired
+    /// The held locks are propagated through the reachable blocks until they are stable
+    fn find_lock_orders(&self) -> BTreeMap<(Rc<Path>, Rc<Path>), (mir::Location, mir::Location)> {
+        let mir = self.body_visitor.wto.get_mir();
+        let is_reachable = |bb: &mir::BasicBlock| {
+            self.body_visitor
+                .post
+                .get(bb)
+                .map_or(false, |state| !state.is_bottom())
+        };
+        let mut orders = BTreeMap::new();
+        let mut entry_states: HashMap<mir::BasicBlock, HeldLocks> = HashMap::new();
+        entry_states.insert(mir::START_BLOCK, HeldLocks::default());
+        let mut worklist = VecDeque::from([mir::START_BLOCK]);
+        while let Some(bb) = worklist.pop_front() {
+            let mut held = entry_states[&bb].clone();
+            let bb_data = &mir.basic_blocks[bb];
+            for statement in &bb_data.statements {
+                // A guard that is moved or copied into another local
+                if let mir::StatementKind::Assign(box (place, mir::Rvalue::Use(operand))) =
+                    &statement.kind
+                {
+                    if let Some(lock) = operand
+                        .place()
+                        .and_then(|source| held.guards.get(&source.as_local()?).cloned())
+                    {
+                        held.guards.insert(place.local, lock);
+                    }
+                }
+            }
+            let location = mir.terminator_loc(bb);
+            match &bb_data.terminator().kind {
+                mir::TerminatorKind::Call {
+                    args, destination, ..
+                } => {
+                    if let Some(acquisition) = self.body_visitor.lock_acquisitions.get(&location) {
+                        for (lock, held_location) in &held.locks {
+                            if *lock != acquisition.lock {
+                                orders
+                                    .entry((lock.clone(), acquisition.lock.clone()))
+                                    .or_insert((*held_location, location));
+                            }
+                        }
+                        held.locks
+                            .entry(acquisition.lock.clone())
+                            .or_insert(location);
+                        held.guards
+                            .insert(destination.local, acquisition.lock.clone());
+                    } else {
+                        let moved_guards: Vec<mir::Local> = args
+                            .iter()
+                            .filter_map(|arg| match &arg.node {
+                                mir::Operand::Move(place) => place.as_local(),
+                                _ => None,
+                            })
+                            .filter(|local| held.guards.contains_key(local))
+                            .collect();
+                        for local in moved_guards {
+                            if mir.local_decls[destination.local].ty.is_unit() {
+                                // The guard is consumed, e.g., by `mem::drop`
+                                held.release(local);
+                            } else {
+                                // The guard is wrapped or unwrapped, e.g., by `LockResult::unwrap`
+                                let lock = held.guards.remove(&local).unwrap();
+                                held.guards.insert(destination.local, lock);
+                            }
+                        }
+                    }
+                }
+                mir::TerminatorKind::Drop { place, .. } => {
+                    if let Some(local) = place.as_local() {
+                        held.release(local);
+                    }
+                }
+                _ => {}
+            }
+            for succ in bb_data.terminator().successors() {
+                if mir.basic_blocks[succ].is_cleanup || !is_reachable(&succ) {
+                    continue;
+                }
+                let changed = match entry_states.get_mut(&succ) {
+                    Some(succ_state) => {
+                        let old_state = succ_state.clone();
+                        succ_state.join(&held);
+                        *succ_state != old_state
+                    }
+                    None => {
+                        entry_states.insert(succ, held.clone());
+                        true
+                    }
+                };
+                if changed && !worklist.contains(&succ) {
+                    worklist.push_back(succ);
+                }
+            }
+        }
+        orders
+    }
+}
@@ -0,0 +1,173 @@
+use crate::analysis::diagnostics::DiagnosticCause;
+use crate::analysis::mir_visitor::body_visitor::WtoFixPointIterator;
+use crate::analysis::mir_visitor::type_visitor::TypeVisitor;
+use crate::analysis::numerical::apron_domain::{
+    ApronAbstractDomain, ApronDomainType, GetManagerTrait,
+};
+use crate::checker::checker_trait::CheckerTrait;
+use crate::checker::transmute_safety_checker::{find_transmutes, Transmute};
+use log::debug;
+use rustc_middle::ty::{IntTy, Ty, TyCtxt, TyKind, UintTy};
+
+/// Returns true if `ty` is an array or a slice of bytes, i.e., of `u8` or `i8`
+fn is_byte_array(ty: Ty<'_>) -> bool {
+    match ty.kind() {
+        TyKind::Array(elem_ty, _) | TyKind::Slice(elem_ty) => {
+            matches!(
+                elem_ty.kind(),
+                TyKind::Uint(UintTy::U8) | TyKind::Int(IntTy::I8)
+            )
+        }
+        _ => false,
+    }
+}
+
+/// Collects the padding bytes of a value of type `ty` that starts at `offset`, as ranges of `[start, end)`
+/// The gaps between the fields of structs and tuples, and after their last fields, are padding, as well as the
+/// padding of the fields and of the elements of arrays. The padding of enums and unions depends on the variant or
+/// the field that is written, so it is not collected
+pub fn collect_padding<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    type_visitor: &TypeVisitor<'tcx>,
+    ty: Ty<'tcx>,
+    offset: u64,
+    padding: &mut Vec<(u64, u64)>,
+) {
+    let layout = match tcx.layout_of(type_visitor.get_param_env().and(ty)) {
+        Ok(layout) => layout,
+        Err(_) => return,
+    };
+    let field_types: Vec<Ty<'tcx>> = match ty.kind() {
+        TyKind::Adt(adt_def, args) if adt_def.is_struct() => adt_def
+            .non_enum_variant()
+            .fields
+            .iter()
+            .map(|field| field.ty(tcx, args))
+            .collect(),
+        TyKind::Tuple(tys) => tys.iter().collect(),
+        TyKind::Array(elem_ty, _) => {
+            let elem_size = match type_visitor.get_type_size(*elem_ty) {
+                Some(elem_size) if elem_size > 0 => elem_size,
+                _ => return,
+            };
+            // All the elements have the same padding
+            let mut elem_padding = Vec::new();
+            collect_padding(tcx, type_visitor, *elem_ty, 0, &mut elem_padding);
+            if !elem_padding.is_empty() {
+                for index in 0..layout.size.bytes() / elem_size {
+                    let elem_offset = offset + index * elem_size;
+                    padding.extend(
+                        elem_padding
+                            .iter()
+                            .map(|(start, end)| (elem_offset + start, elem_offset + end)),
+                    );
+                }
+            }
+            return;
+        }
+        _ => return,
+    };
+    // The fields may be reordered in memory, so visit them in the order of their offsets
+    let mut fields: Vec<(u64, Ty<'tcx>)> = field_types
+        .into_iter()
+        .enumerate()
+        .map(|(index, field_ty)| (layout.fields.offset(index).bytes(), field_ty))
+        .collect();
+    fields.sort_by_key(|(field_offset, _)| *field_offset);
+    let mut end = 0;
+    for (field_offset, field_ty) in fields {
+        if field_offset > end {
+            padding.push((offset + end, offset + field_offset));
+        }
+        collect_padding(tcx, type_visitor, field_ty, offset + field_offset, padding);
+        match type_visitor.get_type_size(field_ty) {
+            Some(field_size) => end = end.max(field_offset + field_size),
+            // The bytes after a field of unknown size may not be padding
+            None => return,
+        }
+    }
+    if layout.size.bytes() > end {
+        padding.push((offset + end, offset + layout.size.bytes()));
+    }
+}
+
+/// Checks the transmutes of values into arrays of bytes, e.g., `mem::transmute::<Header, [u8; 16]>`
+/// The padding bytes of a struct are uninitialized, so reading them from the array is undefined behavior, which is a
+/// common mistake in serialization code. Only the transmutes between types of the same size are checked, the others
+/// are reported by `TransmuteSafetyChecker`
+pub struct PaddingUninitChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>,
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> CheckerTrait<'tcx, 'a, 'b, 'compiler, DomainType>
+    for PaddingUninitChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn new(body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>) -> Self {
+        Self { body_visitor }
+    }
+
+    fn run(&mut self) {
+        info!("====== Padding Uninit Checker starts ======");
+        for transmute in find_transmutes(self.body_visitor) {
+            self.check_transmute(&transmute);
+        }
+        info!("====== Padding Uninit Checker ends ======");
+    }
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> PaddingUninitChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn check_transmute(&mut self, transmute: &Transmute<'tcx>) {
+        let tcx = self.body_visitor.context.tcx;
+        let type_visitor = &self.body_visitor.type_visitor;
+        let source = type_visitor
+            .specialize_generic_argument_type(transmute.source, &type_visitor.generic_argument_map);
+        let target = type_visitor
+            .specialize_generic_argument_type(transmute.target, &type_visitor.generic_argument_map);
+        if !is_byte_array(target) {
+            return;
+        }
+        let source_size = type_visitor.get_type_size(source);
+        if source_size.is_none()
+            || source_size == Some(0)
+            || source_size != type_visitor.get_type_size(target)
+        {
+            return;
+        }
+        let mut padding = Vec::new();
+        collect_padding(tcx, type_visitor, source, 0, &mut padding);
+        debug!("Padding of {:?}: {:?}", source, padding);
+        if padding.is_empty() {
+            return;
+        }
+        let padding_bytes: u64 = padding.iter().map(|(start, end)| end - start).sum();
+        let ranges: Vec<String> = padding
+            .iter()
+            .map(|(start, end)| format!("{}..{}", start, end))
+            .collect();
+        let mut error = self.body_visitor.context.session.dcx().struct_span_warn(
+            transmute.span,
+            "[MirChecker] Provably error: transmute exposes the uninitialized padding bytes of a value",
+        );
+        error.note(format!(
+            "{} of the {} byte(s) of `{}` are padding (bytes {}), which are uninitialized in `{}`",
+            padding_bytes,
+            source_size,
+            source,
+            ranges.join(", "),
+            target
+        ));
+        self.body_visitor
+            .emit_diagnostic(error, true, DiagnosticCause::Memory);
+    }
+}
@@ -0,0 +1,126 @@
+use crate::analysis::diagnostics::DiagnosticCause;
+use crate::analysis::mir_visitor::body_visitor::WtoFixPointIterator;
+use crate::analysis::numerical::apron_domain::{
+    ApronAbstractDomain, ApronDomainType, GetManagerTrait,
+};
+use crate::checker::checker_trait::CheckerTrait;
+use rustc_hir::def_id::DefId;
+use rustc_hir::LangItem;
+use rustc_middle::mir;
+use rustc_middle::ty::TyKind;
+use rustc_span::Span;
+
+/// The lang items of the functions that start a panic
+const PANIC_LANG_ITEMS: [LangItem; 8] = [
+    LangItem::Panic,
+    LangItem::PanicNounwind,
+    LangItem::PanicFmt,
+    LangItem::PanicBoundsCheck,
+    LangItem::PanicMisalignedPointerDereference,
+    LangItem::PanicCannotUnwind,
+    LangItem::PanicInCleanup,
+    LangItem::BeginPanic,
+];
+
+/// Reports the calls that panic or abort in the reachable basic blocks, i.e., the blocks whose state is not
+/// bottom after the fixed-point iteration
+/// The cleanup blocks are skipped, because they only run while unwinding from a panic that is reported already,
+/// which is also why `UnwindTerminate` terminators, that only appear in cleanup blocks, are never reported
+/// The panics of the assertions are reported by `AssertionChecker` when the assertion may fail
+pub struct PanicReachabilityChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>,
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> CheckerTrait<'tcx, 'a, 'b, 'compiler, DomainType>
+    for PanicReachabilityChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn new(body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>) -> Self {
+        Self { body_visitor }
+    }
+
+    fn run(&mut self) {
+        info!("====== Panic Reachability Checker starts ======");
+        let tcx = self.body_visitor.context.tcx;
+        let lang_items = tcx.lang_items();
+        let panic_functions: Vec<DefId> = PANIC_LANG_ITEMS
+            .iter()
+            .filter_map(|item| lang_items.get(*item))
+            .collect();
+        let mut panics: Vec<(Span, Option<String>)> = Vec::new();
+        for (bb, bb_data) in self.body_visitor.wto.basic_blocks.iter_enumerated() {
+            if bb_data.is_cleanup {
+                continue;
+            }
+            let is_reachable = self
+                .body_visitor
+                .post
+                .get(&bb)
+                .map_or(false, |state| !state.is_bottom());
+            if !is_reachable {
+                continue;
+            }
+            let term = bb_data.terminator();
+            if let mir::TerminatorKind::Call { func, args, .. } = &term.kind {
+                let callee = match func.const_fn_def() {
+                    Some((def_id, _)) => def_id,
+                    None => continue,
+                };
+                let is_abort = tcx
+                    .intrinsic(callee)
+                    .map_or(false, |intrinsic| intrinsic.name == rustc_span::sym::abort)
+                    || tcx.def_path_str(callee) == "std::process::abort";
+                if panic_functions.contains(&callee) || is_abort {
+                    let message = args.iter().find_map(|arg| self.get_str_constant(&arg.node));
+                    panics.push((term.source_info.span, message));
+                }
+            }
+        }
+        for (span, message) in panics {
+            let message = match message {
+                Some(message) => format!(
+                    "[MirChecker] Possible error: reachable panic: {:?}",
+                    message
+                ),
+                None => String::from("[MirChecker] Possible error: reachable panic"),
+            };
+            let warning = self
+                .body_visitor
+                .context
+                .session
+                .dcx()
+                .struct_span_warn(span, message);
+            self.body_visitor
+                .emit_diagnostic(warning, false, DiagnosticCause::Panic);
+        }
+        info!("====== Panic Reachability Checker ends ======");
+    }
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType>
+    PanicReachabilityChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    /// The value of `operand` if it is a constant string, e.g., the message of `unreachable!()`
+    fn get_str_constant(&self, operand: &mir::Operand<'tcx>) -> Option<String> {
+        let constant = operand.constant()?;
+        if let mir::Const::Val(value, ty) = constant.const_ {
+            if let TyKind::Ref(_, pointee, _) = ty.kind() {
+                if pointee.is_str() {
+                    let tcx = self.body_visitor.context.tcx;
+                    let bytes = value.try_get_slice_bytes_for_diagnostics(tcx)?;
+                    return Some(String::from_utf8_lossy(bytes).into_owned());
+                }
+            }
+        }
+        None
+    }
+}
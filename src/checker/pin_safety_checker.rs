@@ -0,0 +1,129 @@
+use crate::analysis::diagnostics::DiagnosticCause;
+use crate::analysis::memory::path::Path;
+use crate::analysis::mir_visitor::body_visitor::WtoFixPointIterator;
+use crate::analysis::numerical::apron_domain::{
+    ApronAbstractDomain, ApronDomainType, GetManagerTrait,
+};
+use crate::checker::checker_trait::CheckerTrait;
+use log::debug;
+use rustc_middle::mir;
+use rustc_span::Span;
+use std::rc::Rc;
+
+/// The operations that move a value, or that give up the guarantee of `Pin` that the value is not moved
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PinOperation {
+    // `ptr::read` or `<*const T>::read`
+    Read,
+    // `mem::swap`
+    Swap,
+    // `place = move path`
+    Move,
+    // A `Pin` converted into a type that is not a `Pin`, i.e., by `transmute`, `Pin::get_unchecked_mut` or
+    // `Pin::into_inner_unchecked`
+    Unpin,
+}
+
+/// An operation on a path that may be pinned, recorded during the fixed-point iteration
+#[derive(Clone, Debug)]
+pub struct PinnedAccess {
+    pub span: Span,
+    pub operation: PinOperation,
+    // The moved value, or the `Pin` that is converted
+    pub path: Rc<Path>,
+}
+
+/// Checks that the values pinned by `Pin::new_unchecked` are not moved afterwards, i.e., by `ptr::read`,
+/// `mem::swap` or a move out of the pinned place, and warns about each `Pin` that is converted into a pointer
+/// that is not pinned, which has to be done in unsafe code
+/// A pinned value is identified by its path, so only the values whose address is known when they are pinned
+/// are checked
+pub struct PinSafetyChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>,
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> CheckerTrait<'tcx, 'a, 'b, 'compiler, DomainType>
+    for PinSafetyChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn new(body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>) -> Self {
+        Self { body_visitor }
+    }
+
+    fn run(&mut self) {
+        info!("====== Pin Safety Checker starts ======");
+        let pinned_accesses = self
+            .body_visitor
+            .reachable_in_order(&self.body_visitor.pinned_accesses);
+        for (location, accesses) in pinned_accesses {
+            for access in accesses {
+                self.check_access(location, &access);
+            }
+        }
+        info!("====== Pin Safety Checker ends ======");
+    }
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> PinSafetyChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn check_access(&mut self, location: mir::Location, access: &PinnedAccess) {
+        debug!("Checking pinned access: {:?}", access);
+        let message = match access.operation {
+            PinOperation::Read => "[MirChecker] Possible error: `ptr::read` moves a pinned value",
+            PinOperation::Swap => "[MirChecker] Possible error: `mem::swap` moves a pinned value",
+            PinOperation::Move => "[MirChecker] Possible error: move of a pinned value",
+            PinOperation::Unpin => {
+                "[MirChecker] Possible error: a `Pin` is converted into a pointer that is not pinned"
+            }
+        };
+        let pinned_at = if access.operation == PinOperation::Unpin {
+            None
+        } else {
+            match self.find_pinning(location, &access.path) {
+                Some(pinned_at) => Some(pinned_at),
+                None => return,
+            }
+        };
+        let mut warning = self
+            .body_visitor
+            .context
+            .session
+            .dcx()
+            .struct_span_warn(access.span, message);
+        match pinned_at {
+            Some(span) => {
+                warning.span_note(span, "the value is pinned here");
+            }
+            None => {
+                warning.note("the pointed value may be moved through the returned pointer");
+            }
+        }
+        self.body_visitor
+            .emit_diagnostic(warning, true, DiagnosticCause::Memory);
+    }
+
+    /// The span of the first `Pin::new_unchecked` that may be executed before `location` and pins `path` or a
+    /// path that qualifies it
+    fn find_pinning(&self, location: mir::Location, path: &Rc<Path>) -> Option<Span> {
+        let mir = self.body_visitor.wto.get_mir();
+        let mut pinnings: Vec<(&mir::Location, &Rc<Path>)> =
+            self.body_visitor.pinned_paths.iter().collect();
+        pinnings.sort_by_key(|(pinned_location, _)| **pinned_location);
+        pinnings
+            .into_iter()
+            .find(|(pinned_location, pinned_path)| {
+                (path == *pinned_path || path.is_rooted_by(pinned_path))
+                    && pinned_location.is_predecessor_of(location, mir)
+            })
+            .map(|(pinned_location, _)| mir.source_info(*pinned_location).span)
+    }
+}
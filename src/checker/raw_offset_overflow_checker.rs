@@ -0,0 +1,102 @@
+use crate::analysis::diagnostics::DiagnosticCause;
+use crate::analysis::mir_visitor::body_visitor::WtoFixPointIterator;
+use crate::analysis::numerical::apron_domain::{
+    ApronAbstractDomain, ApronDomainType, GetManagerTrait,
+};
+use crate::analysis::numerical::interval::{Bound, Interval};
+use crate::analysis::stubs::integer::abs;
+use crate::checker::checker_trait::CheckerTrait;
+use log::debug;
+use rug::Integer;
+use rustc_middle::ty::Ty;
+use rustc_span::Span;
+
+/// A call of `<*const T>::offset` or `<*mut T>::offset`, recorded during the fixed-point iteration
+#[derive(Clone, Debug)]
+pub struct RawOffset<'tcx> {
+    pub span: Span,
+    // The type `T` that the pointer points to
+    pub pointee_ty: Ty<'tcx>,
+    // The number of elements that the pointer is moved by
+    pub count: Interval,
+    // `size_of::<T>()`
+    pub elem_size: u64,
+}
+
+impl<'tcx> RawOffset<'tcx> {
+    /// `|count| * size_of::<T>()`, the number of bytes that the pointer is moved by in either direction
+    pub fn byte_distance(&self) -> Interval {
+        let elem_size = Bound::Int(Integer::from(self.elem_size));
+        abs(&self.count) * Interval::new(elem_size.clone(), elem_size)
+    }
+}
+
+/// Checks that the byte offsets of `<*const T>::offset(n)` and `<*mut T>::offset(n)`, i.e., `n * size_of::<T>()`,
+/// do not overflow `isize`, which is undefined behavior even if the pointer is never dereferenced, see
+/// `--check raw-offset-overflow`
+pub struct RawOffsetOverflowChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>,
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> CheckerTrait<'tcx, 'a, 'b, 'compiler, DomainType>
+    for RawOffsetOverflowChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn new(body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>) -> Self {
+        Self { body_visitor }
+    }
+
+    fn run(&mut self) {
+        info!("====== Raw Offset Overflow Checker starts ======");
+        let offsets = self
+            .body_visitor
+            .reachable_in_order(&self.body_visitor.raw_offsets);
+        for (_, offset) in offsets {
+            self.check_offset(&offset);
+        }
+        info!("====== Raw Offset Overflow Checker ends ======");
+    }
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType>
+    RawOffsetOverflowChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn check_offset(&mut self, offset: &RawOffset<'tcx>) {
+        let distance = offset.byte_distance();
+        debug!("Checking raw offset: {:?}, {:?} byte(s)", offset, distance);
+        if distance.is_bottom() {
+            return;
+        }
+        let tcx = self.body_visitor.context.tcx;
+        let pointer_width = tcx.data_layout.pointer_size.bits();
+        let isize_max = (Integer::from(1) << (pointer_width as u32 - 1)) - 1;
+        let message = if distance.low > Bound::Int(isize_max.clone()) {
+            "[MirChecker] Provably error: the byte offset of `offset` overflows `isize`"
+        } else if distance.high > Bound::Int(isize_max.clone()) {
+            "[MirChecker] Possible error: the byte offset of `offset` may overflow `isize`"
+        } else {
+            return;
+        };
+        let mut warning = self
+            .body_visitor
+            .context
+            .session
+            .dcx()
+            .struct_span_warn(offset.span, message);
+        warning.note(format!(
+            "the offset is {:?} element(s) of `{}` of {} byte(s), and `isize::MAX` is {}",
+            offset.count, offset.pointee_ty, offset.elem_size, isize_max
+        ));
+        self.body_visitor
+            .emit_diagnostic(warning, true, DiagnosticCause::Index);
+    }
+}
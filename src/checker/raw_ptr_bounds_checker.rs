@@ -0,0 +1,144 @@
+use crate::analysis::diagnostics::DiagnosticCause;
+use crate::analysis::mir_visitor::body_visitor::WtoFixPointIterator;
+use crate::analysis::numerical::apron_domain::{
+    ApronAbstractDomain, ApronDomainType, GetManagerTrait,
+};
+use crate::analysis::numerical::interval::{Bound, Interval};
+use crate::checker::assertion_checker::CheckerResult;
+use crate::checker::checker_trait::CheckerTrait;
+use log::debug;
+use rug::Integer;
+use rustc_span::Span;
+
+/// A dereference of a raw pointer, recorded during the fixed-point iteration
+/// A raw pointer is modeled as a byte offset from the start of the allocation it is derived from
+#[derive(Clone, Debug)]
+pub struct RawPointerAccess {
+    pub span: Span,
+    // The byte offset of the pointer from the start of its allocation
+    pub offset: Interval,
+    // The number of bytes that are read or written
+    pub access_size: u64,
+    // The number of bytes of the allocation, if known
+    pub allocation_size: Option<Integer>,
+    // The lane of a SIMD gather that loads from the pointer, see `CallVisitor::handle_simd_gather`
+    pub lane: Option<usize>,
+}
+
+/// Checks whether raw pointer dereferences stay within the bounds of the allocation they point to
+/// The upper bound is only checked for pointers whose allocation is known, i.e., pointers derived from a reference
+/// or returned by `__rust_alloc`, possibly moved by `add`, `sub` or `offset`, the other dereferences are reported
+/// with a lower severity
+pub struct RawPtrBoundsChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>,
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> CheckerTrait<'tcx, 'a, 'b, 'compiler, DomainType>
+    for RawPtrBoundsChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn new(body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>) -> Self {
+        Self { body_visitor }
+    }
+
+    fn run(&mut self) {
+        info!("====== Raw Pointer Bounds Checker starts ======");
+        let raw_pointer_accesses = self
+            .body_visitor
+            .reachable_in_order(&self.body_visitor.raw_pointer_accesses);
+        for (_, accesses) in raw_pointer_accesses {
+            for access in accesses {
+                self.check_access(&access);
+            }
+        }
+        info!("====== Raw Pointer Bounds Checker ends ======");
+    }
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> RawPtrBoundsChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn check_access(&mut self, access: &RawPointerAccess) {
+        debug!("Checking raw pointer access: {:?}", access);
+        let result = Self::check_bounds(access);
+        // The dereferences through a pointer whose allocation size is unknown may be in bounds, so they are not
+        // reported as memory-safety issues unless they are provably out of bounds
+        let is_unknown_size = access.allocation_size.is_none();
+        let is_memory_safety = !(is_unknown_size && matches!(result, CheckerResult::Warning));
+        let message = match (result, access.lane) {
+            (CheckerResult::Safe, _) => return,
+            (CheckerResult::Warning, None) if is_unknown_size => {
+                "[MirChecker] Possible error: raw pointer dereference into an allocation of unknown size".to_string()
+            }
+            (CheckerResult::Warning, Some(lane)) if is_unknown_size => format!(
+                "[MirChecker] Possible error: lane {} of the SIMD gather reads from an allocation of unknown size, the offset is {:?}",
+                lane, access.offset
+            ),
+            (CheckerResult::Unsafe, None) => {
+                "[MirChecker] Provably error: out-of-bounds raw pointer dereference".to_string()
+            }
+            (CheckerResult::Warning, None) => {
+                "[MirChecker] Possible error: out-of-bounds raw pointer dereference".to_string()
+            }
+            (CheckerResult::Unsafe, Some(lane)) => format!(
+                "[MirChecker] Provably error: lane {} of the SIMD gather reads out of bounds, the offset is {:?}",
+                lane, access.offset
+            ),
+            (CheckerResult::Warning, Some(lane)) => format!(
+                "[MirChecker] Possible error: lane {} of the SIMD gather may read out of bounds, the offset is {:?}",
+                lane, access.offset
+            ),
+        };
+        let warning = self
+            .body_visitor
+            .context
+            .session
+            .dcx()
+            .struct_span_warn(access.span, message);
+        self.body_visitor
+            .emit_diagnostic(warning, is_memory_safety, DiagnosticCause::Memory);
+    }
+
+    /// The access is in bounds if `0 <= offset` and `offset + access_size <= allocation_size`
+    /// If the allocation size is not known, e.g., not a constant, only `0 <= offset` can be proved, so the access
+    /// is a warning unless the offset is always negative
+    fn check_bounds(access: &RawPointerAccess) -> CheckerResult {
+        if access.offset.is_bottom() {
+            // Unreachable
+            return CheckerResult::Safe;
+        }
+        let allocation_size = if let Some(size) = &access.allocation_size {
+            size
+        } else if matches!(&access.offset.high, Bound::Int(high) if *high < 0) {
+            return CheckerResult::Unsafe;
+        } else {
+            return CheckerResult::Warning;
+        };
+        let last_valid_offset = Integer::from(allocation_size - access.access_size);
+        let low_in_bounds = match &access.offset.low {
+            Bound::Int(low) => *low >= 0 && *low <= last_valid_offset,
+            _ => false,
+        };
+        let high_in_bounds = match &access.offset.high {
+            Bound::Int(high) => *high >= 0 && *high <= last_valid_offset,
+            _ => false,
+        };
+        let high_below = matches!(&access.offset.high, Bound::Int(high) if *high < 0);
+        let low_above = matches!(&access.offset.low, Bound::Int(low) if *low > last_valid_offset);
+        if low_in_bounds && high_in_bounds {
+            CheckerResult::Safe
+        } else if high_below || low_above {
+            CheckerResult::Unsafe
+        } else {
+            CheckerResult::Warning
+        }
+    }
+}
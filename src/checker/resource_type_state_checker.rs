@@ -0,0 +1,182 @@
+use crate::analysis::diagnostics::DiagnosticCause;
+use crate::analysis::memory::path::{Path, PathEnum, PathSelector};
+use crate::analysis::mir_visitor::body_visitor::WtoFixPointIterator;
+use crate::analysis::numerical::apron_domain::{
+    ApronAbstractDomain, ApronDomainType, GetManagerTrait,
+};
+use crate::analysis::numerical::interval::{Bound, Interval};
+use crate::checker::checker_trait::CheckerTrait;
+use log::debug;
+use rug::Integer;
+use rustc_middle::mir;
+use std::rc::Rc;
+
+/// The names of the calls that open the resource they return, e.g., `File::open`
+const OPEN_METHODS: [&str; 2] = ["open", "connect"];
+
+/// The names of the calls that close the resource that their first argument is or refers to
+const CLOSE_METHODS: [&str; 2] = ["close", "shutdown"];
+
+pub fn is_open_method(name: &str) -> bool {
+    OPEN_METHODS.contains(&name)
+}
+
+pub fn is_close_method(name: &str) -> bool {
+    CLOSE_METHODS.contains(&name)
+}
+
+/// The type-state of a resource, which is stored as an integer at its `Path::new_resource_state` path, so that the
+/// states of the branches are joined in the numerical domain, where an interval that contains both `Closed` and
+/// `Open` is `Top`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResourceState {
+    Closed,
+    Open,
+    Top,
+}
+
+impl ResourceState {
+    /// The integer that the state is stored as, `Top` is never stored but computed by the join
+    pub fn value(self) -> Integer {
+        match self {
+            ResourceState::Closed => Integer::from(0),
+            ResourceState::Open => Integer::from(1),
+            ResourceState::Top => unreachable!("`Top` is the join of the other states"),
+        }
+    }
+
+    pub fn from_interval(interval: &Interval) -> Self {
+        let open = Interval::new(
+            Bound::from(ResourceState::Open.value()),
+            Bound::from(ResourceState::Open.value()),
+        );
+        if interval.meet(&open).is_bottom() {
+            ResourceState::Closed
+        } else if interval.equal_to(&open) == Some(true) {
+            ResourceState::Open
+        } else {
+            ResourceState::Top
+        }
+    }
+}
+
+/// Checks that the resources opened in a function are closed before it returns, unless they escape
+/// A resource is a value whose type implements a trait marked with `#[mir_checker::resource]`. It is opened by a call
+/// in `OPEN_METHODS` that returns it, and closed by a call in `CLOSE_METHODS`, see `BlockVisitor::visit_call_resource`
+/// A resource escapes if it is returned, stored behind a reference or in a static variable, or moved into another
+/// call. The heap allocations that do not escape are removed from the exit state, see
+/// `WtoFixPointIterator::forget_non_escaping_allocations`, so the resources stored in them are not checked
+pub struct ResourceTypeStateChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>,
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> CheckerTrait<'tcx, 'a, 'b, 'compiler, DomainType>
+    for ResourceTypeStateChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn new(body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>) -> Self {
+        Self { body_visitor }
+    }
+
+    fn run(&mut self) {
+        info!("====== Resource Type-State Checker starts ======");
+        let exit_state = match self.body_visitor.get_exit_state() {
+            Some(exit_state) => exit_state,
+            None => return,
+        };
+        // Report in the order of paths, so that the output is deterministic
+        let mut resources: Vec<(Rc<Path>, ResourceState)> = exit_state
+            .get_paths_iter()
+            .into_iter()
+            .filter_map(|path| match &path.value {
+                PathEnum::QualifiedPath {
+                    qualifier,
+                    selector,
+                    ..
+                } if **selector == PathSelector::ResourceState => {
+                    let interval = exit_state.numerical_domain.get_interval(&path);
+                    Some((qualifier.clone(), ResourceState::from_interval(&interval)))
+                }
+                _ => None,
+            })
+            .collect();
+        resources.sort_by(|(path1, _), (path2, _)| path1.cmp(path2));
+        for (resource, state) in resources {
+            self.check_resource(&resource, state);
+        }
+        info!("====== Resource Type-State Checker ends ======");
+    }
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType>
+    ResourceTypeStateChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn check_resource(&mut self, resource: &Rc<Path>, state: ResourceState) {
+        debug!("Checking resource {:?} in state {:?}", resource, state);
+        let local = match self.get_owner(resource) {
+            Some(local) => local,
+            None => return,
+        };
+        let message = match state {
+            ResourceState::Closed => return,
+            ResourceState::Open => {
+                "[MirChecker] Provably error: resource leak, the resource is not closed before the function returns"
+            }
+            ResourceState::Top => {
+                "[MirChecker] Possible error: resource leak, the resource may not be closed before the function returns"
+            }
+        };
+        let span = self.body_visitor.wto.get_mir().local_decls[local]
+            .source_info
+            .span;
+        let warning = self
+            .body_visitor
+            .context
+            .session
+            .dcx()
+            .struct_span_warn(span, message);
+        self.body_visitor
+            .emit_diagnostic(warning, true, DiagnosticCause::Memory);
+    }
+
+    /// The local variable or parameter that owns the resource, or `None` if the resource escapes
+    fn get_owner(&self, resource: &Rc<Path>) -> Option<mir::Local> {
+        let mut root = resource;
+        loop {
+            if self.body_visitor.escaped_resources.contains(root) {
+                return None;
+            }
+            match &root.value {
+                PathEnum::QualifiedPath {
+                    qualifier,
+                    selector,
+                    ..
+                } => {
+                    // A resource behind a reference is owned by someone else
+                    if **selector == PathSelector::Deref {
+                        return None;
+                    }
+                    root = qualifier;
+                }
+                PathEnum::LocalVariable { ordinal } | PathEnum::Parameter { ordinal } => {
+                    // The paths returned by the callees are rooted by their locals, which have larger offsets
+                    let local_count = self.body_visitor.wto.get_mir().local_decls.len();
+                    return ordinal
+                        .checked_sub(self.body_visitor.fresh_variable_offset)
+                        .filter(|index| *index < local_count)
+                        .map(mir::Local::from_usize);
+                }
+                _ => return None,
+            }
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use crate::analysis::diagnostics::DiagnosticCause;
+use crate::analysis::mir_visitor::body_visitor::WtoFixPointIterator;
+use crate::analysis::mir_visitor::type_visitor::TypeVisitor;
+use crate::analysis::numerical::apron_domain::{
+    ApronAbstractDomain, ApronDomainType, GetManagerTrait,
+};
+use crate::checker::checker_trait::CheckerTrait;
+use rustc_middle::mir;
+use rustc_middle::ty::{Ty, TyCtxt};
+use rustc_span::Span;
+use std::convert::TryFrom;
+
+/// A shift whose shift amount is a constant, found by the constant folding before the fixed-point iteration
+#[derive(Clone, Debug)]
+pub struct ConstantShift<'tcx> {
+    pub span: Span,
+    // `<<`, or `>>` otherwise
+    pub is_left: bool,
+    // The type of the shifted value
+    pub ty: Ty<'tcx>,
+    // The shift amount, which is negative if it is a negative constant of a signed integer type
+    pub amount: i128,
+    pub bit_width: u64,
+}
+
+impl<'tcx> ConstantShift<'tcx> {
+    /// True if the shift amount is negative, or not less than the bit width of the shifted value
+    pub fn is_out_of_range(&self) -> bool {
+        self.amount < 0 || self.amount >= self.bit_width as i128
+    }
+}
+
+/// Evaluates the shift amount `rhs` of `lhs << rhs` or `lhs >> rhs` if it is a constant, None if `bin_op` is not a
+/// shift, or if the shift amount is not a constant or cannot be evaluated
+pub fn get_constant_shift<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    type_visitor: &TypeVisitor<'tcx>,
+    bin_op: mir::BinOp,
+    lhs: &mir::Operand<'tcx>,
+    rhs: &mir::Operand<'tcx>,
+    span: Span,
+) -> Option<ConstantShift<'tcx>> {
+    use mir::BinOp::*;
+    let is_left = match bin_op {
+        Shl | ShlUnchecked => true,
+        Shr | ShrUnchecked => false,
+        _ => return None,
+    };
+    let constant = match rhs {
+        mir::Operand::Constant(constant) => constant,
+        _ => return None,
+    };
+    let param_env = type_visitor.get_param_env();
+    let bits = constant.const_.try_eval_bits(tcx, param_env)?;
+    let lhs_ty = type_visitor.specialize_generic_argument_type(
+        lhs.ty(&type_visitor.mir, tcx),
+        &type_visitor.generic_argument_map,
+    );
+    let rhs_ty = type_visitor.specialize_generic_argument_type(
+        rhs.ty(&type_visitor.mir, tcx),
+        &type_visitor.generic_argument_map,
+    );
+    let rhs_size = tcx.layout_of(param_env.and(rhs_ty)).ok()?.size;
+    let amount = if rhs_ty.is_signed() {
+        rhs_size.sign_extend(bits) as i128
+    } else {
+        i128::try_from(rhs_size.truncate(bits)).unwrap_or(i128::MAX)
+    };
+    let bit_width = type_visitor.get_type_size(lhs_ty)? * 8;
+    if bit_width == 0 {
+        return None;
+    }
+    Some(ConstantShift {
+        span,
+        is_left,
+        ty: lhs_ty,
+        amount,
+        bit_width,
+    })
+}
+
+/// Reports the shifts whose constant shift amounts are negative or not less than the bit widths of the shifted
+/// values, see `--check shift-by-const`
+/// The shifts are found by `WtoFixPointIterator::fold_constant_shifts` before the fixed-point iteration, so the
+/// errors do not depend on the numerical domain, and the assertions of these shifts are not checked again by it
+pub struct ShiftByConstChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>,
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> CheckerTrait<'tcx, 'a, 'b, 'compiler, DomainType>
+    for ShiftByConstChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn new(body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>) -> Self {
+        Self { body_visitor }
+    }
+
+    fn run(&mut self) {
+        info!("====== Shift By Const Checker starts ======");
+        let shifts = self
+            .body_visitor
+            .reachable_in_order(&self.body_visitor.constant_shifts);
+        for (_, shift) in shifts {
+            let operator = if shift.is_left { "<<" } else { ">>" };
+            let mut error = self.body_visitor.context.session.dcx().struct_span_warn(
+                shift.span,
+                format!(
+                    "[MirChecker] Provably error: the constant shift amount of `{}` is out of range",
+                    operator
+                ),
+            );
+            error.note(format!(
+                "shift amount {} is not in the range 0..{} of the bit width of `{}`",
+                shift.amount, shift.bit_width, shift.ty
+            ));
+            self.body_visitor
+                .emit_diagnostic(error, false, DiagnosticCause::Bitwise);
+        }
+        info!("====== Shift By Const Checker ends ======");
+    }
+}
@@ -0,0 +1,106 @@
+use crate::analysis::diagnostics::DiagnosticCause;
+use crate::analysis::mir_visitor::body_visitor::WtoFixPointIterator;
+use crate::analysis::numerical::apron_domain::{
+    ApronAbstractDomain, ApronDomainType, GetManagerTrait,
+};
+use crate::analysis::numerical::interval::{Bound, Interval};
+use crate::checker::checker_trait::CheckerTrait;
+use log::debug;
+use rustc_span::Span;
+
+/// A shift of a signed integer, recorded during the fixed-point iteration
+/// The range of the shifted value is taken from the state before the shift, so that the last record of a location
+/// is computed from the fixed-point
+#[derive(Clone, Debug)]
+pub struct SignedShift {
+    pub span: Span,
+    // `<<`, or `>>` otherwise
+    pub is_left: bool,
+    // The range of the shifted value
+    pub value: Interval,
+}
+
+/// Checks the shifts of signed integers whose shifted values may be negative, see `--check shift-sign`
+/// A left shift of a negative value is undefined behavior in C, and the sign of the result changes if a bit
+/// different from the sign bit is shifted into it, so it is warned
+/// A right shift of a negative value is well-defined in Rust, i.e., an arithmetic shift that fills with the sign
+/// bit, but it is implementation-defined in C, so it is reported as a note for the code ported from C
+pub struct ShiftSignChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>,
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> CheckerTrait<'tcx, 'a, 'b, 'compiler, DomainType>
+    for ShiftSignChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn new(body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>) -> Self {
+        Self { body_visitor }
+    }
+
+    fn run(&mut self) {
+        info!("====== Shift Sign Checker starts ======");
+        let signed_shifts = self
+            .body_visitor
+            .reachable_in_order(&self.body_visitor.signed_shifts);
+        for (_, shift) in signed_shifts {
+            self.check_shift(&shift);
+        }
+        info!("====== Shift Sign Checker ends ======");
+    }
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> ShiftSignChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn check_shift(&mut self, shift: &SignedShift) {
+        debug!("Checking signed shift: {:?}", shift);
+        let zero = Bound::from(0i128);
+        if shift.value.is_bottom() || shift.value.low >= zero {
+            return;
+        }
+        let always_negative = shift.value.high < zero;
+        let dcx = self.body_visitor.context.session.dcx();
+        let diagnostic = if shift.is_left {
+            let message = if always_negative {
+                format!(
+                    "[MirChecker] Provably error: left shift of a negative value, the shifted value is {:?}",
+                    shift.value
+                )
+            } else {
+                format!(
+                    "[MirChecker] Possible error: left shift of a value that may be negative, the shifted value is {:?}",
+                    shift.value
+                )
+            };
+            let mut warning = dcx.struct_span_warn(shift.span, message);
+            warning.note(
+                "a left shift of a negative value is undefined behavior in C, and it changes the sign of the result \
+                 if a bit different from the sign bit is shifted into it",
+            );
+            warning
+        } else {
+            let mut note = dcx.struct_span_note(
+                shift.span,
+                format!(
+                    "[MirChecker] Signed right shift: the shifted value may be negative, the shifted value is {:?}",
+                    shift.value
+                ),
+            );
+            note.note(
+                "a right shift of a negative value fills with the sign bit in Rust, but it is implementation-defined \
+                 in C, cast the value to an unsigned type for a logical shift",
+            );
+            note
+        };
+        self.body_visitor
+            .emit_diagnostic(diagnostic, false, DiagnosticCause::Bitwise);
+    }
+}
@@ -0,0 +1,123 @@
+use crate::analysis::diagnostics::DiagnosticCause;
+use crate::analysis::mir_visitor::body_visitor::WtoFixPointIterator;
+use crate::analysis::numerical::apron_domain::{
+    ApronAbstractDomain, ApronDomainType, GetManagerTrait,
+};
+use crate::analysis::numerical::interval::Interval;
+use crate::checker::checker_trait::CheckerTrait;
+use log::debug;
+use rustc_span::Span;
+
+/// The operations on `str`s that panic if their indices are out of bounds or not on character boundaries
+#[derive(Clone, Debug, PartialEq)]
+pub enum StrOperation {
+    // `&s[start..end]`, `&s[start..]` or `&s[..end]`, which requires `start <= end <= len`
+    Slice { start: Interval, end: Interval },
+    // `s.split_at(mid)` or `s.split_at_mut(mid)`, which requires `mid <= len`
+    SplitAt { mid: Interval },
+}
+
+/// A slicing or a splitting of a `str`, recorded during the fixed-point iteration
+/// Whether the indices are in bounds is decided in the numerical domain when the call is analyzed, so that the
+/// relations between the indices and the length are used, e.g., `i <= s.len()`
+#[derive(Clone, Debug)]
+pub struct StrIndex {
+    pub span: Span,
+    pub operation: StrOperation,
+    // The length of the string in bytes
+    pub length: Interval,
+    // Whether the indices are in bounds, if known
+    pub in_bounds: Option<bool>,
+}
+
+/// Checks that the ranges of `&s[start..end]` and the indices of `s.split_at(mid)` on a `str` are in bounds, and
+/// warns about the `split_at` at an index that is not a constant, which panics if it is not on a character
+/// boundary, see `--check str-index`
+/// The length of a string literal is the metadata of the reference, and the length of a substring is computed
+/// from the range or the index that it is sliced or split at
+pub struct StrIndexChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>,
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> CheckerTrait<'tcx, 'a, 'b, 'compiler, DomainType>
+    for StrIndexChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn new(body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>) -> Self {
+        Self { body_visitor }
+    }
+
+    fn run(&mut self) {
+        info!("====== Str Index Checker starts ======");
+        let str_indices = self
+            .body_visitor
+            .reachable_in_order(&self.body_visitor.str_indices);
+        for (_, index) in str_indices {
+            self.check_index(&index);
+        }
+        info!("====== Str Index Checker ends ======");
+    }
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> StrIndexChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn check_index(&mut self, index: &StrIndex) {
+        debug!("Checking str index: {:?}", index);
+        let (description, indices) = match &index.operation {
+            StrOperation::Slice { start, end } => (
+                "the range of the string slice",
+                format!("the range is {:?}..{:?}", start, end),
+            ),
+            StrOperation::SplitAt { mid } => {
+                ("the index of `split_at`", format!("the index is {:?}", mid))
+            }
+        };
+        let message = match index.in_bounds {
+            Some(true) => None,
+            Some(false) => Some(format!(
+                "[MirChecker] Provably error: {} is out of bounds, {} and the length is {:?}",
+                description, indices, index.length
+            )),
+            None => Some(format!(
+                "[MirChecker] Possible error: {} may be out of bounds, {} and the length is {:?}",
+                description, indices, index.length
+            )),
+        };
+        if let Some(message) = message {
+            let warning = self
+                .body_visitor
+                .context
+                .session
+                .dcx()
+                .struct_span_warn(index.span, message);
+            self.body_visitor
+                .emit_diagnostic(warning, false, DiagnosticCause::Index);
+        }
+
+        // The contents of the string are not tracked, so only a constant index is assumed to be on a character
+        // boundary, as the author of the code has chosen it
+        if let StrOperation::SplitAt { mid } = &index.operation {
+            if index.in_bounds != Some(false) && mid.low != mid.high {
+                let mut warning = self.body_visitor.context.session.dcx().struct_span_warn(
+                    index.span,
+                    "[MirChecker] Possible error: `split_at` may split the string inside a character",
+                );
+                warning.note(format!(
+                    "the index is {:?}, `split_at` panics if it is not on a UTF-8 character boundary",
+                    mid
+                ));
+                self.body_visitor
+                    .emit_diagnostic(warning, false, DiagnosticCause::Index);
+            }
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use crate::analysis::config_file::{self, ConfigValue};
+use crate::analysis::diagnostics::DiagnosticCause;
+use crate::analysis::mir_visitor::body_visitor::WtoFixPointIterator;
+use crate::analysis::numerical::apron_domain::{
+    ApronAbstractDomain, ApronDomainType, GetManagerTrait,
+};
+use crate::analysis::reachability;
+use crate::checker::checker_trait::CheckerTrait;
+use rustc_hir::def_id::DefId;
+use rustc_middle::mir;
+use rustc_middle::ty::TyCtxt;
+use rustc_span::Span;
+use std::collections::HashSet;
+
+/// The taint sources and sinks in the `[taint]` section of the configuration, as paths of functions
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TaintConfig {
+    // The functions whose return values are tainted
+    pub sources: Vec<String>,
+    // The functions that must not be called with tainted arguments
+    pub sinks: Vec<String>,
+}
+
+/// Parses the `[taint]` section of the configuration, the other sections are skipped, e.g.,
+///
+/// ```toml
+/// [taint]
+/// sources = ["std::io::Read::read", "std::env::var"]
+/// sinks = [
+///     "std::fmt::Write::write_fmt",
+/// ]
+/// ```
+///
+/// The configuration is parsed by `config_file::parse_config`, only arrays of strings are supported, and the error
+/// names the line of the first invalid key
+pub fn parse_taint_config(content: &str) -> Result<TaintConfig, String> {
+    let table = config_file::parse_config(content)?
+        .into_iter()
+        .find(|table| table.name.as_deref() == Some("taint"))
+        .ok_or_else(|| String::from("there is no `[taint]` section"))?;
+    let mut config = TaintConfig::default();
+    for entry in table.entries {
+        let error = |message: &str| format!("line {}: {}", entry.line, message);
+        let items = match entry.value {
+            ConfigValue::Array(items) => items,
+            _ => {
+                return Err(error(
+                    "expected an array of function paths, e.g., `[\"std::env::var\"]`",
+                ))
+            }
+        };
+        let functions = items
+            .into_iter()
+            .map(|item| match item {
+                ConfigValue::String(function) => Ok(function),
+                item => Err(error(&format!(
+                    "`{:?}` is not a quoted function path",
+                    item
+                ))),
+            })
+            .collect::<Result<Vec<String>, String>>()?;
+        match entry.key.as_str() {
+            "sources" => config.sources = functions,
+            "sinks" => config.sinks = functions,
+            key => {
+                return Err(error(&format!(
+                    "unknown key `{}`, expected one of `sources`, `sinks`",
+                    key
+                )))
+            }
+        }
+    }
+    Ok(config)
+}
+
+/// Reads and parses the configuration at `file_path`, see `parse_taint_config`
+pub fn load_taint_config(file_path: &str) -> Result<TaintConfig, String> {
+    let content = std::fs::read_to_string(file_path).map_err(|e| e.to_string())?;
+    parse_taint_config(&content)
+}
+
+/// Resolves the paths of the functions in the configuration, e.g., `std::env::var`, into `DefId`s
+/// A path is compared with `tcx.def_path_str` of the functions of this crate and the functions they call, so a
+/// function that is never called by this crate is not resolved, which does not matter for the analysis
+pub fn resolve_functions(tcx: TyCtxt<'_>, paths: &[String]) -> HashSet<DefId> {
+    let mut candidates: HashSet<DefId> = HashSet::new();
+    for def_id in reachability::local_functions(tcx) {
+        candidates.insert(def_id);
+        for bb_data in tcx.optimized_mir(def_id).basic_blocks.iter() {
+            if let mir::TerminatorKind::Call { func, .. } = &bb_data.terminator().kind {
+                if let Some((callee, _)) = func.const_fn_def() {
+                    candidates.insert(callee);
+                }
+            }
+        }
+    }
+    let resolved: HashSet<DefId> = candidates
+        .into_iter()
+        .filter(|def_id| paths.contains(&tcx.def_path_str(*def_id)))
+        .collect();
+    for path in paths {
+        if !resolved
+            .iter()
+            .any(|def_id| tcx.def_path_str(*def_id) == *path)
+        {
+            warn!(
+                "`{}` in the `[taint]` section is not called in this crate",
+                path
+            );
+        }
+    }
+    resolved
+}
+
+/// A call of a taint sink with an argument derived from a taint source, recorded during the fixed-point iteration
+#[derive(Clone, Debug)]
+pub struct TaintedSinkCall {
+    pub span: Span,
+    pub callee: DefId,
+    // The index of the first tainted argument
+    pub argument: usize,
+    // The call of the taint source that the argument is derived from
+    pub source: Span,
+}
+
+/// Reports the calls of the taint sinks in the `[taint]` section of the configuration whose arguments are derived
+/// from the taint sources, see `BlockVisitor::visit_call_taint`
+pub struct TaintSinkChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>,
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> CheckerTrait<'tcx, 'a, 'b, 'compiler, DomainType>
+    for TaintSinkChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn new(body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>) -> Self {
+        Self { body_visitor }
+    }
+
+    fn run(&mut self) {
+        info!("====== Taint Sink Checker starts ======");
+        let calls = self
+            .body_visitor
+            .reachable_in_order(&self.body_visitor.tainted_sink_calls);
+        for (_, call) in calls {
+            let callee_name = self.body_visitor.context.tcx.def_path_str(call.callee);
+            let mut warning = self.body_visitor.context.session.dcx().struct_span_warn(
+                call.span,
+                format!(
+                    "[MirChecker] Possible error: argument {} of `{}` is derived from a taint source",
+                    call.argument, callee_name
+                ),
+            );
+            warning.span_note(call.source, "the taint source is called here");
+            self.body_visitor
+                .emit_diagnostic(warning, false, DiagnosticCause::TaintSink);
+        }
+        info!("====== Taint Sink Checker ends ======");
+    }
+}
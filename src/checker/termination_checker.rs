@@ -0,0 +1,188 @@
+use crate::analysis::diagnostics::DiagnosticCause;
+use crate::analysis::memory::path::Path;
+use crate::analysis::mir_visitor::body_visitor::WtoFixPointIterator;
+use crate::analysis::numerical::apron_domain::{
+    ApronAbstractDomain, ApronDomainType, GetManagerTrait,
+};
+use crate::analysis::numerical::interval::Bound;
+use crate::analysis::numerical::lattice::LatticeTrait;
+use crate::analysis::numerical::linear_constraint::{
+    LinearConstraint, LinearConstraintSystem, LinearExpression,
+};
+use crate::analysis::numerical::sparse_domain::SparseApronDomain;
+use crate::checker::checker_trait::CheckerTrait;
+use itertools::Itertools;
+use log::debug;
+use rug::Integer;
+use rustc_middle::mir;
+use rustc_middle::mir::visit::{PlaceContext, Visitor};
+use rustc_span::Span;
+use std::collections::HashSet;
+use std::rc::Rc;
+
+/// One iteration of a loop, recorded after the fixed-point iteration by analyzing the loop body once more
+/// from its invariant, see `WtoFixPointIterator::record_loop_transition`
+pub struct LoopTransition<DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    pub span: Span,
+    // Each loop variable, together with the path that keeps its value at the start of the iteration
+    pub variables: Vec<(Rc<Path>, Rc<Path>)>,
+    // A path that is not used by the function, which is assigned the candidate ranking functions
+    pub rank: Rc<Path>,
+    // The numerical state on the back edges to the loop head, which is bottom if the loop never iterates again
+    pub state: SparseApronDomain<DomainType>,
+}
+
+/// Checks that each loop terminates, by searching for a ranking function of the loop variables, i.e., a linear
+/// combination `c1 * x1 + ... + cn * xn` that is bounded below and decreases by at least one in each iteration
+/// The coefficients are enumerated up to `termination_depth` in absolute value, so the search is incomplete,
+/// and a loop without a linear ranking function is reported even if it terminates
+/// Only the loops of the functions of the local crate that have at most `MAX_LOOP_VARIABLES` variables are
+/// checked, and the ranking functions can only be verified in a polyhedra domain
+pub struct TerminationChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>,
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> CheckerTrait<'tcx, 'a, 'b, 'compiler, DomainType>
+    for TerminationChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn new(body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>) -> Self {
+        Self { body_visitor }
+    }
+
+    fn run(&mut self) {
+        info!("====== Termination Checker starts ======");
+        if self.body_visitor.def_id.is_local() {
+            let depth = self.body_visitor.context.analysis_options.termination_depth;
+            // Report in the order of loop heads, so that the output is deterministic
+            let mut loops: Vec<(&mir::BasicBlock, &LoopTransition<DomainType>)> =
+                self.body_visitor.loop_transitions.iter().collect();
+            loops.sort_by_key(|(head_bb, _)| **head_bb);
+            let non_terminating: Vec<Span> = loops
+                .into_iter()
+                .filter(|(_, transition)| !Self::has_ranking_function(transition, depth))
+                .map(|(_, transition)| transition.span)
+                .collect();
+            for span in non_terminating {
+                let warning = self.body_visitor.context.session.dcx().struct_span_warn(
+                    span,
+                    "[MirChecker] Possible error: the loop may not terminate, no linear ranking function is found",
+                );
+                self.body_visitor
+                    .emit_diagnostic(warning, false, DiagnosticCause::Termination);
+            }
+        }
+        info!("====== Termination Checker ends ======");
+    }
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> TerminationChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    /// The largest number of loop variables that the ranking functions are searched for
+    pub const MAX_LOOP_VARIABLES: usize = 5;
+
+    /// Whether a ranking function whose coefficients are in `[-depth, depth]` is found for `transition`
+    fn has_ranking_function(transition: &LoopTransition<DomainType>, depth: u32) -> bool {
+        // The loop head is never reached again from the loop body
+        if transition.state.is_bottom() {
+            return true;
+        }
+        let depth = i64::from(depth);
+        transition
+            .variables
+            .iter()
+            .map(|_| -depth..=depth)
+            .multi_cartesian_product()
+            .filter(|coefficients| coefficients.iter().any(|c| *c != 0))
+            .any(|coefficients| {
+                let mut before = LinearExpression::default();
+                let mut after = LinearExpression::default();
+                for ((variable, initial), c) in transition.variables.iter().zip(coefficients) {
+                    before.add_term(initial.clone(), Integer::from(c));
+                    after.add_term(variable.clone(), Integer::from(c));
+                }
+                let is_ranking = Self::is_decreasing(transition, &before, &after)
+                    && Self::is_bounded_below(transition, &before);
+                if is_ranking {
+                    debug!("Ranking function for {:?}: {:?}", transition.span, after);
+                }
+                is_ranking
+            })
+    }
+
+    /// Whether `before - after >= 1` holds in each iteration, i.e., `before - after <= 0` is unsatisfiable
+    fn is_decreasing(
+        transition: &LoopTransition<DomainType>,
+        before: &LinearExpression,
+        after: &LinearExpression,
+    ) -> bool {
+        let mut state = transition.state.clone();
+        let increasing = LinearConstraint::LessEq(before.clone() - after.clone());
+        state.add_constraints(LinearConstraintSystem::from(increasing));
+        state.is_bottom()
+    }
+
+    /// Whether `before` has a lower bound at the start of the iterations that reach the loop head again
+    fn is_bounded_below(
+        transition: &LoopTransition<DomainType>,
+        before: &LinearExpression,
+    ) -> bool {
+        let mut state = transition.state.clone();
+        let rank = LinearConstraint::Equality(before.clone() - transition.rank.clone());
+        state.add_constraints(LinearConstraintSystem::from(rank));
+        matches!(state.get_interval(&transition.rank).low, Bound::Int(_))
+    }
+}
+
+/// The locals that are used by the basic blocks `blocks` of `mir`
+pub fn get_used_locals(mir: &mir::Body<'_>, blocks: &[mir::BasicBlock]) -> HashSet<mir::Local> {
+    collect_locals(mir, blocks, false)
+}
+
+/// The locals that are modified by the basic blocks `blocks` of `mir`, e.g., assigned, written through a
+/// projection, mutably borrowed, or written as the destinations of calls
+pub fn get_modified_locals(mir: &mir::Body<'_>, blocks: &[mir::BasicBlock]) -> HashSet<mir::Local> {
+    collect_locals(mir, blocks, true)
+}
+
+fn collect_locals(
+    mir: &mir::Body<'_>,
+    blocks: &[mir::BasicBlock],
+    mutating_only: bool,
+) -> HashSet<mir::Local> {
+    let mut collector = LocalCollector {
+        locals: HashSet::new(),
+        mutating_only,
+    };
+    for bb in blocks {
+        collector.visit_basic_block_data(*bb, &mir.basic_blocks[*bb]);
+    }
+    collector.locals
+}
+
+struct LocalCollector {
+    locals: HashSet<mir::Local>,
+    // Only collect the locals that are modified
+    mutating_only: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for LocalCollector {
+    fn visit_local(&mut self, local: mir::Local, context: PlaceContext, _location: mir::Location) {
+        if !self.mutating_only || context.is_mutating_use() {
+            self.locals.insert(local);
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use crate::analysis::diagnostics::DiagnosticCause;
+use crate::analysis::mir_visitor::body_visitor::WtoFixPointIterator;
+use crate::analysis::numerical::apron_domain::{
+    ApronAbstractDomain, ApronDomainType, GetManagerTrait,
+};
+use crate::checker::checker_trait::CheckerTrait;
+use log::debug;
+use rustc_middle::mir;
+use rustc_middle::ty::{Ty, TyKind};
+use rustc_span::{sym, Span};
+
+/// A transmute of a value of type `source` into a value of type `target`
+#[derive(Clone, Debug)]
+pub struct Transmute<'tcx> {
+    pub location: mir::Location,
+    pub span: Span,
+    pub source: Ty<'tcx>,
+    pub target: Ty<'tcx>,
+}
+
+/// The transmutes in the reachable basic blocks of the function analyzed by `body_visitor`, in the order of their
+/// locations, i.e., the calls to `mem::transmute` and `intrinsics::transmute_unchecked`, and the `Transmute` casts
+pub fn find_transmutes<'tcx, DomainType>(
+    body_visitor: &WtoFixPointIterator<'tcx, '_, '_, DomainType>,
+) -> Vec<Transmute<'tcx>>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    let tcx = body_visitor.context.tcx;
+    let mir = body_visitor.wto.get_mir();
+    let mut transmutes = Vec::new();
+    for (bb, bb_data) in body_visitor.wto.basic_blocks.iter_enumerated() {
+        let is_reachable = body_visitor
+            .post
+            .get(&bb)
+            .map_or(false, |state| !state.is_bottom());
+        if bb_data.is_cleanup || !is_reachable {
+            continue;
+        }
+        for (statement_index, statement) in bb_data.statements.iter().enumerate() {
+            if let mir::StatementKind::Assign(box (
+                _,
+                mir::Rvalue::Cast(mir::CastKind::Transmute, operand, target),
+            )) = &statement.kind
+            {
+                transmutes.push(Transmute {
+                    location: mir::Location {
+                        block: bb,
+                        statement_index,
+                    },
+                    span: statement.source_info.span,
+                    source: operand.ty(mir, tcx),
+                    target: *target,
+                });
+            }
+        }
+        let terminator = bb_data.terminator();
+        if let mir::TerminatorKind::Call {
+            func,
+            args,
+            destination,
+            ..
+        } = &terminator.kind
+        {
+            let is_transmute = func.const_fn_def().map_or(false, |(callee, _)| {
+                tcx.intrinsic(callee).map_or(false, |intrinsic| {
+                    intrinsic.name == sym::transmute || intrinsic.name == sym::transmute_unchecked
+                })
+            });
+            if is_transmute && args.len() == 1 {
+                transmutes.push(Transmute {
+                    location: mir::Location {
+                        block: bb,
+                        statement_index: bb_data.statements.len(),
+                    },
+                    span: terminator.source_info.span,
+                    source: args[0].node.ty(mir, tcx),
+                    target: destination.ty(mir, tcx).ty,
+                });
+            }
+        }
+    }
+    transmutes
+}
+
+/// Checks the transmutes in the reachable basic blocks, i.e., the calls to `mem::transmute` and
+/// `intrinsics::transmute_unchecked`, and the `Transmute` casts they are lowered into
+/// A transmute between types of different sizes is an error. rustc rejects such a `mem::transmute`, but not a
+/// `transmute_unchecked`, e.g., in a generic function whose type parameters are only known in the analyzed context
+/// A transmute between pointers of the same size may produce a misaligned reference if the target pointee type
+/// requires a larger alignment than the source pointee type. The pointers whose alignment is known are checked
+/// more precisely by `AlignmentChecker`, so they are skipped
+/// The types whose sizes are unknown, e.g., unsized types or types that depend on unresolved generic parameters,
+/// are not checked
+pub struct TransmuteSafetyChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>,
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> CheckerTrait<'tcx, 'a, 'b, 'compiler, DomainType>
+    for TransmuteSafetyChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn new(body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>) -> Self {
+        Self { body_visitor }
+    }
+
+    fn run(&mut self) {
+        info!("====== Transmute Safety Checker starts ======");
+        for transmute in find_transmutes(self.body_visitor) {
+            self.check_transmute(&transmute);
+        }
+        info!("====== Transmute Safety Checker ends ======");
+    }
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType>
+    TransmuteSafetyChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn check_transmute(&mut self, transmute: &Transmute<'tcx>) {
+        debug!("Checking transmute: {:?}", transmute);
+        let type_visitor = &self.body_visitor.type_visitor;
+        let source = type_visitor
+            .specialize_generic_argument_type(transmute.source, &type_visitor.generic_argument_map);
+        let target = type_visitor
+            .specialize_generic_argument_type(transmute.target, &type_visitor.generic_argument_map);
+        // The transmutes whose sizes are unknown, or of zero-sized types, are skipped
+        let (source_size, target_size) = match (
+            type_visitor.get_type_size(source),
+            type_visitor.get_type_size(target),
+        ) {
+            (Some(source_size), Some(target_size)) if source_size > 0 && target_size > 0 => {
+                (source_size, target_size)
+            }
+            _ => return,
+        };
+        if source_size != target_size {
+            let mut error = self.body_visitor.context.session.dcx().struct_span_warn(
+                transmute.span,
+                "[MirChecker] Provably error: transmute between types of different sizes",
+            );
+            error.note(format!(
+                "`{}` has {} byte(s), but `{}` has {} byte(s)",
+                source, source_size, target, target_size
+            ));
+            self.body_visitor
+                .emit_diagnostic(error, true, DiagnosticCause::Memory);
+            return;
+        }
+        if self
+            .body_visitor
+            .aligned_accesses
+            .contains_key(&transmute.location)
+        {
+            return;
+        }
+        let (source_pointee, target_pointee) = match (source.kind(), target.kind()) {
+            (
+                TyKind::Ref(_, source_pointee, _) | TyKind::RawPtr(source_pointee, _),
+                TyKind::Ref(_, target_pointee, _) | TyKind::RawPtr(target_pointee, _),
+            ) => (*source_pointee, *target_pointee),
+            _ => return,
+        };
+        let source_alignment = type_visitor.get_type_alignment(source_pointee);
+        let target_alignment = type_visitor.get_type_alignment(target_pointee);
+        if source_alignment >= target_alignment {
+            return;
+        }
+        let mut warning = self.body_visitor.context.session.dcx().struct_span_warn(
+            transmute.span,
+            "[MirChecker] Possible error: transmute to a pointer that may be misaligned",
+        );
+        warning.note(format!(
+            "`{}` is aligned to {} byte(s), but `{}` requires {} byte(s), consider `ptr::read_unaligned` instead",
+            source_pointee, source_alignment, target_pointee, target_alignment
+        ));
+        self.body_visitor
+            .emit_diagnostic(warning, true, DiagnosticCause::Memory);
+    }
+}
@@ -0,0 +1,155 @@
+use crate::analysis::diagnostics::DiagnosticCause;
+use crate::analysis::memory::escape;
+use crate::analysis::memory::path::{Path, PathEnum};
+use crate::analysis::mir_visitor::body_visitor::WtoFixPointIterator;
+use crate::analysis::numerical::apron_domain::{
+    ApronAbstractDomain, ApronDomainType, GetManagerTrait,
+};
+use crate::analysis::numerical::interval::{Bound, Interval};
+use crate::checker::checker_trait::CheckerTrait;
+use log::debug;
+use rug::Integer;
+use rustc_hir::def_id::DefId;
+use rustc_hir::Mutability;
+use rustc_middle::ty::TyCtxt;
+use rustc_span::Span;
+use std::rc::Rc;
+
+/// Whether a mutable static has been written, which is stored as an integer at its `Path::new_written` path, so
+/// that the states of the branches are joined in the numerical domain, where an interval that contains both
+/// `Unwritten` and `Written` is `Top`
+/// A static whose path is not in the numerical domain has not been written
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WrittenState {
+    Unwritten,
+    Written,
+    Top,
+}
+
+impl WrittenState {
+    /// The integer that the state is stored as, `Top` is never stored but computed by the join
+    pub fn value(self) -> Integer {
+        match self {
+            WrittenState::Unwritten => Integer::from(0),
+            WrittenState::Written => Integer::from(1),
+            WrittenState::Top => unreachable!("`Top` is the join of the other states"),
+        }
+    }
+
+    pub fn from_interval(interval: &Interval) -> Self {
+        let written = Interval::new(
+            Bound::from(WrittenState::Written.value()),
+            Bound::from(WrittenState::Written.value()),
+        );
+        if interval.meet(&written).is_bottom() {
+            WrittenState::Unwritten
+        } else if interval.equal_to(&written) == Some(true) {
+            WrittenState::Written
+        } else {
+            WrittenState::Top
+        }
+    }
+}
+
+/// Returns the mutable static that `path` is rooted by, if any
+pub fn get_mutable_static(tcx: TyCtxt<'_>, path: &Rc<Path>) -> Option<DefId> {
+    match &escape::get_root(path).value {
+        PathEnum::StaticVariable {
+            def_id: Some(def_id),
+            ..
+        } if tcx.static_mutability(*def_id) == Some(Mutability::Mut) => Some(*def_id),
+        _ => None,
+    }
+}
+
+/// A read of a mutable static that may not have been written, recorded during the fixed-point iteration
+#[derive(Clone, Debug)]
+pub struct StaticRead {
+    pub span: Span,
+    pub def_id: DefId,
+    // The state of the static before the read, either `Unwritten` or `Top`
+    pub state: WrittenState,
+}
+
+/// Checks the reads of mutable statics that are not preceded by a write on all the paths that reach them, see
+/// `--check use-of-uninitialized-global`, e.g., a `static mut` whose initializer is a placeholder that must be
+/// replaced by an `init` function before it is used
+/// The writes in the callees that are analyzed with the state of the caller are tracked as well. A callee that
+/// is not, e.g., it is handled by its summary, may write the statics mentioned in it, see
+/// `BlockVisitor::assume_statics_written`, and so may the functions that are not reachable from the entry points,
+/// see `GlobalContext::statics_written_elsewhere`, so the reads of these statics are not reported
+pub struct UninitializedGlobalChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>,
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> CheckerTrait<'tcx, 'a, 'b, 'compiler, DomainType>
+    for UninitializedGlobalChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn new(body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>) -> Self {
+        Self { body_visitor }
+    }
+
+    fn run(&mut self) {
+        info!("====== Uninitialized Global Checker starts ======");
+        let static_reads = self
+            .body_visitor
+            .reachable_in_order(&self.body_visitor.static_reads);
+        for (_, read) in static_reads {
+            if self
+                .body_visitor
+                .context
+                .statics_written_elsewhere
+                .contains(&read.def_id)
+            {
+                continue;
+            }
+            self.check_read(&read);
+        }
+        info!("====== Uninitialized Global Checker ends ======");
+    }
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType>
+    UninitializedGlobalChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    /// A static is always initialized, so a read before a write is not a memory-safety issue, it is only reported
+    /// because the initial value may be a placeholder that an `init` function must replace
+    fn check_read(&mut self, read: &StaticRead) {
+        debug!("Checking read of static: {:?}", read);
+        let name = self.body_visitor.context.tcx.def_path_str(read.def_id);
+        let session = self.body_visitor.context.session;
+        let mut warning = match read.state {
+            WrittenState::Unwritten => session.dcx().struct_span_warn(
+                read.span,
+                format!(
+                    "[MirChecker] Read of an unwritten static: `{}` is read before it is written",
+                    name
+                ),
+            ),
+            _ => {
+                let mut warning = session.dcx().struct_span_warn(
+                    read.span,
+                    format!(
+                        "[MirChecker] Read of an unwritten static: `{}` may be read before it is written",
+                        name
+                    ),
+                );
+                warning.note("the static is written on some of the paths that reach this read");
+                warning
+            }
+        };
+        warning.note("the read returns the initial value of the static");
+        self.body_visitor
+            .emit_diagnostic(warning, false, DiagnosticCause::Other);
+    }
+}
@@ -0,0 +1,106 @@
+use crate::analysis::diagnostics::DiagnosticCause;
+use crate::analysis::mir_visitor::body_visitor::WtoFixPointIterator;
+use crate::analysis::numerical::apron_domain::{
+    ApronAbstractDomain, ApronDomainType, GetManagerTrait,
+};
+use crate::analysis::numerical::interval::Interval;
+use crate::checker::checker_trait::CheckerTrait;
+use log::debug;
+use rustc_span::Span;
+
+/// The operations on vectors and slices that are undefined behavior if their argument is out of bounds
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VecOperation {
+    // `get_unchecked(index)`, which requires `index < len`
+    GetUnchecked,
+    // `get_unchecked(start..end)` on a slice or a `str`, which requires `end <= len`
+    GetUncheckedRange,
+    // `Vec::set_len(len)`, which requires `len <= capacity`
+    SetLen,
+}
+
+/// A call of `get_unchecked` or `Vec::set_len`, recorded during the fixed-point iteration
+/// Whether the argument is in bounds is decided in the numerical domain when the call is analyzed, so that the
+/// relations between the argument and the bound are used, e.g., `i < v.len()` in a loop
+#[derive(Clone, Debug)]
+pub struct VecAccess {
+    pub span: Span,
+    pub operation: VecOperation,
+    // The index, or the new length
+    pub value: Interval,
+    // The length, or the capacity
+    pub bound: Interval,
+    // Whether the argument is in bounds, if known
+    pub in_bounds: Option<bool>,
+}
+
+/// Checks that the indices of `get_unchecked` are less than the lengths of the slices, that the ends of the ranges
+/// of `get_unchecked` on slices and strings are at most their lengths, and that the lengths set by `Vec::set_len`
+/// are at most the capacities of the vectors, see `--check vec-bounds`
+/// The length of a vector is tracked through `Vec::new`, `Vec::with_capacity`, `push` and `pop`, and it is the
+/// metadata of the slice returned by `Vec::deref`
+pub struct VecBoundsChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>,
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> CheckerTrait<'tcx, 'a, 'b, 'compiler, DomainType>
+    for VecBoundsChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn new(body_visitor: &'b mut WtoFixPointIterator<'tcx, 'a, 'compiler, DomainType>) -> Self {
+        Self { body_visitor }
+    }
+
+    fn run(&mut self) {
+        info!("====== Vec Bounds Checker starts ======");
+        let vec_accesses = self
+            .body_visitor
+            .reachable_in_order(&self.body_visitor.vec_accesses);
+        for (_, access) in vec_accesses {
+            self.check_access(&access);
+        }
+        info!("====== Vec Bounds Checker ends ======");
+    }
+}
+
+impl<'tcx, 'a, 'b, 'compiler, DomainType> VecBoundsChecker<'tcx, 'a, 'b, 'compiler, DomainType>
+where
+    DomainType: ApronDomainType,
+    ApronAbstractDomain<DomainType>: GetManagerTrait,
+{
+    fn check_access(&mut self, access: &VecAccess) {
+        debug!("Checking vector access: {:?}", access);
+        let (description, value, bound) = match access.operation {
+            VecOperation::GetUnchecked => ("the index of `get_unchecked`", "index", "length"),
+            VecOperation::GetUncheckedRange => {
+                ("the end of the range of `get_unchecked`", "end", "length")
+            }
+            VecOperation::SetLen => ("the length set by `set_len`", "length", "capacity"),
+        };
+        let message = match access.in_bounds {
+            Some(true) => return,
+            Some(false) => format!(
+                "[MirChecker] Provably error: {} is out of bounds, the {} is {:?} and the {} is {:?}",
+                description, value, access.value, bound, access.bound
+            ),
+            None => format!(
+                "[MirChecker] Possible error: {} may be out of bounds, the {} is {:?} and the {} is {:?}",
+                description, value, access.value, bound, access.bound
+            ),
+        };
+        let warning = self
+            .body_visitor
+            .context
+            .session
+            .dcx()
+            .struct_span_warn(access.span, message);
+        self.body_visitor
+            .emit_diagnostic(warning, true, DiagnosticCause::Index);
+    }
+}
@@ -15,6 +15,7 @@ extern crate log;
 
 extern crate rustc_ast;
 extern crate rustc_abi;
+extern crate rustc_builtin_macros;
 extern crate rustc_data_structures;
 extern crate rustc_driver;
 extern crate rustc_errors;
@@ -40,13 +41,19 @@ pub mod analysis {
     // Apron numerical domains
     pub mod numerical {
         pub mod apron_domain;
+        pub mod float_domain;
         pub mod interval;
         pub mod lattice;
         pub mod linear_constraint;
+        pub mod predicate_domain;
+        pub mod sparse_domain;
+        pub mod wrapping_integer;
     }
     // Memory model
     pub mod memory {
+        pub mod alias;
         pub mod constant_value;
+        pub mod escape;
         pub mod expression;
         pub mod k_limits;
         pub mod known_names;
@@ -71,6 +78,27 @@ pub mod analysis {
     pub mod wto;
     // Analysis options
     pub mod option;
+    // Parse the configuration files of the checkers, e.g., `--ffi-stubs`
+    pub mod config_file;
+    // Functions reachable from the entry points
+    pub mod reachability;
+    // Data flow graphs of the functions, built from their MIR bodies
+    pub mod dataflow;
+    // Function summaries shared by the crates of a workspace
+    pub mod summary;
+    // Archive the diagnostics into a SQLite database
+    pub mod sqlite_export;
+    // Stubs of the standard library functions, which compute the ranges of their return values
+    pub mod stubs {
+        pub mod cmp;
+        pub mod integer;
+        pub mod primitives;
+        pub mod stub_library;
+    }
+    // Checkpoints of the analysis state, which can be replayed by `mir-checker-replay`
+    pub mod checkpoint;
+    // Compare the diagnostics of two runs, used by `mir-checker-diff`
+    pub mod diagnostic_diff;
     // SMT solver
     // pub mod smt;
     pub mod z3_solver;
@@ -80,8 +108,33 @@ pub mod analysis {
 
 // Modules for program property checkers
 pub mod checker {
+    pub mod alignment_checker;
     pub mod assertion_checker;
+    pub mod channel_capacity_checker;
     pub mod checker_trait;
+    pub mod dangling_pointer_checker;
+    pub mod dead_branch_checker;
+    pub mod dead_code_reporter;
+    pub mod double_free_checker;
+    pub mod ffi_checker;
+    pub mod float_nan_checker;
+    pub mod format_string_checker;
+    pub mod int_promotion_checker;
+    pub mod lock_order_checker;
+    pub mod padding_uninit_checker;
+    pub mod panic_reachability_checker;
+    pub mod pin_safety_checker;
+    pub mod raw_offset_overflow_checker;
+    pub mod raw_ptr_bounds_checker;
+    pub mod resource_type_state_checker;
+    pub mod shift_by_const_checker;
+    pub mod shift_sign_checker;
+    pub mod str_index_checker;
+    pub mod taint_sink_checker;
+    pub mod termination_checker;
+    pub mod transmute_safety_checker;
+    pub mod uninitialized_global_checker;
+    pub mod vec_bounds_checker;
 }
 
 // Useful utilities
@@ -0,0 +1,432 @@
+// Tests for the parsing and validation of the options of MirChecker
+//
+//     cargo test --test analysis_option
+
+use rust_mir_checker::analysis::diagnostics::DiagnosticCause;
+use rust_mir_checker::analysis::option::{
+    glob_matches, AbstractDomainType, AnalysisOption, AnalysisType, OutputFormat,
+};
+
+fn parse(args: &[&str]) -> (Result<AnalysisOption, Vec<String>>, Vec<String>) {
+    let mut args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+    let result = AnalysisOption::from_args(&mut args);
+    (result, args)
+}
+
+fn errors(args: &[&str]) -> Vec<String> {
+    match parse(args).0 {
+        Ok(_) => panic!("{:?} should be rejected", args),
+        Err(errors) => errors,
+    }
+}
+
+#[test]
+fn valid_options_are_removed() {
+    let (result, rest) = parse(&[
+        "main.rs",
+        "--domain",
+        "octagon",
+        "--entry",
+        "foo::bar",
+        "--widening_delay",
+        "3",
+        "--suppress_warnings",
+        "ai",
+        "--check-macros",
+    ]);
+    let options = result.unwrap();
+    assert_eq!(rest, vec!["main.rs"]);
+    assert_eq!(options.domain_type, AbstractDomainType::Octagon);
+    assert_eq!(options.entry_point, "foo::bar");
+    assert_eq!(options.widening_delay, 3);
+    assert_eq!(
+        options.suppressed_warnings.map(|causes| causes.len()),
+        Some(2)
+    );
+    assert!(options.check_macros);
+}
+
+#[test]
+fn unknown_domain() {
+    let errors = errors(&["--domain", "foobar"]);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("`--domain`"), "{}", errors[0]);
+    assert!(errors[0].contains("`foobar`"), "{}", errors[0]);
+    assert!(errors[0].contains("`interval`"), "{}", errors[0]);
+    assert!(
+        errors[0].contains("`pkgrid_polyhedra_linear_congruences`"),
+        "{}",
+        errors[0]
+    );
+}
+
+#[test]
+fn wrapping_integer_domain() {
+    let options = parse(&["--domain", "wrapping-integer"]).0.unwrap();
+    assert_eq!(options.domain_type, AbstractDomainType::WrappingInteger);
+}
+
+#[test]
+fn panic_freedom() {
+    let (result, rest) = parse(&["--panic-freedom", "main.rs"]);
+    assert!(result.unwrap().panic_freedom);
+    assert_eq!(rest, vec!["main.rs"]);
+}
+
+#[test]
+fn dump_analysis_order() {
+    let (result, rest) = parse(&["--dump-analysis-order", "main.rs"]);
+    assert!(result.unwrap().dump_analysis_order);
+    assert_eq!(rest, vec!["main.rs"]);
+}
+
+#[test]
+fn no_std() {
+    assert!(!parse(&[]).0.unwrap().no_std);
+    let (result, rest) = parse(&["--no-std", "main.rs"]);
+    assert!(result.unwrap().no_std);
+    assert_eq!(rest, vec!["main.rs"]);
+}
+
+#[test]
+fn verbose_diagnostics() {
+    assert!(!parse(&[]).0.unwrap().verbose_diagnostics);
+    let (result, rest) = parse(&["--verbose-diagnostics", "main.rs"]);
+    assert!(result.unwrap().verbose_diagnostics);
+    assert_eq!(rest, vec!["main.rs"]);
+}
+
+#[test]
+fn dump_wto() {
+    assert_eq!(parse(&[]).0.unwrap().dump_wto, None);
+    let options = parse(&["--dump-wto", "target/wto"]).0.unwrap();
+    assert_eq!(options.dump_wto, Some(String::from("target/wto")));
+}
+
+#[test]
+fn report_dead_code() {
+    let (result, rest) = parse(&["--report-dead-code", "main.rs"]);
+    assert!(result.unwrap().report_dead_code);
+    assert_eq!(rest, vec!["main.rs"]);
+}
+
+#[test]
+fn negative_widening_delay() {
+    let errors = errors(&["--widening_delay", "-1"]);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("`--widening_delay`"), "{}", errors[0]);
+    assert!(errors[0].contains("non-negative integer"), "{}", errors[0]);
+}
+
+#[test]
+fn entry_patterns() {
+    let options = parse(&["--entry-pattern", "test_*", "--entry-pattern", "fuzz_?"])
+        .0
+        .unwrap();
+    assert_eq!(options.entry_patterns, vec!["test_*", "fuzz_?"]);
+
+    let errors = errors(&["--entry-pattern", "foo::bar"]);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("glob pattern"), "{}", errors[0]);
+}
+
+#[test]
+fn glob_patterns() {
+    assert!(glob_matches("test_*", "test_"));
+    assert!(glob_matches("test_*", "test_parse"));
+    assert!(!glob_matches("test_*", "my_test_parse"));
+    assert!(glob_matches("*_test", "parse_test"));
+    assert!(glob_matches("*a*b", "xaxxab"));
+    assert!(!glob_matches("*a*b", "xaxxba"));
+    assert!(glob_matches("fuzz_?", "fuzz_1"));
+    assert!(!glob_matches("fuzz_?", "fuzz_12"));
+    assert!(glob_matches("*", ""));
+    assert!(!glob_matches("main", "main2"));
+}
+
+#[test]
+fn predicates() {
+    let options = parse(&["--predicate", "x + y < 100", "--predicate", "2 * i <= n"])
+        .0
+        .unwrap();
+    assert_eq!(options.predicates, vec!["x + y < 100", "2 * i <= n"]);
+
+    let errors = errors(&["--predicate", "z mod 4 == 0"]);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("linear inequality"), "{}", errors[0]);
+}
+
+#[test]
+fn inline_depth() {
+    assert_eq!(parse(&[]).0.unwrap().inline_depth, None);
+    let options = parse(&["--inline-depth", "0"]).0.unwrap();
+    assert_eq!(options.inline_depth, Some(0));
+
+    let errors = errors(&["--inline-depth", "one"]);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("non-negative integer"), "{}", errors[0]);
+}
+
+#[test]
+fn max_loop_depth() {
+    assert_eq!(parse(&[]).0.unwrap().max_loop_depth, None);
+    let options = parse(&["--max-loop-depth", "2"]).0.unwrap();
+    assert_eq!(options.max_loop_depth, Some(2));
+
+    let errors = errors(&["--max-loop-depth", "-1"]);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("non-negative integer"), "{}", errors[0]);
+}
+
+#[test]
+fn unknown_suppressed_warnings() {
+    let errors = errors(&["--suppress_warnings", "ax"]);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("`--suppress_warnings`"), "{}", errors[0]);
+    assert!(
+        errors[0].contains("`a` (arithmetic overflow)"),
+        "{}",
+        errors[0]
+    );
+}
+
+#[test]
+fn invalid_entry() {
+    for entry in ["foo::", "1abc", "foo bar", "_", "foo::::bar"] {
+        let errors = errors(&["--entry", entry]);
+        assert_eq!(errors.len(), 1, "{}", entry);
+        assert!(errors[0].contains("`--entry`"), "{}", errors[0]);
+    }
+    for entry in ["main", "::foo::bar", "_start", "Foo::new"] {
+        assert!(parse(&["--entry", entry]).0.is_ok(), "{}", entry);
+    }
+}
+
+#[test]
+fn custom_suppressed_warnings() {
+    let options = parse(&["--suppress_warnings", "ai,Custom:Unsafe-FFI"])
+        .0
+        .unwrap();
+    let suppressed = options.suppressed_warnings.unwrap();
+    assert_eq!(
+        suppressed,
+        vec![
+            DiagnosticCause::Arithmetic,
+            DiagnosticCause::Index,
+            DiagnosticCause::Custom(String::from("unsafe-ffi")),
+        ]
+    );
+    assert!(suppressed[2].matches(&DiagnosticCause::Custom(String::from("UNSAFE-ffi"))));
+    assert!(!suppressed[2].matches(&DiagnosticCause::Other));
+
+    let errors = errors(&["--suppress_warnings", "a,custom:"]);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("`custom:<name>`"), "{}", errors[0]);
+}
+
+#[test]
+fn missing_value() {
+    let errors = errors(&["--entry", "--domain", "interval"]);
+    assert_eq!(errors, vec!["`--entry` requires a value"]);
+}
+
+#[test]
+fn all_errors_are_reported() {
+    let errors = errors(&["--domain", "foobar", "--narrowing_iteration", "many"]);
+    assert_eq!(errors.len(), 2);
+}
+
+#[test]
+fn config_schema() {
+    let schema = AnalysisOption::config_schema();
+    assert_eq!(schema["$schema"], "http://json-schema.org/draft-07/schema#");
+    let properties = schema["properties"].as_object().unwrap();
+    for (name, property) in properties {
+        let description = property["description"].as_str().unwrap_or_default();
+        assert!(!description.is_empty(), "{} has no description", name);
+        assert!(property.get("default").is_some(), "{} has no default", name);
+    }
+
+    let domain_type = &properties["domain_type"];
+    assert_eq!(domain_type["default"], "interval");
+    assert!(domain_type["enum"]
+        .as_array()
+        .unwrap()
+        .contains(&serde_json::json!("octagon")));
+
+    let widening_delay = &properties["widening_delay"];
+    assert_eq!(widening_delay["type"], "integer");
+    assert_eq!(widening_delay["minimum"], 0);
+    assert_eq!(widening_delay["default"], 5);
+
+    let output_format = &properties["output_format"];
+    assert_eq!(output_format["default"], "human");
+
+    let cache_dir = &properties["cache_dir"];
+    assert_eq!(cache_dir["type"], serde_json::json!(["string", "null"]));
+    assert_eq!(cache_dir["default"], serde_json::Value::Null);
+}
+
+#[test]
+fn checkers() {
+    let options = parse(&[
+        "--check",
+        "raw-ptr-bounds",
+        "--check",
+        "raw-offset-overflow",
+        "--check",
+        "panic-reachability",
+        "--check",
+        "shift-overflow",
+        "--check",
+        "shift-by-const",
+        "--check",
+        "shift-sign",
+        "--check",
+        "int-promotion",
+        "--check",
+        "dangling-pointer",
+        "--check",
+        "double-free",
+        "--check",
+        "use-of-uninitialized-global",
+        "--check",
+        "lock-order",
+        "--check",
+        "channel-capacity",
+        "--check",
+        "pin-safety",
+        "--check",
+        "vec-bounds",
+        "--check",
+        "str-index",
+        "--check",
+        "transmute-safety",
+        "--check",
+        "padding-uninit",
+        "--check",
+        "float-nan",
+        "--check",
+        "dead-branch",
+    ])
+    .0
+    .unwrap();
+    assert!(options.check_raw_ptr_bounds);
+    assert!(options.check_raw_offset_overflow);
+    assert!(options.check_panic_reachability);
+    assert!(options.check_shift_overflow);
+    assert!(options.check_shift_by_const);
+    assert!(options.check_shift_sign);
+    assert!(options.check_int_promotion);
+    assert!(options.check_dangling_pointer);
+    assert!(options.check_double_free);
+    assert!(options.check_use_of_uninitialized_global);
+    assert!(options.check_lock_order);
+    assert!(options.check_channel_capacity);
+    assert!(options.check_pin_safety);
+    assert!(options.check_vec_bounds);
+    assert!(options.check_str_index);
+    assert!(options.check_transmute_safety);
+    assert!(options.check_padding_uninit);
+    assert!(options.check_float_nan);
+    assert!(options.check_dead_branch);
+
+    let errors = errors(&["--check", "panic"]);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("`panic-reachability`"), "{}", errors[0]);
+}
+
+#[test]
+fn termination_checker() {
+    let options = parse(&[
+        "--check",
+        "termination",
+        "--domain",
+        "polyhedra",
+        "--termination-depth",
+        "2",
+    ])
+    .0
+    .unwrap();
+    assert!(options.check_termination);
+    assert_eq!(options.termination_depth, 2);
+
+    let interval_errors = errors(&["--check", "termination"]);
+    assert_eq!(interval_errors.len(), 1);
+    assert!(
+        interval_errors[0].contains("polyhedra domain"),
+        "{}",
+        interval_errors[0]
+    );
+
+    let depth_errors = errors(&["--termination-depth", "0"]);
+    assert_eq!(depth_errors.len(), 1);
+    assert!(
+        depth_errors[0].contains("positive integer"),
+        "{}",
+        depth_errors[0]
+    );
+}
+
+#[test]
+fn sqlite_output() {
+    let (result, rest) = parse(&[
+        "--crate-name",
+        "demo",
+        "--output-format",
+        "sqlite",
+        "--output-file",
+        "analysis.db",
+        "--output-crate-name",
+        "renamed",
+    ]);
+    let options = result.unwrap();
+    // `--crate-name` is left for rustc
+    assert_eq!(rest, vec!["--crate-name", "demo"]);
+    assert_eq!(options.output_format, OutputFormat::Sqlite);
+    assert_eq!(options.output_file.as_deref(), Some("analysis.db"));
+    assert_eq!(options.output_crate_name.as_deref(), Some("renamed"));
+
+    let errors = errors(&["--output-format", "sqlite"]);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("`--output-file`"), "{}", errors[0]);
+}
+
+#[test]
+fn missing_ffi_stubs() {
+    let stub_errors = errors(&["--ffi-stubs", "/nonexistent/ffi.toml"]);
+    assert_eq!(stub_errors.len(), 1);
+    assert!(
+        stub_errors[0].starts_with("invalid value `/nonexistent/ffi.toml` for `--ffi-stubs`"),
+        "{}",
+        stub_errors[0]
+    );
+}
+
+#[test]
+fn taint_analysis_type() {
+    let type_errors = errors(&["--analysis-type", "symbolic"]);
+    assert_eq!(type_errors.len(), 1);
+    assert!(
+        type_errors[0].contains("`numerical`, `taint`"),
+        "{}",
+        type_errors[0]
+    );
+
+    let config_errors = errors(&[
+        "--analysis-type",
+        "taint",
+        "--config",
+        "/nonexistent/mir-checker.toml",
+    ]);
+    assert_eq!(config_errors.len(), 1);
+    assert!(
+        config_errors[0].contains("`[taint]` section in `/nonexistent/mir-checker.toml`"),
+        "{}",
+        config_errors[0]
+    );
+
+    let options = parse(&["--config", "ci.toml"]).0.unwrap();
+    assert_eq!(options.analysis_type, AnalysisType::Numerical);
+    assert_eq!(options.config, "ci.toml");
+}
@@ -0,0 +1,96 @@
+// Precision regression tests with annotated programs
+//
+// Each program in `tests/annotated/` is analyzed by `mir-checker`, and its warnings are compared
+// with the annotations of its lines, see `tests/common/mod.rs` for the annotations and the options
+// of the programs. A warning on a line that does not expect its cause is a false positive, and an
+// expected cause without a warning is a false negative.
+//
+// The test fails if any mismatch is found:
+//
+//     cargo test --test annotated -- --nocapture
+
+mod common;
+
+use common::{collect_programs, corpus_dir, difference, parse_annotation, LineCauses};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+/// The mismatches between the warnings and the annotations of a program
+struct Mismatches {
+    name: String,
+    // The lines and causes of the unexpected warnings
+    false_positives: Vec<(usize, String)>,
+    // The lines and causes of the expected warnings that are not emitted
+    false_negatives: Vec<(usize, String)>,
+}
+
+/// The causes of the warnings emitted by the checker
+fn run_checker(program: &Path, out_dir: &Path) -> LineCauses {
+    let output = common::checker_command(program, out_dir)
+        .output()
+        .expect("Cannot run mir-checker");
+    common::reported_causes(&String::from_utf8_lossy(&output.stdout)).unwrap_or_else(|e| {
+        panic!(
+            "Cannot parse the report of {}: {}\n{}",
+            program.display(),
+            e,
+            String::from_utf8_lossy(&output.stderr)
+        )
+    })
+}
+
+fn compare(program: &Path, out_dir: &Path) -> Mismatches {
+    let expected = common::expected_causes(program);
+    let warned = run_checker(program, out_dir);
+    Mismatches {
+        name: program.file_name().unwrap().to_string_lossy().into_owned(),
+        false_positives: difference(&warned, &expected),
+        false_negatives: difference(&expected, &warned),
+    }
+}
+
+#[test]
+fn annotations_parse() {
+    assert_eq!(parse_annotation(" no-warning"), Ok(BTreeSet::new()));
+    assert_eq!(
+        parse_annotation("warning(DivZero, Arithmetic) "),
+        Ok(BTreeSet::from([
+            "DivZero".to_owned(),
+            "Arithmetic".to_owned()
+        ]))
+    );
+    assert!(parse_annotation("warning DivZero").is_err());
+}
+
+#[test]
+fn warnings_match_annotations() {
+    let out_dir = std::env::temp_dir().join("mir-checker-annotated");
+    fs::create_dir_all(&out_dir).expect("Cannot create the output directory");
+
+    let mut failed = vec![];
+    for program in collect_programs(&corpus_dir()) {
+        let mismatches = compare(&program, &out_dir);
+        for (line, cause) in &mismatches.false_positives {
+            eprintln!(
+                "{}:{}: false positive, unexpected warning({})",
+                mismatches.name, line, cause
+            );
+        }
+        for (line, cause) in &mismatches.false_negatives {
+            eprintln!(
+                "{}:{}: false negative, missing warning({})",
+                mismatches.name, line, cause
+            );
+        }
+        if !mismatches.false_positives.is_empty() || !mismatches.false_negatives.is_empty() {
+            failed.push(mismatches.name);
+        }
+    }
+
+    assert!(
+        failed.is_empty(),
+        "The warnings do not match the annotations in: {:?}",
+        failed
+    );
+}
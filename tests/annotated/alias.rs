@@ -0,0 +1,12 @@
+// Writes through a reference that may point to either of two variables
+
+fn main() {
+    let n = std::env::args().count();
+    let mut x: u32 = 1;
+    let mut y: u32 = 1;
+    let p = if n > 1 { &mut x } else { &mut y };
+    // Either `x` or `y` becomes 0, so both of them are in [0, 1] afterwards
+    *p = 0;
+    let _a = 100 / x; // EXPECTED: warning(DivZero)
+    let _b = 100 / y; // EXPECTED: warning(DivZero)
+}
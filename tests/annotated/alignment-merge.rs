@@ -0,0 +1,16 @@
+// Reads through a pointer that is aligned on one branch and misaligned on the other, the alignments of the two
+// branches are joined where they merge
+
+fn main() {
+    let words = [0u32; 4];
+    let bytes = [0u8; 16];
+    let n = std::env::args().count();
+    let aligned = words.as_ptr();
+    let p = if n > 1 {
+        unsafe { bytes.as_ptr().add(1) } as *const u32
+    } else {
+        aligned
+    };
+    let _a = unsafe { std::ptr::read(aligned) }; // EXPECTED: no-warning
+    let _b = unsafe { std::ptr::read(p) }; // EXPECTED: warning(Memory)
+}
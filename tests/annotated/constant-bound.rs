@@ -0,0 +1,12 @@
+// A loop with a constant bound, which every domain proves safe
+
+fn main() {
+    let a = [0u16; 16];
+    let mut sum: u32 = 0;
+    let mut i = 0;
+    while i < 16 {
+        sum += a[i] as u32; // EXPECTED: no-warning
+        i += 1;
+    }
+    let _avg = sum / 16;
+}
@@ -0,0 +1,15 @@
+// Two indices move towards each other, all the accesses are in bounds
+// OPTIONS: --domain polyhedra
+
+fn main() {
+    let v = [1u32; 10];
+    let mut i: usize = 0;
+    let mut j: usize = 9;
+    let mut sum = 0;
+    while i < j {
+        sum += v[i] + v[j]; // EXPECTED: no-warning
+        i += 1;
+        j -= 1;
+    }
+    let _avg = sum / 10;
+}
@@ -0,0 +1,19 @@
+// Divisions by a loop counter, which is zero on some paths
+
+fn divide(n: u32) -> u32 {
+    let mut d: u32 = 0;
+    while d < n {
+        d += 1;
+    }
+    // Here, d == n, which may be zero
+    100 / d // EXPECTED: warning(DivZero)
+}
+
+fn main() {
+    let mut i: u32 = 1;
+    while i < 10 {
+        let _q = 100 / i; // EXPECTED: no-warning
+        i += 1;
+    }
+    let _r = divide(0);
+}
@@ -0,0 +1,16 @@
+// Accesses to an array with a loop counter
+
+fn main() {
+    let a = [1u32; 8];
+    let mut sum: u32 = 0;
+    let mut i = 0;
+    while i < 8 {
+        sum += a[i]; // EXPECTED: no-warning
+        i += 1;
+    }
+    // Here, i == 8
+
+    let _last = a[i - 1]; // EXPECTED: no-warning
+    let _past = a[i]; // EXPECTED: warning(Index)
+    let _avg = sum / 8;
+}
@@ -0,0 +1,14 @@
+// `x == 2 * y` after the loop, which is only represented by polyhedra, so the division is safe
+// OPTIONS: --domain polyhedra
+
+fn main() {
+    let mut x: i32 = 0;
+    let mut y: i32 = 0;
+    while y < 30 {
+        x += 2;
+        y += 1;
+    }
+    // Here, x == 2 * y
+
+    let _q = 1000 / (x - 2 * y + 1); // EXPECTED: no-warning
+}
@@ -0,0 +1,14 @@
+// `x - y` is always 1, the division is safe but proving it needs a relation between `x` and `y`
+// OPTIONS: --domain polyhedra
+
+fn main() {
+    let mut x: i32 = 1;
+    let mut y: i32 = 0;
+    while y < 50 {
+        x += 1;
+        y += 1;
+    }
+    // Here, x == y + 1
+
+    let _q = 100 / (x - y); // EXPECTED: no-warning
+}
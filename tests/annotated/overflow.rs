@@ -0,0 +1,12 @@
+// Additions after loops, one of them overflows
+
+fn main() {
+    let mut t: u8 = 0;
+    while t < 100 {
+        t += 1; // EXPECTED: no-warning
+    }
+    // Here, t == 100
+
+    let _safe = t + 100; // EXPECTED: no-warning
+    let _overflow = t + 200; // EXPECTED: warning(Arithmetic)
+}
@@ -0,0 +1,11 @@
+// A raw pointer access in a basic block that always panics at its end is still checked
+// OPTIONS: --check raw-ptr-bounds
+
+fn main() {
+    let mut array: [u8; 4] = [1, 2, 3, 4];
+    let p = array.as_mut_ptr();
+    let none: Option<u8> = None;
+    let q = unsafe { p.offset(4) };
+    let _x = unsafe { *q }; // EXPECTED: warning(Memory)
+    let _y = none.unwrap(); // EXPECTED: warning(Panic)
+}
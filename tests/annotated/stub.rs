@@ -0,0 +1,14 @@
+// Calls of standard library functions that are handled by their stubs, some of them overflow or divide by zero
+
+fn main() {
+    let n = std::env::args().count() as u32;
+
+    let small = n.min(15) as u8;
+    let _square = small.pow(2); // EXPECTED: no-warning
+    let large = n.min(20) as u8;
+    let _overflow = large.pow(2); // EXPECTED: warning(Arithmetic)
+
+    let d = n.min(4);
+    let _q = 100u32.div_euclid(d.max(1)); // EXPECTED: no-warning
+    let _r = 100u32.rem_euclid(d); // EXPECTED: warning(DivZero)
+}
@@ -0,0 +1,12 @@
+// An access one past the end of an array
+
+fn main() {
+    let a = [0u8; 8];
+    let mut i = 0;
+    while i < 8 {
+        i += 1;
+    }
+    // Here, i == 8
+
+    let _x = a[i]; // EXPECTED: warning(Index)
+}
@@ -0,0 +1,11 @@
+// An unsigned addition that overflows after a loop
+
+fn main() {
+    let mut t: u8 = 0;
+    while t < 200 {
+        t += 1;
+    }
+    // Here, t == 200
+
+    let _c = t + 100; // EXPECTED: warning(Arithmetic)
+}
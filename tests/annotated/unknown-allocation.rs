@@ -0,0 +1,16 @@
+// Dereferences of raw pointers into a slice whose length is not a constant, which are reported as possible errors
+// unless the offset is negative
+// OPTIONS: --check raw-ptr-bounds
+
+fn read(slice: &[u8]) -> u8 {
+    let p = slice.as_ptr();
+    let second = unsafe { *p.add(1) }; // EXPECTED: warning(Memory)
+    let before = unsafe { *p.offset(-1) }; // EXPECTED: warning(Memory)
+    second ^ before
+}
+
+fn main() {
+    let array = [1u8; 8];
+    let n = std::env::args().count().min(8);
+    read(&array[..n]);
+}
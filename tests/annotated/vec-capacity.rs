@@ -0,0 +1,19 @@
+// Vectors passed by mutable references to calls that are not modeled, whose capacities and lengths are no longer
+// known, and the items written by `push`
+// OPTIONS: --check vec-bounds
+
+fn main() {
+    let mut v: Vec<u8> = Vec::with_capacity(8);
+    unsafe { v.set_len(4) }; // EXPECTED: no-warning
+    v.shrink_to_fit();
+    // The buffer may have shrunk to 4 items
+    unsafe { v.set_len(8) }; // EXPECTED: warning(Index)
+
+    let mut w: Vec<u8> = Vec::with_capacity(4);
+    w.push(1);
+    w.push(2);
+    let _first = unsafe { *w.get_unchecked(0) } + 10; // EXPECTED: no-warning
+    w.truncate(1);
+    // The length is no longer known to be 2
+    let _second = unsafe { *w.get_unchecked(1) }; // EXPECTED: warning(Index)
+}
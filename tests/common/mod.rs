@@ -0,0 +1,122 @@
+// The annotated programs in `tests/annotated/`, shared by `tests/annotated.rs` and `benches/domain_precision.rs`
+//
+// A line of a program is annotated with the causes of the warnings it expects, which are the names of
+// `DiagnosticCause`, and the lines without annotations expect no warnings:
+//
+//     let _q = 100 / d; // EXPECTED: warning(DivZero)
+//     let _r = 100 / i; // EXPECTED: no-warning
+//
+// The options of the checker that a program needs are given by a line of the program:
+//
+//     // OPTIONS: --check raw-ptr-bounds
+
+use rust_mir_checker::analysis::diagnostic_diff;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const ANNOTATION: &str = "// EXPECTED:";
+
+const OPTIONS: &str = "// OPTIONS:";
+
+/// The causes of the warnings of each line, only the lines that have warnings are present
+pub type LineCauses = BTreeMap<usize, BTreeSet<String>>;
+
+pub fn corpus_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/annotated")
+}
+
+pub fn collect_programs(dir: &Path) -> Vec<PathBuf> {
+    let mut programs: Vec<_> = fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("Cannot read {}: {}", dir.display(), e))
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().map_or(false, |ext| ext == "rs"))
+        .collect();
+    programs.sort();
+    programs
+}
+
+/// Parse an annotation like `no-warning` or `warning(DivZero, Arithmetic)` into the expected causes
+pub fn parse_annotation(annotation: &str) -> Result<BTreeSet<String>, String> {
+    let annotation = annotation.trim();
+    if annotation == "no-warning" {
+        return Ok(BTreeSet::new());
+    }
+    annotation
+        .strip_prefix("warning(")
+        .and_then(|causes| causes.strip_suffix(')'))
+        .map(|causes| {
+            causes
+                .split(',')
+                .map(|cause| cause.trim().to_owned())
+                .collect()
+        })
+        .ok_or_else(|| format!("invalid annotation `{}`", annotation))
+}
+
+/// The expected causes of the annotated lines, the lines without annotations expect no warnings
+pub fn expected_causes(program: &Path) -> LineCauses {
+    let source = fs::read_to_string(program)
+        .unwrap_or_else(|e| panic!("Cannot read {}: {}", program.display(), e));
+    let mut expected = LineCauses::new();
+    for (index, line) in source.lines().enumerate() {
+        if let Some((_, annotation)) = line.split_once(ANNOTATION) {
+            let causes = parse_annotation(annotation)
+                .unwrap_or_else(|e| panic!("{}:{}: {}", program.display(), index + 1, e));
+            if !causes.is_empty() {
+                expected.insert(index + 1, causes);
+            }
+        }
+    }
+    expected
+}
+
+/// The options given by the `// OPTIONS:` lines of the program
+fn program_options(program: &Path) -> Vec<String> {
+    let source = fs::read_to_string(program)
+        .unwrap_or_else(|e| panic!("Cannot read {}: {}", program.display(), e));
+    source
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix(OPTIONS))
+        .flat_map(|options| options.split_whitespace().map(str::to_owned))
+        .collect()
+}
+
+/// The command that analyzes `program` with the interval domain and its own options, and prints the report of
+/// `--output-format gitlab` to stdout
+/// The last `--domain` takes effect, so an argument added to the command overrides the domain of the program
+pub fn checker_command(program: &Path, out_dir: &Path) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_mir-checker"));
+    command
+        .arg(program)
+        .args(["--crate-type", "bin", "--edition", "2021", "--out-dir"])
+        .arg(out_dir)
+        .args(["--entry", "main", "--domain", "interval"])
+        .args(["--widening_delay", "5", "--narrowing_iteration", "5"])
+        .args(["--output-format", "gitlab"])
+        .args(program_options(program));
+    command
+}
+
+/// The causes of the warnings in the report printed by `checker_command`
+pub fn reported_causes(report: &str) -> Result<LineCauses, String> {
+    let mut warned = LineCauses::new();
+    for entry in diagnostic_diff::parse_report(report)? {
+        warned.entry(entry.line).or_default().insert(entry.cause);
+    }
+    Ok(warned)
+}
+
+/// The `(line, cause)` pairs of `left` that are not in `right`
+pub fn difference(left: &LineCauses, right: &LineCauses) -> Vec<(usize, String)> {
+    let mut result = vec![];
+    for (line, causes) in left {
+        for cause in causes {
+            if !right.get(line).map_or(false, |other| other.contains(cause)) {
+                result.push((*line, cause.clone()));
+            }
+        }
+    }
+    result
+}
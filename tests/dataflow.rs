@@ -0,0 +1,57 @@
+// Tests for the queries of the data flow graphs built by `--dump-dataflow`
+//
+//     cargo test --test dataflow
+
+use rust_mir_checker::analysis::dataflow::DataFlowGraph;
+use rust_mir_checker::analysis::memory::path::Path;
+
+#[test]
+fn edges_are_queried_in_both_directions() {
+    let (a, b, c) = (
+        Path::new_parameter(1, 0),
+        Path::new_local(2, 0),
+        Path::new_result(),
+    );
+    let mut graph = DataFlowGraph::default();
+    graph.add_edge(a.clone(), b.clone());
+    graph.add_edge(b.clone(), c.clone());
+    graph.add_edge(a.clone(), b.clone());
+
+    assert_eq!(graph.nodes().count(), 3);
+    assert_eq!(graph.edge_count(), 2);
+    assert_eq!(graph.successors(&a).collect::<Vec<_>>(), vec![&b]);
+    assert_eq!(graph.predecessors(&c).collect::<Vec<_>>(), vec![&b]);
+    assert_eq!(graph.predecessors(&a).count(), 0);
+}
+
+#[test]
+fn flows_are_transitive() {
+    let (a, b, c) = (
+        Path::new_parameter(1, 0),
+        Path::new_local(2, 0),
+        Path::new_result(),
+    );
+    let field = Path::new_field(Path::new_deref(a.clone()), 0);
+    let mut graph = DataFlowGraph::default();
+    graph.add_edge(a.clone(), b.clone());
+    graph.add_edge(b.clone(), c.clone());
+    // A cycle does not stop the search
+    graph.add_edge(b.clone(), a.clone());
+
+    assert!(graph.flows_to(&a, &c));
+    assert!(graph.flows_to(&a, &a));
+    assert!(!graph.flows_to(&c, &a));
+    assert!(!graph.flows_to(&field, &c));
+}
+
+#[test]
+fn dot_output_is_deterministic() {
+    let mut graph = DataFlowGraph::default();
+    graph.add_edge(Path::new_local(3, 0), Path::new_result());
+    graph.add_edge(Path::new_local(2, 0), Path::new_result());
+    let dot = graph.to_dot("foo::\"bar\"", "f0");
+    assert_eq!(dot, graph.clone().to_dot("foo::\"bar\"", "f0"));
+    assert!(dot.starts_with("    subgraph \"cluster_f0\" {\n        label=\"foo::\\\"bar\\\"\";\n"));
+    assert_eq!(dot.matches(" -> ").count(), 2);
+    assert_eq!(dot.matches("[label=").count(), 3);
+}
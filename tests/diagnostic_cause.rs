@@ -0,0 +1,131 @@
+// Snapshot tests for the classification of `AssertKind` into `DiagnosticCause`
+//
+// The snapshot lists every `AssertKind` variant (and every `BinOp` for overflow assertions)
+// together with the cause it is mapped to. When rustc adds a new variant, `variant_name`
+// stops compiling, and when the classification changes, the snapshot comparison fails.
+//
+//     cargo test --test diagnostic_cause
+
+#![feature(rustc_private)]
+
+extern crate rustc_hir;
+extern crate rustc_middle;
+
+use rust_mir_checker::analysis::diagnostics::DiagnosticCause;
+use rustc_hir::{CoroutineKind, Movability};
+use rustc_middle::mir::{AssertKind, BinOp};
+
+/// Every binary operator that may appear in `AssertKind::Overflow`
+const BIN_OPS: [BinOp; 23] = [
+    BinOp::Add,
+    BinOp::AddUnchecked,
+    BinOp::Sub,
+    BinOp::SubUnchecked,
+    BinOp::Mul,
+    BinOp::MulUnchecked,
+    BinOp::Div,
+    BinOp::Rem,
+    BinOp::BitXor,
+    BinOp::BitAnd,
+    BinOp::BitOr,
+    BinOp::Shl,
+    BinOp::ShlUnchecked,
+    BinOp::Shr,
+    BinOp::ShrUnchecked,
+    BinOp::Eq,
+    BinOp::Lt,
+    BinOp::Le,
+    BinOp::Ne,
+    BinOp::Ge,
+    BinOp::Gt,
+    BinOp::Cmp,
+    BinOp::Offset,
+];
+
+const EXPECTED: &str = "\
+BoundsCheck => Index
+Overflow(Add) => Arithmetic
+Overflow(AddUnchecked) => Arithmetic
+Overflow(Sub) => Arithmetic
+Overflow(SubUnchecked) => Arithmetic
+Overflow(Mul) => Arithmetic
+Overflow(MulUnchecked) => Arithmetic
+Overflow(Div) => Arithmetic
+Overflow(Rem) => Arithmetic
+Overflow(BitXor) => Bitwise
+Overflow(BitAnd) => Bitwise
+Overflow(BitOr) => Bitwise
+Overflow(Shl) => Bitwise
+Overflow(ShlUnchecked) => Bitwise
+Overflow(Shr) => Bitwise
+Overflow(ShrUnchecked) => Bitwise
+Overflow(Eq) => Comparison
+Overflow(Lt) => Comparison
+Overflow(Le) => Comparison
+Overflow(Ne) => Comparison
+Overflow(Ge) => Comparison
+Overflow(Gt) => Comparison
+Overflow(Cmp) => Comparison
+Overflow(Offset) => Index
+OverflowNeg => Arithmetic
+DivisionByZero => DivZero
+RemainderByZero => DivZero
+ResumedAfterReturn => Other
+ResumedAfterPanic => Other
+MisalignedPointerDereference => Other
+";
+
+/// The name of an assertion kind, without a wildcard arm so that new variants must be listed here
+fn variant_name<O>(assert_kind: &AssertKind<O>) -> String {
+    match assert_kind {
+        AssertKind::BoundsCheck { .. } => "BoundsCheck".to_owned(),
+        AssertKind::Overflow(bin_op, ..) => format!("Overflow({:?})", bin_op),
+        AssertKind::OverflowNeg(..) => "OverflowNeg".to_owned(),
+        AssertKind::DivisionByZero(..) => "DivisionByZero".to_owned(),
+        AssertKind::RemainderByZero(..) => "RemainderByZero".to_owned(),
+        AssertKind::ResumedAfterReturn(..) => "ResumedAfterReturn".to_owned(),
+        AssertKind::ResumedAfterPanic(..) => "ResumedAfterPanic".to_owned(),
+        AssertKind::MisalignedPointerDereference { .. } => {
+            "MisalignedPointerDereference".to_owned()
+        }
+    }
+}
+
+/// Mock assertions for every variant, using `u8` as operands
+fn all_assert_kinds() -> Vec<AssertKind<u8>> {
+    let coroutine = CoroutineKind::Coroutine(Movability::Movable);
+    let mut kinds = vec![AssertKind::BoundsCheck { len: 1, index: 2 }];
+    kinds.extend(
+        BIN_OPS
+            .iter()
+            .map(|bin_op| AssertKind::Overflow(*bin_op, 1, 2)),
+    );
+    kinds.push(AssertKind::OverflowNeg(1));
+    kinds.push(AssertKind::DivisionByZero(1));
+    kinds.push(AssertKind::RemainderByZero(1));
+    kinds.push(AssertKind::ResumedAfterReturn(coroutine));
+    kinds.push(AssertKind::ResumedAfterPanic(coroutine));
+    kinds.push(AssertKind::MisalignedPointerDereference {
+        required: 1,
+        found: 2,
+    });
+    kinds
+}
+
+#[test]
+fn diagnostic_cause_snapshot() {
+    let snapshot: String = all_assert_kinds()
+        .iter()
+        .map(|kind| {
+            format!(
+                "{} => {:?}\n",
+                variant_name(kind),
+                DiagnosticCause::from(kind)
+            )
+        })
+        .collect();
+    assert_eq!(
+        snapshot, EXPECTED,
+        "The classification of `AssertKind` has changed, update the snapshot if this is intended"
+    );
+}
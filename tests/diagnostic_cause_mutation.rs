@@ -0,0 +1,154 @@
+// The specification of the classification of `AssertKind` into `DiagnosticCause`
+//
+// Each arm of `DiagnosticCause::from` is checked by its own assertions with MIR operands, so that
+// every mutation of the match, e.g., moving a `BinOp` to another arm or replacing the returned
+// cause, fails at least one test:
+//
+//     cargo test --test diagnostic_cause_mutation
+//     cargo mutants --file src/analysis/diagnostics.rs -- --test diagnostic_cause_mutation
+
+#![feature(rustc_private)]
+
+extern crate rustc_hir;
+extern crate rustc_middle;
+
+use rust_mir_checker::analysis::diagnostics::DiagnosticCause;
+use rustc_hir::{CoroutineDesugaring, CoroutineKind, CoroutineSource, Movability};
+use rustc_middle::mir::{AssertKind, BinOp, Local, Operand, Place};
+
+/// A mock operand that copies the local `_n`
+fn operand<'tcx>(n: u32) -> Operand<'tcx> {
+    Operand::Copy(Place::from(Local::from_u32(n)))
+}
+
+fn overflow_cause(bin_op: BinOp) -> DiagnosticCause {
+    DiagnosticCause::from(&AssertKind::Overflow(bin_op, operand(1), operand(2)))
+}
+
+fn assert_overflow_causes(bin_ops: &[BinOp], expected: DiagnosticCause) {
+    for bin_op in bin_ops {
+        assert_eq!(
+            overflow_cause(*bin_op),
+            expected,
+            "`Overflow({:?})` is misclassified",
+            bin_op
+        );
+    }
+}
+
+#[test]
+fn bounds_check_is_index() {
+    let assert_kind = AssertKind::BoundsCheck {
+        len: operand(1),
+        index: operand(2),
+    };
+    assert_eq!(DiagnosticCause::from(&assert_kind), DiagnosticCause::Index);
+}
+
+#[test]
+fn arithmetic_overflow_is_arithmetic() {
+    assert_overflow_causes(
+        &[
+            BinOp::Add,
+            BinOp::AddUnchecked,
+            BinOp::Sub,
+            BinOp::SubUnchecked,
+            BinOp::Mul,
+            BinOp::MulUnchecked,
+            BinOp::Div,
+            BinOp::Rem,
+        ],
+        DiagnosticCause::Arithmetic,
+    );
+}
+
+#[test]
+fn bitwise_overflow_is_bitwise() {
+    assert_overflow_causes(
+        &[
+            BinOp::BitXor,
+            BinOp::BitAnd,
+            BinOp::BitOr,
+            BinOp::Shl,
+            BinOp::ShlUnchecked,
+            BinOp::Shr,
+            BinOp::ShrUnchecked,
+        ],
+        DiagnosticCause::Bitwise,
+    );
+}
+
+#[test]
+fn comparison_overflow_is_comparison() {
+    assert_overflow_causes(
+        &[
+            BinOp::Eq,
+            BinOp::Lt,
+            BinOp::Le,
+            BinOp::Ne,
+            BinOp::Ge,
+            BinOp::Gt,
+            BinOp::Cmp,
+        ],
+        DiagnosticCause::Comparison,
+    );
+}
+
+#[test]
+fn offset_overflow_is_index() {
+    assert_eq!(overflow_cause(BinOp::Offset), DiagnosticCause::Index);
+}
+
+#[test]
+fn negation_overflow_is_arithmetic() {
+    assert_eq!(
+        DiagnosticCause::from(&AssertKind::OverflowNeg(operand(1))),
+        DiagnosticCause::Arithmetic
+    );
+}
+
+#[test]
+fn division_by_zero_is_div_zero() {
+    assert_eq!(
+        DiagnosticCause::from(&AssertKind::DivisionByZero(operand(1))),
+        DiagnosticCause::DivZero
+    );
+}
+
+#[test]
+fn remainder_by_zero_is_div_zero() {
+    assert_eq!(
+        DiagnosticCause::from(&AssertKind::RemainderByZero(operand(1))),
+        DiagnosticCause::DivZero
+    );
+}
+
+#[test]
+fn resumed_coroutines_are_other() {
+    // The cause does not depend on the kind of the coroutine
+    let coroutines = [
+        CoroutineKind::Coroutine(Movability::Movable),
+        CoroutineKind::Coroutine(Movability::Static),
+        CoroutineKind::Desugared(CoroutineDesugaring::Async, CoroutineSource::Fn),
+        CoroutineKind::Desugared(CoroutineDesugaring::Gen, CoroutineSource::Block),
+    ];
+    for coroutine in coroutines {
+        assert_eq!(
+            DiagnosticCause::from(&AssertKind::<Operand<'_>>::ResumedAfterReturn(coroutine)),
+            DiagnosticCause::Other
+        );
+        assert_eq!(
+            DiagnosticCause::from(&AssertKind::<Operand<'_>>::ResumedAfterPanic(coroutine)),
+            DiagnosticCause::Other
+        );
+    }
+}
+
+#[test]
+fn misaligned_pointer_dereference_is_other() {
+    let assert_kind = AssertKind::MisalignedPointerDereference {
+        required: operand(1),
+        found: operand(2),
+    };
+    assert_eq!(DiagnosticCause::from(&assert_kind), DiagnosticCause::Other);
+}
@@ -0,0 +1,104 @@
+// Tests for comparing the diagnostics of two runs with `mir-checker-diff`
+//
+//     cargo test --test diagnostic_diff
+
+use rust_mir_checker::analysis::diagnostic_diff::{diff, parse_report, DiagnosticEntry};
+
+fn entry(file: &str, line: usize, cause: &str) -> DiagnosticEntry {
+    DiagnosticEntry {
+        file: file.to_string(),
+        line,
+        cause: cause.to_string(),
+        message: format!("[MirChecker] Possible error at line {}", line),
+    }
+}
+
+#[test]
+fn report_is_parsed() {
+    let entries = parse_report(
+        r#"[
+            {
+                "description": "[MirChecker] Possible error: index out of bound",
+                "check_name": "Index",
+                "fingerprint": "0123456789abcdef",
+                "severity": "minor",
+                "location": { "path": "src/main.rs", "lines": { "begin": 3 } }
+            }
+        ]"#,
+    )
+    .unwrap();
+    assert_eq!(
+        entries,
+        vec![DiagnosticEntry {
+            file: "src/main.rs".to_string(),
+            line: 3,
+            cause: "Index".to_string(),
+            message: "[MirChecker] Possible error: index out of bound".to_string(),
+        }]
+    );
+
+    assert!(parse_report("{}").is_err());
+    let missing_line = parse_report(
+        r#"[{ "description": "", "check_name": "Index", "location": { "path": "src/main.rs" } }]"#,
+    );
+    assert_eq!(
+        missing_line,
+        Err("diagnostic 0 has no `location.lines.begin`".to_string())
+    );
+}
+
+#[test]
+fn new_and_fixed_diagnostics() {
+    let old = vec![
+        entry("src/main.rs", 10, "Index"),
+        entry("src/main.rs", 20, "Arithmetic"),
+    ];
+    let new = vec![
+        // Moved by two lines
+        entry("src/main.rs", 12, "Index"),
+        // Moved by three lines, i.e., a different diagnostic
+        entry("src/main.rs", 23, "Arithmetic"),
+        // Another cause at the same line
+        entry("src/main.rs", 12, "DivZero"),
+    ];
+    let result = diff(&old, &new);
+    assert_eq!(result.unchanged, vec![(old[0].clone(), new[0].clone())]);
+    assert_eq!(result.new, vec![new[1].clone(), new[2].clone()]);
+    assert_eq!(result.fixed, vec![old[1].clone()]);
+}
+
+#[test]
+fn closest_diagnostics_are_matched_first() {
+    let old = vec![
+        entry("src/lib.rs", 5, "Index"),
+        entry("src/lib.rs", 7, "Index"),
+    ];
+    // The diagnostic at line 7 did not move, so the one at line 6 is matched with the one at line 5, although it is
+    // as close to line 7
+    let new = vec![
+        entry("src/lib.rs", 6, "Index"),
+        entry("src/lib.rs", 7, "Index"),
+    ];
+    let result = diff(&old, &new);
+    assert!(result.new.is_empty());
+    assert!(result.fixed.is_empty());
+    assert_eq!(result.unchanged.len(), 2);
+    assert!(result.unchanged.contains(&(old[0].clone(), new[0].clone())));
+    assert!(result.unchanged.contains(&(old[1].clone(), new[1].clone())));
+
+    let new = vec![entry("src/lib.rs", 7, "Index")];
+    let result = diff(&old, &new);
+    assert_eq!(result.unchanged, vec![(old[1].clone(), new[0].clone())]);
+    assert_eq!(result.fixed, vec![old[0].clone()]);
+    assert!(result.new.is_empty());
+}
+
+#[test]
+fn files_must_match() {
+    let old = vec![entry("src/a.rs", 1, "Memory")];
+    let new = vec![entry("src/b.rs", 1, "Memory")];
+    let result = diff(&old, &new);
+    assert_eq!(result.new, new);
+    assert_eq!(result.fixed, old);
+    assert!(result.unchanged.is_empty());
+}
@@ -0,0 +1,60 @@
+// Tests for the serialization of the diagnoses rendered by `Diagnostic::pretty_print`
+//
+//     cargo test --test diagnostic_repr
+
+use rust_mir_checker::analysis::diagnostics::{DiagnosticCause, DiagnosticRepr};
+
+fn repr(message: &str, cause: DiagnosticCause) -> DiagnosticRepr {
+    DiagnosticRepr {
+        message: message.to_string(),
+        file: String::from("src/main.rs"),
+        line: 3,
+        col: 5,
+        severity: String::from("warning"),
+        cause,
+    }
+}
+
+#[test]
+fn repr_is_serialized() {
+    let json = repr(
+        "[MirChecker] Possible error: index out of bound",
+        DiagnosticCause::Index,
+    )
+    .to_json();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "message": "[MirChecker] Possible error: index out of bound",
+            "file": "src/main.rs",
+            "line": 3,
+            "col": 5,
+            "severity": "warning",
+            "cause": "Index",
+        })
+    );
+}
+
+#[test]
+fn special_characters_are_escaped() {
+    let json = repr("expected `\"a\"`\nfound `\\`", DiagnosticCause::Other).to_json();
+    let text = serde_json::to_string(&json).unwrap();
+    assert!(
+        text.contains(r#""expected `\"a\"`\nfound `\\`""#),
+        "{}",
+        text
+    );
+    let parsed: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(parsed, json);
+}
+
+#[test]
+fn custom_cause_is_serialized_as_its_name() {
+    let json = repr(
+        "[MirChecker] Possible error: unchecked FFI call",
+        DiagnosticCause::Custom(String::from("unsafe-ffi")),
+    )
+    .to_json();
+    assert_eq!(json["cause"], "unsafe-ffi");
+    assert_eq!(DiagnosticCause::DivZero.to_string(), "DivZero");
+}
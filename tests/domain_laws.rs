@@ -0,0 +1,260 @@
+// Property-based tests for the lattice laws of `ApronAbstractDomain`, and for the agreement between
+// `SparseApronDomain` and the Apron domain it wraps
+//
+// Apron uses a single global manager, so one process can only test one abstract domain.
+// Set `MIR_CHECKER_TEST_DOMAIN` to choose the domain, e.g.:
+//
+//     MIR_CHECKER_TEST_DOMAIN=interval cargo test --test domain_laws
+//     MIR_CHECKER_TEST_DOMAIN=octagon cargo test --test domain_laws
+//     MIR_CHECKER_TEST_DOMAIN=polyhedra cargo test --test domain_laws
+
+use proptest::prelude::*;
+use proptest::test_runner::{Config, TestCaseError, TestRunner};
+use rug::Integer;
+use rust_mir_checker::analysis::memory::path::Path;
+use rust_mir_checker::analysis::numerical::apron_domain::{
+    ApronAbstractDomain, ApronDomainType, ApronInterval, ApronOctagon, ApronPolyhedra,
+    GetManagerTrait,
+};
+use rust_mir_checker::analysis::numerical::lattice::LatticeTrait;
+use rust_mir_checker::analysis::numerical::linear_constraint::{
+    LinearConstraint, LinearConstraintSystem, LinearExpression,
+};
+use rust_mir_checker::analysis::numerical::sparse_domain::SparseApronDomain;
+
+/// The number of variables in each generated element
+const NUM_VARS: usize = 3;
+
+/// A random element, described by the bounds of each variable and some relational constraints
+/// Each relational constraint `(coeffs, cst)` represents `coeffs[0]*x0 + coeffs[1]*x1 + coeffs[2]*x2 + cst <= 0`
+#[derive(Clone, Debug)]
+struct Element {
+    bounds: Vec<(i64, i64)>,
+    relations: Vec<(Vec<i64>, i64)>,
+}
+
+fn element_strategy() -> impl Strategy<Value = Element> {
+    let bounds = prop::collection::vec((-50i64..50, 0i64..100), NUM_VARS)
+        .prop_map(|v| v.into_iter().map(|(low, width)| (low, low + width)).collect());
+    let relations = prop::collection::vec(
+        (prop::collection::vec(-2i64..=2, NUM_VARS), -50i64..50),
+        0..3,
+    );
+    (bounds, relations).prop_map(|(bounds, relations)| Element { bounds, relations })
+}
+
+/// Build an abstract domain from the description of an element
+/// Bounds are added first, so that the variables always have the same dimensions in all elements
+fn build<Type>(element: &Element) -> ApronAbstractDomain<Type>
+where
+    Type: ApronDomainType,
+    ApronAbstractDomain<Type>: GetManagerTrait,
+{
+    let mut domain = ApronAbstractDomain::<Type>::default();
+    domain.add_constraints(constraints(element));
+    domain
+}
+
+fn constraints(element: &Element) -> LinearConstraintSystem {
+    let vars: Vec<_> = (1..=NUM_VARS).map(|i| Path::new_local(i, 0)).collect();
+    let mut csts = LinearConstraintSystem::default();
+    for (var, (low, high)) in vars.iter().zip(element.bounds.iter()) {
+        // x - high <= 0
+        let mut upper = LinearExpression::from(Integer::from(-high));
+        upper.add_term(var.clone(), Integer::from(1));
+        csts.add(LinearConstraint::LessEq(upper));
+        // low - x <= 0
+        let mut lower = LinearExpression::from(Integer::from(*low));
+        lower.add_term(var.clone(), Integer::from(-1));
+        csts.add(LinearConstraint::LessEq(lower));
+    }
+    for (coeffs, cst) in &element.relations {
+        let mut expr = LinearExpression::from(Integer::from(*cst));
+        for (var, coeff) in vars.iter().zip(coeffs.iter()) {
+            if *coeff != 0 {
+                expr.add_term(var.clone(), Integer::from(*coeff));
+            }
+        }
+        csts.add(LinearConstraint::LessEq(expr));
+    }
+    csts
+}
+
+/// An element where some variables are then assigned constants, as most variables are in real programs
+#[derive(Clone, Debug)]
+struct SparseElement {
+    element: Element,
+    constants: Vec<Option<i64>>,
+}
+
+fn sparse_element_strategy() -> impl Strategy<Value = SparseElement> {
+    // Constants are chosen from a small range, so that both operands often agree on them
+    let constants = prop::collection::vec(prop::option::of(-2i64..=2), NUM_VARS);
+    (element_strategy(), constants)
+        .prop_map(|(element, constants)| SparseElement { element, constants })
+}
+
+/// Build the same element as both an Apron domain and a sparse domain
+fn build_both<Type>(element: &SparseElement) -> (ApronAbstractDomain<Type>, SparseApronDomain<Type>)
+where
+    Type: ApronDomainType,
+    ApronAbstractDomain<Type>: GetManagerTrait,
+{
+    let mut dense = build::<Type>(&element.element);
+    let mut sparse = SparseApronDomain::<Type>::default();
+    sparse.add_constraints(constraints(&element.element));
+    for (i, constant) in element.constants.iter().enumerate() {
+        if let Some(n) = constant {
+            let var = Path::new_local(i + 1, 0);
+            dense.assign_int(var.clone(), Integer::from(*n));
+            sparse.assign_int(var, Integer::from(*n));
+        }
+    }
+    (dense, sparse)
+}
+
+fn check_agreement<Type>(a: &SparseElement, b: &SparseElement) -> Result<(), TestCaseError>
+where
+    Type: ApronDomainType,
+    ApronAbstractDomain<Type>: GetManagerTrait,
+{
+    let (dense_a, sparse_a) = build_both::<Type>(a);
+    let (dense_b, sparse_b) = build_both::<Type>(b);
+    let dense_join = dense_a.join(&dense_b);
+    let sparse_join = sparse_a.join(&sparse_b);
+    let dense_meet = dense_a.meet(&dense_b);
+    let sparse_meet = sparse_a.meet(&sparse_b);
+    prop_assert_eq!(dense_meet.is_bottom(), sparse_meet.is_bottom());
+    for var in (1..=NUM_VARS).map(|i| Path::new_local(i, 0)) {
+        prop_assert_eq!(
+            dense_join.get_interval(&var),
+            sparse_join.get_interval(&var),
+            "join disagrees on {:?}",
+            var
+        );
+        if !dense_meet.is_bottom() {
+            prop_assert_eq!(
+                dense_meet.get_interval(&var),
+                sparse_meet.get_interval(&var),
+                "meet disagrees on {:?}",
+                var
+            );
+        }
+    }
+    prop_assert!(
+        sparse_join.leq(&sparse_a.widening_with(&sparse_join)),
+        "a.join(b) is not below a.widen(a.join(b))"
+    );
+    Ok(())
+}
+
+fn equal<Type>(a: &ApronAbstractDomain<Type>, b: &ApronAbstractDomain<Type>) -> bool
+where
+    Type: ApronDomainType,
+    ApronAbstractDomain<Type>: GetManagerTrait,
+{
+    a.leq(b) && b.leq(a)
+}
+
+fn check_laws<Type>(
+    a: &ApronAbstractDomain<Type>,
+    b: &ApronAbstractDomain<Type>,
+    c: &ApronAbstractDomain<Type>,
+) -> Result<(), TestCaseError>
+where
+    Type: ApronDomainType,
+    ApronAbstractDomain<Type>: GetManagerTrait,
+{
+    let ab = a.join(b);
+
+    // Join is commutative, associative and idempotent
+    prop_assert!(equal(&ab, &b.join(a)), "join is not commutative");
+    prop_assert!(
+        equal(&ab.join(c), &a.join(&b.join(c))),
+        "join is not associative"
+    );
+    prop_assert!(equal(&a.join(a), a), "join is not idempotent");
+
+    // Join is an upper bound
+    prop_assert!(a.leq(&ab), "a is not below a.join(b)");
+    prop_assert!(b.leq(&ab), "b is not below a.join(b)");
+
+    // Meet is commutative, idempotent and a lower bound
+    let a_meet_b = a.meet(b);
+    prop_assert!(equal(&a_meet_b, &b.meet(a)), "meet is not commutative");
+    prop_assert!(equal(&a.meet(a), a), "meet is not idempotent");
+    prop_assert!(a_meet_b.leq(a), "a.meet(b) is not below a");
+    prop_assert!(a_meet_b.leq(b), "a.meet(b) is not below b");
+
+    // Absorption laws
+    prop_assert!(equal(&a.join(&a_meet_b), a), "a.join(a.meet(b)) != a");
+
+    // Widening over-approximates both operands, where the second operand is above the first one
+    let widened = a.widening_with(&ab);
+    prop_assert!(a.leq(&widened), "a is not below a.widen(a.join(b))");
+    prop_assert!(ab.leq(&widened), "a.join(b) is not below a.widen(a.join(b))");
+
+    Ok(())
+}
+
+/// A state read back from its serialization has the same bounds on each variable, see `--checkpoint-dir`
+fn check_round_trip<Type>(element: &Element) -> Result<(), TestCaseError>
+where
+    Type: ApronDomainType,
+    ApronAbstractDomain<Type>: GetManagerTrait,
+{
+    let domain = build::<Type>(element);
+    let json = domain.to_json();
+    let restored = ApronAbstractDomain::<Type>::from_json(&json);
+    prop_assert!(restored.is_some(), "cannot read back {}", json);
+    let restored = restored.unwrap();
+    prop_assert_eq!(domain.is_bottom(), restored.is_bottom());
+    for var in (1..=NUM_VARS).map(|i| Path::new_local(i, 0)) {
+        prop_assert_eq!(
+            domain.get_interval(&var),
+            restored.get_interval(&Path::new_named(&format!("{:?}", var))),
+            "round trip disagrees on {:?}",
+            var
+        );
+    }
+    Ok(())
+}
+
+fn run_lattice_laws<Type>()
+where
+    Type: ApronDomainType,
+    ApronAbstractDomain<Type>: GetManagerTrait,
+{
+    let mut runner = TestRunner::new(Config::with_cases(256));
+    let result = runner.run(
+        &(element_strategy(), element_strategy(), element_strategy()),
+        |(a, b, c)| check_laws(&build::<Type>(&a), &build::<Type>(&b), &build::<Type>(&c)),
+    );
+    if let Err(e) = result {
+        panic!("{}", e);
+    }
+
+    let result = runner.run(
+        &(sparse_element_strategy(), sparse_element_strategy()),
+        |(a, b)| check_agreement::<Type>(&a, &b),
+    );
+    if let Err(e) = result {
+        panic!("{}", e);
+    }
+
+    let result = runner.run(&element_strategy(), |a| check_round_trip::<Type>(&a));
+    if let Err(e) = result {
+        panic!("{}", e);
+    }
+}
+
+#[test]
+fn lattice_laws() {
+    let domain = std::env::var("MIR_CHECKER_TEST_DOMAIN").unwrap_or_else(|_| "interval".into());
+    match domain.as_str() {
+        "interval" => run_lattice_laws::<ApronInterval>(),
+        "octagon" => run_lattice_laws::<ApronOctagon>(),
+        "polyhedra" => run_lattice_laws::<ApronPolyhedra>(),
+        _ => panic!("Unsupported domain for lattice law tests: {}", domain),
+    }
+}
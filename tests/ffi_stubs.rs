@@ -0,0 +1,44 @@
+// Tests for the parsing of the contracts of C functions in `--ffi-stubs`
+//
+//     cargo test --test ffi_stubs
+
+use rust_mir_checker::checker::ffi_checker::{parse_ffi_stubs, FfiContract};
+
+#[test]
+fn contracts_are_parsed() {
+    let contracts = parse_ffi_stubs(
+        r#"
+        # The contracts of the functions in libc
+        [memcpy]
+        non_null = [0, 1]
+        positive = [2]
+
+        ["strlen"]
+        non_null = [0] # the string must be terminated
+        non_negative = []
+        "#,
+    )
+    .unwrap();
+    assert_eq!(contracts.len(), 2);
+    assert_eq!(
+        contracts["memcpy"],
+        FfiContract {
+            non_null: vec![0, 1],
+            positive: vec![2],
+            non_negative: vec![],
+        }
+    );
+    assert_eq!(contracts["strlen"].non_null, vec![0]);
+    assert!(contracts["strlen"].positive.is_empty());
+}
+
+#[test]
+fn invalid_lines_are_reported() {
+    let error = |content: &str| parse_ffi_stubs(content).unwrap_err();
+    assert!(error("non_null = [0]").starts_with("line 1:"));
+    assert!(error("[memcpy]\nnon_null = 0").starts_with("line 2:"));
+    assert!(error("[memcpy]\nnon_null = [-1]").contains("non-negative integers"));
+    assert!(error("[memcpy]\nnullable = [0]").contains("unknown key `nullable`"));
+    assert!(error("[memcpy]\n[memcpy]").contains("defined twice"));
+    assert!(error("[memcpy").contains("not closed"));
+}
@@ -0,0 +1,42 @@
+// Tests for the stubs of the methods of the integer types
+//
+//     cargo test --test integer_stubs
+
+use rust_mir_checker::analysis::numerical::interval::{Bound, Interval};
+use rust_mir_checker::analysis::stubs::stub_library::StubLibrary;
+
+fn interval(low: i64, high: i64) -> Interval {
+    Interval::new(Bound::from(low as i128), Bound::from(high as i128))
+}
+
+fn call(method: &str, args: &[Interval]) -> Interval {
+    let library = StubLibrary::new(64, false);
+    let stub = library
+        .get(&format!("core.num.implement_i32.{}", method))
+        .expect("the method has no stub");
+    stub(args)
+}
+
+#[test]
+fn rem_euclid_of_non_negative_dividends() {
+    assert_eq!(
+        call("rem_euclid", &[interval(7, 7), interval(3, 3)]),
+        interval(0, 1)
+    );
+    assert_eq!(
+        call("rem_euclid", &[interval(0, 100), interval(-4, -4)]),
+        interval(0, 3)
+    );
+}
+
+#[test]
+fn rem_euclid_of_negative_dividends() {
+    // `(-1).rem_euclid(3)` is 2, the remainder of a negative dividend is only bounded by the divisor
+    let result = call("rem_euclid", &[interval(-1, -1), interval(3, 3)]);
+    assert_eq!(result, interval(0, 2));
+    assert_eq!(
+        call("rem_euclid", &[interval(-10, 5), interval(-8, 4)]),
+        interval(0, 7)
+    );
+    assert!(call("rem_euclid", &[interval(-1, -1), interval(0, 0)]).is_bottom());
+}
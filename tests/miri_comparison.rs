@@ -0,0 +1,186 @@
+// Differential testing of overflow warnings against Miri
+//
+// Each program in `tests/overflow_programs/` is analyzed by `mir-checker` and executed by Miri.
+// Every overflow detected by Miri at runtime must also be warned by the checker (soundness).
+// Warnings that Miri does not confirm are reported as possible false positives (precision).
+// Programs in `tests/overflow_programs/known_unsound/` are excluded from the soundness check,
+// but their results are still reported.
+//
+// Miri stops at the first panic, so each program should contain at most one runtime overflow.
+// The test is skipped if `cargo miri` is not installed:
+//
+//     rustup component add miri
+//     cargo test --test miri_comparison -- --nocapture
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The result of comparing the two tools on a single program
+struct Comparison {
+    name: String,
+    known_unsound: bool,
+    // Lines of overflows detected by Miri but not warned by the checker
+    missed: BTreeSet<usize>,
+    // Lines warned by the checker but not detected by Miri
+    unconfirmed: BTreeSet<usize>,
+}
+
+fn corpus_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/overflow_programs")
+}
+
+fn collect_programs(dir: &Path) -> Vec<PathBuf> {
+    let mut programs: Vec<_> = fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("Cannot read {}: {}", dir.display(), e))
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().map_or(false, |ext| ext == "rs"))
+        .collect();
+    programs.sort();
+    programs
+}
+
+fn miri_available() -> bool {
+    Command::new("cargo")
+        .args(["miri", "--version"])
+        .output()
+        .map_or(false, |output| output.status.success())
+}
+
+/// Get the line number from a location like `path/to/file.rs:12:5`
+fn parse_line(location: &str) -> Option<usize> {
+    let mut parts = location.trim().rsplitn(3, ':');
+    let _column = parts.next()?;
+    parts.next()?.parse().ok()
+}
+
+/// Lines of overflow warnings emitted by the checker, e.g.
+///
+///     warning: [MirChecker] Possible error: "attempt to compute `x + 1`, which would overflow"
+///      --> tests/overflow_programs/add-overflow.rs:12:14
+fn parse_checker_output(stderr: &str) -> BTreeSet<usize> {
+    let mut lines = BTreeSet::new();
+    let mut in_overflow_warning = false;
+    for line in stderr.lines() {
+        if line.contains("[MirChecker]") {
+            in_overflow_warning = line.contains("which would overflow");
+        } else if in_overflow_warning {
+            if let Some(location) = line.trim_start().strip_prefix("--> ") {
+                if let Some(line_number) = parse_line(location) {
+                    lines.insert(line_number);
+                }
+                in_overflow_warning = false;
+            }
+        }
+    }
+    lines
+}
+
+/// Lines of overflow panics reported by Miri, e.g.
+///
+///     thread 'main' panicked at src/main.rs:12:14:
+///     attempt to add with overflow
+fn parse_miri_output(stderr: &str) -> BTreeSet<usize> {
+    let mut lines = BTreeSet::new();
+    let mut panic_line = None;
+    for line in stderr.lines() {
+        if let Some((_, location)) = line.split_once("panicked at ") {
+            panic_line = parse_line(location.trim_end_matches(':'));
+        } else if let Some(line_number) = panic_line.take() {
+            if line.contains("with overflow") {
+                lines.insert(line_number);
+            }
+        }
+    }
+    lines
+}
+
+fn run_checker(program: &Path, out_dir: &Path) -> BTreeSet<usize> {
+    let output = Command::new(env!("CARGO_BIN_EXE_mir-checker"))
+        .arg(program)
+        .args(["--crate-type", "bin", "--edition", "2021", "--out-dir"])
+        .arg(out_dir)
+        .args(["--entry", "main", "--domain", "interval"])
+        .args(["--widening_delay", "5", "--narrowing_iteration", "5"])
+        .output()
+        .expect("Cannot run mir-checker");
+    parse_checker_output(&String::from_utf8_lossy(&output.stderr))
+}
+
+/// Run a program under Miri, by copying it into a temporary cargo project
+fn run_miri(program: &Path, name: &str, work_dir: &Path) -> BTreeSet<usize> {
+    let project = work_dir.join(name);
+    fs::create_dir_all(project.join("src")).expect("Cannot create the Miri project");
+    fs::write(
+        project.join("Cargo.toml"),
+        format!(
+            "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[workspace]\n",
+            name
+        ),
+    )
+    .expect("Cannot write Cargo.toml");
+    fs::copy(program, project.join("src/main.rs")).expect("Cannot copy the program");
+
+    let output = Command::new("cargo")
+        .args(["miri", "run", "-q"])
+        .current_dir(&project)
+        .env("CARGO_TARGET_DIR", work_dir.join("target"))
+        .output()
+        .expect("Cannot run cargo miri");
+    parse_miri_output(&String::from_utf8_lossy(&output.stderr))
+}
+
+fn compare(program: &Path, known_unsound: bool, work_dir: &Path) -> Comparison {
+    let name = program.file_stem().unwrap().to_string_lossy().into_owned();
+    let warned = run_checker(program, work_dir);
+    let detected = run_miri(program, &name, work_dir);
+    Comparison {
+        name,
+        known_unsound,
+        missed: detected.difference(&warned).cloned().collect(),
+        unconfirmed: warned.difference(&detected).cloned().collect(),
+    }
+}
+
+#[test]
+fn overflow_warnings_cover_miri_overflows() {
+    if !miri_available() {
+        eprintln!("`cargo miri` is not available, skip the comparison with Miri");
+        return;
+    }
+
+    let work_dir = std::env::temp_dir().join("mir-checker-miri-comparison");
+    fs::create_dir_all(&work_dir).expect("Cannot create the working directory");
+
+    let corpus = corpus_dir();
+    let mut comparisons = vec![];
+    for program in collect_programs(&corpus) {
+        comparisons.push(compare(&program, false, &work_dir));
+    }
+    for program in collect_programs(&corpus.join("known_unsound")) {
+        comparisons.push(compare(&program, true, &work_dir));
+    }
+
+    let mut unsound = vec![];
+    for comparison in &comparisons {
+        let tag = if comparison.known_unsound {
+            " (known unsound)"
+        } else {
+            ""
+        };
+        eprintln!(
+            "{}{}: missed lines {:?}, unconfirmed lines {:?}",
+            comparison.name, tag, comparison.missed, comparison.unconfirmed
+        );
+        if !comparison.known_unsound && !comparison.missed.is_empty() {
+            unsound.push(comparison.name.as_str());
+        }
+    }
+
+    assert!(
+        unsound.is_empty(),
+        "Overflows detected by Miri are not warned by mir-checker in: {:?}",
+        unsound
+    );
+}
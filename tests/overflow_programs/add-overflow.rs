@@ -0,0 +1,13 @@
+// Signed addition overflows after a loop
+
+fn main() {
+    // We use a loop to make a non-constant variable `t`
+    let mut t: i32 = 0;
+    while t < 100 {
+        t += 1;
+    }
+    // Here, t == 100
+
+    let b = i32::MAX - 50;
+    let _c = b + t; // Error: integer overflow
+}
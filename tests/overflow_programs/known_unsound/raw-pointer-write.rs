@@ -0,0 +1,16 @@
+// Overflow of a value written through a raw pointer
+// The checker does not track writes through raw pointers precisely, so it may miss this one
+
+fn main() {
+    let mut x: i32 = 0;
+    let p = &mut x as *mut i32;
+    unsafe {
+        *p = i32::MAX;
+    }
+
+    let mut t: i32 = 0;
+    while t < 10 {
+        t += 1;
+    }
+    let _y = x + t; // Error: integer overflow
+}
@@ -0,0 +1,15 @@
+// Multiplication overflows inside a function call
+
+fn scale(x: u8, factor: u8) -> u8 {
+    x * factor // Error: integer overflow when `x * factor > 255`
+}
+
+fn main() {
+    let mut t: u8 = 0;
+    while t < 20 {
+        t += 1;
+    }
+    // Here, t == 20
+
+    let _r = scale(t, 16);
+}
@@ -0,0 +1,11 @@
+// Negation of the minimum value overflows
+
+fn main() {
+    let mut t: i64 = 0;
+    while t > i64::MIN {
+        t = i64::MIN;
+    }
+    // Here, t == i64::MIN
+
+    let _n = -t; // Error: negation overflow
+}
@@ -0,0 +1,13 @@
+// No overflow, used to measure the precision of the checker
+
+fn main() {
+    let mut sum: u32 = 0;
+    let mut i: u32 = 0;
+    while i < 100 {
+        sum += i;
+        i += 1;
+    }
+    // Here, sum == 4950
+
+    let _avg = sum / i;
+}
@@ -0,0 +1,12 @@
+// Unsigned subtraction underflows after a loop
+
+fn main() {
+    let mut t: u32 = 0;
+    while t < 10 {
+        t += 1;
+    }
+    // Here, t == 10
+
+    let a: u32 = 5;
+    let _b = a - t; // Error: u32 cannot be negative
+}
@@ -0,0 +1,113 @@
+use rug::Integer;
+use rust_mir_checker::analysis::memory::path::Path;
+use rust_mir_checker::analysis::numerical::apron_domain::ApronInterval;
+use rust_mir_checker::analysis::numerical::interval::{Bound, Interval};
+use rust_mir_checker::analysis::numerical::lattice::LatticeTrait;
+use rust_mir_checker::analysis::numerical::linear_constraint::{
+    LinearConstraint, LinearExpression,
+};
+use rust_mir_checker::analysis::numerical::predicate_domain::{parse_predicate, PredicateDomain};
+use rust_mir_checker::analysis::numerical::sparse_domain::SparseApronDomain;
+
+fn parse(text: &str) -> Result<LinearConstraint, String> {
+    parse_predicate(text, |name| Some(Path::new_named(name)))
+}
+
+fn expression(terms: &[(&str, i32)], constant: i32) -> LinearExpression {
+    let mut expression = LinearExpression::from(constant);
+    for (name, coefficient) in terms {
+        expression.add_term(Path::new_named(name), Integer::from(*coefficient));
+    }
+    expression
+}
+
+fn range(low: i32, high: i32) -> Interval {
+    Interval::new(
+        Bound::Int(Integer::from(low)),
+        Bound::Int(Integer::from(high)),
+    )
+}
+
+#[test]
+fn parse_operators() {
+    assert_eq!(
+        parse("x + y < 100").unwrap(),
+        LinearConstraint::LessThan(expression(&[("x", 1), ("y", 1)], -100))
+    );
+    assert_eq!(
+        parse("x >= 2 * y").unwrap(),
+        LinearConstraint::LessEq(expression(&[("x", -1), ("y", 2)], 0))
+    );
+    assert_eq!(
+        parse("-x + y * 3 == 4").unwrap(),
+        LinearConstraint::Equality(expression(&[("x", -1), ("y", 3)], -4))
+    );
+    assert_eq!(
+        parse("x != 0").unwrap(),
+        LinearConstraint::Inequality(expression(&[("x", 1)], 0))
+    );
+}
+
+#[test]
+fn parse_errors() {
+    assert!(parse("x + y")
+        .unwrap_err()
+        .contains("no comparison operator"));
+    assert!(parse("x <").unwrap_err().contains("no expression"));
+    assert!(parse("x * y < 1").unwrap_err().contains("not an integer"));
+    assert!(parse("z mod 4 == 0")
+        .unwrap_err()
+        .contains("not a variable"));
+    let unknown = parse_predicate("x < y", |name| {
+        if name == "x" {
+            Some(Path::new_named(name))
+        } else {
+            None
+        }
+    });
+    assert!(unknown.unwrap_err().contains("unknown variable `y`"));
+}
+
+#[test]
+fn predicates_entailed_by_intervals() {
+    let (x, y) = (Path::new_named("x"), Path::new_named("y"));
+    let mut numerical_domain = SparseApronDomain::<ApronInterval>::top();
+    numerical_domain.set_interval(&x, range(0, 10));
+    numerical_domain.assign_int(y.clone(), Integer::from(5));
+    let predicates = vec![
+        parse("x + y <= 15").unwrap(),
+        parse("x + y < 15").unwrap(),
+        parse("y == 5").unwrap(),
+        parse("x != 11").unwrap(),
+    ];
+    let domain = PredicateDomain::from_numerical(&numerical_domain, &predicates);
+    assert!(domain.holds(0));
+    assert!(!domain.holds(1));
+    assert!(domain.holds(2));
+    assert!(domain.holds(3));
+
+    let bottom =
+        PredicateDomain::from_numerical(&SparseApronDomain::<ApronInterval>::bottom(), &predicates);
+    assert!(bottom.is_bottom());
+    assert!(bottom.holds(1));
+}
+
+#[test]
+fn join_and_meet() {
+    let x = Path::new_named("x");
+    let predicates = vec![parse("x >= 0").unwrap(), parse("x <= 10").unwrap()];
+    let domain_of = |low, high| {
+        let mut numerical_domain = SparseApronDomain::<ApronInterval>::top();
+        numerical_domain.set_interval(&x, range(low, high));
+        PredicateDomain::from_numerical(&numerical_domain, &predicates)
+    };
+    let low = domain_of(0, 20);
+    let high = domain_of(-5, 10);
+    // The join keeps the predicates that hold in both, and the meet keeps the predicates that hold in either
+    assert!(low.lub(&high).is_top());
+    let meet = low.meet(&high);
+    assert!(meet.holds(0) && meet.holds(1));
+    // The bottom is the identity of the join
+    assert_eq!(low.lub(&PredicateDomain::bottom()), low);
+    assert_eq!(meet.to_constraints(&predicates).size(), predicates.len());
+}
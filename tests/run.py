@@ -7,43 +7,102 @@ import subprocess
 import time
 
 unit_tests_list = [
+    {"name": "alias-test", "entry": "main"},
     {"name": "alloc-test", "entry": "main"},
+    {"name": "analysis-order", "entry": "main", "options": ["--dump-analysis-order"]},
     {"name": "annotation", "entry": "main"},
     {"name": "arith", "entry": "main"},
     {"name": "array", "entry": "main"},
     {"name": "assignment", "entry": "main"},
     {"name": "big-loop", "entry": "main"},
+    {"name": "bottom-up", "entry": "main", "options": ["--bottom-up"]},
+    {"name": "box-test", "entry": "main"},
+    {"name": "branch-condition", "entry": "main"},
+    {"name": "call-graph", "entry": "main", "options": ["--dump-callgraph", "target/callgraph.dot"]},
     {"name": "cast", "entry": "main"},
+    {"name": "cell-test", "entry": "main"},
+    {"name": "checked-arithmetic", "entry": "main"},
+    {"name": "const-length", "entry": "main"},
     {"name": "crate-bin-test", "entry": "main"},
     {"name": "crate-lib-test", "entry": "foo"},
+    {"name": "dangling-pointer", "entry": "main", "options": ["--check", "dangling-pointer"]},
+    {"name": "dead-branch", "entry": "main", "options": ["--check", "dead-branch"]},
+    {"name": "dead-code", "entry": "main", "options": ["--report-dead-code"]},
+    {"name": "double-free", "entry": "main", "options": ["--check", "double-free"]},
     {"name": "empty", "entry": "main"},
+    {"name": "enum-discriminant", "entry": "main"},
     {"name": "enum-test", "entry": "main"},
+    {"name": "escape-analysis", "entry": "main"},
     {"name": "function-call", "entry": "main"},
     {"name": "index", "entry": "main"},
+    {"name": "inline-depth", "entry": "main", "options": ["--inline-depth", "1"]},
+    {"name": "int-promotion", "entry": "main", "options": ["--check", "int-promotion"]},
     {"name": "iterator", "entry": "main"},
+    {"name": "iterator-adaptor", "entry": "main"},
     {"name": "loop-test", "entry": "main"},
+    {"name": "loop-unroll", "entry": "main"},
+    {"name": "max-loop-depth", "entry": "main", "options": ["--max-loop-depth", "1"]},
+    {"name": "mutual-recursion", "entry": "main"},
+    {"name": "newtype", "entry": "main"},
+    {"name": "no-std", "entry": "foo", "options": ["--no-std"]},
     {"name": "negation", "entry": "main"},
+    {"name": "pointer-provenance", "entry": "main"},
     {"name": "recursion", "entry": "main"},
+    {"name": "recursive-summary", "entry": "main"},
+    {"name": "requires", "entry": "main"},
+    {"name": "saturating-arithmetic", "entry": "main"},
+    {"name": "shift", "entry": "main", "options": ["--check", "shift-overflow"]},
+    {"name": "shift-by-const", "entry": "main", "options": ["--check", "shift-by-const"]},
+    {"name": "shift-sign", "entry": "main", "options": ["--check", "shift-sign"]},
     {"name": "size-of", "entry": "main"},
+    {"name": "slice-test", "entry": "main"},
+    {"name": "stdlib-stubs", "entry": "main"},
+    {"name": "string-literal", "entry": "main"},
     {"name": "struct-test", "entry": "main"},
+    {"name": "trait-method", "entry": "main"},
+    {"name": "try-operator", "entry": "main"},
     {"name": "vector", "entry": "main"},
     {"name": "widen-narrow", "entry": "main"},
+    {"name": "workspace-test", "entry": "main", "cargo_options": ["--workspace"]},
+    {"name": "wrapping-integer", "entry": "main", "options": ["--domain", "wrapping-integer"]},
 ]
 
 safe_bugs_list = [
     {"name": "division-by-zero", "entry": "main"},
+    {"name": "float-nan", "entry": "main", "options": ["--check", "float-nan"]},
+    {"name": "full-channel", "entry": "main", "options": ["--check", "channel-capacity"]},
     {"name": "incorrect-boundary-check", "entry": "main"},
     {"name": "incorrect-cast", "entry": "main"},
     {"name": "integer-overflow", "entry": "main"},
+    {"name": "lock-order", "entry": "main", "options": ["--check", "lock-order"]},
+    {"name": "non-termination", "entry": "main", "options": ["--check", "termination", "--domain", "polyhedra"]},
     {"name": "out-of-bound-index", "entry": "main"},
+    {"name": "panic-freedom", "entry": "main", "options": ["--panic-freedom"]},
+    {"name": "reachable-panic", "entry": "main", "options": ["--check", "panic-reachability"]},
+    {"name": "resource-leak", "entry": "main"},
+    {"name": "str-index", "entry": "main", "options": ["--check", "str-index"]},
+    {"name": "taint-sink", "entry": "main", "options": ["--analysis-type", "taint"]},
+    {"name": "uninitialized-global", "entry": "main", "options": ["--check", "use-of-uninitialized-global"]},
     {"name": "unreachable", "entry": "main"},
+    {"name": "unwrap-none", "entry": "main"},
 ]
 
 unsafe_bugs_list = [
     {"name": "double-free", "entry": "main"},
-    {"name": "offset", "entry": "main"},
+    {"name": "ffi-contract", "entry": "main", "options": ["--ffi-stubs", "ffi.toml"]},
+    {"name": "format-string", "entry": "main"},
+    {"name": "format-string-register-tool", "entry": "main"},
+    {"name": "get-unchecked-range", "entry": "main", "options": ["--check", "vec-bounds"]},
+    {"name": "misaligned-read", "entry": "main"},
+    {"name": "offset", "entry": "main", "options": ["--check", "raw-ptr-bounds"]},
+    {"name": "padding-uninit", "entry": "main", "options": ["--check", "padding-uninit"]},
+    {"name": "pin-move", "entry": "main", "options": ["--check", "pin-safety"]},
+    {"name": "raw-offset-overflow", "entry": "main", "options": ["--check", "raw-offset-overflow"]},
+    {"name": "simd-gather", "entry": "main", "options": ["--check", "raw-ptr-bounds"]},
+    {"name": "transmute-misaligned", "entry": "main", "options": ["--check", "transmute-safety"]},
     {"name": "use-after-free(CVE-2019-15551)", "entry": "main"},
     {"name": "use-after-free(CVE-2019-16140)", "entry": "main"},
+    {"name": "vec-set-len", "entry": "main", "options": ["--check", "vec-bounds"]},
 ]
 
 abstract_domains = ["interval", "octagon", "polyhedra", "linear_equalities", "ppl_polyhedra",
@@ -66,8 +125,8 @@ def run_test(test_list, test_dir, allow_error):
             # my_env["RUST_LOG"] = "rust_mir_checker"
 
             # Customized options
-            p = subprocess.Popen([executable, "mir-checker", "--", "--domain", domain_type, "--entry", test["entry"], "--widening_delay", "5",
-                                 "--narrowing_iteration", "5", "--deny_warnings"], cwd=os.path.join(test_dir, test["name"]), env=my_env)
+            p = subprocess.Popen([executable, "mir-checker"] + test.get("cargo_options", []) + ["--", "--domain", domain_type, "--entry", test["entry"], "--widening_delay", "5",
+                                 "--narrowing_iteration", "5", "--deny_warnings"] + test.get("options", []), cwd=os.path.join(test_dir, test["name"]), env=my_env)
             p.communicate()[0]
             rc = p.returncode
             if rc == 0:
@@ -0,0 +1,76 @@
+// Analyzed with `--check float-nan`, the comparisons with values that may be NaN are reported
+
+fn average(total: f64, count: u32) -> f64 {
+    // `0.0 / 0.0` is NaN if `count` is zero
+    let mean = total / count as f64;
+    // Possible error: the comparison is false if `mean` is NaN
+    if mean > 100.0 {
+        100.0
+    } else {
+        mean
+    }
+}
+
+fn distance(x: f64) -> f64 {
+    // Not reported: the argument of `sqrt` is not negative
+    let root = (x.abs() + 1.0).sqrt();
+    if root < 1.0 {
+        1.0
+    } else {
+        root
+    }
+}
+
+fn normalize(x: f64) -> f64 {
+    let zero = x * 0.0;
+    // `0.0 / 0.0` is NaN
+    let ratio = zero / zero;
+    // Provably error: the comparison is always false
+    if ratio == 1.0 {
+        return 1.0;
+    }
+    // Not reported: `x != x` is how `is_nan` is implemented
+    if ratio != ratio {
+        return 0.0;
+    }
+    ratio
+}
+
+fn root_of_negative(x: f64) -> f64 {
+    let negative = -x.abs() - 1.0;
+    // `sqrt` of a negative number is NaN
+    let root = negative.sqrt();
+    // Provably error: the comparison is always false
+    if root >= 0.0 {
+        root
+    } else {
+        0.0
+    }
+}
+
+fn reset(x: f64) -> f64 {
+    let zero = x * 0.0;
+    let mut ratio = zero / zero;
+    if ratio.is_nan() {
+        ratio = 1.0;
+    } else {
+        ratio = 2.0;
+    }
+    // Not reported: `ratio` is assigned a number on both branches, so the NaN above does not reach here
+    if ratio > 1.5 {
+        ratio
+    } else {
+        0.0
+    }
+}
+
+fn main() {
+    println!(
+        "{} {} {} {} {}",
+        average(10.0, 0),
+        distance(3.0),
+        normalize(2.0),
+        root_of_negative(4.0),
+        reset(5.0)
+    );
+}
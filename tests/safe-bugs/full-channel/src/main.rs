@@ -0,0 +1,37 @@
+// Analyzed with `--check channel-capacity`, the sends that may block on a full channel are reported
+
+use std::sync::mpsc::sync_channel;
+
+fn within_capacity() {
+    // Not reported: two items fit in the buffer
+    let (sender, receiver) = sync_channel(2);
+    sender.send(1).unwrap();
+    sender.send(2).unwrap();
+    let _a = receiver.recv().unwrap();
+    let _b = receiver.recv().unwrap();
+}
+
+fn over_capacity() {
+    // The third send blocks forever, because nothing is received before it
+    let (sender, receiver) = sync_channel(2);
+    sender.send(1).unwrap();
+    sender.send(2).unwrap();
+    sender.send(3).unwrap();
+    let _a = receiver.recv().unwrap();
+}
+
+fn interleaved(n: usize) {
+    // Not reported: each item is received before the next one is sent
+    let (sender, receiver) = sync_channel(1);
+    for i in 0..n {
+        sender.send(i).unwrap();
+        let _item = receiver.recv().unwrap();
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    within_capacity();
+    over_capacity();
+    interleaved(args.len());
+}
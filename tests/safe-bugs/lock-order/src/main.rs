@@ -0,0 +1,38 @@
+// Analyzed with `--check lock-order`, the mutexes that may be acquired in both orders are reported
+
+use std::sync::Mutex;
+
+fn transfer(a: &Mutex<i32>, b: &Mutex<i32>, forward: bool) {
+    if forward {
+        let mut from = a.lock().unwrap();
+        let mut to = b.lock().unwrap();
+        *from -= 1;
+        *to += 1;
+    } else {
+        // Deadlocks with the branch above if both run in two threads
+        let mut from = b.lock().unwrap();
+        let mut to = a.lock().unwrap();
+        *from -= 1;
+        *to += 1;
+    }
+}
+
+fn sequential(a: &Mutex<i32>, b: &Mutex<i32>) {
+    // Not reported: `a` is released before `b` is acquired
+    {
+        let mut x = b.lock().unwrap();
+        *x += 1;
+    }
+    let guard = a.lock().unwrap();
+    drop(guard);
+    let mut y = b.lock().unwrap();
+    *y += 1;
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let a = Mutex::new(0);
+    let b = Mutex::new(0);
+    transfer(&a, &b, args.len() > 1);
+    sequential(&a, &b);
+}
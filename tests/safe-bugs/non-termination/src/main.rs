@@ -0,0 +1,55 @@
+// Analyzed with `--check termination --domain polyhedra`, the loops without a linear ranking function are reported
+
+fn count_up(n: u32) -> u32 {
+    // Terminates: `n - i` decreases in each iteration and is positive
+    let mut i = 0;
+    while i < n {
+        i += 1;
+    }
+    i
+}
+
+fn count_down(mut n: u32) -> u32 {
+    // Terminates: `n` decreases in each iteration and is positive
+    let mut steps = 0;
+    while n > 0 {
+        n -= 1;
+        steps += 1;
+    }
+    steps
+}
+
+fn triangle(n: u32) -> u32 {
+    // Terminates: `n - i` decreases in the outer loop, and `i - j` in the inner loop, whose transition is not
+    // changed when the outer loop is analyzed once more for its own transition
+    let mut i = 0;
+    let mut steps = 0;
+    while i < n {
+        let mut j = 0;
+        while j < i {
+            j += 1;
+            steps += 1;
+        }
+        i += 1;
+    }
+    steps
+}
+
+fn spin(n: u32) -> u32 {
+    // Does not terminate when `n > 0`, because `i` is never updated
+    let i = 0;
+    let mut sum = 0;
+    while i < n {
+        sum = (sum + 1) % 100;
+    }
+    sum
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let n = args.len() as u32;
+    let _a = count_up(n);
+    let _b = count_down(n);
+    let _c = triangle(n);
+    let _d = spin(n);
+}
@@ -0,0 +1,26 @@
+// Every site that may panic is reported with `--panic-freedom`, and the compilation fails
+
+fn parse(s: &str) -> Result<u8, String> {
+    if s.is_empty() {
+        return Err(String::from("empty"));
+    }
+    Ok(s.len() as u8)
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let n = args.len();
+
+    // The discriminant of the result is unknown
+    let _a = args[0].parse::<i32>().unwrap();
+
+    // May be `Err`
+    let _b = parse(&args[0]).expect("invalid input");
+
+    // May be out of bounds
+    let _c = args[n - 1].len();
+
+    if n > 10 {
+        panic!("too many arguments");
+    }
+}
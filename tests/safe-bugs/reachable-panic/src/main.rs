@@ -0,0 +1,17 @@
+// Panics that are reachable from `main`, reported with `--check panic-reachability`
+
+fn parse_level(arg: usize) -> u8 {
+    match arg {
+        0..=3 => arg as u8,
+        // The message is a constant, so it is included in the diagnostic
+        _ => unreachable!(),
+    }
+}
+
+fn main() {
+    let n = std::env::args().count();
+    let level = parse_level(n);
+    if level > 2 {
+        std::process::abort();
+    }
+}
@@ -0,0 +1,43 @@
+// The connections that are opened but not closed on every path are reported as resource leaks
+
+#[cfg_attr(mir_checker, mir_checker::resource)]
+trait Resource {}
+
+struct Connection {
+    port: u16,
+}
+
+impl Resource for Connection {}
+
+impl Connection {
+    fn open(port: u16) -> Connection {
+        Connection { port }
+    }
+
+    fn close(self) {}
+}
+
+fn send(connection: &Connection, data: u8) -> u16 {
+    connection.port + data as u16
+}
+
+// The connection is returned, so the caller is responsible for closing it
+fn connect_to(port: u16) -> Connection {
+    Connection::open(port)
+}
+
+fn main() {
+    let closed = Connection::open(80);
+    send(&closed, 1);
+    closed.close();
+
+    let returned = connect_to(443);
+    returned.close();
+
+    // Leaked if `data` is zero
+    let leaked = Connection::open(8080);
+    let data = send(&leaked, 0) as u8;
+    if data != 0 {
+        leaked.close();
+    }
+}
@@ -0,0 +1,16 @@
+// Analyzed with `--check str-index`, the ranges of string slices are checked against the lengths of the strings
+
+fn main() {
+    let greeting = "hello, world";
+    // In bounds, the length is 12
+    let hello = &greeting[..5];
+    // In bounds, the length of `hello` is 5
+    let _ell = &hello[1..4];
+    // Provably error: the end exceeds the length of `hello`
+    let _out = &hello[2..8];
+
+    let args: Vec<String> = std::env::args().collect();
+    let mid = args.len();
+    // Possible error: `mid` may exceed the length, and it may not be on a character boundary
+    let (_left, _right) = greeting.split_at(mid);
+}
@@ -0,0 +1,9 @@
+use std::process::Command;
+
+fn main() {
+    let file = std::env::var("INPUT_FILE").unwrap_or_default();
+    // The environment can pass any argument to the command, e.g., `--help`
+    let _ = Command::new("cat").arg(file).status();
+    // A constant argument is not reported
+    let _ = Command::new("cat").arg("Cargo.toml").status();
+}
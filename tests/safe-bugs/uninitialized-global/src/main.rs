@@ -0,0 +1,34 @@
+// A placeholder that `init` must replace before the configuration is used
+static mut BUFFER_SIZE: usize = 0;
+static mut VERBOSE: bool = false;
+
+fn init(size: usize) {
+    unsafe {
+        BUFFER_SIZE = size;
+    }
+}
+
+fn buffer_size() -> usize {
+    unsafe { BUFFER_SIZE }
+}
+
+fn main() {
+    let n = std::env::args().count();
+
+    // Read before `init` is called
+    let early = buffer_size();
+
+    if n > 1 {
+        init(n * 1024);
+    }
+    // `init` is called in one branch only
+    let maybe = buffer_size();
+
+    init(4096);
+    // Written on all the paths
+    let size = buffer_size();
+
+    // Never written
+    let verbose = unsafe { VERBOSE };
+    let _ = (early, maybe, size, verbose);
+}
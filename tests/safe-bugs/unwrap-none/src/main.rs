@@ -0,0 +1,26 @@
+// `Option::unwrap` on an option whose discriminant is known to be `None`
+
+fn find(v: &[i32], target: i32) -> Option<usize> {
+    if v.len() > 3 {
+        return Some(0);
+    }
+    if target < 0 {
+        return None;
+    }
+    Some(v.len())
+}
+
+fn main() {
+    let n = std::env::args().count() as i32;
+
+    // Never panics
+    let present = Some(n);
+    let _a = present.unwrap();
+
+    // Panics if `find` returns `None`
+    let _b = find(&[1, 2], n - 1).expect("not found");
+
+    // Always panics
+    let missing: Option<i32> = None;
+    let _c = missing.unwrap();
+}
@@ -0,0 +1,94 @@
+// Tests for archiving the diagnostics into a SQLite database with `--output-format sqlite`
+//
+//     cargo test --test sqlite_export
+
+use rusqlite::Connection;
+use rust_mir_checker::analysis::sqlite_export::{
+    export_diagnostics, AnalysisRun, DiagnosticRecord,
+};
+
+fn record(file: &str, line: usize, cause: &str) -> DiagnosticRecord {
+    DiagnosticRecord {
+        file: file.to_string(),
+        line,
+        col: 5,
+        cause: cause.to_string(),
+        is_memory_safety: cause == "Memory",
+        message: format!("[MirChecker] Possible error: {} in {}", cause, file),
+    }
+}
+
+fn run(run_id: &str, timestamp: u64) -> AnalysisRun {
+    AnalysisRun {
+        run_id: run_id.to_string(),
+        crate_name: "demo".to_string(),
+        timestamp,
+    }
+}
+
+#[test]
+fn runs_are_appended() {
+    let db = std::env::temp_dir().join(format!("mir-checker-export-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&db);
+    let file_name = db.to_str().unwrap();
+
+    let first = [
+        record("src/main.rs", 3, "Index"),
+        record("src/lib.rs", 7, "Memory"),
+    ];
+    assert_eq!(export_diagnostics(file_name, &run("1", 100), &first), Ok(2));
+    let second = [record("src/main.rs", 3, "Index")];
+    assert_eq!(
+        export_diagnostics(file_name, &run("2", 200), &second),
+        Ok(1)
+    );
+    // A run without diagnostics still creates the table
+    assert_eq!(export_diagnostics(file_name, &run("3", 300), &[]), Ok(0));
+
+    let connection = Connection::open(&db).unwrap();
+    let count: i64 = connection
+        .query_row("SELECT COUNT(*) FROM diagnostics", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(count, 3);
+
+    // The files with the most warnings across the runs
+    let mut statement = connection
+        .prepare("SELECT file, COUNT(*) FROM diagnostics GROUP BY file ORDER BY COUNT(*) DESC")
+        .unwrap();
+    let files: Vec<(String, i64)> = statement
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(
+        files,
+        vec![
+            ("src/main.rs".to_string(), 2),
+            ("src/lib.rs".to_string(), 1)
+        ]
+    );
+
+    let (crate_name, line, col, is_memory_safety, timestamp): (String, i64, i64, bool, i64) =
+        connection
+            .query_row(
+                "SELECT crate_name, line, col, is_memory_safety, run_timestamp FROM diagnostics \
+                 WHERE run_id = '1' AND cause = 'Memory'",
+                [],
+                |row| {
+                    Ok((
+                        row.get(0)?,
+                        row.get(1)?,
+                        row.get(2)?,
+                        row.get(3)?,
+                        row.get(4)?,
+                    ))
+                },
+            )
+            .unwrap();
+    assert_eq!(crate_name, "demo");
+    assert_eq!((line, col), (7, 5));
+    assert!(is_memory_safety);
+    assert_eq!(timestamp, 100);
+
+    std::fs::remove_file(&db).unwrap();
+}
@@ -0,0 +1,60 @@
+// Tests for the parsing of the taint sources and sinks in the configuration of `--analysis-type taint`
+//
+//     cargo test --test taint_config
+
+use rust_mir_checker::checker::taint_sink_checker::{parse_taint_config, TaintConfig};
+
+#[test]
+fn taint_section_is_parsed() {
+    let config = parse_taint_config(
+        r#"
+        [other]
+        sources = 1
+
+        # The untrusted inputs
+        [taint]
+        sources = ["std::io::Read::read", "std::env::var"]
+        sinks = [
+            "std::fmt::Write::write_fmt", # formatted output
+            "std::process::Command::arg",
+        ]
+        "#,
+    )
+    .unwrap();
+    assert_eq!(
+        config,
+        TaintConfig {
+            sources: vec![
+                "std::io::Read::read".to_string(),
+                "std::env::var".to_string()
+            ],
+            sinks: vec![
+                "std::fmt::Write::write_fmt".to_string(),
+                "std::process::Command::arg".to_string()
+            ],
+        }
+    );
+    assert_eq!(
+        parse_taint_config("[taint]").unwrap(),
+        TaintConfig::default()
+    );
+}
+
+#[test]
+fn comments_do_not_start_in_strings() {
+    let config =
+        parse_taint_config("[taint]\nsinks = [\"crate::log#raw\"] # the raw logger").unwrap();
+    assert_eq!(config.sinks, vec!["crate::log#raw".to_string()]);
+}
+
+#[test]
+fn invalid_lines_are_reported() {
+    let error = |content: &str| parse_taint_config(content).unwrap_err();
+    assert!(error("[other]\nsources = []").contains("no `[taint]` section"));
+    assert!(error("[taint]\nsources").starts_with("line 2:"));
+    assert!(error("[taint]\nsinks = \"std::env::var\"").contains("array of function paths"));
+    assert!(error("[taint]\nsinks = [std::env::var]").contains("not a quoted string"));
+    assert!(error("[taint]\nsinks = [1]").contains("not a quoted function path"));
+    assert!(error("[taint]\nfilters = []").contains("unknown key `filters`"));
+    assert!(error("[taint]\n\nsinks = [\n\"std::env::var\",").starts_with("line 3:"));
+}
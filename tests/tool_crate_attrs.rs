@@ -0,0 +1,46 @@
+// Tests for the crate attributes that register `mir_checker` as a tool in the analyzed crate
+//
+//     cargo test --test tool_crate_attrs
+
+use rust_mir_checker::analysis::callback::tool_crate_attrs;
+
+/// An inner attribute of the crate root with the names in its list
+fn attr(name: &str, names: &[&str]) -> (String, Vec<String>) {
+    (
+        name.to_string(),
+        names.iter().map(|name| name.to_string()).collect(),
+    )
+}
+
+#[test]
+fn tool_is_registered_by_default() {
+    assert_eq!(
+        tool_crate_attrs(&[]),
+        vec![
+            "feature(register_tool)".to_string(),
+            "register_tool(mir_checker)".to_string()
+        ]
+    );
+}
+
+#[test]
+fn registered_tool_is_not_registered_again() {
+    let crate_attrs = [
+        attr("feature", &["register_tool"]),
+        attr("register_tool", &["mir_checker"]),
+    ];
+    assert!(tool_crate_attrs(&crate_attrs).is_empty());
+}
+
+#[test]
+fn enabled_feature_is_not_enabled_again() {
+    let crate_attrs = [
+        attr("feature", &["never_type", "register_tool"]),
+        attr("register_tool", &["other_tool"]),
+        attr("doc", &[]),
+    ];
+    assert_eq!(
+        tool_crate_attrs(&crate_attrs),
+        vec!["register_tool(mir_checker)".to_string()]
+    );
+}
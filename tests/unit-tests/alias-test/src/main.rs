@@ -0,0 +1,19 @@
+#[macro_use]
+extern crate macros;
+
+#[allow(unused_variables)]
+fn main() {
+    let mut x = 1;
+    let p = &mut x;
+    let q = &mut *p;
+    *q = 2;
+    verify!(*p == 2);
+    let a = 10 / *p; // OK
+
+    let mut y = 1;
+    let r = &mut y as *mut i32;
+    unsafe {
+        *r = 0;
+    }
+    let b = y; // The value of `y` is unknown after the write through `r`
+}
@@ -0,0 +1,34 @@
+// `--dump-analysis-order` prints `is_even` and `is_odd` together, then `leaf`, `parity` and `main`
+
+fn leaf(x: u32) -> u32 {
+    x + 1
+}
+
+fn is_even(n: u32) -> bool {
+    if n == 0 {
+        true
+    } else {
+        is_odd(n - 1)
+    }
+}
+
+fn is_odd(n: u32) -> bool {
+    if n == 0 {
+        false
+    } else {
+        is_even(n - 1)
+    }
+}
+
+fn parity(n: u32) -> u32 {
+    if is_even(leaf(n)) {
+        0
+    } else {
+        1
+    }
+}
+
+fn main() {
+    let n = std::env::args().count() as u32;
+    let _ = parity(n);
+}
@@ -0,0 +1,22 @@
+#[macro_use]
+extern crate macros;
+
+fn clamp(x: usize) -> usize {
+    if x > 100 {
+        100
+    } else {
+        x
+    }
+}
+
+fn scaled(x: usize) -> usize {
+    // `clamp` is analyzed before `scaled`, so its summary is used here
+    clamp(x) * 2
+}
+
+fn main() {
+    let n = std::env::args().count();
+    // The summary of `scaled` is used here
+    let s = scaled(n);
+    verify!(s <= 200);
+}
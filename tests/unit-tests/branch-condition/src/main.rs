@@ -0,0 +1,26 @@
+#[macro_use]
+extern crate macros;
+
+fn clamp(x: i32) -> i32 {
+    // `x < 10` holds in the `true` branch and `x >= 10` holds in the `false` branch
+    if x < 10 {
+        verify!(x <= 9);
+        x
+    } else {
+        verify!(x >= 10);
+        10
+    }
+}
+
+fn main() {
+    let x = std::env::args().count() as i32;
+    let y = clamp(x);
+    verify!(y <= 10);
+
+    let mut i = 0;
+    while i != 5 {
+        i += 1;
+    }
+    // The loop exits only if `i == 5`
+    verify!(i == 5);
+}
@@ -0,0 +1,59 @@
+#[macro_use]
+extern crate macros;
+
+trait Shape {
+    fn corners(&self) -> u32;
+}
+
+struct Square;
+
+struct Triangle;
+
+impl Shape for Square {
+    fn corners(&self) -> u32 {
+        4
+    }
+}
+
+impl Shape for Triangle {
+    fn corners(&self) -> u32 {
+        3
+    }
+}
+
+// Called through a trait object, so both `Square::corners` and `Triangle::corners` are callees
+fn is_polygon(shape: &dyn Shape) -> bool {
+    shape.corners() >= 3
+}
+
+// Only called by `slice::sort`, which is not in the call graph, so `Depth::cmp` and `Depth::partial_cmp` are callees
+// of `main` instead, as the implementations of `Ord` and of its supertrait `PartialOrd`
+#[derive(PartialEq, Eq)]
+struct Depth(u32);
+
+impl Ord for Depth {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl PartialOrd for Depth {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Never called, so it is not in the call graph
+#[allow(dead_code)]
+fn unused() -> u32 {
+    0
+}
+
+fn main() {
+    let square = Square;
+    let corners = square.corners();
+    verify!(corners == 4);
+    let _ = is_polygon(&Triangle);
+    let mut depths = vec![Depth(2), Depth(1)];
+    depths.sort();
+}
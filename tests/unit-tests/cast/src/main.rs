@@ -10,4 +10,25 @@ fn main() {
     verify!(b == 232);
     b = b + 1;
     verify!(b == 233);
+
+    // A widening cast preserves the range
+    let c = std::env::args().count() as u8;
+    let d = c as i32;
+    verify!(d >= 0 && d <= 255);
+
+    // A narrowing cast wraps the values around into the range of the target type
+    let mut e: i32 = -2;
+    if std::env::args().count() > 1 {
+        e = -1;
+    }
+    let f = e as u8;
+    verify!(f >= 254);
+
+    // The values out of the range of the target type are not discarded
+    let mut g: i32 = 256;
+    if std::env::args().count() > 1 {
+        g = 260;
+    }
+    let h = g as u8;
+    verify!(h <= 4);
 }
@@ -0,0 +1,16 @@
+#[macro_use]
+extern crate macros;
+
+use std::cell::{Cell, RefCell};
+
+#[allow(unused_variables)]
+fn main() {
+    let c = Cell::new(1);
+    c.set(2);
+    verify!(c.get() == 2);
+    let a = 1 / c.get(); // OK
+
+    let r = RefCell::new(0);
+    *r.borrow_mut() += 1;
+    let b = *r.borrow(); // The value is unknown after `borrow_mut`
+}
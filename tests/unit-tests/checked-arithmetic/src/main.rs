@@ -0,0 +1,28 @@
+#[macro_use]
+extern crate macros;
+
+fn increment(x: u8) -> u8 {
+    match x.checked_add(1) {
+        Some(y) => {
+            verify!(y >= 1);
+            y
+        }
+        None => 0,
+    }
+}
+
+fn main() {
+    let a = increment(255);
+    verify!(a == 0);
+    let b = increment(41);
+    verify!(b == 42);
+
+    let c = 100u8.checked_mul(2).unwrap();
+    verify!(c == 200);
+
+    let d = match 3u32.checked_sub(5) {
+        Some(v) => v,
+        None => 7,
+    };
+    verify!(d == 7);
+}
@@ -0,0 +1,24 @@
+#[macro_use]
+extern crate macros;
+
+const FOO: usize = 3;
+const BAR: usize = 5;
+const N: usize = FOO + BAR;
+const M: usize = N * 2 - FOO;
+
+// When `first` is analyzed on its own, the length of the array cannot be evaluated, so it is unknown rather
+// than zero
+fn first<const K: usize>(a: [i32; K]) -> i32 {
+    a[0]
+}
+
+fn main() {
+    let a = [0; N];
+    verify!(a.len() == 8);
+    let b = [1; M];
+    verify!(b.len() == 13);
+    let c = b[M - 1];
+    verify!(c == 1);
+    let d = first(a);
+    verify!(d == 0);
+}
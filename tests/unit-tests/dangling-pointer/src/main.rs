@@ -0,0 +1,43 @@
+static mut LAST: *const i32 = std::ptr::null();
+
+// The pointer to `x` dangles after the function returns
+fn returned() -> *const i32 {
+    let x = 42;
+    &x as *const i32
+}
+
+// The static variable outlives `x`
+fn stored_into_static() {
+    let x = 42;
+    unsafe {
+        LAST = &x as *const i32;
+    }
+}
+
+// The caller's memory outlives `x`
+fn stored_into_parameter(out: &mut *const i32) {
+    let x = 42;
+    *out = &x as *const i32;
+}
+
+// The pointer is only used within the frame of `x`, so it is not reported
+fn used_locally() -> i32 {
+    let x = 42;
+    let p = &x as *const i32;
+    unsafe { *p }
+}
+
+// The pointer to the caller's memory does not dangle
+fn forwarded(x: &i32) -> *const i32 {
+    x as *const i32
+}
+
+fn main() {
+    let mut out = std::ptr::null();
+    let _ = returned();
+    stored_into_static();
+    stored_into_parameter(&mut out);
+    let _ = used_locally();
+    let y = 1;
+    let _ = forwarded(&y);
+}
@@ -0,0 +1,25 @@
+#[macro_use]
+extern crate macros;
+
+#[allow(unused_assignments)]
+#[allow(unused_variables)]
+fn main() {
+    let x = Some(1);
+    let mut a = 0;
+    // The `None` arm is never taken
+    match x {
+        None => a = 1,
+        Some(v) => a = v,
+    }
+    verify!(a == 1);
+
+    let b = 3;
+    let mut c = 0;
+    // Only the `3` arm is taken
+    match b {
+        1 | 2 => c = 1,
+        3 => c = 2,
+        _ => c = 3,
+    }
+    verify!(c == 2);
+}
@@ -0,0 +1,36 @@
+// Analyzed with `--report-dead-code`, the code that is never reached is reported as notes
+#[macro_use]
+extern crate macros;
+
+fn clamp(x: u32) -> u32 {
+    if x > 10 {
+        10
+    } else {
+        x
+    }
+}
+
+#[allow(unused_assignments)]
+fn main() {
+    let y = clamp(42);
+    let mut z = 0;
+    // `y` is always 10, so the `else` branch is dead
+    if y == 10 {
+        z = 1;
+    } else {
+        z = 2;
+    }
+    verify!(z == 1);
+
+    // The code after the loop is dead, and the `unreachable!()` is generated by a macro, so it is not reported
+    let mut i = 0;
+    loop {
+        i += 1;
+        if i >= 5 {
+            break;
+        }
+    }
+    if i < 5 {
+        unreachable!();
+    }
+}
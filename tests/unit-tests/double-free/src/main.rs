@@ -0,0 +1,54 @@
+// Both calls take the ownership of the same memory
+fn freed_twice() {
+    let p = Box::into_raw(Box::new(1));
+    unsafe {
+        drop(Box::from_raw(p));
+        drop(Box::from_raw(p));
+    }
+}
+
+// The copy points to the same memory as `p`
+fn freed_through_copy() {
+    let p = Box::into_raw(Box::new(2));
+    let q = p;
+    unsafe {
+        drop(Box::from_raw(p));
+        drop(Box::from_raw(q));
+    }
+}
+
+// The memory is freed in one branch before the join point
+fn freed_in_branch(early: bool) {
+    let p = Box::into_raw(Box::new(3));
+    if early {
+        unsafe { drop(Box::from_raw(p)) };
+    }
+    unsafe { drop(Box::from_raw(p)) };
+}
+
+// Each iteration frees a new allocation, so it is not reported
+fn freed_in_loop(n: usize) {
+    for i in 0..n {
+        let p = Box::into_raw(Box::new(i));
+        unsafe { drop(Box::from_raw(p)) };
+    }
+}
+
+// Different allocations are freed, so it is not reported
+fn freed_separately() {
+    let p = Box::into_raw(Box::new(4));
+    let q = Box::into_raw(Box::new(5));
+    unsafe {
+        drop(Box::from_raw(p));
+        drop(Box::from_raw(q));
+    }
+}
+
+fn main() {
+    let n = std::env::args().count();
+    freed_twice();
+    freed_through_copy();
+    freed_in_branch(n > 1);
+    freed_in_loop(n);
+    freed_separately();
+}
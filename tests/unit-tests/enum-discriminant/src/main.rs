@@ -0,0 +1,40 @@
+#[macro_use]
+extern crate macros;
+
+enum Kind {
+    Small,
+    Big,
+}
+
+fn main() {
+    let n = std::env::args().count();
+    let kind = if n > 10 { Kind::Big } else { Kind::Small };
+    if let Kind::Big = kind {
+        // The discriminant of `kind` is constrained by the branch above,
+        // so only the first arm is reachable
+        let size = match kind {
+            Kind::Big => 1,
+            Kind::Small => 0,
+        };
+        verify!(size == 1);
+    }
+    let w = weight(&Level::Medium);
+    verify!(w == 2);
+}
+
+enum Level {
+    Low = -1,
+    Medium = 2,
+    High = 5,
+}
+
+// The discriminant of a parameter is unknown, but it is one of the discriminants of the variants
+fn weight(level: &Level) -> i32 {
+    let discriminant = match level {
+        Level::Low => -1,
+        Level::Medium => 2,
+        Level::High => 5,
+    };
+    verify!(discriminant >= -1 && discriminant <= 5);
+    discriminant
+}
@@ -0,0 +1,20 @@
+#[macro_use]
+extern crate macros;
+
+// The allocation does not escape, so it is removed from the state returned to the caller
+fn add_one(x: u32) -> u32 {
+    let b = Box::new(x);
+    *b + 1
+}
+
+// The allocation escapes through the return value, so it is kept
+fn make_box(x: u32) -> Box<u32> {
+    Box::new(x)
+}
+
+fn main() {
+    let a = add_one(3);
+    verify!(a == 4);
+    let b = make_box(5);
+    verify!(*b == 5);
+}
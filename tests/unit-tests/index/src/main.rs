@@ -10,4 +10,18 @@ fn main() {
     // Make sure `b` is 4, `c` is 5
     verify!(b == 4);
     verify!(c == 5);
+
+    // No out-of-bounds access is reported in `sum`
+    let d = sum(&a[1..]);
+}
+
+// The length of a slice is the metadata of the fat pointer, so `i < s.len()` bounds the index
+fn sum(s: &[i32]) -> i32 {
+    let mut total = 0;
+    let mut i = 0;
+    while i < s.len() {
+        total += s[i];
+        i += 1;
+    }
+    total
 }
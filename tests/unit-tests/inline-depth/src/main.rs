@@ -0,0 +1,33 @@
+#[macro_use]
+extern crate macros;
+
+fn clamp(x: usize) -> usize {
+    if x > 100 {
+        100
+    } else {
+        x
+    }
+}
+
+fn scaled(x: usize) -> usize {
+    // With `--inline-depth 1`, `clamp` is not analyzed in the context of this call, its summary is used instead
+    clamp(x) * 2
+}
+
+fn factorial(n: u32) -> u32 {
+    // The recursive call uses the summary of `factorial`
+    if n <= 1 {
+        1
+    } else {
+        n.wrapping_mul(factorial(n - 1))
+    }
+}
+
+fn main() {
+    // `scaled` is inlined, so the range of its argument is known
+    let s = scaled(10);
+    verify!(s <= 200);
+
+    let f = factorial(5);
+    verify!(f >= 1);
+}
@@ -0,0 +1,23 @@
+fn checksum(data: &[u8]) -> u32 {
+    let mut sum: u32 = 0;
+    for byte in data {
+        sum = sum.wrapping_mul(31).wrapping_add(*byte as u32);
+    }
+    sum
+}
+
+fn main() {
+    let n = std::env::args().count() as u32;
+    // `n` is small, so the cast keeps its value
+    let small = (n % 100) as i32;
+
+    // The checksum may be any `u32`, the values above `i32::MAX` become negative
+    let hash = checksum(b"mir-checker") as i32;
+
+    // `i64` holds every `u32`, so the widening cast is not reported
+    let wide = checksum(b"mir-checker") as i64;
+
+    // Always above `i32::MAX`
+    let large = (n + 3_000_000_000) as i32;
+    let _ = (small, hash, wide, large);
+}
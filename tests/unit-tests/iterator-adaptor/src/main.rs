@@ -0,0 +1,25 @@
+#[macro_use]
+extern crate macros;
+
+fn main() {
+    for i in 0..10 {
+        verify!(i >= 0 && i < 10);
+    }
+
+    for x in (1..5).map(|x| x * 2) {
+        verify!(x >= 2 && x <= 8);
+    }
+
+    for x in (-5..5).filter(|x| *x > 0) {
+        verify!(x > 0 && x < 5);
+    }
+
+    for (i, x) in (10..20).enumerate() {
+        verify!(i < 10);
+        verify!(x >= 10);
+    }
+
+    for i in (0..100).take(3) {
+        verify!(i < 100);
+    }
+}
@@ -0,0 +1,22 @@
+#[macro_use]
+extern crate macros;
+
+// The loop is bounded by a small constant, so it is unrolled instead of widened,
+// and the range of `x`, which does not appear in the loop condition, is still precise
+fn main() {
+    let mut i = 0;
+    let mut x = 0;
+    while i < 4 {
+        x += 2;
+        i += 1;
+    }
+    verify!(i == 4);
+    verify!(x <= 8);
+
+    // The loop iterates over a range of 4 items, so it is unrolled as well
+    let mut y = 0;
+    for _ in 0..4 {
+        y += 3;
+    }
+    verify!(y <= 12);
+}
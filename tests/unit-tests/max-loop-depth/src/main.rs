@@ -0,0 +1,18 @@
+#[macro_use]
+extern crate macros;
+
+fn main() {
+    let mut total = 0;
+    for i in 0..10 {
+        // With `--max-loop-depth 1`, the fixed-point of this loop is abandoned, so `count` becomes unknown and a
+        // note is emitted at its header
+        let mut count = 0;
+        for j in 0..i {
+            count += j;
+        }
+        verify!(i < 10);
+        total += count;
+    }
+    // The outer loop is still iterated to a fixed point
+    verify!(total >= 0);
+}
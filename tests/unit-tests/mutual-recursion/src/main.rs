@@ -0,0 +1,28 @@
+#[macro_use]
+extern crate macros;
+
+// `ping` and `pong` call each other, so their summaries are computed together before `main` is analyzed
+
+fn ping(n: u32) -> u32 {
+    if n > 0 {
+        pong(n - 1)
+    } else {
+        0
+    }
+}
+
+fn pong(n: u32) -> u32 {
+    if n > 0 {
+        ping(n - 1)
+    } else {
+        1
+    }
+}
+
+fn main() {
+    let n = std::env::args().count() as u32;
+    let p = ping(n);
+    verify!(p <= 1);
+    let q = pong(n);
+    verify!(q <= 1);
+}
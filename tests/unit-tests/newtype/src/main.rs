@@ -0,0 +1,13 @@
+#[macro_use]
+extern crate macros;
+
+struct Meters(u32);
+
+// The constant is serialized as a single scalar, which is interpreted
+// using the type of the value wrapped by `Meters`
+const LIMIT: Meters = Meters(5);
+
+fn main() {
+    let m = LIMIT;
+    verify!(m.0 == 5);
+}
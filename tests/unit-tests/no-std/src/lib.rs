@@ -0,0 +1,24 @@
+#![no_std]
+#![feature(core_intrinsics)]
+#![allow(internal_features)]
+
+use core::intrinsics;
+
+// The primitives of `core` are called directly, which are handled by their stubs with `--no-std`
+pub fn foo() {
+    let buf = [0u8; 16];
+
+    // The sum is at most `u8::MAX`, otherwise it is undefined behavior
+    let a = unsafe { intrinsics::unchecked_add(buf[0], 15) };
+    let b = buf[a as usize];
+
+    // A value in `[0, 15]` has at most 4 ones
+    let c = intrinsics::ctpop(a);
+    let d = buf[c as usize];
+
+    // The number of items is not negative, so the index may still be out of bounds
+    let n = buf.iter().count();
+    let e = buf[n];
+
+    let _ = (b, d, e);
+}
@@ -0,0 +1,25 @@
+#[macro_use]
+extern crate macros;
+
+#[allow(unused_variables)]
+fn main() {
+    let x = 7;
+    let p = &x;
+    let q = p;
+    verify!(*q == 7);
+
+    let mut y = 3;
+    let r = &mut y;
+    for i in 0..10 {
+        // `r` points to `y` in every iteration, so the write is not lost at the loop head
+        *r = i;
+    }
+    verify!(y >= 0 && y < 10);
+    let a = 10 / (y + 1); // OK
+
+    // `s` points to `z` on one branch, and to the value returned by a call on the other, so `*s` is unknown
+    let n = std::env::args().count();
+    let z = 1;
+    let s: &i32 = if n > 1 { &z } else { Box::leak(Box::new(0)) };
+    let b = 10 / *s; // Possible division by zero
+}
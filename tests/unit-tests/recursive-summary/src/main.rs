@@ -0,0 +1,28 @@
+#[macro_use]
+extern crate macros;
+
+fn count_down(n: u32) -> u32 {
+    if n > 0 {
+        count_down(n - 1)
+    } else {
+        0
+    }
+}
+
+fn parity(n: u32) -> u32 {
+    if n < 2 {
+        n
+    } else {
+        // The recursive call uses the summary of `parity` computed so far
+        parity(n - 2)
+    }
+}
+
+fn main() {
+    let n = std::env::args().count() as u32;
+    // The summaries are valid for any argument, so the results are precise without unrolling the recursion
+    let c = count_down(n);
+    verify!(c == 0);
+    let p = parity(n);
+    verify!(p <= 1);
+}
@@ -0,0 +1,14 @@
+use mir_checker_macros::requires;
+
+#[requires(i < len)]
+fn clamp_index(i: usize, len: usize) -> usize {
+    len - 1 - i
+}
+
+fn main() {
+    // The precondition holds
+    let a = clamp_index(3, 10);
+    // The precondition is violated
+    let b = clamp_index(10, 10);
+    println!("{} {}", a, b);
+}
@@ -0,0 +1,23 @@
+#[macro_use]
+extern crate macros;
+
+fn gain(sample: u8, level: u8) -> u8 {
+    sample.saturating_mul(level)
+}
+
+fn main() {
+    let a = 250u8.saturating_add(10);
+    verify!(a == 255);
+    let b = 3u8.saturating_sub(5);
+    verify!(b == 0);
+    let c = (-100i8).saturating_sub(100);
+    verify!(c == -128);
+
+    let mut level = 1u8;
+    for _ in 0..4 {
+        level = level.saturating_add(100);
+    }
+    verify!(level >= 1);
+    let d = gain(200, level);
+    verify!(d >= 200);
+}
@@ -0,0 +1,21 @@
+#![allow(arithmetic_overflow)]
+
+fn main() {
+    let n = std::env::args().count() as u32;
+    // The shift amounts are less than the bit widths, so they are not reported
+    let low = n << 31;
+    let byte = (n as u8) >> 7;
+
+    // 40 is not less than the bit width of `u32`, the shift is a provable error
+    let wide = n << 40;
+
+    // 8 is not less than the bit width of `u8`
+    let narrow = (n as u8) >> 8;
+
+    // A negative shift amount is out of range as well
+    let negative = (n as i64) << -1;
+
+    // `wrapping_shl` masks the shift amount, so it is not a shift with an out-of-range amount
+    let wrapped = n.wrapping_shl(40);
+    let _ = (low, byte, wide, narrow, negative, wrapped);
+}
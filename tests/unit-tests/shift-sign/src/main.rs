@@ -0,0 +1,19 @@
+fn main() {
+    let n = std::env::args().count() as i32;
+    // `n` is positive, so its shifts are not reported
+    let scaled = n << 2;
+    let halved = n >> 1;
+
+    // `offset` may be negative, the left shift is warned and the right shift is noted
+    let offset = n - 5;
+    let left = offset << 2;
+    let right = offset >> 1;
+
+    // `negative` is always negative, the left shift is a provable error
+    let negative = -n;
+    let always = negative << 1;
+
+    // Unsigned integers are never negative
+    let unsigned = (n as u32) << 3;
+    let _ = (scaled, halved, left, right, always, unsigned);
+}
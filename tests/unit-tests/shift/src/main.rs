@@ -0,0 +1,12 @@
+#[macro_use]
+extern crate macros;
+
+fn main() {
+    let n = std::env::args().count();
+    let shift = if n > 7 { 7 } else { n };
+    verify!(shift < 8);
+    // The shift amount is less than the bit width of `u8`, so the shifts cannot overflow
+    let byte: u8 = 1 << shift;
+    let signed: i8 = 64 >> (shift as i32);
+    let _ = (byte, signed);
+}
@@ -0,0 +1,13 @@
+#[macro_use]
+extern crate macros;
+
+#[allow(unused_variables)]
+fn main() {
+    let a = [1, 2, 3, 4, 5];
+    let s: &[i32] = &a;
+    let t = &*s;
+    // The length of `s` and `t` is known from the array
+    verify!(s.len() == 5);
+    let b = s[4]; // OK
+    let c = t[3]; // OK
+}
@@ -0,0 +1,22 @@
+#[macro_use]
+extern crate macros;
+
+// The calls to the standard library functions are handled by their stubs
+fn main() {
+    let n = std::env::args().count();
+
+    let a = n.min(10);
+    verify!(a <= 10);
+    let b = std::cmp::max(n, 4);
+    verify!(b >= 4);
+
+    let c = (-5i32).abs();
+    verify!(c == 5);
+    let d = 3u32.pow(4);
+    verify!(d == 81);
+
+    let e = (n as u32).count_ones();
+    verify!(e <= 32);
+    let f = (a as u8).saturating_pow(3);
+    verify!(f <= 255);
+}
@@ -0,0 +1,15 @@
+#[macro_use]
+extern crate macros;
+
+fn main() {
+    let s = "hello";
+    verify!(s.len() == 5);
+    let bytes: &[u8] = b"mir-checker";
+    verify!(bytes.len() == 11);
+    // In bounds, the length of the literal is known
+    let mut i = 0;
+    while i < 4 {
+        let _c = bytes[i + 7];
+        i += 1;
+    }
+}
@@ -0,0 +1,26 @@
+#[macro_use]
+extern crate macros;
+
+trait Area {
+    fn area(&self) -> u32;
+}
+
+struct Square(u32);
+
+impl Area for Square {
+    fn area(&self) -> u32 {
+        self.0 * self.0
+    }
+}
+
+fn total_area<T: Area>(a: &T, b: &T) -> u32 {
+    a.area() + b.area()
+}
+
+fn main() {
+    let s = Square(3);
+    // The call to the trait method is resolved to `<Square as Area>::area`
+    verify!(s.area() == 9);
+    // Also when the implementation is selected through a generic parameter
+    verify!(total_area(&s, &Square(2)) == 13);
+}
@@ -0,0 +1,44 @@
+#[macro_use]
+extern crate macros;
+
+fn half(x: u32) -> Result<u32, String> {
+    if x % 2 == 0 {
+        Ok(x / 2)
+    } else {
+        Err(String::from("odd"))
+    }
+}
+
+fn fail() -> Result<u8, String> {
+    Err(String::from("invalid"))
+}
+
+fn quarter(x: u32) -> Result<u32, String> {
+    let h = half(x)?;
+    let q = half(h)?;
+    Ok(q)
+}
+
+fn never(x: u8) -> Result<u8, String> {
+    let v = fail()?;
+    // Unreachable, because `fail` always returns `Err`, so the overflow is not reported
+    Ok(v + 255 + x)
+}
+
+fn first(v: &[u8]) -> Option<u8> {
+    let x = v.first().copied()?;
+    Some(x)
+}
+
+fn main() {
+    let a = quarter(8).unwrap();
+    verify!(a == 2);
+
+    let b = match never(1) {
+        Ok(v) => v,
+        Err(_) => 0,
+    };
+    verify!(b == 0);
+
+    let _c = first(&[1, 2, 3]);
+}
@@ -0,0 +1,7 @@
+fn main() {
+    let a = [1, 2, 3, 4];
+    let n = std::env::args().count();
+    // Safe, because `util` is analyzed first and `clamp_index` returns a value in [0, 3]
+    let i = util::clamp_index(n);
+    let _ = a[i];
+}
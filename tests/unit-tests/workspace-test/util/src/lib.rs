@@ -0,0 +1,8 @@
+/// Analyzed before `app`, the summary says that the result is in [0, 3]
+pub fn clamp_index(i: usize) -> usize {
+    if i < 4 {
+        i
+    } else {
+        3
+    }
+}
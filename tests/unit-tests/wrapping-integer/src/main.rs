@@ -0,0 +1,18 @@
+#[macro_use]
+extern crate macros;
+
+fn add(x: u8, y: u8) -> u8 {
+    x + y
+}
+
+fn sub(x: i8, y: i8) -> i8 {
+    x - y
+}
+
+fn main() {
+    // Analyzed with `--domain wrapping-integer`, where the overflow wraps around instead of being reported
+    let a = add(250, 10);
+    verify!(a == 4);
+    let b = sub(-128, 1);
+    verify!(b == 127);
+}
@@ -0,0 +1,25 @@
+// Analyzed with `--ffi-stubs ffi.toml`, the arguments that violate the contracts of the C functions are reported
+
+use std::os::raw::{c_char, c_int, c_void};
+use std::ptr;
+
+extern "C" {
+    fn memset(dest: *mut c_void, c: c_int, n: usize) -> *mut c_void;
+    fn strlen(s: *const c_char) -> usize;
+}
+
+fn clear(buffer: &mut [u8; 16], length: usize) {
+    // Not reported: the pointer is derived from a reference and the length is positive
+    if length > 0 && length <= 16 {
+        unsafe { memset(buffer as *mut [u8; 16] as *mut c_void, 0, length) };
+    }
+}
+
+fn main() {
+    let mut buffer = [1u8; 16];
+    clear(&mut buffer, 8);
+    // The length is zero, which the contract of `memset` does not allow
+    unsafe { memset(&mut buffer as *mut [u8; 16] as *mut c_void, 0, 0) };
+    // `strlen` dereferences the null pointer
+    let _length = unsafe { strlen(ptr::null()) };
+}
@@ -0,0 +1,21 @@
+// The crate registers the tool itself, which is not registered again by MirChecker
+#![feature(register_tool)]
+#![register_tool(mir_checker)]
+
+use std::ffi::CString;
+use std::os::raw::{c_char, c_int};
+
+extern "C" {
+    fn printf(format: *const c_char, ...) -> c_int;
+}
+
+#[mir_checker::taint_source]
+fn read_input() -> String {
+    std::env::args().nth(1).unwrap_or_default()
+}
+
+fn main() {
+    let input = read_input();
+    let format = CString::new(input).unwrap();
+    unsafe { printf(format.as_ptr()) };
+}
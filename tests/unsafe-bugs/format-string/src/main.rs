@@ -0,0 +1,18 @@
+use std::ffi::CString;
+use std::os::raw::{c_char, c_int};
+
+extern "C" {
+    fn printf(format: *const c_char, ...) -> c_int;
+}
+
+#[cfg_attr(mir_checker, mir_checker::taint_source)]
+fn read_input() -> String {
+    std::env::args().nth(1).unwrap_or_default()
+}
+
+fn main() {
+    let input = read_input();
+    let format = CString::new(input).unwrap();
+    // The user can read the stack with an input like "%x %x %x"
+    unsafe { printf(format.as_ptr()) };
+}
@@ -0,0 +1,18 @@
+// Analyzed with `--check vec-bounds`, the end of the range of `get_unchecked` must not exceed the length
+
+fn main() {
+    let mut v: Vec<i32> = Vec::new();
+    v.push(1);
+    v.push(2);
+    v.push(3);
+    // In bounds, the length is 3
+    let _head = unsafe { v.get_unchecked(0..3) };
+    // Out of bounds, the end exceeds the length
+    let _tail = unsafe { v.get_unchecked(1..4) };
+
+    let s = "hello";
+    // In bounds, the length is 5
+    let _hell = unsafe { s.get_unchecked(0..4) };
+    // Out of bounds, the end exceeds the length
+    let _world = unsafe { s.get_unchecked(0..10) };
+}
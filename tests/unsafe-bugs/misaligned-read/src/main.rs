@@ -0,0 +1,11 @@
+fn read_header(bytes: &[u8]) -> u32 {
+    // The elements of `[u8]` are only aligned to 1 byte, but `u32` requires 4 bytes
+    let ptr = unsafe { bytes.as_ptr().add(1) } as *const u32;
+    unsafe { std::ptr::read(ptr) }
+}
+
+fn main() {
+    let bytes = [0u8; 8];
+    let first: &u32 = unsafe { std::mem::transmute(&bytes[0]) };
+    println!("{} {}", first, read_header(&bytes));
+}
@@ -0,0 +1,30 @@
+use std::mem;
+
+// 1 byte of `tag`, 3 bytes of padding, 4 bytes of `len`
+#[repr(C)]
+struct Header {
+    tag: u8,
+    len: u32,
+}
+
+// No padding between or after the fields
+#[repr(C)]
+struct Packed {
+    tag: u32,
+    len: u32,
+}
+
+fn serialize(header: Header) -> [u8; 8] {
+    // Bytes 1..4 are uninitialized
+    unsafe { mem::transmute::<Header, [u8; 8]>(header) }
+}
+
+fn serialize_packed(packed: Packed) -> [u8; 8] {
+    unsafe { mem::transmute::<Packed, [u8; 8]>(packed) }
+}
+
+fn main() {
+    let bytes = serialize(Header { tag: 1, len: 16 });
+    let packed_bytes = serialize_packed(Packed { tag: 1, len: 16 });
+    println!("{:?} {:?}", bytes, packed_bytes);
+}
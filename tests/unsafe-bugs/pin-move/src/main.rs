@@ -0,0 +1,39 @@
+// Analyzed with `--check pin-safety`, the pinned values that are moved afterwards are reported
+
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+use std::ptr;
+
+// A self-referential struct, whose `pointer` points to its own `data`
+struct SelfReferential {
+    data: i32,
+    pointer: *const i32,
+    _pinned: PhantomPinned,
+}
+
+impl SelfReferential {
+    fn new(data: i32) -> Self {
+        SelfReferential {
+            data,
+            pointer: ptr::null(),
+            _pinned: PhantomPinned,
+        }
+    }
+
+    fn init(self: Pin<&mut Self>) {
+        // Unpinning is reported, because the value may be moved through the returned reference
+        let this = unsafe { self.get_unchecked_mut() };
+        this.pointer = &this.data;
+    }
+}
+
+fn main() {
+    let mut value = SelfReferential::new(1);
+    let pinned = unsafe { Pin::new_unchecked(&mut value) };
+    pinned.init();
+    // Moving the pinned value leaves `pointer` dangling
+    let moved = unsafe { ptr::read(&value) };
+    let mut other = SelfReferential::new(2);
+    std::mem::swap(&mut value, &mut other);
+    let _data = unsafe { *moved.pointer };
+}
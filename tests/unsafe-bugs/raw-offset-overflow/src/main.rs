@@ -0,0 +1,13 @@
+// Analyzed with `--check raw-offset-overflow`, the byte offsets of `offset` must not overflow `isize`
+
+fn main() {
+    let buf = [0u64; 4];
+    let p = buf.as_ptr();
+    // The byte offset is 16
+    let _q = unsafe { p.offset(2) };
+    // The byte offset is `isize::MAX / 4 * 8`, which overflows `isize` even though it is never dereferenced
+    let _r = unsafe { p.offset(isize::MAX / 4) };
+    // The number of elements is unknown, so the byte offset may overflow `isize`
+    let n = std::env::args().count() as isize;
+    let _s = unsafe { p.offset(n) };
+}
@@ -0,0 +1,46 @@
+// Analyzed with `--check raw-ptr-bounds`, the lanes of the gathers that load out of the bounds of the array or
+// the slice are reported
+
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+// Every lane is in bounds
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn in_bounds(array: &[i32; 8]) -> __m128i {
+    let offsets = _mm_setr_epi32(0, 2, 4, 7);
+    _mm_i32gather_epi32::<4>(array.as_ptr(), offsets)
+}
+
+// Lane 3 loads `array[8]`
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn past_the_end(array: &[i32; 8]) -> __m128i {
+    let offsets = _mm_set_epi32(8, 4, 2, 0);
+    _mm_i32gather_epi32::<4>(array.as_ptr(), offsets)
+}
+
+// Every lane loads `slice[n]`, which is out of bounds if `n >= 4`
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn broadcast(n: i32) -> __m128i {
+    let array = [1, 2, 3, 4];
+    let slice: &[i32] = &array;
+    let offsets = _mm_set1_epi32(n);
+    _mm_i32gather_epi32::<4>(slice.as_ptr(), offsets)
+}
+
+#[cfg(target_arch = "x86_64")]
+fn main() {
+    if is_x86_feature_detected!("avx2") {
+        let array = [0, 1, 2, 3, 4, 5, 6, 7];
+        unsafe {
+            in_bounds(&array);
+            past_the_end(&array);
+            broadcast(std::env::args().count() as i32);
+        }
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn main() {}
@@ -0,0 +1,18 @@
+// Analyzed with `--check transmute-safety`
+#![feature(core_intrinsics)]
+#![allow(internal_features)]
+
+use std::intrinsics::transmute_unchecked;
+use std::mem::transmute;
+
+// The alignment of `bytes` is unknown, but `u32` requires 4 bytes
+fn as_u32(bytes: *const u8) -> &'static u32 {
+    unsafe { transmute(bytes) }
+}
+
+fn main() {
+    let buffer = [0u8; 8];
+    let _value = as_u32(buffer.as_ptr().wrapping_add(1));
+    // The sizes are not checked by rustc, but `u64` has 8 bytes and `u32` has 4 bytes
+    let _truncated: u32 = unsafe { transmute_unchecked(1u64) };
+}
@@ -0,0 +1,14 @@
+// Analyzed with `--check vec-bounds`, the length and the capacity of the vector are tracked
+
+fn main() {
+    let mut v: Vec<i32> = Vec::with_capacity(4);
+    v.push(1);
+    v.push(2);
+    let _last = v.pop();
+    // In bounds, the length is 1
+    let _first = unsafe { *v.get_unchecked(0) };
+    // Out of bounds, the index is equal to the length
+    let _second = unsafe { *v.get_unchecked(1) };
+    // Exceeds the capacity, the items after the buffer are uninitialized
+    unsafe { v.set_len(8) };
+}
@@ -0,0 +1,60 @@
+// Tests for the wraparound of the values of machine integers in the `wrapping-integer` domain
+//
+//     cargo test --test wrapping_integer
+
+use rust_mir_checker::analysis::numerical::interval::{Bound, Interval};
+use rust_mir_checker::analysis::numerical::wrapping_integer::WrappingIntegerDomain;
+
+fn interval(low: i64, high: i64) -> Interval {
+    Interval::new(Bound::from(low as i128), Bound::from(high as i128))
+}
+
+#[test]
+fn values_in_range_are_unchanged() {
+    let u8_domain = WrappingIntegerDomain::new(8, false);
+    assert_eq!(u8_domain.wrap(&interval(3, 10)), interval(3, 10));
+    assert_eq!(u8_domain.wrap(&interval(0, 255)), interval(0, 255));
+    assert!(u8_domain.wrap(&Interval::bottom()).is_bottom());
+}
+
+#[test]
+fn unsigned_overflow_wraps_around() {
+    let u8_domain = WrappingIntegerDomain::new(8, false);
+    assert_eq!(u8_domain.wrap(&interval(260, 265)), interval(4, 9));
+    assert_eq!(u8_domain.wrap(&interval(-3, -1)), interval(253, 255));
+    assert_eq!(u8_domain.wrap(&interval(1000, 1000)), interval(232, 232));
+}
+
+#[test]
+fn signed_overflow_wraps_around() {
+    let i8_domain = WrappingIntegerDomain::new(8, true);
+    assert_eq!(i8_domain.wrap(&interval(128, 130)), interval(-128, -126));
+    assert_eq!(i8_domain.wrap(&interval(-130, -129)), interval(126, 127));
+}
+
+#[test]
+fn crossing_a_bound_gives_the_full_range() {
+    let u8_domain = WrappingIntegerDomain::new(8, false);
+    assert_eq!(u8_domain.wrap(&interval(250, 260)), interval(0, 255));
+    assert_eq!(u8_domain.wrap(&interval(0, 256)), interval(0, 255));
+    assert_eq!(u8_domain.wrap(&Interval::top()), interval(0, 255));
+
+    let i32_domain = WrappingIntegerDomain::new(32, true);
+    assert_eq!(
+        i32_domain.wrap(&interval(i32::MAX as i64, i32::MAX as i64 + 1)),
+        interval(i32::MIN as i64, i32::MAX as i64)
+    );
+}
+
+#[test]
+fn join_is_computed_modulo_the_bit_width() {
+    let u8_domain = WrappingIntegerDomain::new(8, false);
+    assert_eq!(
+        u8_domain.join(&interval(1, 2), &interval(257, 258)),
+        interval(1, 2)
+    );
+    assert_eq!(
+        u8_domain.join(&interval(1, 2), &interval(260, 265)),
+        interval(1, 9)
+    );
+}